@@ -177,6 +177,10 @@ impl VecnoCli {
         &self.daemons
     }
 
+    pub fn miner(&self) -> Option<Arc<Miner>> {
+        self.miner.lock().unwrap().clone()
+    }
+
     pub fn handlers(&self) -> Arc<HandlerCli> {
         self.handlers.clone()
     }
@@ -52,6 +52,10 @@ impl Handler for Metrics {
 
         self.metrics.bind_rpc(Some(ctx.wallet().rpc_api().clone()));
 
+        if let Some(miner) = ctx.miner() {
+            self.metrics.register_miner(Arc::new(Box::new(move || miner.metrics())));
+        }
+
         Ok(())
     }
 
@@ -1,5 +1,6 @@
 use crate::imports::*;
 use vecno_daemon::{locate_binaries, CpuMinerConfig};
+use vecno_metrics_core::MinerMetrics;
 pub use workflow_node::process::Event;
 
 #[derive(Describe, Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -11,6 +12,8 @@ pub enum MinerSettings {
     Server,
     #[describe("Miner throttle (milliseconds, default: 5,000; lower = higher CPU usage)")]
     Throttle,
+    #[describe("Mining threads (default: 1)")]
+    Threads,
     #[describe("Mute logs")]
     Mute,
 }
@@ -35,6 +38,9 @@ pub struct Miner {
     settings: SettingsStore<MinerSettings>,
     mute: Arc<AtomicBool>,
     is_running: Arc<AtomicBool>,
+    // in-process miner, used when no CPU miner daemon is available
+    #[cfg(not(target_arch = "wasm32"))]
+    native: Mutex<Option<Arc<vecno_miner::Miner>>>,
 }
 
 impl Default for Miner {
@@ -43,6 +49,8 @@ impl Default for Miner {
             settings: SettingsStore::try_new("miner").expect("Failed to create miner settings store"),
             mute: Arc::new(AtomicBool::new(true)),
             is_running: Arc::new(AtomicBool::new(false)),
+            #[cfg(not(target_arch = "wasm32"))]
+            native: Mutex::new(None),
         }
     }
 }
@@ -51,7 +59,12 @@ impl Default for Miner {
 impl Handler for Miner {
     fn verb(&self, ctx: &Arc<dyn Context>) -> Option<&'static str> {
         if let Ok(ctx) = ctx.clone().downcast_arc::<VecnoCli>() {
-            ctx.daemons().clone().cpu_miner.as_ref().map(|_| "miner")
+            // native platforms can always fall back to the in-process miner
+            if ctx.daemons().cpu_miner.is_some() || cfg!(not(target_arch = "wasm32")) {
+                Some("miner")
+            } else {
+                None
+            }
         } else {
             None
         }
@@ -70,6 +83,12 @@ impl Handler for Miner {
         Ok(())
     }
 
+    async fn stop(self: Arc<Self>, _ctx: &Arc<dyn Context>) -> cli::Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.stop_native().await?;
+        Ok(())
+    }
+
     async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
         let ctx = ctx.clone().downcast_arc::<VecnoCli>()?;
         self.main(ctx, argv, cmd).await.map_err(|e| e.into())
@@ -91,14 +110,25 @@ impl Miner {
         let server: String = self.settings.get(MinerSettings::Server).unwrap_or("127.0.0.1".to_string());
         let throttle: usize = self.settings.get(MinerSettings::Throttle).unwrap_or(5_000);
         let mute = self.mute.load(Ordering::SeqCst);
-        let config = CpuMinerConfig::new(location.as_str(), network_id.into(), address, server, throttle, mute);
+        let mut config = CpuMinerConfig::new(location.as_str(), network_id.into(), address, server, throttle, mute);
+        config.threads = Some(self.threads());
         Ok(config)
     }
 
+    fn threads(&self) -> usize {
+        self.settings.get(MinerSettings::Threads).unwrap_or(1)
+    }
+
     async fn main(self: Arc<Self>, ctx: Arc<VecnoCli>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         if argv.is_empty() {
             return self.display_help(ctx, argv).await;
         }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if ctx.daemons().try_cpu_miner().is_none() {
+            return self.main_native(ctx, argv).await;
+        }
+
         let cpu_miner = ctx.daemons().cpu_miner();
         match argv.remove(0).as_str() {
             "start" => {
@@ -124,6 +154,12 @@ impl Miner {
                 cpu_miner.configure(self.create_config(&ctx).await?).await?;
                 cpu_miner.restart().await?;
             }
+            "threads" => {
+                let threads = parse_threads(&mut argv)?;
+                self.settings.set(MinerSettings::Threads, threads).await?;
+                cpu_miner.configure(self.create_config(&ctx).await?).await?;
+                cpu_miner.restart().await?;
+            }
             "restart" => {
                 cpu_miner.configure(self.create_config(&ctx).await?).await?;
                 cpu_miner.restart().await?;
@@ -169,6 +205,20 @@ impl Miner {
     }
 
     async fn display_help(self: Arc<Self>, ctx: Arc<VecnoCli>, _argv: Vec<String>) -> Result<()> {
+        if ctx.daemons().try_cpu_miner().is_none() {
+            ctx.term().help(
+                &[
+                    ("start", "Start the in-process CPU miner"),
+                    ("stop", "Stop the in-process CPU miner"),
+                    ("restart", "Restart the in-process CPU miner"),
+                    ("status", "Get the hashrate and block counts of the in-process CPU miner"),
+                    ("threads <count>", "Change the amount of mining threads"),
+                ],
+                None,
+            )?;
+            return Ok(());
+        }
+
         ctx.term().help(
             &[
                 ("select [<path>]", "Select CPU miner executable (binary) location"),
@@ -178,6 +228,7 @@ impl Miner {
                 ("kill", "Kill the local CPU miner instance"),
                 ("status", "Get the status of the local CPU miner instance"),
                 ("throttle <msec>", "Change CPU miner throttle value"),
+                ("threads <count>", "Change the amount of CPU miner threads"),
             ],
             None,
         )?;
@@ -259,3 +310,132 @@ impl Miner {
         Ok(())
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Miner {
+    async fn main_native(self: Arc<Self>, ctx: Arc<VecnoCli>, mut argv: Vec<String>) -> Result<()> {
+        match argv.remove(0).as_str() {
+            "start" => {
+                self.start_native(&ctx).await?;
+            }
+            "stop" => {
+                if !self.stop_native().await? {
+                    tprintln!(ctx, "miner is not running");
+                }
+            }
+            "restart" => {
+                self.stop_native().await?;
+                self.start_native(&ctx).await?;
+            }
+            "threads" => {
+                let threads = parse_threads(&mut argv)?;
+                self.settings.set(MinerSettings::Threads, threads).await?;
+                if self.stop_native().await? {
+                    self.start_native(&ctx).await?;
+                }
+            }
+            "status" => {
+                if let Some(snapshot) = self.native_snapshot() {
+                    tprintln!(ctx, "running - {}", format_snapshot(&snapshot));
+                } else {
+                    tprintln!(ctx, "not running");
+                }
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn start_native(&self, ctx: &Arc<VecnoCli>) -> Result<()> {
+        if self.native.lock().unwrap().is_some() {
+            return Err(Error::Custom("Miner is already running.".to_string()));
+        }
+        if !ctx.wallet().is_connected() {
+            return Err(Error::Custom("Wallet is not connected to a node, please use 'connect' first.".to_string()));
+        }
+
+        let address = ctx.account().await?.receive_address()?;
+        let threads = self.threads();
+        tprintln!(ctx, "starting miner with {threads} thread(s), mining to {address}...");
+
+        let config = vecno_miner::MinerConfig::new(threads, address);
+        let miner = Arc::new(vecno_miner::Miner::try_new(ctx.wallet().rpc_api().clone(), config).map_err(|err| err.to_string())?);
+        miner.start().await.map_err(|err| err.to_string())?;
+        self.native.lock().unwrap().replace(miner);
+        self.is_running.store(true, Ordering::SeqCst);
+        ctx.term().refresh_prompt();
+        Ok(())
+    }
+
+    /// Stops the in-process miner, returns `false` if it was not running
+    async fn stop_native(&self) -> Result<bool> {
+        let miner = self.native.lock().unwrap().take();
+        if let Some(miner) = miner {
+            self.is_running.store(false, Ordering::SeqCst);
+            miner.stop().await.map_err(|err| err.to_string())?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn native_snapshot(&self) -> Option<vecno_miner::counters::MinerCountersSnapshot> {
+        self.native.lock().unwrap().as_ref().map(|miner| miner.counters().snapshot())
+    }
+}
+
+impl Miner {
+    /// Returns a one line summary of the in-process miner activity if it is running
+    pub fn status_line(&self) -> Option<String> {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                self.native_snapshot().map(|snapshot| format_snapshot(&snapshot))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the counters of the in-process miner if it is running
+    pub fn metrics(&self) -> Option<MinerMetrics> {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                self.native_snapshot().map(|snapshot| MinerMetrics {
+                    hashes: snapshot.hashes,
+                    blocks_accepted: snapshot.blocks_accepted,
+                    blocks_rejected: snapshot.blocks_rejected,
+                    stale_blocks: snapshot.stale_blocks,
+                })
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn format_snapshot(snapshot: &vecno_miner::counters::MinerCountersSnapshot) -> String {
+    format!(
+        "{} H/s (avg), {} templates, {} blocks found: {} accepted, {} rejected, {} stale",
+        (snapshot.hashrate() as u64).separated_string(),
+        snapshot.templates_received.separated_string(),
+        snapshot.blocks_found.separated_string(),
+        snapshot.blocks_accepted.separated_string(),
+        snapshot.blocks_rejected.separated_string(),
+        snapshot.stale_blocks.separated_string(),
+    )
+}
+
+fn parse_threads(argv: &mut Vec<String>) -> Result<usize> {
+    if argv.is_empty() {
+        return Err(Error::Custom("Please specify the amount of mining threads".into()));
+    }
+    match argv.remove(0).parse::<usize>() {
+        Ok(threads) if threads > 0 => Ok(threads),
+        _ => Err(Error::Custom("Invalid amount of threads, please specify a positive number".into())),
+    }
+}
@@ -29,6 +29,7 @@ pub mod ping;
 pub mod pskb;
 pub mod reload;
 pub mod rpc;
+pub mod script;
 pub mod select;
 pub mod send;
 pub mod server;
@@ -58,7 +59,7 @@ pub fn register_handlers(cli: &Arc<VecnoCli>) -> Result<()> {
         cli.handlers(),
         [
             account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, pskb, reload, select, send, server, settings, sweep, track, transfer,
+            message, monitor, mute, network, node, open, ping, pskb, reload, script, select, send, server, settings, sweep, track, transfer,
             wallet,
             // halt,
             // theme,  start, stop
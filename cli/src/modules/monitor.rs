@@ -1,4 +1,5 @@
 use crate::imports::*;
+use vecno_metrics_core::MetricsData;
 use workflow_core::channel::*;
 use workflow_terminal::clear::*;
 use workflow_terminal::cursor::*;
@@ -20,7 +21,7 @@ impl Handler for Monitor {
     }
 
     fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
-        "Balance monitor"
+        "Balance and mempool monitor"
     }
 
     async fn stop(self: Arc<Self>, _ctx: &Arc<dyn Context>) -> cli::Result<()> {
@@ -106,6 +107,18 @@ impl Monitor {
 
         ctx.list().await?;
 
+        if let Some(status) = ctx.miner().and_then(|miner| miner.status_line()) {
+            tprintln!(ctx, "{} {status}", style("miner".pad_to_width(8)).green());
+            tprintln!(ctx);
+        }
+
+        if wallet.is_connected() {
+            // Nodes without mempool statistics are silently skipped
+            if let Ok(stats) = wallet.rpc_api().get_mempool_stats(MEMPOOL_CHART_WIDTH as u32).await {
+                self.draw_mempool_stats(ctx, &stats);
+            }
+        }
+
         let events = events.lock().unwrap();
         events.iter().for_each(|event| match event.deref() {
             Events::DaaScoreChange { .. } => {}
@@ -130,4 +143,64 @@ impl Monitor {
 
         Ok(())
     }
+
+    fn draw_mempool_stats(&self, ctx: &Arc<VecnoCli>, stats: &GetMempoolStatsResponse) {
+        let Some(last) = stats.samples.last() else {
+            return;
+        };
+        let samples = &stats.samples;
+        let label = style("mempool".pad_to_width(8)).cyan();
+
+        let transaction_counts = samples.iter().map(|sample| sample.transaction_count).collect::<Vec<_>>();
+        let window = format!("last {}s", samples.len() as u64 * stats.sample_interval);
+        tprintln!(
+            ctx,
+            "{label} {} txs, {} mass, {} orphans   {}",
+            last.transaction_count.separated_string(),
+            last.total_mass.separated_string(),
+            last.orphan_count.separated_string(),
+            style(&window).dim()
+        );
+        tprintln!(ctx, "{} {}", "".pad_to_width(8), sparkline(&transaction_counts));
+
+        let evicted = samples.iter().map(|sample| sample.evicted_count).sum::<u64>();
+        let replaced = samples.iter().map(|sample| sample.replaced_count).sum::<u64>();
+        let policy_rejected = samples.iter().map(|sample| sample.policy_rejected_count).sum::<u64>();
+        tprintln!(
+            ctx,
+            "{} {} evicted, {} replaced by fee, {} rejected by policy",
+            "".pad_to_width(8),
+            evicted.separated_string(),
+            replaced.separated_string(),
+            policy_rejected.separated_string()
+        );
+
+        let mut metrics = MetricsData::default();
+        metrics.set_mempool_metrics(&stats.feerate_buckets, last);
+        let histogram = metrics.mempool_feerate_distribution();
+        let max_count = histogram.iter().map(|(_, count)| *count).max().unwrap_or_default();
+        if max_count > 0 {
+            tprintln!(ctx, "{} {}", "".pad_to_width(8), style("feerate (sompi/gram)").dim());
+            for (lower_bound, count) in histogram {
+                if count > 0 {
+                    let bar = "█".repeat((count * MEMPOOL_HISTOGRAM_WIDTH as u64).div_ceil(max_count) as usize);
+                    tprintln!(ctx, "{} {} {bar} {count}", "".pad_to_width(8), format!(">= {lower_bound}").pad_to_width(9));
+                }
+            }
+        }
+        tprintln!(ctx);
+    }
+}
+
+/// The number of most recent mempool samples charted by the monitor
+const MEMPOOL_CHART_WIDTH: usize = 60;
+
+/// The width of the longest bar of the feerate histogram
+const MEMPOOL_HISTOGRAM_WIDTH: usize = 40;
+
+/// Draws `values` as a line of block characters scaled to the maximum value
+fn sparkline(values: &[u64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or_default();
+    values.iter().map(|value| BLOCKS[(*value * (BLOCKS.len() as u64 - 1)).checked_div(max).unwrap_or_default() as usize]).collect()
 }
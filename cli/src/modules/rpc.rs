@@ -260,6 +260,11 @@ impl Rpc {
                 let result = rpc.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetMempoolStats => {
+                let limit = if argv.is_empty() { 0 } else { argv.remove(0).parse::<u32>()? };
+                let result = rpc.get_mempool_stats_call(None, GetMempoolStatsRequest { limit }).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetCurrentBlockColor => {
                 if argv.is_empty() {
                     return Err(Error::custom("Missing block hash argument"));
@@ -269,6 +274,73 @@ impl Rpc {
                 let result = rpc.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcTransactionId::from_hex(transaction_id.as_str())?;
+                let include_transaction = argv.first().and_then(|x| x.parse::<bool>().ok()).unwrap_or(true);
+                let result = rpc.get_transaction_call(None, GetTransactionRequest { transaction_id, include_transaction }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByIds => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id arguments"));
+                }
+                let transaction_ids = argv
+                    .iter()
+                    .map(|transaction_id| RpcTransactionId::from_hex(transaction_id.as_str()))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_transactions_by_ids_call(None, GetTransactionsByIdsRequest { transaction_ids, include_transactions: true })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressHistory => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let limit = argv.first().and_then(|x| x.parse::<u32>().ok()).unwrap_or_default();
+                let result = rpc
+                    .get_address_history_call(
+                        None,
+                        GetAddressHistoryRequest { address, start_daa_score: None, end_daa_score: None, cursor: None, limit },
+                    )
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetUtxosAtChainBlock => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing chain block hash argument"));
+                }
+                let chain_block_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result =
+                    rpc.get_utxos_at_chain_block_call(None, GetUtxosAtChainBlockRequest { chain_block_hash, addresses }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetArchivalChainBlocks => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing start DAA score argument"));
+                }
+                let start_score = argv.remove(0).parse::<u64>()?;
+                let limit = argv.first().and_then(|x| x.parse::<u32>().ok()).unwrap_or_default();
+                let result = rpc
+                    .get_archival_chain_blocks_call(
+                        None,
+                        GetArchivalChainBlocksRequest {
+                            score_kind: RpcChainScoreKind::DaaScore,
+                            start_score,
+                            end_score: None,
+                            limit,
+                            include_acceptance_data: true,
+                        },
+                    )
+                    .await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
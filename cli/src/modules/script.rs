@@ -0,0 +1,218 @@
+use crate::imports::*;
+use vecno_consensus_core::{
+    hashing::sighash::{SigHashReusedValues, SigHashReusedValuesSync},
+    tx::{MutableTransaction, VerifiableTransaction},
+};
+use vecno_txscript::{
+    caches::Cache,
+    debugger::{Breakpoint, ScriptDebugger, ScriptKind, ScriptStep},
+    script_asm::{assemble, disassemble},
+    TxScriptEngine,
+};
+use vecno_wallet_pskt::prelude::{Bundle, Extractor, PSKT};
+
+#[derive(Default, Handler)]
+#[help("Disassemble, assemble, trace or step through transaction scripts")]
+pub struct Script;
+
+impl Script {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<VecnoCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "disasm" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let script = Vec::<u8>::from_hex(argv[0].as_str()).map_err(|e| Error::custom(format!("Invalid script hex: {e}")))?;
+                tprintln!(ctx, "{}", disassemble(&script));
+            }
+            "asm" => {
+                if argv.is_empty() {
+                    return self.display_help(ctx, argv).await;
+                }
+                let script = assemble(&argv.join(" ")).map_err(|e| Error::custom(format!("Invalid script: {e}")))?;
+                tprintln!(ctx, "{}", script.to_hex());
+            }
+            "trace" | "debug" => {
+                if argv.len() < 2 || argv.len() > 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let pskb =
+                    Bundle::try_from(argv[0].as_str()).map_err(|e| Error::custom(format!("Error while parsing input PSKB {e}")))?;
+                let input_index = argv[1].parse::<usize>()?;
+                let pskt_index = argv.get(2).map(|index| index.parse::<usize>()).transpose()?.unwrap_or_default();
+
+                let inner = pskb
+                    .iter()
+                    .nth(pskt_index)
+                    .ok_or_else(|| Error::custom(format!("PSKT #{pskt_index} is missing from the PSKB")))?;
+                let extract = PSKT::<Extractor>::from(inner.clone())
+                    .extract_tx_unchecked()
+                    .map_err(|_| Error::custom("The PSKT must be finalized for its scripts to be executed"))?;
+                let (tx, entries) = extract(0);
+                let entries = entries
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| Error::custom("The PSKT is missing some UTXO entries"))?;
+                if input_index >= tx.inputs.len() {
+                    return Err(Error::custom(format!(
+                        "Input index {input_index} is out of range, the transaction has {} inputs",
+                        tx.inputs.len()
+                    )));
+                }
+                let tx = MutableTransaction::with_entries(tx, entries);
+                let tx = tx.as_verifiable();
+                let (input, entry) = tx.populated_input(input_index);
+
+                let sig_cache = Cache::new(0);
+                let reused_values = SigHashReusedValuesSync::new();
+                let mut debugger = ScriptDebugger::new(TxScriptEngine::from_transaction_input(
+                    &tx,
+                    input,
+                    input_index,
+                    entry,
+                    &reused_values,
+                    &sig_cache,
+                    Default::default(),
+                ));
+
+                if action == "trace" {
+                    let _ = debugger.run();
+                    debugger.trace().iter().for_each(|step| Self::print_step(&ctx, step));
+                } else {
+                    Self::debug(&ctx, &mut debugger).await?;
+                }
+
+                match debugger.outcome() {
+                    Some(Ok(())) => tprintln!(ctx, "Script execution succeeded"),
+                    Some(Err(err)) => terrorln!(ctx, "Script execution failed: {err}"),
+                    None => twarnln!(ctx, "Script execution aborted"),
+                }
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn debug<T: VerifiableTransaction, Reused: SigHashReusedValues>(
+        ctx: &Arc<VecnoCli>,
+        debugger: &mut ScriptDebugger<'_, T, Reused>,
+    ) -> Result<()> {
+        tprintln!(ctx, "Commands: step [count], continue, break <script>:<pc>, delete <script>:<pc>, stack, trace, quit");
+        tprintln!(
+            ctx,
+            "Scripts: sig (signature script), spk (script public key), redeem (P2SH redeem script), leaf (MAST leaf script)"
+        );
+
+        while let Some(position) = debugger.position() {
+            let line = ctx.term().ask(false, &format!("{} > ", Self::format_position(&position))).await?;
+            let mut args = line.split_whitespace();
+            match args.next().unwrap_or("step") {
+                "s" | "step" => {
+                    let count = args.next().map(|count| count.parse::<usize>()).transpose()?.unwrap_or(1);
+                    for _ in 0..count {
+                        match debugger.step() {
+                            Some(step) => Self::print_step(ctx, step),
+                            None => break,
+                        }
+                    }
+                }
+                "c" | "continue" => {
+                    let len = debugger.trace().len();
+                    let hit = debugger.resume();
+                    debugger.trace()[len..].iter().for_each(|step| Self::print_step(ctx, step));
+                    if let Some(breakpoint) = hit {
+                        tprintln!(ctx, "Breakpoint hit at {}", Self::format_position(&breakpoint));
+                    }
+                }
+                "b" | "break" => match args.next().and_then(Self::parse_position) {
+                    Some(breakpoint) => debugger.add_breakpoint(breakpoint),
+                    _ => terrorln!(ctx, "usage: break <script>:<pc>"),
+                },
+                "d" | "delete" => match args.next().and_then(Self::parse_position) {
+                    Some(breakpoint) => {
+                        if !debugger.remove_breakpoint(&breakpoint) {
+                            twarnln!(ctx, "No breakpoint at {}", Self::format_position(&breakpoint));
+                        }
+                    }
+                    _ => terrorln!(ctx, "usage: delete <script>:<pc>"),
+                },
+                "stack" => {
+                    tprintln!(ctx, "  data stack: {}", Self::format_stack(debugger.data_stack()));
+                    tprintln!(ctx, "  alt stack:  {}", Self::format_stack(debugger.alt_stack()));
+                    tprintln!(ctx, "  branches:   {:?}", debugger.cond_stack());
+                }
+                "trace" => debugger.trace().iter().for_each(|step| Self::print_step(ctx, step)),
+                "q" | "quit" => return Ok(()),
+                v => terrorln!(ctx, "unknown debugger command: '{v}'"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_position(position: &str) -> Option<Breakpoint> {
+        let (script, pc) = position.split_once(':')?;
+        let script = match script {
+            "sig" => ScriptKind::SignatureScript,
+            "spk" => ScriptKind::ScriptPublicKey,
+            "redeem" => ScriptKind::RedeemScript,
+            "leaf" => ScriptKind::LeafScript,
+            _ => return None,
+        };
+        Some(Breakpoint::new(script, pc.parse::<usize>().ok()?))
+    }
+
+    fn format_position(position: &Breakpoint) -> String {
+        let script = match position.script {
+            ScriptKind::SignatureScript => "sig",
+            ScriptKind::ScriptPublicKey => "spk",
+            ScriptKind::RedeemScript => "redeem",
+            ScriptKind::LeafScript => "leaf",
+            ScriptKind::StandAlone => "script",
+        };
+        format!("{script}:{}", position.pc)
+    }
+
+    fn format_stack(stack: &[Vec<u8>]) -> String {
+        format!("[{}]", stack.iter().map(|item| item.to_hex()).collect::<Vec<_>>().join(", "))
+    }
+
+    fn print_step(ctx: &Arc<VecnoCli>, step: &ScriptStep) {
+        let data = if step.data.is_empty() { String::new() } else { format!(" {}", step.data.to_hex()) };
+        let skipped = if step.executed { "" } else { " (skipped)" };
+        tprintln!(ctx, "{:>10} {}{data}{skipped}", Self::format_position(&Breakpoint::new(step.script, step.pc)), step.opcode_name());
+        tprintln!(ctx, "{:>10} {}", "", Self::format_stack(&step.data_stack));
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<VecnoCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("script disasm <hex>", "Disassemble a hex encoded script, such as a script public key, into opcode names"),
+                ("script asm <opcodes>", "Assemble opcode names, 0x-prefixed hex data and numbers into a hex encoded script"),
+                (
+                    "script trace <pskb> <input index> [pskt index]",
+                    "Print every opcode executed by the scripts of a finalized PSKB input, together with the data stack after it",
+                ),
+                (
+                    "script debug <pskb> <input index> [pskt index]",
+                    "Step through the scripts of a finalized PSKB input with breakpoints",
+                ),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -13,8 +13,8 @@ use vecno_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath, Hash,
+    tx::{MutableTransaction, ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+    BlockHashSet, BlueWorkType, ChainPath, ChainScoreKind, Hash,
 };
 use vecno_utils::sync::rwlock::*;
 
@@ -285,6 +285,24 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_virtual_utxos(from_outpoint, chunk_size, skip_first)).await
     }
 
+    pub async fn async_get_chain_block_utxos(
+        &self,
+        chain_block: Hash,
+        script_public_keys: Option<Vec<ScriptPublicKey>>,
+    ) -> ConsensusResult<Vec<(TransactionOutpoint, UtxoEntry)>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_utxos(chain_block, script_public_keys)).await
+    }
+
+    pub async fn async_get_chain_blocks_by_score(
+        &self,
+        kind: ChainScoreKind,
+        start: u64,
+        end: Option<u64>,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_blocks_by_score(kind, start, end, limit)).await
+    }
+
     pub async fn async_get_tips(&self) -> Vec<Hash> {
         self.clone().spawn_blocking(|c| c.get_tips()).await
     }
@@ -1,10 +1,13 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, SamplingMode};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, SamplingMode};
 use rand::{thread_rng, Rng};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use secp256k1::Keypair;
 use vecno_addresses::{Address, Prefix, Version};
+use vecno_consensus::params::MAINNET_PARAMS;
 use vecno_consensus::processes::transaction_validator::tx_validation_in_utxo_context::{
-    check_scripts_par_iter, check_scripts_par_iter_pool, check_scripts_sequential,
+    check_scripts_par_iter, check_scripts_par_iter_pool, check_scripts_sequential, TxValidationFlags,
 };
+use vecno_consensus::processes::transaction_validator::TransactionValidator;
 use vecno_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync};
 use vecno_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
 use vecno_consensus_core::subnets::SubnetworkId;
@@ -89,7 +92,7 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                 let cache = Cache::new(inputs_count as u64);
                 b.iter(|| {
                     cache.clear();
-                    check_scripts_sequential(black_box(&cache), black_box(&tx.as_verifiable()), false).unwrap();
+                    check_scripts_sequential(black_box(&cache), black_box(&tx.as_verifiable()), Default::default()).unwrap();
                 })
             });
 
@@ -98,7 +101,7 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                 let cache = Cache::new(inputs_count as u64);
                 b.iter(|| {
                     cache.clear();
-                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), false).unwrap();
+                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), Default::default()).unwrap();
                 })
             });
 
@@ -110,8 +113,13 @@ fn benchmark_check_scripts(c: &mut Criterion) {
                         let cache = Cache::new(inputs_count as u64);
                         b.iter(|| {
                             cache.clear();
-                            check_scripts_par_iter_pool(black_box(&cache), black_box(&tx.as_verifiable()), black_box(&pool), false)
-                                .unwrap();
+                            check_scripts_par_iter_pool(
+                                black_box(&cache),
+                                black_box(&tx.as_verifiable()),
+                                black_box(&pool),
+                                Default::default(),
+                            )
+                            .unwrap();
                         })
                     });
                 }
@@ -147,17 +155,75 @@ fn benchmark_check_scripts_with_payload(c: &mut Criterion) {
                 let cache = Cache::new(inputs_count as u64);
                 b.iter(|| {
                     cache.clear();
-                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), false).unwrap();
+                    check_scripts_par_iter(black_box(&cache), black_box(&tx.as_verifiable()), Default::default()).unwrap();
                 })
             });
         }
     }
 }
 
+/// Benchmarks the validation of the transactions of a block as done by `validate_transactions_in_parallel`, with the
+/// scripts batch checked beforehand against checking them transaction by transaction.
+///
+/// Each iteration starts with a fresh validator, so that the signature cache is empty as for the transactions of a new block.
+fn benchmark_validate_transactions_in_parallel(c: &mut Criterion) {
+    const POV_DAA_SCORE: u64 = 1000;
+    for (txs_count, inputs_count) in [(1, 2), (10, 2), (10, 10), (50, 2), (50, 10), (100, 5)] {
+        let txs = (0..txs_count).map(|_| mock_tx_with_payload(inputs_count, 0, 0)).collect::<Vec<_>>();
+        let mutable_txs = txs.iter().map(|(tx, utxos)| MutableTransaction::with_entries(tx, utxos.clone())).collect::<Vec<_>>();
+        let txs = mutable_txs.iter().map(|tx| tx.as_verifiable()).collect::<Vec<_>>();
+        let validator = || {
+            let params = &MAINNET_PARAMS;
+            TransactionValidator::new_for_tests(
+                params.max_tx_inputs,
+                params.max_tx_outputs,
+                params.max_signature_script_len,
+                params.max_script_public_key_len,
+                params.ghostdag_k,
+                params.coinbase_payload_script_public_key_max_len,
+                params.coinbase_maturity,
+                Default::default(),
+            )
+        };
+        let mut group =
+            c.benchmark_group(format!("validate_transactions_in_parallel/txs: {txs_count}, inputs per tx: {inputs_count}"));
+        group.sampling_mode(SamplingMode::Flat);
+
+        group.bench_function("without batching", |b| {
+            b.iter_batched(
+                validator,
+                |validator| {
+                    txs.par_iter().for_each(|tx| {
+                        validator
+                            .validate_populated_transaction_and_get_fee(black_box(tx), POV_DAA_SCORE, TxValidationFlags::Full, None)
+                            .unwrap();
+                    })
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function("with batching", |b| {
+            b.iter_batched(
+                validator,
+                |validator| {
+                    let results = validator.validate_populated_transactions_in_parallel_and_get_fees(
+                        black_box(&txs),
+                        POV_DAA_SCORE,
+                        TxValidationFlags::Full,
+                    );
+                    assert!(results.iter().all(|result| result.is_ok()));
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().with_output_color(true).measurement_time(std::time::Duration::new(20, 0));
-    targets = benchmark_check_scripts, benchmark_check_scripts_with_payload
+    targets = benchmark_check_scripts, benchmark_check_scripts_with_payload, benchmark_validate_transactions_in_parallel
 }
 
 criterion_main!(benches);
@@ -29,6 +29,7 @@ export interface ITransactionOutput {
 export interface ITransactionOutputVerboseData {
     scriptPublicKeyType : string;
     scriptPublicKeyAddress : string;
+    scriptPublicKeyAsm : string;
 }
 "#;
 
@@ -6,12 +6,20 @@
 
 use crate::imports::*;
 use crate::result::Result;
+use crate::Transaction;
 use vecno_addresses::*;
 use vecno_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
     network::{NetworkType, NetworkTypeT},
-    tx::ScriptPublicKeyT,
+    tx::{PopulatedTransaction, ScriptPublicKeyT, VerifiableTransaction},
+};
+use vecno_txscript::{
+    caches::Cache,
+    debugger::{ScriptDebugger, ScriptKind, ScriptStep},
+    opcodes::OpCond,
+    script_class::ScriptClass,
+    standard, TxScriptEngine,
 };
-use vecno_txscript::{script_class::ScriptClass, standard};
 use vecno_utils::hex::ToHex;
 use vecno_wasm_core::types::{BinaryT, HexString};
 
@@ -85,3 +93,125 @@ pub fn is_script_pay_to_script_hash(script: BinaryT) -> Result<bool> {
     let script = script.try_as_vec_u8()?;
     Ok(ScriptClass::is_pay_to_script_hash(script.as_slice()))
 }
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_SCRIPT_TRACE: &'static str = r#"
+/**
+ * A single opcode executed by the script engine, together with the
+ * state of the engine right after it.
+ *
+ * @category Wallet SDK
+ */
+export interface IScriptStep {
+    script : "signatureScript" | "scriptPublicKey" | "redeemScript" | "standAlone";
+    pc : number;
+    opcode : number;
+    opcodeName : string;
+    data : HexString;
+    executed : boolean;
+    dataStack : HexString[];
+    altStack : HexString[];
+    condStack : ("true" | "false" | "skip")[];
+}
+
+/**
+ * Trace of the execution of the scripts of a transaction input.
+ * `error` is set if the input failed script validation.
+ *
+ * @category Wallet SDK
+ */
+export interface IScriptTrace {
+    steps : IScriptStep[];
+    error? : string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IScriptTrace")]
+    pub type IScriptTrace;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableScriptStep {
+    script: &'static str,
+    pc: usize,
+    opcode: u8,
+    opcode_name: &'static str,
+    data: String,
+    executed: bool,
+    data_stack: Vec<String>,
+    alt_stack: Vec<String>,
+    cond_stack: Vec<&'static str>,
+}
+
+impl From<&ScriptStep> for SerializableScriptStep {
+    fn from(step: &ScriptStep) -> Self {
+        Self {
+            script: match step.script {
+                ScriptKind::SignatureScript => "signatureScript",
+                ScriptKind::ScriptPublicKey => "scriptPublicKey",
+                ScriptKind::RedeemScript => "redeemScript",
+                ScriptKind::LeafScript => "leafScript",
+                ScriptKind::StandAlone => "standAlone",
+            },
+            pc: step.pc,
+            opcode: step.opcode,
+            opcode_name: step.opcode_name(),
+            data: step.data.to_hex(),
+            executed: step.executed,
+            data_stack: step.data_stack.iter().map(|item| item.to_hex()).collect(),
+            alt_stack: step.alt_stack.iter().map(|item| item.to_hex()).collect(),
+            cond_stack: step
+                .cond_stack
+                .iter()
+                .map(|cond| match cond {
+                    OpCond::True => "true",
+                    OpCond::False => "false",
+                    OpCond::Skip => "skip",
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SerializableScriptTrace {
+    steps: Vec<SerializableScriptStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Executes the scripts of a transaction input opcode by opcode and returns the
+/// trace of every executed opcode, together with the script error, if any.
+/// All transaction inputs must carry their UTXO entries.
+/// @param tx - The transaction ({@link Transaction}).
+/// @param input_index - The index of the input to trace.
+/// @category Wallet SDK
+#[wasm_bindgen(js_name = traceTransactionInput)]
+pub fn trace_transaction_input(tx: &Transaction, input_index: u32) -> Result<IScriptTrace> {
+    let (cctx, utxos) = tx.tx_and_utxos()?;
+    let input_index = input_index as usize;
+    if input_index >= cctx.inputs.len() {
+        return Err(Error::custom(format!("input index {input_index} is out of range")));
+    }
+    let populated_transaction = PopulatedTransaction::new(&cctx, utxos);
+    let (input, entry) = populated_transaction.populated_input(input_index);
+
+    let sig_cache = Cache::new(0);
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let mut debugger = ScriptDebugger::new(TxScriptEngine::from_transaction_input(
+        &populated_transaction,
+        input,
+        input_index,
+        entry,
+        &reused_values,
+        &sig_cache,
+        Default::default(),
+    ));
+    let error = debugger.run().err().map(|err| err.to_string());
+    let trace = SerializableScriptTrace { steps: debugger.trace().iter().map(Into::into).collect(), error };
+
+    Ok(serde_wasm_bindgen::to_value(&trace)?.unchecked_into())
+}
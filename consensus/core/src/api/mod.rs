@@ -17,10 +17,12 @@ use crate::{
         tx::TxResult,
     },
     header::Header,
+    integrity::IntegrityReport,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath,
+    tx::{MutableTransaction, ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet, BlueWorkType, ChainPath, ChainScoreKind,
 };
 use vecno_hashes::Hash;
 
@@ -187,6 +189,31 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Rebuilds the UTXO set as of the given selected chain block by reverse-applying the UTXO diffs of
+    /// the chain blocks above it to the virtual UTXO set. The rebuilt set is verified against the UTXO
+    /// commitment of the block header, and only the entries paying to `script_public_keys` (or all the
+    /// entries if `None`) are returned.
+    fn get_chain_block_utxos(
+        &self,
+        chain_block: Hash,
+        script_public_keys: Option<Vec<ScriptPublicKey>>,
+    ) -> ConsensusResult<Vec<(TransactionOutpoint, UtxoEntry)>> {
+        unimplemented!()
+    }
+
+    /// Returns up to `limit` selected chain blocks, ordered from low to high, whose score of the given kind
+    /// is at least `start` and, if `end` is provided, lower than `end`. Only chain blocks from the source
+    /// and above are considered, i.e., the history root on archival nodes and the pruning point otherwise.
+    fn get_chain_blocks_by_score(
+        &self,
+        kind: ChainScoreKind,
+        start: u64,
+        end: Option<u64>,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        unimplemented!()
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         unimplemented!()
     }
@@ -310,6 +337,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXO diff of a selected parent chain block, i.e., the UTXOs created and spent by
+    /// the transactions it accepted, relative to the UTXO state of its selected parent.
+    fn get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
@@ -362,6 +395,17 @@ pub trait ConsensusApi: Send + Sync {
     fn finality_point(&self) -> Hash {
         unimplemented!()
     }
+
+    /// Walks the consensus stores and reports any inconsistency found between them
+    fn check_integrity(&self) -> IntegrityReport {
+        unimplemented!()
+    }
+
+    /// Repairs the inconsistencies reported by [`Self::check_integrity`] where possible, rolling the
+    /// virtual state back to the pruning point if needed. Returns the report of a check made after the repair.
+    fn repair_integrity(&self) -> ConsensusResult<IntegrityReport> {
+        unimplemented!()
+    }
 }
 
 pub type DynConsensus = Arc<dyn ConsensusApi>;
@@ -122,6 +122,21 @@ pub struct Params {
     ///    - OpTxOutputSpk (0xc3): Get output script public key
     pub kip10_activation: ForkActivation,
 
+    /// DAA score from which tx engine supports the previously disabled splice opcodes
+    /// OpCat (0x7e), OpSubStr (0x7f), OpLeft (0x80) and OpRight (0x81), and the arithmetic
+    /// opcodes OpMul (0x95), OpDiv (0x96), OpMod (0x97), OpLShift (0x98) and OpRShift (0x99)
+    pub splice_and_arithmetic_activation: ForkActivation,
+
+    /// DAA score from which tx engine supports the remaining transaction introspection opcodes:
+    ///    - OpTxVersion (0xb2), OpTxLockTime (0xb5), OpTxSubnetId (0xb6), OpTxGas (0xb7), OpTxPayload (0xb8)
+    ///    - OpOutpointTxId (0xba), OpOutpointIndex (0xbb), OpTxInputScriptSig (0xbc), OpTxInputSeq (0xbd)
+    ///    - OpTxInputBlockDaaScore (0xc0), OpTxInputIsCoinbase (0xc1)
+    pub full_introspection_activation: ForkActivation,
+
+    /// DAA score from which tx engine validates spends of MAST outputs, which commit to the merkle root of a set of
+    /// scripts (`OpTrue OpData32 <root>`) and are spent by revealing a single leaf script along with its merkle proof
+    pub mast_activation: ForkActivation,
+
     /// DAA score after which the pre-deflationary period switches to the deflationary period
     pub premine_daa_score: u64,
 
@@ -299,6 +314,10 @@ impl Params {
         self.net.default_rpc_port()
     }
 
+    pub fn default_stratum_port(&self) -> u16 {
+        self.net.default_stratum_port()
+    }
+
     pub fn finality_duration(&self) -> u64 {
         self.target_time_per_block * self.finality_depth
     }
@@ -368,6 +387,9 @@ pub const MAINNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::always(),
     kip10_activation: ForkActivation::always(),
+    splice_and_arithmetic_activation: ForkActivation::never(),
+    full_introspection_activation: ForkActivation::never(),
+    mast_activation: ForkActivation::never(),
 
     // premine_daa_score is the DAA score after which the pre-deflationary period
     premine_daa_score: 1,
@@ -380,6 +402,12 @@ pub const MAINNET_PARAMS: Params = Params {
     payload_activation: ForkActivation::always(),
 };
 
+/// DAA score from which the script forks added on top of KIP-10 activate on testnet. Testnet runs at 1 BPS, so the
+/// score advances by ~31.5M a year and 100M is reached over three years after the testnet genesis, well ahead of the
+/// live testnet. Nodes are expected to upgrade before it is reached, so check it against the virtual DAA score of the
+/// live testnet (`getBlockDagInfo`) before releasing a node which changes it.
+pub const TESTNET_SCRIPT_FORKS_ACTIVATION_DAA_SCORE: u64 = 100_000_000;
+
 pub const TESTNET_PARAMS: Params = Params {
     peers: &[],
     net: NetworkId::with_suffix(NetworkType::Testnet, 10),
@@ -418,6 +446,9 @@ pub const TESTNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::never(),
     kip10_activation: ForkActivation::never(),
+    splice_and_arithmetic_activation: ForkActivation::new(TESTNET_SCRIPT_FORKS_ACTIVATION_DAA_SCORE),
+    full_introspection_activation: ForkActivation::new(TESTNET_SCRIPT_FORKS_ACTIVATION_DAA_SCORE),
+    mast_activation: ForkActivation::new(TESTNET_SCRIPT_FORKS_ACTIVATION_DAA_SCORE),
 
     premine_daa_score: 1,
     premine_phase_base_subsidy: 1500000000000000, // 15,000,000 premine
@@ -478,6 +509,9 @@ pub const SIMNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::always(),
     kip10_activation: ForkActivation::never(),
+    splice_and_arithmetic_activation: ForkActivation::never(),
+    full_introspection_activation: ForkActivation::never(),
+    mast_activation: ForkActivation::never(),
 
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
@@ -523,6 +557,9 @@ pub const DEVNET_PARAMS: Params = Params {
     storage_mass_parameter: STORAGE_MASS_PARAMETER,
     storage_mass_activation: ForkActivation::never(),
     kip10_activation: ForkActivation::never(),
+    splice_and_arithmetic_activation: ForkActivation::never(),
+    full_introspection_activation: ForkActivation::never(),
+    mast_activation: ForkActivation::never(),
 
     premine_daa_score: 1,
     premine_phase_base_subsidy: 1500000000000000, // 15,000,000 premine
@@ -17,6 +17,12 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --stratum-diff must be positive")]
+    StratumDifficultyNotPositive,
+
+    #[error("Configuration: --stratum-shares-per-min must be positive")]
+    StratumSharesPerMinNotPositive,
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -17,6 +17,12 @@ pub enum ConsensusError {
     #[error("some data is missing for block {0}")]
     MissingData(Hash),
 
+    #[error("block {0} is not a selected chain block in the future of the pruning point")]
+    NotAChainBlock(Hash),
+
+    #[error("the UTXO set rebuilt at block {0} hashes to {2} while the block commits to {1}")]
+    UtxoCommitmentMismatch(Hash, Hash, Hash),
+
     #[error("got unexpected pruning point")]
     UnexpectedPruningPoint,
 
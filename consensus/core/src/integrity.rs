@@ -0,0 +1,172 @@
+//!
+//! Types describing the outcome of a consensus database integrity check.
+//!
+
+use thiserror::Error;
+use vecno_hashes::Hash;
+
+/// The maximum amount of issues kept by a report. Issues found beyond this limit are only counted.
+pub const MAX_REPORTED_ISSUES: usize = 10_000;
+
+/// An inconsistency found between the consensus stores
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    #[error("block {0} has a status but no header")]
+    MissingHeader(Hash),
+
+    #[error("block {0} has a body status but no GHOSTDAG data")]
+    MissingGhostdagData(Hash),
+
+    #[error("block {0} has a body status but no reachability data")]
+    MissingReachabilityData(Hash),
+
+    #[error("block {0} has a body status but no relations")]
+    MissingRelations(Hash),
+
+    #[error("block {0} has a body status but no transactions")]
+    MissingBlockTransactions(Hash),
+
+    #[error("block {0} has parent {1} which has no relations")]
+    MissingRelationParent(Hash, Hash),
+
+    #[error("block {0} has child {1} which has no relations")]
+    DanglingRelationChild(Hash, Hash),
+
+    #[error("the reachability interval of block {0} is not nested within the interval of its tree parent {1}")]
+    ReachabilityIntervalNotNested(Hash, Hash),
+
+    #[error("block {0} is missing from the reachability tree children of its tree parent {1}")]
+    ReachabilityTreeChildMissing(Hash, Hash),
+
+    #[error("body tip {0} has no block body")]
+    InvalidBodyTip(Hash),
+
+    #[error("the headers selected tip {0} has no header status")]
+    InvalidHeadersSelectedTip(Hash),
+
+    #[error("virtual parent {0} is not UTXO valid")]
+    InvalidVirtualParent(Hash),
+
+    #[error("the selected chain tip {1} does not match the virtual selected parent {0}")]
+    SelectedChainTipMismatch(Hash, Hash),
+
+    #[error("the virtual UTXO set hashes to {1} while the virtual state multiset hashes to {0}")]
+    VirtualUtxoSetMismatch(Hash, Hash),
+
+    #[error("the UTXO set of pruning point {0} hashes to {2} while the pruning point commits to {1}")]
+    PruningUtxoSetMismatch(Hash, Hash, Hash),
+
+    #[error("store {0} could not be read: {1}")]
+    UnreadableStore(&'static str, String),
+}
+
+/// The way an integrity issue can be repaired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntegrityRepair {
+    /// The dangling store entry is deleted
+    RemoveEntry,
+
+    /// The virtual state and UTXO set are rolled back to the pruning point
+    RollbackToPruningPoint,
+
+    /// The database cannot be repaired in place and needs to be reset
+    Reset,
+}
+
+impl IntegrityIssue {
+    pub fn repair(&self) -> IntegrityRepair {
+        match self {
+            IntegrityIssue::DanglingRelationChild(..) | IntegrityIssue::InvalidBodyTip(_) => IntegrityRepair::RemoveEntry,
+            IntegrityIssue::InvalidVirtualParent(_)
+            | IntegrityIssue::SelectedChainTipMismatch(..)
+            | IntegrityIssue::VirtualUtxoSetMismatch(..) => IntegrityRepair::RollbackToPruningPoint,
+            IntegrityIssue::MissingHeader(_)
+            | IntegrityIssue::MissingGhostdagData(_)
+            | IntegrityIssue::MissingReachabilityData(_)
+            | IntegrityIssue::MissingRelations(_)
+            | IntegrityIssue::MissingBlockTransactions(_)
+            | IntegrityIssue::MissingRelationParent(..)
+            | IntegrityIssue::ReachabilityIntervalNotNested(..)
+            | IntegrityIssue::ReachabilityTreeChildMissing(..)
+            | IntegrityIssue::InvalidHeadersSelectedTip(_)
+            | IntegrityIssue::PruningUtxoSetMismatch(..)
+            | IntegrityIssue::UnreadableStore(..) => IntegrityRepair::Reset,
+        }
+    }
+}
+
+/// The report of a consensus database integrity check
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// The pruning point at the time of the check
+    pub pruning_point: Hash,
+
+    /// The amount of blocks whose store entries were checked
+    pub blocks_checked: u64,
+
+    /// The amount of UTXO entries hashed while checking the UTXO sets
+    pub utxos_checked: u64,
+
+    /// The issues found, up to [`MAX_REPORTED_ISSUES`]
+    pub issues: Vec<IntegrityIssue>,
+
+    /// The amount of issues found beyond [`MAX_REPORTED_ISSUES`]
+    pub omitted_issues: u64,
+}
+
+impl IntegrityReport {
+    pub fn new(pruning_point: Hash) -> Self {
+        Self { pruning_point, ..Default::default() }
+    }
+
+    pub fn push(&mut self, issue: IntegrityIssue) {
+        if self.issues.len() < MAX_REPORTED_ISSUES {
+            self.issues.push(issue);
+        } else {
+            self.omitted_issues += 1;
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The most invasive repair required for fixing all the reported issues, or `None` if the database is consistent
+    pub fn required_repair(&self) -> Option<IntegrityRepair> {
+        // Omitted issues are of unknown kind, so they are conservatively assumed to require a reset
+        if self.omitted_issues > 0 {
+            return Some(IntegrityRepair::Reset);
+        }
+        self.issues.iter().map(|issue| issue.repair()).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_repair() {
+        let mut report = IntegrityReport::new(Hash::from_u64_word(1));
+        assert!(report.is_consistent());
+        assert_eq!(report.required_repair(), None);
+
+        report.push(IntegrityIssue::InvalidBodyTip(Hash::from_u64_word(2)));
+        assert_eq!(report.required_repair(), Some(IntegrityRepair::RemoveEntry));
+
+        report.push(IntegrityIssue::VirtualUtxoSetMismatch(Hash::from_u64_word(3), Hash::from_u64_word(4)));
+        report.push(IntegrityIssue::DanglingRelationChild(Hash::from_u64_word(5), Hash::from_u64_word(6)));
+        assert_eq!(report.required_repair(), Some(IntegrityRepair::RollbackToPruningPoint));
+
+        report.push(IntegrityIssue::MissingGhostdagData(Hash::from_u64_word(7)));
+        assert_eq!(report.required_repair(), Some(IntegrityRepair::Reset));
+
+        let mut report = IntegrityReport::new(Hash::from_u64_word(1));
+        for i in 0..MAX_REPORTED_ISSUES as u64 + 5 {
+            report.push(IntegrityIssue::InvalidBodyTip(Hash::from_u64_word(i)));
+        }
+        assert_eq!(report.issues.len(), MAX_REPORTED_ISSUES);
+        assert_eq!(report.omitted_issues, 5);
+        assert_eq!(report.required_repair(), Some(IntegrityRepair::Reset));
+    }
+}
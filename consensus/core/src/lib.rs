@@ -25,6 +25,7 @@ pub mod daa_score_timestamp;
 pub mod errors;
 pub mod hashing;
 pub mod header;
+pub mod integrity;
 pub mod mass;
 pub mod merkle;
 pub mod muhash;
@@ -94,6 +95,13 @@ pub struct ChainPath {
     pub removed: Vec<Hash>,
 }
 
+/// The score by which selected chain blocks are located. Both scores are strictly increasing along the selected chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainScoreKind {
+    DaaScore,
+    BlueScore,
+}
+
 /// `hashes::Hash` writes 4 u64s so we just use the last one as the hash here
 #[derive(Default, Clone, Copy)]
 pub struct BlockHasher(u64);
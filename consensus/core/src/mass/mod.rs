@@ -30,7 +30,7 @@ pub fn transaction_estimated_serialized_size(tx: &Transaction) -> u64 {
     size
 }
 
-fn transaction_input_estimated_serialized_size(input: &TransactionInput) -> u64 {
+pub fn transaction_input_estimated_serialized_size(input: &TransactionInput) -> u64 {
     let mut size = 0;
     size += outpoint_estimated_serialized_size();
 
@@ -104,6 +104,12 @@ impl MassCalculator {
         mass_for_size + total_script_public_key_mass + total_sigops_mass
     }
 
+    /// Calculates the compute mass contributed by a single transaction input, i.e. the mass of its
+    /// serialized size and of its signature operations
+    pub fn calc_input_compute_mass(&self, input: &TransactionInput) -> u64 {
+        transaction_input_estimated_serialized_size(input) * self.mass_per_tx_byte + input.sig_op_count as u64 * self.mass_per_sig_op
+    }
+
     /// Calculates the storage mass for this populated transaction.
     /// Assumptions which must be verified before this call:
     ///     1. All output values are non-zero
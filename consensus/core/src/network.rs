@@ -66,6 +66,15 @@ impl NetworkType {
         }
     }
 
+    pub fn default_stratum_port(&self) -> u16 {
+        match self {
+            NetworkType::Mainnet => 5110,
+            NetworkType::Testnet => 5210,
+            NetworkType::Simnet => 5310,
+            NetworkType::Devnet => 5410,
+        }
+    }
+
     pub fn iter() -> impl Iterator<Item = Self> {
         static NETWORK_TYPES: [NetworkType; 4] =
             [NetworkType::Mainnet, NetworkType::Testnet, NetworkType::Devnet, NetworkType::Simnet];
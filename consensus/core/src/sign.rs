@@ -23,6 +23,9 @@ pub enum Error {
 
     #[error("The transaction is fully signed")]
     FullySigned,
+
+    #[error("Expected {0} sighash types, one per input, but got {1}")]
+    SigHashTypeCountMismatch(usize, usize),
 }
 
 /// A wrapper enum that represents the transaction signed state. A transaction
@@ -123,25 +126,48 @@ pub fn sign_with_multiple(mut mutable_tx: SignableTransaction, privkeys: Vec<[u8
 /// TODO (aspect) - merge this with `v1` fn above or refactor wallet core to use the script engine.
 /// Sign a transaction using schnorr
 #[allow(clippy::result_large_err)]
-pub fn sign_with_multiple_v2(mut mutable_tx: SignableTransaction, privkeys: &[[u8; 32]]) -> Signed {
+pub fn sign_with_multiple_v2(mutable_tx: SignableTransaction, privkeys: &[[u8; 32]]) -> Signed {
+    let hash_types = vec![SIG_HASH_ALL; mutable_tx.tx.inputs.len()];
+    sign_inputs_with_hash_types(mutable_tx, privkeys, &hash_types)
+}
+
+/// Sign a transaction using schnorr, signing every input with its own sighash type
+///
+/// The sighash types are given by input index, e.g. `SIG_HASH_ALL | SIG_HASH_ANY_ONE_CAN_PAY` lets other parties add
+/// inputs to the transaction without invalidating the signature of the input.
+///
+/// Fails with [`Error::SigHashTypeCountMismatch`] if the number of sighash types differs from the number of inputs.
+pub fn sign_with_multiple_and_hash_types(
+    mutable_tx: SignableTransaction,
+    privkeys: &[[u8; 32]],
+    hash_types: &[SigHashType],
+) -> Result<Signed, Error> {
+    if hash_types.len() != mutable_tx.tx.inputs.len() {
+        return Err(Error::SigHashTypeCountMismatch(mutable_tx.tx.inputs.len(), hash_types.len()));
+    }
+    Ok(sign_inputs_with_hash_types(mutable_tx, privkeys, hash_types))
+}
+
+/// Signs every input with its sighash type, assuming a sighash type per input
+fn sign_inputs_with_hash_types(mut mutable_tx: SignableTransaction, privkeys: &[[u8; 32]], hash_types: &[SigHashType]) -> Signed {
     let mut map = BTreeMap::new();
     for privkey in privkeys {
         let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, privkey).unwrap();
         let schnorr_public_key = schnorr_key.public_key().x_only_public_key().0;
-        let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize().into_iter()).chain(once(0xac)).collect_vec();
+        let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize()).chain(once(0xac)).collect_vec();
         map.insert(script_pub_key_script, schnorr_key);
     }
 
     let reused_values = SigHashReusedValuesUnsync::new();
     let mut additional_signatures_required = false;
-    for i in 0..mutable_tx.tx.inputs.len() {
+    for (i, hash_type) in hash_types.iter().copied().enumerate() {
         let script = mutable_tx.entries[i].as_ref().unwrap().script_public_key.script();
         if let Some(schnorr_key) = map.get(script) {
-            let sig_hash = calc_schnorr_signature_hash(&mutable_tx.as_verifiable(), i, SIG_HASH_ALL, &reused_values);
+            let sig_hash = calc_schnorr_signature_hash(&mutable_tx.as_verifiable(), i, hash_type, &reused_values);
             let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
             let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
             // This represents OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> (since signature length is 64 bytes and SIGHASH_TYPE is one byte)
-            mutable_tx.tx.inputs[i].signature_script = std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect();
+            mutable_tx.tx.inputs[i].signature_script = std::iter::once(65u8).chain(sig).chain([hash_type.to_u8()]).collect();
         } else {
             additional_signatures_required = true;
         }
@@ -175,7 +201,12 @@ pub fn verify(tx: &impl VerifiableTransaction) -> Result<(), Error> {
         let pk = &entry.script_public_key.script()[1..33];
         let pk = secp256k1::XOnlyPublicKey::from_slice(pk)?;
         let sig = secp256k1::schnorr::Signature::from_slice(&input.signature_script[1..65])?;
-        let sig_hash = calc_schnorr_signature_hash(tx, i, SIG_HASH_ALL, &reused_values);
+        // The sighash type follows the signature, and is assumed to be SIG_HASH_ALL if omitted
+        let hash_type = match input.signature_script.get(65) {
+            Some(&hash_type) => SigHashType::from_u8(hash_type).map_err(|err| Error::Message(format!("{err} for input: {i}")))?,
+            None => SIG_HASH_ALL,
+        };
+        let sig_hash = calc_schnorr_signature_hash(tx, i, hash_type, &reused_values);
         let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice())?;
         sig.verify(&msg, &pk)?;
     }
@@ -186,7 +217,7 @@ pub fn verify(tx: &impl VerifiableTransaction) -> Result<(), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{subnets::SubnetworkId, tx::*};
+    use crate::{hashing::sighash_type::SIG_HASH_ANY_ONE_CAN_PAY, subnets::SubnetworkId, tx::*};
     use secp256k1::{rand, Secp256k1};
     use std::str::FromStr;
 
@@ -259,4 +290,50 @@ mod tests {
 
         assert!(verify(&signed_tx.as_verifiable()).is_ok());
     }
+
+    #[test]
+    fn test_sign_with_hash_types() {
+        let keypairs = [[1u8; 32], [2u8; 32]].map(|key| secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &key).unwrap());
+        let entries = keypairs
+            .iter()
+            .map(|keypair| {
+                let script = once(0x20).chain(keypair.x_only_public_key().0.serialize()).chain(once(0xac)).collect_vec();
+                UtxoEntry::new(1000, ScriptPublicKey::from_vec(0, script), 0, false)
+            })
+            .collect_vec();
+        let inputs = (0..2)
+            .map(|index| TransactionInput::new(TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), index), vec![], 0, 1))
+            .collect();
+        let outputs = vec![TransactionOutput::new(1500, entries[0].script_public_key.clone())];
+        let unsigned_tx = Transaction::new(0, inputs, outputs, 0, SubnetworkId::from_bytes([0; 20]), 0, vec![]);
+        let anyone_can_pay = SigHashType::from_u8(SIG_HASH_ALL.to_u8() | SIG_HASH_ANY_ONE_CAN_PAY.to_u8()).unwrap();
+
+        let signed_tx = sign_with_multiple_and_hash_types(
+            SignableTransaction::with_entries(unsigned_tx, entries),
+            &keypairs.map(|keypair| keypair.secret_bytes()),
+            &[anyone_can_pay, SIG_HASH_ALL],
+        )
+        .unwrap()
+        .fully_signed()
+        .unwrap();
+        assert_eq!(signed_tx.tx.inputs[0].signature_script[65], anyone_can_pay.to_u8());
+        assert!(verify(&signed_tx.as_verifiable()).is_ok());
+
+        // The anyone-can-pay input remains valid on its own, unlike the input signing all the inputs
+        for (kept, valid) in [(0, true), (1, false)] {
+            let mut tx = signed_tx.clone();
+            tx.tx.inputs = vec![tx.tx.inputs[kept].clone()];
+            tx.entries = vec![tx.entries[kept].clone()];
+            assert_eq!(verify(&tx.as_verifiable()).is_ok(), valid);
+        }
+
+        // Both inputs sign all the outputs
+        let mut tx = signed_tx;
+        tx.tx.outputs[0].value -= 1;
+        assert!(verify(&tx.as_verifiable()).is_err());
+
+        // A sighash type is required for every input
+        let result = sign_with_multiple_and_hash_types(tx, &keypairs.map(|keypair| keypair.secret_bytes()), &[SIG_HASH_ALL]);
+        assert!(matches!(result, Err(Error::SigHashTypeCountMismatch(2, 1))));
+    }
 }
@@ -1,7 +1,12 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
+use vecno_consensus_core::header::Header;
 use vecno_hashes::Hash;
-use vecno_pow::{matrix::Matrix, xoshiro::XoShiRo256PlusPlus};
+use vecno_pow::{
+    calc_block_level_check_pow, calc_block_level_check_pow_batch,
+    matrix::{simd::supported_products, Matrix},
+    xoshiro::XoShiRo256PlusPlus,
+};
 
 // Big enough to make the cache not very useful
 const ITERS: usize = 1024;
@@ -27,7 +32,50 @@ fn bench_pow(c: &mut Criterion) {
             }
         });
     });
+
+    for (name, product) in supported_products() {
+        c.bench_function(&format!("HeavyHash ({name})"), |b| {
+            b.iter(|| {
+                for (matrix, &hash) in matrices.iter().zip(hashes.iter()) {
+                    black_box(matrix.heavy_hash_with(hash, product));
+                }
+            });
+        });
+    }
+}
+
+// The number of headers of a typical header sync batch
+const HEADERS_BATCH_SIZE: usize = 256;
+
+const MAX_BLOCK_LEVEL: u8 = 225;
+
+/// Compares checking the pow of a batch of headers one after the other, as done for every header independently by
+/// the header processor, with checking it as a batch
+fn bench_check_pow_batch(c: &mut Criterion) {
+    let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([42; 32]));
+    let gen_hash = |gen: &mut XoShiRo256PlusPlus| Hash::from_le_u64([gen.u64(), gen.u64(), gen.u64(), gen.u64()]);
+    let headers: Vec<_> = (0..HEADERS_BATCH_SIZE)
+        .map(|_| {
+            let mut header = Header::from_precomputed_hash(gen_hash(&mut gen), vec![gen_hash(&mut gen)]);
+            header.bits = 0x207fffff;
+            header.nonce = gen.u64();
+            header
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("Check PoW of a headers batch");
+    group.bench_function("Sequential", |b| {
+        b.iter(|| {
+            for header in &headers {
+                black_box(calc_block_level_check_pow(header, MAX_BLOCK_LEVEL));
+            }
+        });
+    });
+    group.bench_function(format!("Batch ({} threads)", rayon::current_num_threads()), |b| {
+        b.iter(|| black_box(calc_block_level_check_pow_batch(&headers, MAX_BLOCK_LEVEL)));
+    });
+    group.finish();
 }
 
-criterion_group!(benches, bench_pow);
+criterion_group!(benches, bench_pow, bench_check_pow_batch);
 criterion_main!(benches);
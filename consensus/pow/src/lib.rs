@@ -6,7 +6,7 @@ pub mod wasm;
 #[doc(hidden)]
 pub mod xoshiro;
 
-use std::cmp::max;
+use std::{borrow::Borrow, cmp::max};
 
 use crate::matrix::Matrix;
 use vecno_consensus_core::{hashing, header::Header, BlockLevel};
@@ -69,7 +69,79 @@ pub fn calc_block_level_check_pow(header: &Header, max_block_level: BlockLevel)
     (block_level, passed)
 }
 
+/// Same as [`calc_block_level_check_pow`] for a batch of headers, returning the results in the order of `headers`.
+///
+/// The headers are checked in parallel on the current rayon thread pool, and every worker thread regenerates the
+/// matrix of each header it checks in a single buffer of its own instead of building a [`State`] per header.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn calc_block_level_check_pow_batch<H: Borrow<Header> + Sync>(
+    headers: &[H],
+    max_block_level: BlockLevel,
+) -> Vec<(BlockLevel, bool)> {
+    use rayon::prelude::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static MATRIX: RefCell<Box<Matrix>> = RefCell::new(Box::new(Matrix::zeroed()));
+    }
+
+    headers
+        .par_iter()
+        .map(|header| {
+            let header = header.borrow();
+            if header.parents_by_level.is_empty() {
+                return (max_block_level, true); // Genesis has the max block level
+            }
+            let (passed, pow) = MATRIX.with(|matrix| check_pow_with_matrix(header, &mut matrix.borrow_mut()));
+            (calc_level_from_pow(pow, max_block_level), passed)
+        })
+        .collect()
+}
+
+/// Same as [`State::check_pow`] for the header nonce, generating the matrix into `matrix`
+#[cfg(not(target_arch = "wasm32"))]
+fn check_pow_with_matrix(header: &Header, matrix: &mut Matrix) -> (bool, Uint256) {
+    let target = Uint256::from_compact_target_bits(header.bits);
+    // Zero out the time and nonce.
+    let pre_pow_hash = hashing::header::hash_override_nonce_time(header, 0, 0);
+    matrix.regenerate(pre_pow_hash);
+    let hash = PowHash::new(pre_pow_hash, header.timestamp).finalize_with_nonce(header.nonce);
+    let pow = Uint256::from_le_bytes(matrix.heavy_hash(hash).as_bytes());
+    // The pow hash must be less or equal than the claimed target.
+    (pow <= target, pow)
+}
+
 pub fn calc_level_from_pow(pow: Uint256, max_block_level: BlockLevel) -> BlockLevel {
     let signed_block_level = max_block_level as i64 - pow.bits() as i64;
     max(signed_block_level, 0) as BlockLevel
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{calc_block_level_check_pow, calc_block_level_check_pow_batch};
+    use vecno_consensus_core::header::Header;
+    use vecno_hashes::Hash;
+
+    #[test]
+    fn test_calc_block_level_check_pow_batch() {
+        let max_block_level = 225;
+        let mut headers = vec![Header::from_precomputed_hash(Hash::from_u64_word(0), vec![])];
+        headers[0].parents_by_level.clear();
+        for i in 1..64u64 {
+            let mut header = Header::from_precomputed_hash(Hash::from_u64_word(i), vec![Hash::from_u64_word(100 + i)]);
+            // Alternate between an easy target, passed by any pow, and a hard one, failed by all but a negligible fraction
+            header.bits = if i % 2 == 0 { 0x207fffff } else { 0x03000001 };
+            header.nonce = i;
+            header.timestamp = i * 1000;
+            headers.push(header);
+        }
+
+        let results = calc_block_level_check_pow_batch(&headers, max_block_level);
+        assert_eq!(results.len(), headers.len());
+        assert_eq!(results[0], (max_block_level, true));
+        for (header, result) in headers.iter().zip(results) {
+            assert_eq!(calc_block_level_check_pow(header, max_block_level), result);
+        }
+        assert!(headers.iter().skip(1).step_by(2).all(|header| !calc_block_level_check_pow(header, max_block_level).1));
+    }
+}
@@ -2,6 +2,10 @@ use crate::xoshiro::XoShiRo256PlusPlus;
 use std::mem::MaybeUninit;
 use vecno_hashes::{Hash, KHeavyHash};
 
+pub mod simd;
+
+pub use simd::ProductFn;
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Matrix([[u16; 64]; 64]);
 
@@ -35,6 +39,30 @@ impl Matrix {
         }
     }
 
+    /// Generates the matrix of `hash` in place, reusing the buffer of `self`. Equivalent to [`Matrix::generate`].
+    pub fn regenerate(&mut self, hash: Hash) {
+        let mut generator = XoShiRo256PlusPlus::new(hash);
+        loop {
+            for row in self.0.iter_mut() {
+                let mut val = 0;
+                for (j, element) in row.iter_mut().enumerate() {
+                    let shift = j % 16;
+                    if shift == 0 {
+                        val = generator.u64();
+                    }
+                    *element = (val >> (4 * shift) & 0x0F) as u16;
+                }
+            }
+            if self.compute_rank() == 64 {
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn zeroed() -> Self {
+        Self([[0; 64]; 64])
+    }
+
     #[inline(always)]
     fn rand_matrix_no_rank_check(generator: &mut XoShiRo256PlusPlus) -> Self {
         Self(array_from_fn(|_| {
@@ -98,30 +126,47 @@ impl Matrix {
         rank
     }
 
+    /// Computes the heavy hash of `hash`, using the fastest matrix-vector product implementation supported by the CPU.
     pub fn heavy_hash(&self, hash: Hash) -> Hash {
+        self.heavy_hash_with(hash, simd::product_fn())
+    }
+
+    /// Computes the heavy hash of `hash` using the scalar reference implementation of the matrix-vector product.
+    pub fn heavy_hash_scalar(&self, hash: Hash) -> Hash {
+        self.heavy_hash_with(hash, Self::product_scalar)
+    }
+
+    #[inline(always)]
+    pub fn heavy_hash_with(&self, hash: Hash, product: ProductFn) -> Hash {
         // SAFETY: An uninitialized MaybrUninit is always safe.
-        let mut vec: [MaybeUninit<u8>; 64] = unsafe { MaybeUninit::uninit().assume_init() };
+        let mut vec: [MaybeUninit<u16>; 64] = unsafe { MaybeUninit::uninit().assume_init() };
         for (i, element) in hash.as_bytes().into_iter().enumerate() {
-            vec[2 * i].write(element >> 4);
-            vec[2 * i + 1].write(element & 0x0F);
+            vec[2 * i].write((element >> 4) as u16);
+            vec[2 * i + 1].write((element & 0x0F) as u16);
         }
         // SAFETY: The loop above wrote into all indexes.
-        let vec: [u8; 64] = unsafe { std::mem::transmute(vec) };
+        let vec: [u16; 64] = unsafe { std::mem::transmute(vec) };
+
+        let mut product = product(self, &vec);
+
+        // Concatenate 4 LSBs back to 8 bit xor with sum1
+        product.iter_mut().zip(hash.as_bytes()).for_each(|(p, h)| *p ^= h);
+        KHeavyHash::hash(Hash::from_bytes(product))
+    }
 
-        // Matrix-vector multiplication, convert to 4 bits, and then combine back to 8 bits.
-        let mut product: [u8; 32] = array_from_fn(|i| {
+    /// Matrix-vector multiplication, convert to 4 bits, and then combine back to 8 bits.
+    ///
+    /// This is the reference implementation, see [`simd`] for the vectorized ones.
+    fn product_scalar(&self, vec: &[u16; 64]) -> [u8; 32] {
+        array_from_fn(|i| {
             let mut sum1 = 0;
             let mut sum2 = 0;
             for (j, &elem) in vec.iter().enumerate() {
-                sum1 += self.0[2 * i][j] * (elem as u16);
-                sum2 += self.0[2 * i + 1][j] * (elem as u16);
+                sum1 += self.0[2 * i][j] * elem;
+                sum2 += self.0[2 * i + 1][j] * elem;
             }
             ((sum1 >> 10) << 4) as u8 | (sum2 >> 10) as u8
-        });
-
-        // Concatenate 4 LSBs back to 8 bit xor with sum1
-        product.iter_mut().zip(hash.as_bytes()).for_each(|(p, h)| *p ^= h);
-        KHeavyHash::hash(Hash::from_bytes(product))
+        })
     }
 }
 
@@ -139,7 +184,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::Matrix;
+    use super::{simd::supported_products, Matrix};
     use crate::xoshiro::XoShiRo256PlusPlus;
     use vecno_hashes::Hash;
 
@@ -237,8 +282,37 @@ mod tests {
             82, 46, 212, 218, 28, 192, 143, 92, 213, 66, 86, 63, 245, 241, 155, 189, 73, 159, 229, 180, 202, 105, 159, 166, 109, 172,
             128, 136, 169, 195, 97, 41,
         ]);
+        let scalar_hash = test_matrix.heavy_hash_scalar(hash);
+        for (name, product) in supported_products() {
+            assert_eq!(test_matrix.heavy_hash_with(hash, product), scalar_hash, "{name} heavy hash mismatch");
+        }
         assert_eq!(test_matrix.heavy_hash(hash), expected_hash);
     }
+
+    #[test]
+    fn test_heavy_hash_implementations() {
+        let mut gen = XoShiRo256PlusPlus::new(Hash::from_bytes([7; 32]));
+        let mut gen_hash = || Hash::from_le_u64([gen.u64(), gen.u64(), gen.u64(), gen.u64()]);
+
+        // Random matrices, along with the extreme ones where every row sum is either zero or maximal
+        let mut matrices: Vec<_> = (0..64).map(|_| Matrix::generate(gen_hash())).collect();
+        matrices.push(Matrix([[0; 64]; 64]));
+        matrices.push(Matrix([[15; 64]; 64]));
+        let mut hashes: Vec<_> = (0..64).map(|_| gen_hash()).collect();
+        hashes.push(Hash::from_bytes([0; 32]));
+        hashes.push(Hash::from_bytes([0xFF; 32]));
+
+        let products = supported_products();
+        for matrix in matrices.iter() {
+            for &hash in hashes.iter() {
+                let expected = matrix.heavy_hash_scalar(hash);
+                assert_eq!(matrix.heavy_hash(hash), expected);
+                for (name, product) in products.iter() {
+                    assert_eq!(matrix.heavy_hash_with(hash, *product), expected, "{name} heavy hash mismatch");
+                }
+            }
+        }
+    }
     #[test]
     fn test_generate_matrix() {
         #[rustfmt::skip]
@@ -311,5 +385,9 @@ mod tests {
         let hash = Hash::from_bytes([42; 32]);
         let matrix = Matrix::generate(hash);
         assert_eq!(matrix, expected_matrix);
+
+        let mut matrix = Matrix::generate(Hash::from_bytes([7; 32]));
+        matrix.regenerate(hash);
+        assert_eq!(matrix, expected_matrix);
     }
 }
@@ -0,0 +1,147 @@
+//! Vectorized implementations of the heavy hash matrix-vector product.
+//!
+//! Every implementation computes exactly what `Matrix::product_scalar` does, which serves as the reference.
+//! The fastest implementation supported by the running CPU is detected once, on first use.
+
+use super::{array_from_fn, Matrix};
+use std::sync::OnceLock;
+
+/// Multiplies the matrix by a vector of 4-bit elements, and packs the 4 MSBs of every pair of 14-bit row sums into a byte.
+pub type ProductFn = fn(&Matrix, &[u16; 64]) -> [u8; 32];
+
+/// Returns the fastest product implementation supported by the running CPU.
+#[inline]
+pub fn product_fn() -> ProductFn {
+    static PRODUCT: OnceLock<ProductFn> = OnceLock::new();
+    *PRODUCT.get_or_init(|| supported_products().last().unwrap().1)
+}
+
+/// Returns the product implementations supported by the running CPU along with their names, from the scalar
+/// reference implementation to the fastest one.
+pub fn supported_products() -> Vec<(&'static str, ProductFn)> {
+    let mut products: Vec<(&'static str, ProductFn)> = vec![("scalar", Matrix::product_scalar)];
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse4.1") {
+            products.push(("sse4.1", x86::product_sse41));
+        }
+        if is_x86_feature_detected!("avx2") {
+            products.push(("avx2", x86::product_avx2));
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            products.push(("neon", aarch64::product_neon));
+        }
+    }
+    products
+}
+
+/// Packs the 4 MSBs of every pair of row sums into a byte, the first row of the pair being the high nibble.
+#[inline(always)]
+fn pack(sums: &[u32; 64]) -> [u8; 32] {
+    array_from_fn(|i| ((sums[2 * i] >> 10) << 4) as u8 | (sums[2 * i + 1] >> 10) as u8)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::{pack, Matrix};
+    use std::arch::x86_64::*;
+
+    pub fn product_avx2(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        // SAFETY: Only exposed by `supported_products` if AVX2 support was detected.
+        unsafe { product_avx2_impl(matrix, vec) }
+    }
+
+    pub fn product_sse41(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        // SAFETY: Only exposed by `supported_products` if SSE4.1 support was detected.
+        unsafe { product_sse41_impl(matrix, vec) }
+    }
+
+    /// Sums rows by groups of 8, each row being 4 registers of 16 elements.
+    #[target_feature(enable = "avx2")]
+    unsafe fn product_avx2_impl(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        let v0 = _mm256_loadu_si256(vec.as_ptr() as *const __m256i);
+        let v1 = _mm256_loadu_si256(vec.as_ptr().add(16) as *const __m256i);
+        let v2 = _mm256_loadu_si256(vec.as_ptr().add(32) as *const __m256i);
+        let v3 = _mm256_loadu_si256(vec.as_ptr().add(48) as *const __m256i);
+
+        let mut sums = [0u32; 64];
+        for (rows, out) in matrix.0.chunks_exact(8).zip(sums.chunks_exact_mut(8)) {
+            let mut acc = [_mm256_setzero_si256(); 8];
+            for (row, acc) in rows.iter().zip(acc.iter_mut()) {
+                // Elements are 4-bit, so the signed 16-bit multiplications and 32-bit pair sums of `madd` are exact
+                let s0 = _mm256_madd_epi16(_mm256_loadu_si256(row.as_ptr() as *const __m256i), v0);
+                let s1 = _mm256_madd_epi16(_mm256_loadu_si256(row.as_ptr().add(16) as *const __m256i), v1);
+                let s2 = _mm256_madd_epi16(_mm256_loadu_si256(row.as_ptr().add(32) as *const __m256i), v2);
+                let s3 = _mm256_madd_epi16(_mm256_loadu_si256(row.as_ptr().add(48) as *const __m256i), v3);
+                *acc = _mm256_add_epi32(_mm256_add_epi32(s0, s1), _mm256_add_epi32(s2, s3));
+            }
+            // Horizontal sums: each 128-bit lane of `u0` ends up with the partial sums of rows 0..4 within that lane,
+            // and likewise for `u1` with rows 4..8. Adding the lanes completes the row sums.
+            let t0 = _mm256_hadd_epi32(acc[0], acc[1]);
+            let t1 = _mm256_hadd_epi32(acc[2], acc[3]);
+            let t2 = _mm256_hadd_epi32(acc[4], acc[5]);
+            let t3 = _mm256_hadd_epi32(acc[6], acc[7]);
+            let u0 = _mm256_hadd_epi32(t0, t1);
+            let u1 = _mm256_hadd_epi32(t2, t3);
+            let row_sums = _mm256_add_epi32(_mm256_permute2x128_si256(u0, u1, 0x20), _mm256_permute2x128_si256(u0, u1, 0x31));
+            _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, row_sums);
+        }
+        pack(&sums)
+    }
+
+    /// Sums rows by groups of 4, each row being 8 registers of 8 elements.
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn product_sse41_impl(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        let mut v = [_mm_setzero_si128(); 8];
+        for (k, v) in v.iter_mut().enumerate() {
+            *v = _mm_loadu_si128(vec.as_ptr().add(8 * k) as *const __m128i);
+        }
+
+        let mut sums = [0u32; 64];
+        for (rows, out) in matrix.0.chunks_exact(4).zip(sums.chunks_exact_mut(4)) {
+            let mut acc = [_mm_setzero_si128(); 4];
+            for (row, acc) in rows.iter().zip(acc.iter_mut()) {
+                for (k, v) in v.iter().enumerate() {
+                    *acc = _mm_add_epi32(*acc, _mm_madd_epi16(_mm_loadu_si128(row.as_ptr().add(8 * k) as *const __m128i), *v));
+                }
+            }
+            let row_sums = _mm_hadd_epi32(_mm_hadd_epi32(acc[0], acc[1]), _mm_hadd_epi32(acc[2], acc[3]));
+            _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, row_sums);
+        }
+        pack(&sums)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use super::{pack, Matrix};
+    use std::arch::aarch64::*;
+
+    pub fn product_neon(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        // SAFETY: Only exposed by `supported_products` if NEON support was detected.
+        unsafe { product_neon_impl(matrix, vec) }
+    }
+
+    /// Sums every row as 8 registers of 8 elements.
+    #[target_feature(enable = "neon")]
+    unsafe fn product_neon_impl(matrix: &Matrix, vec: &[u16; 64]) -> [u8; 32] {
+        let mut v = [vdupq_n_u16(0); 8];
+        for (k, v) in v.iter_mut().enumerate() {
+            *v = vld1q_u16(vec.as_ptr().add(8 * k));
+        }
+
+        let mut sums = [0u32; 64];
+        for (row, sum) in matrix.0.iter().zip(sums.iter_mut()) {
+            // A row sum is at most 64 * 15 * 15, so accumulating in 16-bit lanes cannot overflow
+            let mut acc = vdupq_n_u16(0);
+            for (k, v) in v.iter().enumerate() {
+                acc = vmlaq_u16(acc, vld1q_u16(row.as_ptr().add(8 * k)), *v);
+            }
+            *sum = vaddvq_u16(acc) as u32;
+        }
+        pack(&sums)
+    }
+}
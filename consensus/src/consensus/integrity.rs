@@ -0,0 +1,371 @@
+use super::storage::ConsensusStorage;
+use crate::{
+    model::stores::{
+        block_transactions::BlockTransactionsStoreReader,
+        children::ChildrenStore,
+        ghostdag::GhostdagStoreReader,
+        headers::HeaderStoreReader,
+        headers_selected_tip::HeadersSelectedTipStoreReader,
+        pruning::PruningStoreReader,
+        reachability::ReachabilityStoreReader,
+        relations::RelationsStoreReader,
+        selected_chain::{SelectedChainStore, SelectedChainStoreReader},
+        statuses::StatusesStoreReader,
+        tips::{TipsStore, TipsStoreReader},
+        utxo_set::DbUtxoSetStore,
+        virtual_state::VirtualStateStoreReader,
+        DB,
+    },
+    pipeline::virtual_processor::VirtualStateProcessor,
+};
+use rocksdb::WriteBatch;
+use std::sync::Arc;
+use vecno_consensus_core::{
+    blockhash::ORIGIN,
+    blockstatus::BlockStatus,
+    errors::consensus::{ConsensusError, ConsensusResult},
+    integrity::{IntegrityIssue, IntegrityRepair, IntegrityReport},
+    muhash::MuHashExtensions,
+    ChainPath,
+};
+use vecno_core::{info, warn};
+use vecno_database::prelude::{DirectDbWriter, StoreError, StoreResult, StoreResultExtensions};
+use vecno_hashes::Hash;
+use vecno_muhash::MuHash;
+
+/// The amount of checked blocks between consecutive progress logs
+const PROGRESS_LOG_INTERVAL: u64 = 100_000;
+
+/// Checks the consistency of the consensus stores with each other. Expected to run over a
+/// quiescent consensus, i.e., while no blocks are being processed.
+pub(super) struct IntegrityChecker<'a> {
+    storage: &'a ConsensusStorage,
+}
+
+impl<'a> IntegrityChecker<'a> {
+    pub fn new(storage: &'a ConsensusStorage) -> Self {
+        Self { storage }
+    }
+
+    pub fn check(&self) -> IntegrityReport {
+        let pruning_point = self.storage.pruning_point_store.read().pruning_point();
+        let mut report = IntegrityReport::new(pruning_point.as_ref().copied().unwrap_or_default());
+        if let Err(err) = pruning_point {
+            report.push(IntegrityIssue::UnreadableStore("pruning point", err.to_string()));
+        }
+        self.check_blocks(&mut report);
+        self.check_tips(&mut report);
+        self.check_virtual(&mut report);
+        self.check_utxo_sets(&mut report);
+        report
+    }
+
+    fn check_blocks(&self, report: &mut IntegrityReport) {
+        let statuses = self.storage.statuses_store.read();
+        let relations_stores = self.storage.relations_stores.read();
+        let relations = &relations_stores[0];
+        let reachability = self.storage.reachability_store.read();
+
+        for result in statuses.iterator() {
+            let (hash, status) = match result {
+                Ok(entry) => entry,
+                Err(err) => {
+                    report.push(IntegrityIssue::UnreadableStore("statuses", err.to_string()));
+                    return;
+                }
+            };
+            report.blocks_checked += 1;
+            if report.blocks_checked % PROGRESS_LOG_INTERVAL == 0 {
+                info!("Checked {} blocks", report.blocks_checked);
+            }
+
+            if status.has_block_header()
+                && matches!(read_optional(self.storage.headers_store.get_header(hash), "headers", report), Some(None))
+            {
+                report.push(IntegrityIssue::MissingHeader(hash));
+            }
+
+            match read_store(relations.has(hash), "relations", report) {
+                Some(true) => {
+                    if let Some(parents) = read_store(relations.get_parents(hash), "relations", report) {
+                        for parent in parents.iter().copied() {
+                            if parent != ORIGIN && read_store(relations.has(parent), "relations", report) == Some(false) {
+                                report.push(IntegrityIssue::MissingRelationParent(hash, parent));
+                            }
+                        }
+                    }
+                    if let Some(children) = read_store(relations.get_children(hash), "relations", report) {
+                        for child in children.read().iter().copied() {
+                            if read_store(relations.has(child), "relations", report) == Some(false) {
+                                report.push(IntegrityIssue::DanglingRelationChild(hash, child));
+                            }
+                        }
+                    }
+                }
+                Some(false) if status.has_block_body() => report.push(IntegrityIssue::MissingRelations(hash)),
+                _ => {}
+            }
+
+            // Blocks below the pruning point keep only partial data, so the remaining checks apply to blocks with a body
+            if !status.has_block_body() {
+                continue;
+            }
+
+            if matches!(read_optional(self.storage.ghostdag_store.get_blue_score(hash), "GHOSTDAG", report), Some(None)) {
+                report.push(IntegrityIssue::MissingGhostdagData(hash));
+            }
+            if matches!(read_optional(self.storage.block_transactions_store.get(hash), "block transactions", report), Some(None)) {
+                report.push(IntegrityIssue::MissingBlockTransactions(hash));
+            }
+
+            match read_store(reachability.has(hash), "reachability", report) {
+                Some(true) => {}
+                Some(false) => {
+                    report.push(IntegrityIssue::MissingReachabilityData(hash));
+                    continue;
+                }
+                None => continue,
+            }
+            let (Some(interval), Some(tree_parent)) = (
+                read_store(reachability.get_interval(hash), "reachability", report),
+                read_store(reachability.get_parent(hash), "reachability", report),
+            ) else {
+                continue;
+            };
+            match read_optional(reachability.get_interval(tree_parent), "reachability", report) {
+                Some(Some(parent_interval)) if parent_interval.strictly_contains(interval) => {}
+                Some(_) => report.push(IntegrityIssue::ReachabilityIntervalNotNested(hash, tree_parent)),
+                None => {}
+            }
+            match read_optional(reachability.get_children(tree_parent), "reachability", report) {
+                Some(Some(children)) if children.contains(&hash) => {}
+                Some(_) => report.push(IntegrityIssue::ReachabilityTreeChildMissing(hash, tree_parent)),
+                None => {}
+            }
+        }
+    }
+
+    fn check_tips(&self, report: &mut IntegrityReport) {
+        let statuses = self.storage.statuses_store.read();
+        if let Some(tips) = read_store(self.storage.body_tips_store.read().get(), "body tips", report) {
+            for tip in tips.read().iter().copied() {
+                match read_optional(statuses.get(tip), "statuses", report) {
+                    Some(Some(status)) if status.has_block_body() => {}
+                    Some(_) => report.push(IntegrityIssue::InvalidBodyTip(tip)),
+                    None => {}
+                }
+            }
+        }
+
+        if let Some(hst) = read_store(self.storage.headers_selected_tip_store.read().get(), "headers selected tip", report) {
+            match read_optional(statuses.get(hst.hash), "statuses", report) {
+                Some(Some(status)) if status.has_block_header() => {}
+                Some(_) => report.push(IntegrityIssue::InvalidHeadersSelectedTip(hst.hash)),
+                None => {}
+            }
+        }
+    }
+
+    fn check_virtual(&self, report: &mut IntegrityReport) {
+        let statuses = self.storage.statuses_store.read();
+        let Some(virtual_state) = read_store(self.storage.virtual_stores.read().state.get(), "virtual state", report) else {
+            return;
+        };
+        for parent in virtual_state.parents.iter().copied() {
+            match read_optional(statuses.get(parent), "statuses", report) {
+                Some(Some(BlockStatus::StatusUTXOValid)) | None => {}
+                Some(_) => report.push(IntegrityIssue::InvalidVirtualParent(parent)),
+            }
+        }
+
+        let sink = virtual_state.ghostdag_data.selected_parent;
+        match self.storage.selected_chain_store.read().get_tip() {
+            Ok((_, chain_tip)) if chain_tip == sink => {}
+            Ok((_, chain_tip)) => report.push(IntegrityIssue::SelectedChainTipMismatch(sink, chain_tip)),
+            Err(err) => report.push(IntegrityIssue::UnreadableStore("selected chain", err.to_string())),
+        }
+    }
+
+    fn check_utxo_sets(&self, report: &mut IntegrityReport) {
+        let pruning_utxoset = self.storage.pruning_utxoset_stores.read();
+        if let Some(position) = read_store(pruning_utxoset.utxoset_position(), "pruning point UTXO set position", report) {
+            if let Some(computed) = self.utxo_set_hash(&pruning_utxoset.utxo_set, "pruning point UTXO set", report) {
+                match read_optional(self.storage.headers_store.get_header(position), "headers", report) {
+                    Some(Some(header)) if header.utxo_commitment != computed => {
+                        report.push(IntegrityIssue::PruningUtxoSetMismatch(position, header.utxo_commitment, computed))
+                    }
+                    Some(Some(_)) | None => {}
+                    Some(None) => report.push(IntegrityIssue::MissingHeader(position)),
+                }
+            }
+        }
+        drop(pruning_utxoset);
+
+        let virtual_stores = self.storage.virtual_stores.read();
+        let Some(virtual_state) = read_store(virtual_stores.state.get(), "virtual state", report) else {
+            return;
+        };
+        if let Some(computed) = self.utxo_set_hash(&virtual_stores.utxo_set, "virtual UTXO set", report) {
+            let expected = virtual_state.multiset.clone().finalize();
+            if computed != expected {
+                report.push(IntegrityIssue::VirtualUtxoSetMismatch(expected, computed));
+            }
+        }
+    }
+
+    fn utxo_set_hash(&self, utxo_set: &DbUtxoSetStore, name: &'static str, report: &mut IntegrityReport) -> Option<Hash> {
+        let mut multiset = MuHash::new();
+        for result in utxo_set.iterator() {
+            match result {
+                Ok((outpoint, entry)) => multiset.add_utxo(&outpoint, &entry),
+                Err(err) => {
+                    report.push(IntegrityIssue::UnreadableStore(name, err.to_string()));
+                    return None;
+                }
+            }
+            report.utxos_checked += 1;
+        }
+        Some(multiset.finalize())
+    }
+}
+
+/// Returns the value read from `store`, or reports the store as unreadable
+fn read_store<T>(result: StoreResult<T>, store: &'static str, report: &mut IntegrityReport) -> Option<T> {
+    result.map_err(|err| report.push(IntegrityIssue::UnreadableStore(store, err.to_string()))).ok()
+}
+
+/// Like [`read_store`], but with a missing entry read as `Some(None)` so that callers can report it as an issue of its own
+fn read_optional<T>(result: StoreResult<T>, store: &'static str, report: &mut IntegrityReport) -> Option<Option<T>> {
+    match result {
+        Err(StoreError::KeyNotFound(_)) => Some(None),
+        result => read_store(result, store, report).map(Some),
+    }
+}
+
+/// Repairs the issues of `report` which do not require a database reset. Returns whether any repair was made.
+pub(super) fn repair(
+    db: &Arc<DB>,
+    storage: &ConsensusStorage,
+    virtual_processor: &VirtualStateProcessor,
+    report: &IntegrityReport,
+) -> ConsensusResult<bool> {
+    match report.required_repair() {
+        None => return Ok(false),
+        Some(IntegrityRepair::Reset) => {
+            return Err(ConsensusError::General("the database has inconsistencies which cannot be repaired in place"))
+        }
+        Some(IntegrityRepair::RemoveEntry) | Some(IntegrityRepair::RollbackToPruningPoint) => {}
+    }
+
+    for issue in report.issues.iter() {
+        match *issue {
+            IntegrityIssue::DanglingRelationChild(parent, child) => {
+                warn!("Removing dangling child {} of block {}", child, parent);
+                storage.relations_stores.write()[0].delete_child(DirectDbWriter::new(db), parent, child).unwrap();
+            }
+            IntegrityIssue::InvalidBodyTip(tip) => {
+                warn!("Removing invalid body tip {}", tip);
+                storage.body_tips_store.write().prune_tips_with_writer(DirectDbWriter::new(db), &[tip]).unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    if report.required_repair() == Some(IntegrityRepair::RollbackToPruningPoint) {
+        rollback_to_pruning_point(db, storage, virtual_processor)?;
+    }
+    Ok(true)
+}
+
+/// Resets the virtual state to the pruning point and its UTXO set. The chain above the pruning point
+/// is kept as is and is resolved back into the virtual state once new blocks arrive.
+fn rollback_to_pruning_point(
+    db: &Arc<DB>,
+    storage: &ConsensusStorage,
+    virtual_processor: &VirtualStateProcessor,
+) -> ConsensusResult<()> {
+    let pruning_point = storage
+        .pruning_point_store
+        .read()
+        .pruning_point()
+        .map_err(|_| ConsensusError::General("the pruning point store could not be read"))?;
+    let pruning_utxoset = storage.pruning_utxoset_stores.read();
+    let position = pruning_utxoset
+        .utxoset_position()
+        .map_err(|_| ConsensusError::General("the pruning point UTXO set position could not be read"))?;
+    if position != pruning_point {
+        return Err(ConsensusError::General("the pruning point UTXO set is not synced with the pruning point"));
+    }
+    let mut multiset = MuHash::new();
+    for result in pruning_utxoset.utxo_set.iterator() {
+        let (outpoint, entry) = result.map_err(|_| ConsensusError::General("the pruning point UTXO set could not be read"))?;
+        multiset.add_utxo(&outpoint, &entry);
+    }
+    drop(pruning_utxoset);
+
+    warn!("Rolling the virtual state back to pruning point {}", pruning_point);
+    {
+        let mut selected_chain_write = storage.selected_chain_store.write();
+        let pruning_point_index = selected_chain_write
+            .get_by_hash(pruning_point)
+            .unwrap_option()
+            .ok_or(ConsensusError::General("the pruning point is missing from the selected chain"))?;
+        let unreadable = |_| ConsensusError::General("the selected chain store could not be read");
+        let (tip_index, _) = selected_chain_write.get_tip().map_err(unreadable)?;
+        let removed = (pruning_point_index + 1..=tip_index)
+            .rev()
+            .map(|index| selected_chain_write.get_by_index(index).map_err(unreadable))
+            .collect::<ConsensusResult<_>>()?;
+        let mut batch = WriteBatch::default();
+        selected_chain_write.apply_changes(&mut batch, &ChainPath { added: vec![], removed }).unwrap();
+        db.write(batch).unwrap();
+    }
+
+    virtual_processor
+        .import_pruning_point_utxo_set(pruning_point, multiset)
+        .map_err(|_| ConsensusError::General("failed rebuilding the virtual state from the pruning point UTXO set"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consensus::test_consensus::TestConsensus;
+    use crate::model::stores::{children::ChildrenStore, tips::TipsStore};
+    use vecno_consensus_core::{
+        api::ConsensusApi,
+        config::{params::DEVNET_PARAMS, ConfigBuilder},
+        integrity::{IntegrityIssue, IntegrityRepair},
+    };
+    use vecno_database::prelude::DirectDbWriter;
+    use vecno_hashes::Hash;
+
+    #[tokio::test]
+    async fn test_check_and_repair_integrity() {
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let tc = TestConsensus::new(&config);
+        let wait_handles = tc.init();
+        tc.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+        tc.add_utxo_valid_block_with_parents(2.into(), vec![1.into()], vec![]).await.unwrap();
+        assert!(tc.check_integrity().is_consistent(), "a freshly built consensus should be consistent");
+
+        // Corrupt the stores with a child and a body tip referring to a block unknown to the consensus
+        let consensus = tc.consensus_clone();
+        let unknown = Hash::from_u64_word(100);
+        consensus.storage.relations_stores.write()[0].insert_child(DirectDbWriter::new(&consensus.db), 2.into(), unknown).unwrap();
+        consensus.storage.body_tips_store.write().add_tip(unknown, &[]).unwrap();
+
+        let report = tc.check_integrity();
+        assert!(report.issues.contains(&IntegrityIssue::DanglingRelationChild(2.into(), unknown)), "{:?}", report.issues);
+        assert!(report.issues.contains(&IntegrityIssue::InvalidBodyTip(unknown)), "{:?}", report.issues);
+        assert_eq!(report.required_repair(), Some(IntegrityRepair::RemoveEntry));
+
+        let report = tc.repair_integrity().unwrap();
+        assert!(report.is_consistent(), "the repaired consensus should be consistent: {:?}", report.issues);
+        assert!(!tc.body_tips().contains(&unknown));
+
+        // The repaired consensus keeps processing blocks
+        tc.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![]).await.unwrap();
+        assert!(tc.check_integrity().is_consistent());
+
+        tc.shutdown(wait_handles);
+    }
+}
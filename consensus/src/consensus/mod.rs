@@ -1,6 +1,7 @@
 pub mod cache_policy_builder;
 pub mod ctl;
 pub mod factory;
+mod integrity;
 pub mod services;
 pub mod storage;
 pub mod test_consensus;
@@ -24,7 +25,9 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
+            virtual_state::VirtualStateStoreReader,
             DB,
         },
     },
@@ -61,13 +64,15 @@ use vecno_consensus_core::{
         tx::TxResult,
     },
     header::Header,
+    integrity::IntegrityReport,
     merkle::calc_hash_merkle_root,
     muhash::MuHashExtensions,
     network::NetworkType,
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
-    BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
+    tx::{MutableTransaction, ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
+    BlockHashSet, BlueWorkType, ChainPath, ChainScoreKind, HashMapCustomHasher,
 };
 use vecno_consensus_notify::root::ConsensusNotificationRoot;
 
@@ -85,7 +90,7 @@ use vecno_txscript::caches::TxScriptCacheCounters;
 
 use std::{
     cmp::Reverse,
-    collections::BinaryHeap,
+    collections::{BinaryHeap, HashSet},
     future::Future,
     iter::once,
     ops::Deref,
@@ -97,7 +102,7 @@ use std::{
 };
 use tokio::sync::oneshot;
 
-use self::{services::ConsensusServices, storage::ConsensusStorage};
+use self::{integrity::IntegrityChecker, services::ConsensusServices, storage::ConsensusStorage};
 
 use crate::model::stores::selected_chain::SelectedChainStoreReader;
 
@@ -689,6 +694,110 @@ impl ConsensusApi for Consensus {
         iter.map(|item| item.unwrap()).collect()
     }
 
+    fn get_chain_block_utxos(
+        &self,
+        chain_block: Hash,
+        script_public_keys: Option<Vec<ScriptPublicKey>>,
+    ) -> ConsensusResult<Vec<(TransactionOutpoint, UtxoEntry)>> {
+        // Collect everything needed from the virtual state under the locks, and release them before scanning the
+        // UTXO set. The pruning lock guarantees the UTXO diffs of the chain are not pruned meanwhile, while the virtual
+        // stores lock keeps the virtual UTXO set, the virtual diff and the selected chain consistent with each other
+        let (diff, expected, utxo_set, snapshot) = {
+            let _guard = self.pruning_lock.blocking_read();
+            let virtual_stores = self.virtual_stores.read();
+            let selected_chain = self.selected_chain_store.read();
+
+            // The selected chain store only holds chain blocks from the pruning point and above
+            let chain_block_index =
+                selected_chain.get_by_hash(chain_block).unwrap_option().ok_or(ConsensusError::NotAChainBlock(chain_block))?;
+            let (sink_index, _) = selected_chain.get_tip().unwrap();
+            let expected = self
+                .headers_store
+                .get_header(chain_block)
+                .unwrap_option()
+                .ok_or(ConsensusError::MissingData(chain_block))?
+                .utxo_commitment;
+
+            // Accumulate the reversed diffs leading from the virtual UTXO state down to the state of the chain block
+            let mut diff = virtual_stores.state.get().unwrap().utxo_diff.clone().to_reversed();
+            for index in (chain_block_index + 1..=sink_index).rev() {
+                let block_diff = self.utxo_diffs_store.get(selected_chain.get_by_index(index).unwrap()).unwrap();
+                diff.with_diff_in_place(&block_diff.as_reversed())
+                    .map_err(|_| ConsensusError::General("the UTXO diffs of the selected chain do not compose"))?;
+            }
+
+            // The virtual UTXO set is only written under the virtual stores write lock, so a snapshot taken
+            // here matches the virtual state the diff was collected against
+            (diff, expected, virtual_stores.utxo_set.clone(), self.db.snapshot())
+        };
+
+        let script_public_keys: Option<HashSet<ScriptPublicKey>> = script_public_keys.map(|spks| spks.into_iter().collect());
+        let is_requested = |entry: &UtxoEntry| script_public_keys.as_ref().is_none_or(|spks| spks.contains(&entry.script_public_key));
+        let mut multiset = MuHash::new();
+        let mut entries = Vec::new();
+        for (outpoint, entry) in utxo_set.snapshot_iterator(&snapshot).map(|item| item.unwrap()) {
+            if diff.remove.contains_key(&outpoint) {
+                continue;
+            }
+            multiset.add_utxo(&outpoint, &entry);
+            if is_requested(&entry) {
+                entries.push((outpoint, UtxoEntry::clone(&entry)));
+            }
+        }
+        for (outpoint, entry) in diff.add {
+            multiset.add_utxo(&outpoint, &entry);
+            if is_requested(&entry) {
+                entries.push((outpoint, entry));
+            }
+        }
+
+        let computed = multiset.finalize();
+        if computed != expected {
+            return Err(ConsensusError::UtxoCommitmentMismatch(chain_block, expected, computed));
+        }
+        Ok(entries)
+    }
+
+    fn get_chain_blocks_by_score(
+        &self,
+        kind: ChainScoreKind,
+        start: u64,
+        end: Option<u64>,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        let _guard = self.pruning_lock.blocking_read();
+        let selected_chain = self.selected_chain_store.read();
+        let low_index = selected_chain
+            .get_by_hash(self.get_source())
+            .unwrap_option()
+            .ok_or(ConsensusError::General("the source is missing from the selected chain"))?;
+        let (tip_index, _) = selected_chain.get_tip().unwrap();
+        let score = |index: u64| {
+            let hash = selected_chain.get_by_index(index).unwrap();
+            match kind {
+                ChainScoreKind::DaaScore => self.headers_store.get_daa_score(hash).unwrap(),
+                ChainScoreKind::BlueScore => self.headers_store.get_blue_score(hash).unwrap(),
+            }
+        };
+
+        // Binary search for the lowest chain index whose score is at least `start`, relying on scores strictly increasing along the chain
+        let (mut low, mut high) = (low_index, tip_index + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if score(mid) < start {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok((low..=tip_index)
+            .take_while(|&index| end.is_none_or(|end| score(index) < end))
+            .take(limit)
+            .map(|index| selected_chain.get_by_index(index).unwrap())
+            .collect())
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         self.body_tips_store.read().get().unwrap().read().iter().copied().collect_vec()
     }
@@ -943,6 +1052,10 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.storage.utxo_diffs_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
@@ -1027,4 +1140,18 @@ impl ConsensusApi for Consensus {
     fn finality_point(&self) -> Hash {
         self.virtual_processor.virtual_finality_point(&self.lkg_virtual_state.load().ghostdag_data, self.pruning_point())
     }
+
+    fn check_integrity(&self) -> IntegrityReport {
+        let _guard = self.pruning_lock.blocking_read();
+        IntegrityChecker::new(&self.storage).check()
+    }
+
+    fn repair_integrity(&self) -> ConsensusResult<IntegrityReport> {
+        let _guard = self.pruning_lock.blocking_write();
+        let report = IntegrityChecker::new(&self.storage).check();
+        if !integrity::repair(&self.db, &self.storage, &self.virtual_processor, &report)? {
+            return Ok(report);
+        }
+        Ok(IntegrityChecker::new(&self.storage).check())
+    }
 }
@@ -147,6 +147,9 @@ impl ConsensusServices {
             mass_calculator.clone(),
             params.storage_mass_activation,
             params.kip10_activation,
+            params.splice_and_arithmetic_activation,
+            params.full_introspection_activation,
+            params.mast_activation,
             params.payload_activation,
         );
 
@@ -1,6 +1,6 @@
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rocksdb::WriteBatch;
-use std::sync::Arc;
+use std::{error::Error, sync::Arc};
 use vecno_consensus_core::{blockstatus::BlockStatus, BlockHasher};
 use vecno_database::registry::DatabaseStorePrefixes;
 
@@ -46,6 +46,17 @@ impl DbStatusesStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Iterates all the statuses in the store, bypassing the cache
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(Hash, BlockStatus), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| match iter_result {
+            Ok((key_bytes, status)) => match Hash::try_from_slice(key_bytes.as_ref()) {
+                Ok(hash) => Ok((hash, status)),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        })
+    }
 }
 
 pub trait StatusesStoreBatchExtensions {
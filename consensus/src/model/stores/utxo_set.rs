@@ -8,9 +8,9 @@ use vecno_consensus_core::{
     },
 };
 use vecno_database::prelude::StoreResultExtensions;
-use vecno_database::prelude::DB;
 use vecno_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use vecno_database::prelude::{CachePolicy, StoreError};
+use vecno_database::prelude::{DbSnapshot, DB};
 use vecno_hashes::Hash;
 
 type UtxoCollectionIterator<'a> = Box<dyn Iterator<Item = Result<(TransactionOutpoint, UtxoEntry), Box<dyn Error>>> + 'a>;
@@ -112,7 +112,21 @@ impl DbUtxoSetStore {
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(TransactionOutpoint, Arc<UtxoEntry>), Box<dyn Error>>> + '_ {
-        self.access.iterator().map(|iter_result| match iter_result {
+        self.access.iterator().map(Self::map_iter_result)
+    }
+
+    /// Same as [`Self::iterator`] but reading the UTXO set as of the given DB snapshot
+    pub fn snapshot_iterator<'a>(
+        &'a self,
+        snapshot: &'a DbSnapshot<'a>,
+    ) -> impl Iterator<Item = Result<(TransactionOutpoint, Arc<UtxoEntry>), Box<dyn Error>>> + 'a {
+        self.access.snapshot_iterator(snapshot).map(Self::map_iter_result)
+    }
+
+    fn map_iter_result(
+        iter_result: Result<(Box<[u8]>, Arc<UtxoEntry>), Box<dyn Error>>,
+    ) -> Result<(TransactionOutpoint, Arc<UtxoEntry>), Box<dyn Error>> {
+        match iter_result {
             Ok((key_bytes, utxo_entry)) => match UtxoKey::try_from(key_bytes.as_ref()) {
                 Ok(utxo_key) => {
                     let outpoint: TransactionOutpoint = utxo_key.into();
@@ -121,7 +135,7 @@ impl DbUtxoSetStore {
                 Err(e) => Err(e.into()),
             },
             Err(e) => Err(e),
-        })
+        }
     }
 
     /// Clear the store completely in DB and cache
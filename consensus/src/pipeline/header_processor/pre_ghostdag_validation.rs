@@ -99,10 +99,13 @@ impl HeaderProcessor {
     }
 
     fn check_pow_and_calc_block_level(&self, header: &Header) -> BlockProcessResult<BlockLevel> {
-        let state = vecno_pow::State::new(header);
-        let (passed, pow) = state.check_pow(header.nonce);
+        let (block_level, passed) = self.take_batch_checked_pow(header.hash).unwrap_or_else(|| {
+            let state = vecno_pow::State::new(header);
+            let (passed, pow) = state.check_pow(header.nonce);
+            (calc_level_from_pow(pow, self.max_block_level), passed)
+        });
         if passed || self.skip_proof_of_work {
-            Ok(calc_level_from_pow(pow, self.max_block_level))
+            Ok(block_level)
         } else {
             Err(RuleError::InvalidPoW)
         }
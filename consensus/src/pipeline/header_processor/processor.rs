@@ -29,10 +29,13 @@ use crate::{
 };
 use crossbeam_channel::{Receiver, Sender};
 use itertools::Itertools;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use rayon::ThreadPool;
 use rocksdb::WriteBatch;
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc},
+};
 use vecno_consensus_core::{
     blockhash::{BlockHashes, ORIGIN},
     blockstatus::BlockStatus::{self, StatusHeaderOnly, StatusInvalid},
@@ -47,6 +50,10 @@ use vecno_utils::vec::VecExtensions;
 
 use super::super::ProcessingCounters;
 
+/// The maximal number of queued block processing messages received at once, so that the pow of their headers is
+/// checked as a batch
+const POW_BATCH_SIZE: usize = 512;
+
 pub struct HeaderProcessingContext {
     pub hash: Hash,
     pub header: Arc<Header>,
@@ -152,6 +159,9 @@ pub struct HeaderProcessor {
     // Dependency manager
     task_manager: BlockTaskDependencyManager,
 
+    // The (block level, passed) pow results of queued headers, checked as a batch ahead of header validation
+    batch_checked_pow: Mutex<HashMap<Hash, (BlockLevel, bool)>>,
+
     // Counters
     counters: Arc<ProcessingCounters>,
 }
@@ -197,6 +207,7 @@ impl HeaderProcessor {
             parents_manager: services.parents_manager.clone(),
 
             task_manager: BlockTaskDependencyManager::new(),
+            batch_checked_pow: Mutex::new(HashMap::new()),
             pruning_lock,
             counters,
             // TODO (HF): make sure to also pass `new_timestamp_deviation_tolerance` and use according to HF activation score
@@ -211,20 +222,40 @@ impl HeaderProcessor {
 
     pub fn worker(self: &Arc<HeaderProcessor>) {
         while let Ok(msg) = self.receiver.recv() {
-            match msg {
-                BlockProcessingMessage::Exit => {
-                    break;
-                }
-                BlockProcessingMessage::Process(task, block_result_transmitter, virtual_state_result_transmitter) => {
-                    if let Some(task_id) = self.task_manager.register(task, block_result_transmitter, virtual_state_result_transmitter)
-                    {
-                        let processor = self.clone();
-                        self.thread_pool.spawn(move || {
-                            processor.queue_block(task_id);
-                        });
+            // Receive the messages already queued along with this one, so that the pow of their headers is checked as a batch
+            let mut exit = false;
+            let mut tasks = Vec::new();
+            for msg in std::iter::once(msg).chain(self.receiver.try_iter().take(POW_BATCH_SIZE - 1)) {
+                match msg {
+                    BlockProcessingMessage::Exit => {
+                        exit = true;
+                        break;
+                    }
+                    BlockProcessingMessage::Process(task, block_result_transmitter, virtual_state_result_transmitter) => {
+                        tasks.push((task, block_result_transmitter, virtual_state_result_transmitter));
                     }
+                };
+            }
+
+            // The pow results are stored before the tasks are registered, since a registered task might be started
+            // at any time by the completion of its dependencies
+            self.check_pow_batch(tasks.iter().map(|(task, _, _)| task.block().header.clone()).collect());
+            for (task, block_result_transmitter, virtual_state_result_transmitter) in tasks {
+                let hash = task.block().hash();
+                if let Some(task_id) = self.task_manager.register(task, block_result_transmitter, virtual_state_result_transmitter) {
+                    let processor = self.clone();
+                    self.thread_pool.spawn(move || {
+                        processor.queue_block(task_id);
+                    });
+                } else {
+                    // A duplicate of a task already pending, which might have already consumed its own pow result
+                    self.batch_checked_pow.lock().remove(&hash);
                 }
-            };
+            }
+
+            if exit {
+                break;
+            }
         }
 
         // Wait until all workers are idle before exiting
@@ -234,9 +265,33 @@ impl HeaderProcessor {
         self.body_sender.send(BlockProcessingMessage::Exit).unwrap();
     }
 
+    /// Checks the pow of a batch of newly registered headers in parallel, ahead of their validation which consumes the
+    /// results (see `check_pow_and_calc_block_level`). Header validation is mostly sequenced by the dependencies between
+    /// headers, so this takes the pow computation out of the critical path of header sync.
+    fn check_pow_batch(&self, headers: Vec<Arc<Header>>) {
+        let headers = {
+            let statuses_read = self.statuses_store.read();
+            headers.into_iter().filter(|header| !statuses_read.has(header.hash).unwrap()).collect_vec()
+        };
+        if headers.len() < 2 {
+            // A single header gains nothing from batching, leave it to header validation
+            return;
+        }
+
+        let results = self.thread_pool.install(|| vecno_pow::calc_block_level_check_pow_batch(&headers, self.max_block_level));
+        self.batch_checked_pow.lock().extend(headers.iter().map(|header| header.hash).zip(results));
+    }
+
+    /// Takes the batch checked pow result (block level, passed) of the header, if such exists
+    pub(super) fn take_batch_checked_pow(&self, hash: Hash) -> Option<(BlockLevel, bool)> {
+        self.batch_checked_pow.lock().remove(&hash)
+    }
+
     fn queue_block(self: &Arc<HeaderProcessor>, task_id: TaskId) {
         if let Some(task) = self.task_manager.try_begin(task_id) {
             let res = self.process_header(&task);
+            // Discard the batch checked pow if it was not consumed, e.g. if the block was already known
+            self.batch_checked_pow.lock().remove(&task_id);
 
             let dependent_tasks = self.task_manager.end(
                 task,
@@ -817,7 +817,13 @@ impl VirtualStateProcessor {
             virtual_daa_score,
             virtual_past_median_time,
         )?;
-        self.validate_mempool_transaction_in_utxo_context(mutable_tx, virtual_utxo_view, virtual_daa_score, args)?;
+        self.validate_mempool_transaction_in_utxo_context(
+            mutable_tx,
+            virtual_utxo_view,
+            virtual_daa_score,
+            args,
+            TxValidationFlags::SkipMassCheck,
+        )?;
         Ok(())
     }
 
@@ -845,21 +851,74 @@ impl VirtualStateProcessor {
         let virtual_past_median_time = virtual_state.past_median_time;
 
         self.thread_pool.install(|| {
+            let (mut results, scripts_checked) = self.populate_and_batch_check_mempool_transactions(
+                mutable_txs,
+                &virtual_utxo_view,
+                virtual_daa_score,
+                virtual_past_median_time,
+            );
             mutable_txs
                 .par_iter_mut()
-                .map(|mtx| {
-                    self.validate_mempool_transaction_impl(
+                .zip(results.par_iter_mut())
+                .zip(scripts_checked)
+                .filter(|((_, result), _)| result.is_ok())
+                .for_each(|((mtx, result), scripts_checked)| {
+                    let flags = if scripts_checked {
+                        TxValidationFlags::SkipMassCheck.with_scripts_checked()
+                    } else {
+                        TxValidationFlags::SkipMassCheck
+                    };
+                    *result = self.validate_mempool_transaction_in_utxo_context(
                         mtx,
                         &virtual_utxo_view,
                         virtual_daa_score,
-                        virtual_past_median_time,
                         args.get(&mtx.id()),
-                    )
-                })
-                .collect::<Vec<TxResult<()>>>()
+                        flags,
+                    );
+                });
+            results
         })
     }
 
+    /// Performs the isolation and header context validations of the mempool transactions and populates them with their
+    /// UTXO entries, then batch checks the scripts of the transactions which passed so far. Returns the result of every
+    /// transaction up to this point, along with whether its scripts were checked.
+    pub(super) fn populate_and_batch_check_mempool_transactions(
+        &self,
+        mutable_txs: &mut [MutableTransaction],
+        virtual_utxo_view: &(impl UtxoView + Sync),
+        virtual_daa_score: u64,
+        virtual_past_median_time: u64,
+    ) -> (Vec<TxResult<()>>, Vec<bool>) {
+        let results = mutable_txs
+            .par_iter_mut()
+            .map(|mtx| {
+                self.transaction_validator.validate_tx_in_isolation(&mtx.tx)?;
+                self.transaction_validator.validate_tx_in_header_context_with_args(
+                    &mtx.tx,
+                    virtual_daa_score,
+                    virtual_past_median_time,
+                )?;
+                self.populate_mempool_transaction_in_utxo_context(mtx, virtual_utxo_view)
+            })
+            .collect::<Vec<TxResult<()>>>();
+
+        let (verifiable_txs, indices): (Vec<_>, Vec<_>) = mutable_txs
+            .iter()
+            .zip(results.iter())
+            .enumerate()
+            .filter(|(_, (_, result))| result.is_ok())
+            .map(|(i, (mtx, _))| (mtx.as_verifiable(), i))
+            .unzip();
+        let mut scripts_checked = vec![false; mutable_txs.len()];
+        let batch_checked =
+            self.transaction_validator.batch_check_scripts(&verifiable_txs, virtual_daa_score, TxValidationFlags::SkipMassCheck);
+        for (i, checked) in indices.into_iter().zip(batch_checked) {
+            scripts_checked[i] = checked;
+        }
+        (results, scripts_checked)
+    }
+
     fn populate_mempool_transaction_impl(
         &self,
         mutable_tx: &mut MutableTransaction,
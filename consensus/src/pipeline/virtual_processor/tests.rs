@@ -1,13 +1,22 @@
-use crate::{consensus::test_consensus::TestConsensus, model::services::reachability::ReachabilityService};
-use std::{collections::VecDeque, thread::JoinHandle};
+use crate::{
+    consensus::test_consensus::TestConsensus,
+    model::{services::reachability::ReachabilityService, stores::utxo_set::UtxoSetStore},
+};
+use std::{collections::VecDeque, iter::once, thread::JoinHandle};
 use vecno_consensus_core::{
-    api::ConsensusApi,
+    api::{args::TransactionValidationBatchArgs, ConsensusApi},
     block::{Block, BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockhash,
     blockstatus::BlockStatus,
     coinbase::MinerData,
     config::{params::MAINNET_PARAMS, ConfigBuilder},
-    tx::{ScriptPublicKey, ScriptVec, Transaction},
+    constants::SOMPI_PER_VECNO,
+    sign::sign,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{
+        MutableTransaction, ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput,
+        UtxoEntry,
+    },
     BlockHashSet,
 };
 use vecno_hashes::Hash;
@@ -296,6 +305,75 @@ async fn double_search_disqualified_test() {
     ctx.assert_tips_num(1);
 }
 
+#[tokio::test]
+async fn mempool_batch_signature_verification_test() {
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
+    let tc = TestConsensus::new(&config);
+    let wait_handles = tc.init();
+
+    // A UTXO of the virtual UTXO set paying to a Schnorr public key
+    let keypair = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[7; 32]).unwrap();
+    let script = once(0x20).chain(keypair.x_only_public_key().0.serialize()).chain(once(0xac)).collect::<ScriptVec>();
+    let script_public_key = ScriptPublicKey::new(0, script);
+    let outpoint = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+    let entry = UtxoEntry::new(SOMPI_PER_VECNO, script_public_key.clone(), 0, false);
+    tc.virtual_stores().write().utxo_set.write_many(&[(outpoint, entry.clone())]).unwrap();
+
+    let tx = Transaction::new(
+        0,
+        vec![TransactionInput::new(outpoint, vec![], 0, 1)],
+        vec![TransactionOutput::new(SOMPI_PER_VECNO - 100_000, script_public_key)],
+        0,
+        SUBNETWORK_ID_NATIVE,
+        0,
+        vec![],
+    );
+    let signed_tx = sign(MutableTransaction::with_entries(tx, vec![entry]), keypair).tx;
+
+    // The mempool submits transactions without their UTXO entries, which are populated by the consensus
+    let mut mutable_txs = vec![MutableTransaction::from_tx(signed_tx)];
+    mutable_txs[0].calculated_compute_mass = Some(tc.calculate_transaction_compute_mass(&mutable_txs[0].tx));
+    assert!(!mutable_txs[0].is_verifiable());
+
+    let virtual_processor = tc.virtual_processor();
+    let virtual_daa_score = tc.get_virtual_daa_score();
+    let virtual_past_median_time = tc.get_virtual_past_median_time();
+    let (results, scripts_checked) = virtual_processor.populate_and_batch_check_mempool_transactions(
+        &mut mutable_txs,
+        &tc.virtual_stores().read().utxo_set,
+        virtual_daa_score,
+        virtual_past_median_time,
+    );
+    assert_eq!(results, vec![Ok(())]);
+    assert!(mutable_txs[0].is_verifiable(), "the transaction should be populated before its scripts are batch checked");
+    assert_eq!(scripts_checked, vec![true], "the signature of the populated transaction should have been batch verified");
+
+    // A transaction whose signature is invalid fails the batch, and is left to the regular script checks
+    let mut invalid_tx = mutable_txs[0].clone();
+    invalid_tx.tx.inputs[0].signature_script[10] ^= 1;
+    let mut invalid_txs = vec![invalid_tx];
+    let (results, scripts_checked) = virtual_processor.populate_and_batch_check_mempool_transactions(
+        &mut invalid_txs,
+        &tc.virtual_stores().read().utxo_set,
+        virtual_daa_score,
+        virtual_past_median_time,
+    );
+    assert_eq!(results, vec![Ok(())]);
+    assert_eq!(scripts_checked, vec![false]);
+
+    // The full mempool validation path accepts the transaction, and rejects the one with the invalid signature
+    let mut mutable_txs = vec![MutableTransaction::new(mutable_txs.remove(0).tx), MutableTransaction::new(invalid_txs.remove(0).tx)];
+    for mtx in mutable_txs.iter_mut() {
+        mtx.calculated_compute_mass = Some(tc.calculate_transaction_compute_mass(&mtx.tx));
+    }
+    let results = tc.validate_mempool_transactions_in_parallel(&mut mutable_txs, &TransactionValidationBatchArgs::new());
+    assert_eq!(results[0], Ok(()));
+    assert!(mutable_txs[0].calculated_fee.is_some());
+    assert!(results[1].is_err());
+
+    tc.shutdown(wait_handles);
+}
+
 fn new_miner_data() -> MinerData {
     let secp = secp256k1::Secp256k1::new();
     let mut rng = rand::thread_rng();
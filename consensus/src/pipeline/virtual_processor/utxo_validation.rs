@@ -221,12 +221,17 @@ impl VirtualStateProcessor {
         flags: TxValidationFlags,
     ) -> Vec<(ValidatedTransaction<'a>, u32)> {
         self.thread_pool.install(|| {
-            txs
-                .par_iter() // We can do this in parallel without complications since block body validation already ensured
-                            // that all txs within each block are independent
-                .enumerate()
-                .skip(1) // Skip the coinbase tx.
-                .filter_map(|(i, tx)| self.validate_transaction_in_utxo_context(tx, &utxo_view, pov_daa_score, flags).ok().map(|vtx| (vtx, i as u32)))
+            let (populated_txs, indices) = self.populate_transactions_in_parallel(txs, utxo_view);
+            let results = self.transaction_validator.validate_populated_transactions_in_parallel_and_get_fees(
+                &populated_txs,
+                pov_daa_score,
+                flags,
+            );
+            populated_txs
+                .into_par_iter()
+                .zip(indices)
+                .zip(results)
+                .filter_map(|((tx, i), res)| Self::into_validated_transaction(tx, res).ok().map(|vtx| (vtx, i)))
                 .collect()
         })
     }
@@ -241,16 +246,22 @@ impl VirtualStateProcessor {
         flags: TxValidationFlags,
     ) -> (SmallVec<[(ValidatedTransaction<'a>, u32); 2]>, MuHash) {
         self.thread_pool.install(|| {
-            txs
-                .par_iter() // We can do this in parallel without complications since block body validation already ensured
-                            // that all txs within each block are independent
-                .enumerate()
-                .skip(1) // Skip the coinbase tx.
-                .filter_map(|(i, tx)| self.validate_transaction_in_utxo_context(tx, &utxo_view, pov_daa_score, flags).ok().map(|vtx| {
-                    let mh = MuHash::from_transaction(&vtx, pov_daa_score);
-                    (smallvec![(vtx, i as u32)], mh)
-                }
-                ))
+            let (populated_txs, indices) = self.populate_transactions_in_parallel(txs, utxo_view);
+            let results = self.transaction_validator.validate_populated_transactions_in_parallel_and_get_fees(
+                &populated_txs,
+                pov_daa_score,
+                flags,
+            );
+            populated_txs
+                .into_par_iter()
+                .zip(indices)
+                .zip(results)
+                .filter_map(|((tx, i), res)| {
+                    Self::into_validated_transaction(tx, res).ok().map(|vtx| {
+                        let mh = MuHash::from_transaction(&vtx, pov_daa_score);
+                        (smallvec![(vtx, i)], mh)
+                    })
+                })
                 .reduce(
                     || (smallvec![], MuHash::new()),
                     |mut a, mut b| {
@@ -262,6 +273,22 @@ impl VirtualStateProcessor {
         })
     }
 
+    /// Populates the block transactions, except for the coinbase, with their UTXO entries, and returns the ones which were
+    /// fully populated along with their original indices within the containing block
+    fn populate_transactions_in_parallel<'a, V: UtxoView + Sync>(
+        &self,
+        txs: &'a [Transaction],
+        utxo_view: &V,
+    ) -> (Vec<PopulatedTransaction<'a>>, Vec<u32>) {
+        txs
+            .par_iter() // We can do this in parallel without complications since block body validation already ensured
+                        // that all txs within each block are independent
+            .enumerate()
+            .skip(1) // Skip the coinbase tx.
+            .filter_map(|(i, tx)| self.populate_transaction_in_utxo_context(tx, utxo_view).ok().map(|ptx| (ptx, i as u32)))
+            .unzip()
+    }
+
     /// Attempts to populate the transaction with UTXO entries and performs all utxo-related tx validations
     pub(super) fn validate_transaction_in_utxo_context<'a>(
         &self,
@@ -270,6 +297,16 @@ impl VirtualStateProcessor {
         pov_daa_score: u64,
         flags: TxValidationFlags,
     ) -> TxResult<ValidatedTransaction<'a>> {
+        let populated_tx = self.populate_transaction_in_utxo_context(transaction, utxo_view)?;
+        self.validate_populated_transaction_in_utxo_context(populated_tx, pov_daa_score, flags)
+    }
+
+    /// Populates the transaction with UTXO entries, failing if any of them is missing
+    fn populate_transaction_in_utxo_context<'a>(
+        &self,
+        transaction: &'a Transaction,
+        utxo_view: &impl UtxoView,
+    ) -> TxResult<PopulatedTransaction<'a>> {
         let mut entries = Vec::with_capacity(transaction.inputs.len());
         for input in transaction.inputs.iter() {
             if let Some(entry) = utxo_view.get(&input.previous_outpoint) {
@@ -279,12 +316,26 @@ impl VirtualStateProcessor {
                 return Err(TxRuleError::MissingTxOutpoints);
             }
         }
-        let populated_tx = PopulatedTransaction::new(transaction, entries);
+        Ok(PopulatedTransaction::new(transaction, entries))
+    }
+
+    /// Performs all utxo-related tx validations of the populated transaction
+    fn validate_populated_transaction_in_utxo_context<'a>(
+        &self,
+        populated_tx: PopulatedTransaction<'a>,
+        pov_daa_score: u64,
+        flags: TxValidationFlags,
+    ) -> TxResult<ValidatedTransaction<'a>> {
         let res = self.transaction_validator.validate_populated_transaction_and_get_fee(&populated_tx, pov_daa_score, flags, None);
+        Self::into_validated_transaction(populated_tx, res)
+    }
+
+    /// Turns the transaction into a validated one given the result of its validation
+    fn into_validated_transaction(populated_tx: PopulatedTransaction<'_>, res: TxResult<u64>) -> TxResult<ValidatedTransaction<'_>> {
         match res {
             Ok(calculated_fee) => Ok(ValidatedTransaction::new(populated_tx, calculated_fee)),
             Err(tx_rule_error) => {
-                info!("Rejecting transaction {} due to transaction rule error: {}", transaction.id(), tx_rule_error);
+                info!("Rejecting transaction {} due to transaction rule error: {}", populated_tx.tx.id(), tx_rule_error);
                 Err(tx_rule_error)
             }
         }
@@ -315,13 +366,15 @@ impl VirtualStateProcessor {
         Ok(())
     }
 
-    /// Populates the mempool transaction with maximally found UTXO entry data and proceeds to validation if all found
+    /// Populates the mempool transaction with maximally found UTXO entry data and proceeds to validation if all found.
+    /// The flags are expected to skip the mass check, and may skip the script checks if these were already performed.
     pub(super) fn validate_mempool_transaction_in_utxo_context(
         &self,
         mutable_tx: &mut MutableTransaction,
         utxo_view: &impl UtxoView,
         pov_daa_score: u64,
         args: &TransactionValidationArgs,
+        flags: TxValidationFlags,
     ) -> TxResult<()> {
         self.populate_mempool_transaction_in_utxo_context(mutable_tx, utxo_view)?;
 
@@ -340,7 +393,7 @@ impl VirtualStateProcessor {
         let calculated_fee = self.transaction_validator.validate_populated_transaction_and_get_fee(
             &mutable_tx.as_verifiable(),
             pov_daa_score,
-            TxValidationFlags::SkipMassCheck, // we can skip the mass check since we just set it
+            flags, // we can skip the mass check since we just set it
             mass_and_feerate_threshold,
         )?;
         mutable_tx.calculated_fee = Some(calculated_fee);
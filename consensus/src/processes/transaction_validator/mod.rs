@@ -8,7 +8,7 @@ use crate::model::stores::ghostdag;
 
 use vecno_txscript::{
     caches::{Cache, TxScriptCacheCounters},
-    SigCacheKey,
+    EngineFlags, SigCacheKey,
 };
 
 use vecno_consensus_core::{config::params::ForkActivation, mass::MassCalculator};
@@ -30,6 +30,12 @@ pub struct TransactionValidator {
     storage_mass_activation: ForkActivation,
     /// KIP-10 hardfork DAA score
     kip10_activation: ForkActivation,
+    /// Splice and arithmetic opcodes hardfork DAA score
+    splice_and_arithmetic_activation: ForkActivation,
+    /// Full transaction introspection hardfork DAA score
+    full_introspection_activation: ForkActivation,
+    /// MAST script commitments hardfork DAA score
+    mast_activation: ForkActivation,
     payload_activation: ForkActivation,
 }
 
@@ -47,6 +53,9 @@ impl TransactionValidator {
         mass_calculator: MassCalculator,
         storage_mass_activation: ForkActivation,
         kip10_activation: ForkActivation,
+        splice_and_arithmetic_activation: ForkActivation,
+        full_introspection_activation: ForkActivation,
+        mast_activation: ForkActivation,
         payload_activation: ForkActivation,
     ) -> Self {
         Self {
@@ -61,6 +70,9 @@ impl TransactionValidator {
             mass_calculator,
             storage_mass_activation,
             kip10_activation,
+            splice_and_arithmetic_activation,
+            full_introspection_activation,
+            mast_activation,
             payload_activation,
         }
     }
@@ -87,7 +99,20 @@ impl TransactionValidator {
             mass_calculator: MassCalculator::new(0, 0, 0, 0),
             storage_mass_activation: ForkActivation::never(),
             kip10_activation: ForkActivation::never(),
+            splice_and_arithmetic_activation: ForkActivation::never(),
+            full_introspection_activation: ForkActivation::never(),
+            mast_activation: ForkActivation::never(),
             payload_activation: ForkActivation::never(),
         }
     }
+
+    /// Returns the script engine features which are active at the given DAA score
+    pub fn engine_flags(&self, pov_daa_score: u64) -> EngineFlags {
+        EngineFlags {
+            kip10_enabled: self.kip10_activation.is_active(pov_daa_score),
+            splice_and_arithmetic_enabled: self.splice_and_arithmetic_activation.is_active(pov_daa_score),
+            full_introspection_enabled: self.full_introspection_activation.is_active(pov_daa_score),
+            mast_enabled: self.mast_activation.is_active(pov_daa_score),
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use crate::constants::{MAX_SOMPI, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
 use rayon::ThreadPool;
 use std::marker::Sync;
 use vecno_consensus_core::{
@@ -7,7 +9,11 @@ use vecno_consensus_core::{
     tx::{TransactionInput, VerifiableTransaction},
 };
 use vecno_core::warn;
-use vecno_txscript::{caches::Cache, get_sig_op_count, SigCacheKey, TxScriptEngine};
+use vecno_txscript::{
+    batch::{batch_verify_schnorr, SchnorrCheck},
+    caches::Cache,
+    get_sig_op_count, EngineFlags, SigCacheKey, TxScriptEngine,
+};
 use vecno_txscript_errors::TxScriptError;
 
 use super::{
@@ -18,6 +24,10 @@ use super::{
 /// The threshold above which we apply parallelism to input script processing
 const CHECK_SCRIPTS_PARALLELISM_THRESHOLD: usize = 1;
 
+/// The minimal number of Schnorr signatures verified together as a single batch. Batches are verified in parallel, and
+/// an invalid signature only makes the transactions of its own batch fall back to verifying their signatures one by one.
+const SCHNORR_BATCH_SIZE: usize = 64;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum TxValidationFlags {
     /// Perform full validation including script verification
@@ -29,6 +39,19 @@ pub enum TxValidationFlags {
 
     /// When validating mempool transactions, we just set this value ourselves
     SkipMassCheck,
+
+    /// Same as `SkipMassCheck`, for mempool transactions whose scripts were already checked
+    SkipMassAndScriptChecks,
+}
+
+impl TxValidationFlags {
+    /// Returns the same flags, except that script checks are skipped
+    pub fn with_scripts_checked(self) -> Self {
+        match self {
+            Self::Full | Self::SkipScriptChecks => Self::SkipScriptChecks,
+            Self::SkipMassCheck | Self::SkipMassAndScriptChecks => Self::SkipMassAndScriptChecks,
+        }
+    }
 }
 
 impl TransactionValidator {
@@ -43,7 +66,9 @@ impl TransactionValidator {
         let total_in = self.check_transaction_input_amounts(tx)?;
         let total_out = Self::check_transaction_output_values(tx, total_in)?;
         let fee = total_in - total_out;
-        if flags != TxValidationFlags::SkipMassCheck && self.storage_mass_activation.is_active(pov_daa_score) {
+        if !matches!(flags, TxValidationFlags::SkipMassCheck | TxValidationFlags::SkipMassAndScriptChecks)
+            && self.storage_mass_activation.is_active(pov_daa_score)
+        {
             // Storage mass hardfork was activated
             self.check_mass_commitment(tx)?;
 
@@ -62,11 +87,29 @@ impl TransactionValidator {
                 Self::check_sig_op_counts(tx)?;
                 self.check_scripts(tx, pov_daa_score)?;
             }
-            TxValidationFlags::SkipScriptChecks => {}
+            TxValidationFlags::SkipScriptChecks | TxValidationFlags::SkipMassAndScriptChecks => {}
         }
         Ok(fee)
     }
 
+    /// Validates the transactions in parallel as [`Self::validate_populated_transaction_and_get_fee`] does, except that
+    /// their scripts are first checked together by [`Self::batch_check_scripts`]. Returns the result of every transaction.
+    pub fn validate_populated_transactions_in_parallel_and_get_fees<T: VerifiableTransaction + Sync>(
+        &self,
+        txs: &[T],
+        pov_daa_score: u64,
+        flags: TxValidationFlags,
+    ) -> Vec<TxResult<u64>> {
+        let scripts_checked = self.batch_check_scripts(txs, pov_daa_score, flags);
+        txs.par_iter()
+            .zip(scripts_checked)
+            .map(|(tx, scripts_checked)| {
+                let flags = if scripts_checked { flags.with_scripts_checked() } else { flags };
+                self.validate_populated_transaction_and_get_fee(tx, pov_daa_score, flags, None)
+            })
+            .collect()
+    }
+
     fn check_feerate_threshold(fee: u64, mass_and_feerate_threshold: Option<(u64, f64)>) -> TxResult<()> {
         // An actual check can only occur if some mass and threshold are provided,
         // otherwise, the check does not verify anything and exits successfully.
@@ -171,30 +214,103 @@ impl TransactionValidator {
     }
 
     pub fn check_scripts(&self, tx: &(impl VerifiableTransaction + Sync), pov_daa_score: u64) -> TxResult<()> {
-        check_scripts(&self.sig_cache, tx, self.kip10_activation.is_active(pov_daa_score))
+        check_scripts(&self.sig_cache, tx, self.engine_flags(pov_daa_score))
+    }
+
+    /// Checks the sig op counts and the scripts of the transactions, batch verifying their Schnorr signatures, unless the
+    /// flags skip script checks. Returns for each transaction whether it passed these checks, the others being left to
+    /// the regular checks which tell the actual error.
+    pub fn batch_check_scripts(
+        &self,
+        txs: &[impl VerifiableTransaction + Sync],
+        pov_daa_score: u64,
+        flags: TxValidationFlags,
+    ) -> Vec<bool> {
+        if matches!(flags, TxValidationFlags::SkipScriptChecks | TxValidationFlags::SkipMassAndScriptChecks) {
+            return vec![false; txs.len()];
+        }
+        let mut scripts_checked = batch_check_scripts(&self.sig_cache, txs, self.engine_flags(pov_daa_score));
+        txs.par_iter().zip(scripts_checked.par_iter_mut()).filter(|(_, checked)| **checked).for_each(|(tx, checked)| {
+            *checked = Self::check_sig_op_counts(tx).is_ok();
+        });
+        scripts_checked
+    }
+}
+
+/// Executes the scripts of all the transaction inputs once, deferring the Schnorr signature checks missing from the signature
+/// cache. Returns these checks if all the inputs succeeded, in which case the scripts are valid if the checks are as well.
+pub fn deferred_schnorr_checks(
+    sig_cache: &Cache<SigCacheKey, bool>,
+    tx: &impl VerifiableTransaction,
+    flags: EngineFlags,
+) -> Option<Vec<SchnorrCheck>> {
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let mut checks = vec![];
+    for (i, (input, entry)) in tx.populated_inputs().enumerate() {
+        let mut engine = TxScriptEngine::from_transaction_input(tx, input, i, entry, &reused_values, sig_cache, flags);
+        checks.extend(engine.execute_deferring_schnorr_checks().ok()?);
+    }
+    Some(checks)
+}
+
+/// Checks the scripts of the transactions by executing them once with deferred Schnorr signature checks, and batch verifying
+/// the deferred checks of consecutive transactions. Returns for each transaction whether its scripts were found valid this way.
+/// A failing batch does not tell which of its signatures are invalid, so its transactions, as well as the ones failing their
+/// deferred execution, are left to [`check_scripts`] which verifies the signatures individually.
+pub fn batch_check_scripts<T: VerifiableTransaction + Sync>(
+    sig_cache: &Cache<SigCacheKey, bool>,
+    txs: &[T],
+    flags: EngineFlags,
+) -> Vec<bool> {
+    let deferred: Vec<Option<Vec<SchnorrCheck>>> = txs.par_iter().map(|tx| deferred_schnorr_checks(sig_cache, tx, flags)).collect();
+
+    // Group the transactions into batches of at least `SCHNORR_BATCH_SIZE` checks, without splitting the checks of a transaction
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut batch_checks = 0;
+    for (i, checks) in deferred.iter().enumerate() {
+        let Some(checks) = checks else { continue };
+        batch.push(i);
+        batch_checks += checks.len();
+        if batch_checks >= SCHNORR_BATCH_SIZE {
+            batches.push(std::mem::take(&mut batch));
+            batch_checks = 0;
+        }
     }
+    batches.push(batch);
+
+    let valid_batches: Vec<Vec<usize>> = batches
+        .into_par_iter()
+        .filter(|batch| {
+            let checks = batch.iter().flat_map(|&i| deferred[i].iter().flatten().cloned()).collect::<Vec<_>>();
+            batch_verify_schnorr(&checks)
+        })
+        .collect();
+    let mut scripts_checked = vec![false; txs.len()];
+    valid_batches.into_iter().flatten().for_each(|i| scripts_checked[i] = true);
+    scripts_checked
 }
 
 pub fn check_scripts(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &(impl VerifiableTransaction + Sync),
-    kip10_enabled: bool,
+    flags: EngineFlags,
 ) -> TxResult<()> {
     if tx.inputs().len() > CHECK_SCRIPTS_PARALLELISM_THRESHOLD {
-        check_scripts_par_iter(sig_cache, tx, kip10_enabled)
+        check_scripts_par_iter(sig_cache, tx, flags)
     } else {
-        check_scripts_sequential(sig_cache, tx, kip10_enabled)
+        check_scripts_sequential(sig_cache, tx, flags)
     }
 }
 
 pub fn check_scripts_sequential(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &impl VerifiableTransaction,
-    kip10_enabled: bool,
+    flags: EngineFlags,
 ) -> TxResult<()> {
     let reused_values = SigHashReusedValuesUnsync::new();
     for (i, (input, entry)) in tx.populated_inputs().enumerate() {
-        TxScriptEngine::from_transaction_input(tx, input, i, entry, &reused_values, sig_cache, kip10_enabled)
+        TxScriptEngine::from_transaction_input(tx, input, i, entry, &reused_values, sig_cache, flags)
             .execute()
             .map_err(|err| map_script_err(err, input))?;
     }
@@ -204,12 +320,12 @@ pub fn check_scripts_sequential(
 pub fn check_scripts_par_iter(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &(impl VerifiableTransaction + Sync),
-    kip10_enabled: bool,
+    flags: EngineFlags,
 ) -> TxResult<()> {
     let reused_values = SigHashReusedValuesSync::new();
     (0..tx.inputs().len()).into_par_iter().try_for_each(|idx| {
         let (input, utxo) = tx.populated_input(idx);
-        TxScriptEngine::from_transaction_input(tx, input, idx, utxo, &reused_values, sig_cache, kip10_enabled)
+        TxScriptEngine::from_transaction_input(tx, input, idx, utxo, &reused_values, sig_cache, flags)
             .execute()
             .map_err(|err| map_script_err(err, input))
     })
@@ -219,9 +335,9 @@ pub fn check_scripts_par_iter_pool(
     sig_cache: &Cache<SigCacheKey, bool>,
     tx: &(impl VerifiableTransaction + Sync),
     pool: &ThreadPool,
-    kip10_enabled: bool,
+    flags: EngineFlags,
 ) -> TxResult<()> {
-    pool.install(|| check_scripts_par_iter(sig_cache, tx, kip10_enabled))
+    pool.install(|| check_scripts_par_iter(sig_cache, tx, flags))
 }
 
 fn map_script_err(script_err: TxScriptError, input: &TransactionInput) -> TxRuleError {
@@ -31,6 +31,7 @@ blake3_hasher! {
     struct MuHashFinalizeHash => b"MuHashFinalize\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
     struct PersonalMessageSigningHash => b"PersonalMessageSigningHash\0\0\0\0\0\0",
     struct ContractAddress => b"ContractAddress\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
+    struct MastLeafHash => b"MastLeafHash\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0",
 }
 
 sha256_hasher! {
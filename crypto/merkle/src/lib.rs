@@ -22,8 +22,58 @@ pub fn calc_merkle_root(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
     merkles.last().unwrap().unwrap()
 }
 
+/// Returns the sibling hashes along the path from the leaf at `index` up to the root of the tree built by
+/// [`calc_merkle_root`], from the bottom level up, or `None` if the index is out of range.
+pub fn calc_merkle_proof(hashes: impl ExactSizeIterator<Item = Hash>, index: usize) -> Option<Vec<Hash>> {
+    if index >= hashes.len() {
+        return None;
+    }
+    let mut level = hashes.collect::<Vec<_>>();
+    let mut index = index;
+    let mut proof = vec![];
+    while level.len() > 1 {
+        // A missing right sibling is the zero hash, just like in calc_merkle_root
+        proof.push(level.get(index ^ 1).copied().unwrap_or(ZERO_HASH));
+        level = level.chunks(2).map(|pair| merkle_hash(pair[0], pair.get(1).copied().unwrap_or(ZERO_HASH))).collect();
+        index /= 2;
+    }
+    Some(proof)
+}
+
+/// Computes the root of the tree from one of its leaves, the index of the leaf and its proof, as returned by
+/// [`calc_merkle_proof`]. The bits of the index, from the least significant up, tell whether the node at each level
+/// is a right child.
+pub fn calc_merkle_root_from_proof(leaf: Hash, index: usize, proof: &[Hash]) -> Hash {
+    proof.iter().enumerate().fold(leaf, |node, (level, sibling)| match (index >> level) & 1 {
+        0 => merkle_hash(node, *sibling),
+        _ => merkle_hash(*sibling, node),
+    })
+}
+
 fn merkle_hash(left: Hash, right: Hash) -> Hash {
     let mut hasher = MerkleBranchHash::new();
     hasher.update(left).update(right);
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_proof() {
+        for count in 1..=17u64 {
+            let hashes = (0..count).map(Hash::from_u64_word).collect::<Vec<_>>();
+            let root = calc_merkle_root(hashes.iter().copied());
+            for (index, leaf) in hashes.iter().enumerate() {
+                let proof = calc_merkle_proof(hashes.iter().copied(), index).unwrap();
+                assert_eq!(proof.len(), count.next_power_of_two().trailing_zeros() as usize);
+                assert_eq!(calc_merkle_root_from_proof(*leaf, index, &proof), root, "leaf {index} of {count}");
+                if count > 1 {
+                    assert_ne!(calc_merkle_root_from_proof(*leaf, index ^ 1, &proof), root);
+                }
+            }
+            assert_eq!(calc_merkle_proof(hashes.iter().copied(), count as usize), None);
+        }
+    }
+}
@@ -71,6 +71,16 @@ pub enum TxScriptError {
     ScriptSize(usize, usize),
     #[error("transaction output {0} is out of bounds, should be non-negative below {1}")]
     InvalidOutputIndex(i32, usize),
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("splice range {0}..{1} is out of bounds of an element of size {2}")]
+    SpliceOutOfRange(i64, i64, usize),
+    #[error("shift amount {0} is out of range, should be non-negative below 64")]
+    InvalidShiftAmount(i64),
+    #[error("invalid MAST control block: {0}")]
+    InvalidMastControlBlock(String),
+    #[error("revealed leaf script is not committed to by the MAST root")]
+    MastProofMismatch,
     #[error(transparent)]
     Serialization(#[from] SerializationError),
 }
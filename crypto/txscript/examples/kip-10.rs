@@ -19,7 +19,7 @@ use vecno_txscript::{
     },
     pay_to_address_script, pay_to_script_hash_script,
     script_builder::{ScriptBuilder, ScriptBuilderResult},
-    TxScriptEngine,
+    EngineFlags, TxScriptEngine,
 };
 use vecno_txscript_errors::TxScriptError::{EvalFalse, VerifyError};
 
@@ -126,7 +126,15 @@ fn threshold_scenario() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[STANDARD] Owner branch execution successful");
     }
@@ -136,7 +144,15 @@ fn threshold_scenario() -> ScriptBuilderResult<()> {
         println!("[STANDARD] Checking borrower branch");
         tx.inputs[0].signature_script = ScriptBuilder::new().add_op(OpFalse)?.add_data(&script)?.drain();
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[STANDARD] Borrower branch execution successful");
     }
@@ -147,7 +163,15 @@ fn threshold_scenario() -> ScriptBuilderResult<()> {
         // Less than threshold
         tx.outputs[0].value -= 1;
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Err(EvalFalse));
         println!("[STANDARD] Borrower branch with threshold not reached failed as expected");
     }
@@ -295,7 +319,15 @@ fn threshold_scenario_limited_one_time() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[ONE-TIME] Owner branch execution successful");
     }
@@ -305,7 +337,15 @@ fn threshold_scenario_limited_one_time() -> ScriptBuilderResult<()> {
         println!("[ONE-TIME] Checking borrower branch");
         tx.inputs[0].signature_script = ScriptBuilder::new().add_op(OpFalse)?.add_data(&script)?.drain();
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[ONE-TIME] Borrower branch execution successful");
     }
@@ -316,7 +356,15 @@ fn threshold_scenario_limited_one_time() -> ScriptBuilderResult<()> {
         // Less than threshold
         tx.outputs[0].value -= 1;
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Err(EvalFalse));
         println!("[ONE-TIME] Borrower branch with threshold not reached failed as expected");
     }
@@ -345,7 +393,7 @@ fn threshold_scenario_limited_one_time() -> ScriptBuilderResult<()> {
             &utxo_entry,
             &reused_values,
             &sig_cache,
-            true,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
         );
         assert_eq!(vm.execute(), Err(VerifyError));
         println!("[ONE-TIME] Borrower branch with output going to wrong address failed as expected");
@@ -455,7 +503,15 @@ fn threshold_scenario_limited_2_times() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[TWO-TIMES] Owner branch execution successful");
     }
@@ -465,7 +521,15 @@ fn threshold_scenario_limited_2_times() -> ScriptBuilderResult<()> {
         println!("[TWO-TIMES] Checking borrower branch (first borrowing)");
         tx.inputs[0].signature_script = ScriptBuilder::new().add_op(OpFalse)?.add_data(&two_times_script)?.drain();
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[TWO-TIMES] Borrower branch (first borrowing) execution successful");
     }
@@ -476,7 +540,15 @@ fn threshold_scenario_limited_2_times() -> ScriptBuilderResult<()> {
         // Less than threshold
         tx.outputs[0].value -= 1;
         let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.tx.inputs[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Err(EvalFalse));
         println!("[TWO-TIMES] Borrower branch with threshold not reached failed as expected");
     }
@@ -505,7 +577,7 @@ fn threshold_scenario_limited_2_times() -> ScriptBuilderResult<()> {
             &utxo_entry,
             &reused_values,
             &sig_cache,
-            true,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
         );
         assert_eq!(vm.execute(), Err(VerifyError));
         println!("[TWO-TIMES] Borrower branch with output going to wrong address failed as expected");
@@ -617,7 +689,15 @@ fn shared_secret_scenario() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[SHARED-SECRET] Owner branch execution successful");
     }
@@ -635,7 +715,15 @@ fn shared_secret_scenario() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Ok(()));
         println!("[SHARED-SECRET] Borrower branch with correct shared secret execution successful");
     }
@@ -653,7 +741,15 @@ fn shared_secret_scenario() -> ScriptBuilderResult<()> {
         }
 
         let tx = tx.as_verifiable();
-        let mut vm = TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, &utxo_entry, &reused_values, &sig_cache, true);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            EngineFlags { kip10_enabled: true, ..Default::default() },
+        );
         assert_eq!(vm.execute(), Err(VerifyError));
         println!("[SHARED-SECRET] Borrower branch with incorrect secret failed as expected");
     }
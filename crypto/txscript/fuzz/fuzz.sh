@@ -0,0 +1,8 @@
+#!/bin/sh -ex
+rustc --version
+cargo install cargo-fuzz
+fuzzer="$1"
+shift;
+# Seed the corpus of every target from the script test vectors
+cargo run --release --bin build_seeds -- corpus
+cargo fuzz run "$fuzzer" --debug-assertions --release -- -use_counters=1 -use_value_profile=1 "$@" corpus/"$fuzzer"
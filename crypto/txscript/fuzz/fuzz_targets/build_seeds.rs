@@ -0,0 +1,116 @@
+//! Writes the seed corpus of the fuzz targets from the script test vectors of `vecno-txscript`, into
+//! `corpus/<target>` or the directory given as first argument. Run by `fuzz.sh` before fuzzing.
+
+mod utils;
+
+use std::{collections::HashSet, path::PathBuf};
+use utils::{BuilderCall, ScriptInput};
+use vecno_consensus_core::constants::MAX_TX_IN_SEQUENCE_NUM;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+use vecno_txscript::{
+    opcodes::{codes, deserialize_next_opcode, opcode_name},
+    script_builder::ScriptBuilder,
+    EngineFlags,
+};
+
+const SCRIPT_TESTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../test-data/script_tests.json");
+
+fn main() {
+    let corpus =
+        std::env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus"));
+    let rows: Vec<Vec<serde_json::Value>> = serde_json::from_str(&std::fs::read_to_string(SCRIPT_TESTS).unwrap()).unwrap();
+
+    let mut seeds: Vec<(&str, Vec<u8>)> = vec![];
+    for row in rows {
+        // Rows of a single item are comments
+        let [serde_json::Value::String(sig_script), serde_json::Value::String(script_pub_key), ..] = row.as_slice() else {
+            continue;
+        };
+        let (Some(sig_script), Some(script_pub_key)) = (parse_short_form(sig_script), parse_short_form(script_pub_key)) else {
+            continue;
+        };
+
+        // Inputs of the `execute` and `sig_op_count` targets, before and after the activation of all the script forks
+        let all_enabled = EngineFlags {
+            kip10_enabled: true,
+            splice_and_arithmetic_enabled: true,
+            full_introspection_enabled: true,
+            mast_enabled: true,
+        };
+        for flags in [EngineFlags::default(), all_enabled] {
+            let input = ScriptInput {
+                flags,
+                p2sh: false,
+                lock_time: 0,
+                sequence: MAX_TX_IN_SEQUENCE_NUM,
+                signature_script: &sig_script,
+                script_public_key: &script_pub_key,
+            };
+            let seed = input.encode();
+            assert_eq!(ScriptInput::parse(&seed), Some(input));
+            seeds.extend([("execute", seed.clone()), ("sig_op_count", seed)]);
+        }
+
+        // Inputs of the `parse_script` target, and of the `script_builder` target as the builder calls adding the same opcodes
+        for script in [&sig_script, &script_pub_key] {
+            seeds.push(("parse_script", script.clone()));
+            let calls = parse_builder_calls(script);
+            let seed = calls.iter().flat_map(BuilderCall::encode).collect::<Vec<_>>();
+            let mut data = seed.as_slice();
+            assert!(calls.iter().all(|call| BuilderCall::parse(&mut data).as_ref() == Some(call)));
+            seeds.push(("script_builder", seed));
+        }
+    }
+
+    let mut unique = HashSet::new();
+    for (index, (target, seed)) in seeds.into_iter().filter(|seed| unique.insert(seed.clone())).enumerate() {
+        let dir = corpus.join(target);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("seed-{index}")), seed).unwrap();
+    }
+}
+
+/// Parses the short form of a script used by the test vectors, e.g. `DUP 0x14 0x1a2b... 'abc' 1000`, where
+/// hex is raw bytes while numbers and quoted strings are pushed. Returns `None` for scripts the builder rejects.
+fn parse_short_form(script: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    for token in script.split_whitespace() {
+        if let Ok(value) = token.parse::<i64>() {
+            bytes.extend(ScriptBuilder::new().add_i64(value).ok()?.drain());
+        } else if let Some(hex) = token.strip_prefix("0x") {
+            bytes.extend(hex::decode(hex).ok()?);
+        } else if let Some(data) = token.strip_prefix('\'').and_then(|token| token.strip_suffix('\'')) {
+            bytes.extend(ScriptBuilder::new().add_data(data.as_bytes()).ok()?.drain());
+        } else {
+            bytes.push(parse_opcode_name(&token.replace('_', "").to_uppercase())?);
+        }
+    }
+    Some(bytes)
+}
+
+/// Resolves an opcode name of the short form, given with or without its `Op` prefix except for small integers
+fn parse_opcode_name(token: &str) -> Option<u8> {
+    (0..=u8::MAX).find(|&opcode| {
+        let name = opcode_name(opcode).to_uppercase();
+        let small_integer = (codes::Op1..=codes::Op16).contains(&opcode) && !matches!(name.as_str(), "OPFALSE" | "OPTRUE");
+        token == name || (!small_integer && token == &name[2..])
+    })
+}
+
+/// The builder calls adding the opcodes of `script`, data pushes being added as data
+fn parse_builder_calls(script: &[u8]) -> Vec<BuilderCall<'_>> {
+    let mut calls = vec![];
+    let mut it = script.iter();
+    while let Some(Ok(opcode)) = deserialize_next_opcode::<_, PopulatedTransaction, SigHashReusedValuesUnsync>(&mut it) {
+        calls.push(match opcode.value() {
+            codes::OpData1..=codes::OpPushData4 => {
+                // The data of the opcode is borrowed from the script, right before the remaining bytes
+                let end = script.len() - it.len();
+                BuilderCall::AddData(&script[end - opcode.len()..end])
+            }
+            value => BuilderCall::AddOp(value),
+        });
+    }
+    calls
+}
@@ -0,0 +1,24 @@
+#![no_main]
+mod utils;
+
+use libfuzzer_sys::fuzz_target;
+use utils::{try_opt, ScriptInput};
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::{PopulatedTransaction, VerifiableTransaction};
+use vecno_txscript::{caches::Cache, debugger::ScriptDebugger, TxScriptEngine};
+
+fuzz_target!(|data: &[u8]| {
+    let script_input = try_opt!(ScriptInput::parse(data));
+    let (tx, entry) = script_input.transaction();
+    let tx = PopulatedTransaction::new(&tx, vec![entry]);
+    let (input, entry) = tx.populated_input(0);
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let sig_cache = Cache::new(0);
+
+    let result =
+        TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &sig_cache, script_input.flags).execute();
+
+    // The debugger executes the same scripts opcode by opcode and must reach the same outcome
+    let engine = TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &sig_cache, script_input.flags);
+    assert_eq!(ScriptDebugger::new(engine).run(), result);
+});
@@ -0,0 +1,32 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+use vecno_txscript::{
+    is_unspendable,
+    opcodes::deserialize_next_opcode,
+    script_asm::{assemble, disassemble},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let mut it = data.iter();
+    let mut parsed = 0;
+    while let Some(opcode) = deserialize_next_opcode::<_, PopulatedTransaction, SigHashReusedValuesUnsync>(&mut it) {
+        let Ok(opcode) = opcode else {
+            break;
+        };
+        // Every opcode consumes its value, the length prefix of its data and the data itself
+        let consumed = data.len() - it.len() - parsed;
+        assert!(consumed > opcode.len());
+        assert!(consumed <= opcode.len() + 5);
+        parsed += consumed;
+    }
+
+    if parsed < data.len() {
+        assert!(is_unspendable::<PopulatedTransaction, SigHashReusedValuesUnsync>(data));
+    }
+
+    // Any byte sequence can be disassembled, and assembling the text yields the original bytes
+    assert_eq!(assemble(&disassemble(data)).unwrap(), data);
+});
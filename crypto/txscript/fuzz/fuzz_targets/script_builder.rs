@@ -0,0 +1,83 @@
+#![no_main]
+mod utils;
+
+use libfuzzer_sys::fuzz_target;
+use utils::BuilderCall;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+use vecno_txscript::{
+    opcodes::{codes, deserialize_next_opcode},
+    script_builder::ScriptBuilder,
+};
+
+/// The stack item an opcode of the built script is expected to push, or the opcode itself for non-push opcodes
+enum Expected {
+    Opcode(u8),
+    Data(Vec<u8>),
+    Number(i64),
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut data = data;
+    let mut builder = ScriptBuilder::new();
+    let mut expected = vec![];
+
+    // Interpret the input as a sequence of builder calls, keeping track of the ones which modified the script
+    while let Some(call) = BuilderCall::parse(&mut data) {
+        let added = match call {
+            BuilderCall::AddOp(opcode) => {
+                // Data push opcodes are followed by their data, which add_op does not add
+                if (codes::OpData1..=codes::OpPushData4).contains(&opcode) {
+                    continue;
+                }
+                builder.add_op(opcode).is_ok().then_some(Expected::Opcode(opcode))
+            }
+            BuilderCall::AddData(push) => {
+                // A single zero byte is canonically pushed with Op0, which pushes an empty item
+                let pushed = if push == [0] { vec![] } else { push.to_vec() };
+                builder.add_data(push).is_ok().then_some(Expected::Data(pushed))
+            }
+            BuilderCall::AddI64(value) => builder.add_i64(value).is_ok().then_some(Expected::Number(value)),
+            BuilderCall::AddLockTime(value) => {
+                let trimmed = value.to_le_bytes().into_iter().rev().skip_while(|byte| *byte == 0).collect::<Vec<_>>();
+                builder.add_lock_time(value).is_ok().then(|| Expected::Data(trimmed.into_iter().rev().collect()))
+            }
+        };
+        expected.extend(added);
+    }
+
+    // Parsing the script must yield exactly the added items, each pushed canonically
+    let script = builder.drain();
+    let mut it = script.iter();
+    let mut expected = expected.into_iter();
+    while let Some(opcode) = deserialize_next_opcode::<_, PopulatedTransaction, SigHashReusedValuesUnsync>(&mut it) {
+        let opcode = opcode.expect("built scripts are well formed");
+        let pushed = match opcode.value() {
+            codes::Op1Negate => vec![0x81],
+            value @ codes::Op1..=codes::Op16 => vec![value - codes::Op1 + 1],
+            _ => opcode.get_data().to_vec(),
+        };
+        // The engine requires data pushes, unlike small integer opcodes, to be minimally encoded
+        let minimal = opcode.value() > codes::OpPushData4 || opcode.check_minimal_data_push().is_ok();
+        match expected.next().expect("no opcodes beyond the added ones") {
+            Expected::Opcode(value) => assert_eq!(opcode.value(), value),
+            Expected::Data(data) => {
+                assert!(minimal);
+                assert_eq!(pushed, data);
+            }
+            Expected::Number(value) => {
+                assert!(minimal);
+                assert_eq!(decode_number(&pushed), value);
+            }
+        }
+    }
+    assert!(expected.next().is_none());
+});
+
+/// Decodes a minimally encoded little-endian sign-magnitude script number
+fn decode_number(data: &[u8]) -> i64 {
+    let Some((last, _)) = data.split_last() else { return 0 };
+    let magnitude = data.iter().rev().fold(0i128, |acc, byte| (acc << 8) | *byte as i128) & !(0x80i128 << (8 * (data.len() - 1)));
+    let value = if last & 0x80 != 0 { -magnitude } else { magnitude };
+    value.try_into().expect("pushed numbers fit in an i64")
+}
@@ -0,0 +1,26 @@
+#![no_main]
+mod utils;
+
+use libfuzzer_sys::fuzz_target;
+use utils::{try_opt, ScriptInput};
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+use vecno_txscript::{get_sig_op_count, script_class::ScriptClass, MAX_PUB_KEYS_PER_MUTLTISIG};
+
+fuzz_target!(|data: &[u8]| {
+    let script_input = try_opt!(ScriptInput::parse(data));
+    let (tx, entry) = script_input.transaction();
+    let count =
+        get_sig_op_count::<PopulatedTransaction, SigHashReusedValuesUnsync>(&tx.inputs[0].signature_script, &entry.script_public_key);
+
+    // Sig ops are counted over the redeem script of P2SH spends, over the revealed leaf script of MAST spends and
+    // over the script public key otherwise, with every opcode of the counted script accounting for at most a full multisig
+    let counted_script = if script_input.p2sh {
+        script_input.script_public_key
+    } else if ScriptClass::is_pay_to_mast(entry.script_public_key.script()) {
+        tx.inputs[0].signature_script.as_slice()
+    } else {
+        entry.script_public_key.script()
+    };
+    assert!(count <= counted_script.len() as u64 * MAX_PUB_KEYS_PER_MUTLTISIG as u64);
+});
@@ -0,0 +1,154 @@
+#![allow(unused)]
+
+use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+use vecno_consensus_core::tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry};
+use vecno_txscript::{opcodes::codes, pay_to_script_hash_script, EngineFlags};
+
+macro_rules! try_opt {
+    ($expr: expr) => {
+        match $expr {
+            Some(value) => value,
+            None => return,
+        }
+    };
+}
+
+pub fn consume<const N: usize>(data: &mut &[u8]) -> Option<[u8; N]> {
+    if data.len() < N {
+        None
+    } else {
+        let ret = &data[..N];
+        *data = &(*data)[N..];
+        Some(ret.try_into().unwrap())
+    }
+}
+
+pub(crate) use try_opt;
+
+/// A transaction input spending a script public key, encoded as
+/// `<flags: u8> <lock time: u64> <sequence: u64> <signature script length: u16> <signature script> <script public key>`.
+///
+/// The flag bits enable, in order, KIP-10, the splice and arithmetic opcodes and full introspection, while the
+/// fourth bit makes the script public key the redeem script of a P2SH spend, pushed at the end of the signature script.
+/// The fifth bit enables MAST spends.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScriptInput<'a> {
+    pub flags: EngineFlags,
+    pub p2sh: bool,
+    pub lock_time: u64,
+    pub sequence: u64,
+    pub signature_script: &'a [u8],
+    pub script_public_key: &'a [u8],
+}
+
+impl<'a> ScriptInput<'a> {
+    pub fn parse(mut data: &'a [u8]) -> Option<Self> {
+        let [flags] = consume::<1>(&mut data)?;
+        let lock_time = u64::from_le_bytes(consume(&mut data)?);
+        let sequence = u64::from_le_bytes(consume(&mut data)?);
+        let signature_script_len = u16::from_le_bytes(consume(&mut data)?) as usize;
+        if data.len() < signature_script_len {
+            return None;
+        }
+        let (signature_script, script_public_key) = data.split_at(signature_script_len);
+        Some(Self {
+            flags: EngineFlags {
+                kip10_enabled: flags & 1 != 0,
+                splice_and_arithmetic_enabled: flags & 2 != 0,
+                full_introspection_enabled: flags & 4 != 0,
+                mast_enabled: flags & 16 != 0,
+            },
+            p2sh: flags & 8 != 0,
+            lock_time,
+            sequence,
+            signature_script,
+            script_public_key,
+        })
+    }
+
+    /// Encodes the input, the inverse of [`ScriptInput::parse`].
+    pub fn encode(&self) -> Vec<u8> {
+        let flags = self.flags.kip10_enabled as u8
+            | (self.flags.splice_and_arithmetic_enabled as u8) << 1
+            | (self.flags.full_introspection_enabled as u8) << 2
+            | (self.p2sh as u8) << 3
+            | (self.flags.mast_enabled as u8) << 4;
+        [flags]
+            .into_iter()
+            .chain(self.lock_time.to_le_bytes())
+            .chain(self.sequence.to_le_bytes())
+            .chain((self.signature_script.len() as u16).to_le_bytes())
+            .chain(self.signature_script.iter().copied())
+            .chain(self.script_public_key.iter().copied())
+            .collect()
+    }
+
+    /// Builds a transaction with this input as its single input, along with the UTXO entry it spends.
+    pub fn transaction(&self) -> (Transaction, UtxoEntry) {
+        let (signature_script, script_public_key) = if self.p2sh {
+            (
+                self.signature_script.iter().copied().chain(push_data(self.script_public_key)).collect(),
+                pay_to_script_hash_script(self.script_public_key),
+            )
+        } else {
+            (self.signature_script.to_vec(), ScriptPublicKey::from_vec(0, self.script_public_key.to_vec()))
+        };
+        let input = TransactionInput::new(TransactionOutpoint::default(), signature_script, self.sequence, 0);
+        let output = TransactionOutput::new(1000, ScriptPublicKey::from_vec(0, vec![codes::OpTrue]));
+        let tx = Transaction::new(0, vec![input], vec![output], self.lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        (tx, UtxoEntry::new(1000, script_public_key, 0, false))
+    }
+}
+
+/// A call to `ScriptBuilder`, encoded as a command byte whose value modulo 4 selects the call, followed by
+/// `<opcode: u8>`, `<data length: u16> <data>`, `<number: i64>` or `<lock time: u64>` respectively.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BuilderCall<'a> {
+    AddOp(u8),
+    AddData(&'a [u8]),
+    AddI64(i64),
+    AddLockTime(u64),
+}
+
+impl<'a> BuilderCall<'a> {
+    /// Parses the next call, consuming its bytes from `data`. Data pushes are truncated to the remaining bytes.
+    pub fn parse(data: &mut &'a [u8]) -> Option<Self> {
+        let [command] = consume::<1>(data)?;
+        Some(match command % 4 {
+            0 => {
+                let [opcode] = consume::<1>(data)?;
+                Self::AddOp(opcode)
+            }
+            1 => {
+                let len = (u16::from_le_bytes(consume(data)?) as usize).min(data.len());
+                let (push, rest) = data.split_at(len);
+                *data = rest;
+                Self::AddData(push)
+            }
+            2 => Self::AddI64(i64::from_le_bytes(consume(data)?)),
+            _ => Self::AddLockTime(u64::from_le_bytes(consume(data)?)),
+        })
+    }
+
+    /// Encodes the call, the inverse of [`BuilderCall::parse`].
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::AddOp(opcode) => vec![0, *opcode],
+            Self::AddData(push) => [1].into_iter().chain((push.len() as u16).to_le_bytes()).chain(push.iter().copied()).collect(),
+            Self::AddI64(value) => [2].into_iter().chain(value.to_le_bytes()).collect(),
+            Self::AddLockTime(value) => [3].into_iter().chain(value.to_le_bytes()).collect(),
+        }
+    }
+}
+
+/// Pushes data of any size with the smallest push opcode able to hold it, bypassing the
+/// size limits of `ScriptBuilder` so that the engine gets to reject oversized pushes.
+fn push_data(data: &[u8]) -> Vec<u8> {
+    let prefix = match data.len() {
+        len @ 0..=0x4b => vec![len as u8],
+        len @ 0x4c..=0xff => vec![codes::OpPushData1, len as u8],
+        len @ 0x100..=0xffff => [codes::OpPushData2].into_iter().chain((len as u16).to_le_bytes()).collect(),
+        len => [codes::OpPushData4].into_iter().chain((len as u32).to_le_bytes()).collect(),
+    };
+    prefix.into_iter().chain(data.iter().copied()).collect()
+}
@@ -0,0 +1,832 @@
+//!
+//! Static analysis of pay-to-script-hash redeem scripts.
+//!
+//! [`analyze_redeem_script`] walks every combination of conditional branches of a redeem script without
+//! evaluating signatures or reading the spending transaction. For each path it tracks the stack symbolically:
+//! items which the script pops before pushing them are items the signature script has to provide, and they
+//! are classified by the first opcode consuming them (e.g. a signature for `OpCheckSig`, a condition for
+//! `OpIf`), which gives the worst-case size of the signature script spending through the path.
+//!
+//! Numbers pushed by the script itself are tracked, so that `OpPick`, `OpRoll` and `OpCheckMultiSig` operands
+//! and constant conditions are resolved. When such an operand is only known at execution time the analysis
+//! falls back to a worst-case assumption and the report is marked as [approximate](ScriptAnalysis::approximate).
+//!
+
+use crate::data_stack::OpcodeData;
+use crate::opcodes::{codes, deserialize_next_opcode};
+use crate::script_builder::ScriptBuilder;
+use crate::{
+    get_sig_op_count_by_opcodes, parse_script, DynOpcodeImplementation, EngineFlags, MAX_OPS_PER_SCRIPT, MAX_PUB_KEYS_PER_MUTLTISIG,
+    MAX_SCRIPT_ELEMENT_SIZE, MAX_STACK_SIZE,
+};
+use thiserror::Error;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::mass::MassCalculator;
+use vecno_consensus_core::tx::{PopulatedTransaction, TransactionInput, TransactionOutpoint};
+
+/// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
+/// that are considered standard in a pay-to-script-hash script.
+pub const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
+
+/// MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE is the maximum size allowed for a
+/// transaction input signature script to be considered standard. This
+/// value allows for a 15-of-15 CHECKMULTISIG pay-to-script-hash with
+/// compressed keys.
+///
+/// The form of the overall script is: OP_0 <15 signatures> OP_PUSHDATA2
+/// <2 bytes len> [OP_15 <15 pubkeys> OP_15 OP_CHECKMULTISIG]
+///
+/// For the p2sh script portion, each of the 15 compressed pubkeys are
+/// 33 bytes (plus one for the OP_DATA_33 opcode), and the thus it totals
+/// to (15*34)+3 = 513 bytes. Next, each of the 15 signatures is a max
+/// of 73 bytes (plus one for the OP_DATA_73 opcode). Also, there is one
+/// extra byte for the initial extra OP_0 push and 3 bytes for the
+/// OP_PUSHDATA2 needed to specify the 513 bytes for the script push.
+/// That brings the total to 1+(15*74)+3+513 = 1627. This value also
+/// adds a few extra bytes to provide a little buffer.
+/// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
+pub const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
+
+/// The maximum number of paths walked by [`analyze_redeem_script`]. Scripts with more paths are
+/// reported as [truncated](ScriptAnalysis::truncated).
+pub const MAX_ANALYZED_PATHS: usize = 1024;
+
+type Opcode = DynOpcodeImplementation<PopulatedTransaction<'static>, SigHashReusedValuesUnsync>;
+
+/// The role of an item the signature script has to push, as determined by the first opcode consuming it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StackItemKind {
+    /// Dropped or left on the stack without being inspected
+    Unused,
+    /// Consumed by `OpIf`, `OpNotIf` or `OpVerify`
+    Condition,
+    Signature,
+    PublicKey,
+    /// Consumed as a number, e.g. by arithmetic or a timelock opcode
+    Number,
+    /// Consumed as arbitrary data, e.g. by a hash or comparison opcode
+    Data,
+}
+
+impl StackItemKind {
+    /// The worst-case size of the signature script push of an item of this kind.
+    pub fn max_push_size(&self) -> u64 {
+        match self {
+            StackItemKind::Unused | StackItemKind::Condition => 1,
+            // OpData65 <64-byte signature> <sighash type>
+            StackItemKind::Signature => 66,
+            // OpData33 <33-byte ECDSA public key>
+            StackItemKind::PublicKey => 34,
+            StackItemKind::Number => 9,
+            // OpPushData2 <2-byte length> <data>
+            StackItemKind::Data => 3 + MAX_SCRIPT_ELEMENT_SIZE as u64,
+        }
+    }
+}
+
+/// A reason for a script, or one of its paths, to fail on every execution.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum ScriptIssue {
+    #[error("malformed opcode at offset {offset}")]
+    Malformed { offset: usize },
+    #[error("redeem script of {0} bytes exceeds the max push size of {MAX_SCRIPT_ELEMENT_SIZE}")]
+    ScriptTooLarge(usize),
+    #[error("disabled opcode {opcode:#04x} at offset {offset}")]
+    DisabledOpcode { offset: usize, opcode: u8 },
+    #[error("illegal opcode {opcode:#04x} at offset {offset}")]
+    IllegalOpcode { offset: usize, opcode: u8 },
+    #[error("push of {size} bytes at offset {offset} exceeds the max element size of {MAX_SCRIPT_ELEMENT_SIZE}")]
+    ElementTooBig { offset: usize, size: usize },
+    #[error("unbalanced conditional")]
+    UnbalancedConditional,
+    #[error("reserved opcode {opcode:#04x} executed at offset {offset}")]
+    ReservedOpcode { offset: usize, opcode: u8 },
+    #[error("non-minimal data push at offset {offset}")]
+    NonMinimalPush { offset: usize },
+    #[error("constant non-boolean condition at offset {offset}")]
+    InvalidCondition { offset: usize },
+    #[error("OpReturn executed at offset {offset}")]
+    OpReturn { offset: usize },
+    #[error("empty alt stack popped at offset {offset}")]
+    InvalidStackOperation { offset: usize },
+    #[error("{0} operations exceed the max of {MAX_OPS_PER_SCRIPT}")]
+    TooManyOperations(i32),
+    #[error("stack depth {0} exceeds the max of {MAX_STACK_SIZE}")]
+    StackSizeExceeded(usize),
+    #[error("{0} items are left on the stack besides the result")]
+    CleanStack(usize),
+    #[error("stack is empty at the end of the script")]
+    EmptyStack,
+    #[error("script evaluates to false")]
+    EvalFalse,
+}
+
+/// A reason for the mempool to reject a spend as non-standard.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum NonStandardReason {
+    #[error("{0} signature operations exceed the standard max of {1}")]
+    TooManySigOps(u64, u8),
+    #[error("signature script of up to {0} bytes exceeds the standard max of {1}")]
+    SignatureScriptTooLarge(u64, u64),
+}
+
+/// The analysis of a single combination of conditional branches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathAnalysis {
+    /// The conditions consumed by the executed `OpIf`/`OpNotIf` opcodes, in execution order
+    pub branches: Vec<bool>,
+    /// Signature verifications executed on this path
+    pub sig_ops: u64,
+    /// Operations counted towards [`MAX_OPS_PER_SCRIPT`]
+    pub op_count: i32,
+    /// Items the signature script has to push before the redeem script, bottom of the stack first
+    pub required_stack_items: Vec<StackItemKind>,
+    /// Worst-case combined depth of the data and alt stacks
+    pub max_stack_depth: usize,
+    /// Worst-case size of the signature script, including the redeem script push
+    pub signature_script_size: u64,
+    /// Compute mass of a spending input with a worst-case signature script
+    pub compute_mass: u64,
+    /// Reasons for this path to always fail, empty if it can succeed
+    pub issues: Vec<ScriptIssue>,
+    pub non_standard: Vec<NonStandardReason>,
+}
+
+impl PathAnalysis {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn is_standard(&self) -> bool {
+        self.is_valid() && self.non_standard.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptAnalysis {
+    /// Sig op count of the redeem script, which every spending input has to declare
+    pub sig_op_count: u64,
+    /// Reasons for every path to fail, regardless of the branches taken
+    pub issues: Vec<ScriptIssue>,
+    pub paths: Vec<PathAnalysis>,
+    /// Whether some stack operands are only known at execution time, in which case worst-case values are assumed
+    pub approximate: bool,
+    /// Whether the script has more than [`MAX_ANALYZED_PATHS`] paths, of which only the first were analyzed
+    pub truncated: bool,
+}
+
+impl ScriptAnalysis {
+    /// Whether some path of the script can succeed.
+    pub fn is_spendable(&self) -> bool {
+        self.issues.is_empty() && self.paths.iter().any(PathAnalysis::is_valid)
+    }
+
+    /// Whether the mempool would relay a spend through some path of the script.
+    pub fn is_standard(&self) -> bool {
+        self.issues.is_empty() && self.paths.iter().any(PathAnalysis::is_standard)
+    }
+
+    pub fn max_sig_ops(&self) -> u64 {
+        self.valid_paths().map(|path| path.sig_ops).max().unwrap_or_default()
+    }
+
+    pub fn max_stack_depth(&self) -> usize {
+        self.valid_paths().map(|path| path.max_stack_depth).max().unwrap_or_default()
+    }
+
+    pub fn max_compute_mass(&self) -> u64 {
+        self.valid_paths().map(|path| path.compute_mass).max().unwrap_or_default()
+    }
+
+    fn valid_paths(&self) -> impl Iterator<Item = &PathAnalysis> {
+        self.paths.iter().filter(|path| path.is_valid())
+    }
+}
+
+/// Analyzes every path of a pay-to-script-hash redeem script under the given engine flags. The compute mass of
+/// spending inputs is calculated with `mass_calculator`.
+pub fn analyze_redeem_script(redeem_script: &[u8], flags: EngineFlags, mass_calculator: &MassCalculator) -> ScriptAnalysis {
+    let mut issues = vec![];
+    if redeem_script.len() > MAX_SCRIPT_ELEMENT_SIZE {
+        issues.push(ScriptIssue::ScriptTooLarge(redeem_script.len()));
+    }
+
+    let mut ops = vec![];
+    let mut it = redeem_script.iter();
+    while it.len() > 0 {
+        let offset = redeem_script.len() - it.len();
+        match deserialize_next_opcode(&mut it) {
+            Some(Ok(opcode)) => ops.push((offset, opcode)),
+            _ => {
+                issues.push(ScriptIssue::Malformed { offset });
+                return ScriptAnalysis { sig_op_count: 0, issues, paths: vec![], approximate: false, truncated: false };
+            }
+        }
+    }
+    let sig_op_count = get_sig_op_count_by_opcodes::<PopulatedTransaction, SigHashReusedValuesUnsync>(
+        &parse_script(redeem_script).collect::<Vec<_>>(),
+    );
+
+    // The engine rejects these opcodes, and counts operations, whether or not their branch is executed
+    let mut depth = 0usize;
+    let mut static_op_count = 0;
+    for (offset, opcode) in ops.iter() {
+        let (offset, value) = (*offset, opcode.value());
+        if opcode.is_disabled() && !(flags.splice_and_arithmetic_enabled && opcode.is_splice_or_arithmetic()) {
+            issues.push(ScriptIssue::DisabledOpcode { offset, opcode: value });
+        } else if opcode.always_illegal() {
+            issues.push(ScriptIssue::IllegalOpcode { offset, opcode: value });
+        }
+        if opcode.is_push_opcode() {
+            if opcode.len() > MAX_SCRIPT_ELEMENT_SIZE {
+                issues.push(ScriptIssue::ElementTooBig { offset, size: opcode.len() });
+            }
+        } else {
+            static_op_count += 1;
+        }
+        match value {
+            codes::OpIf | codes::OpNotIf => depth += 1,
+            codes::OpElse if depth == 0 => issues.push(ScriptIssue::UnbalancedConditional),
+            codes::OpEndIf if depth == 0 => issues.push(ScriptIssue::UnbalancedConditional),
+            codes::OpEndIf => depth -= 1,
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        issues.push(ScriptIssue::UnbalancedConditional);
+    }
+    issues.dedup();
+
+    let mut analyzer = Analyzer { ops: &ops, flags, approximate: false };
+    let redeem_script_push_size = ScriptBuilder::canonical_data_size(redeem_script) as u64;
+    let mut paths = vec![];
+    let mut pending = vec![PathState::new(static_op_count)];
+    let mut truncated = false;
+    while let Some(state) = pending.pop() {
+        if paths.len() == MAX_ANALYZED_PATHS {
+            truncated = true;
+            break;
+        }
+        let state = analyzer.walk(state, &mut pending);
+        paths.push(state.finish(sig_op_count, redeem_script_push_size, mass_calculator));
+    }
+
+    ScriptAnalysis { sig_op_count, issues, paths, approximate: analyzer.approximate, truncated }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cond {
+    True,
+    False,
+    Skip,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Item {
+    /// The numeric value of the item, when pushed by the script itself
+    value: Option<i64>,
+    /// Index of the required signature script item this item is, or was copied from
+    origin: Option<usize>,
+}
+
+impl Item {
+    const UNKNOWN: Item = Item { value: None, origin: None };
+}
+
+#[derive(Clone)]
+struct PathState {
+    pc: usize,
+    cond_stack: Vec<Cond>,
+    stack: Vec<Item>,
+    alt_stack: Vec<Item>,
+    /// Required signature script items, top of the stack first
+    required: Vec<StackItemKind>,
+    /// The max over the execution of the stack depth minus the number of required items discovered so far
+    max_excess: isize,
+    branches: Vec<bool>,
+    sig_ops: u64,
+    op_count: i32,
+    issue: Option<ScriptIssue>,
+}
+
+impl PathState {
+    fn new(static_op_count: i32) -> Self {
+        Self {
+            pc: 0,
+            cond_stack: vec![],
+            stack: vec![],
+            alt_stack: vec![],
+            required: vec![],
+            max_excess: 0,
+            branches: vec![],
+            sig_ops: 0,
+            op_count: static_op_count,
+            issue: None,
+        }
+    }
+
+    fn is_executing(&self) -> bool {
+        self.cond_stack.iter().all(|cond| *cond == Cond::True)
+    }
+
+    /// Makes sure the stack holds at least `depth` items, the missing ones being pushed by the signature script.
+    fn ensure(&mut self, depth: usize) {
+        while self.stack.len() < depth {
+            self.stack.insert(0, Item { value: None, origin: Some(self.required.len()) });
+            self.required.push(StackItemKind::Unused);
+        }
+    }
+
+    fn pop(&mut self, kind: StackItemKind) -> Item {
+        self.ensure(1);
+        let item = self.stack.pop().expect("ensured above");
+        if let Some(origin) = item.origin {
+            if self.required[origin] == StackItemKind::Unused {
+                self.required[origin] = kind;
+            }
+        }
+        item
+    }
+
+    fn pop_n(&mut self, count: usize, kind: StackItemKind) {
+        (0..count).for_each(|_| {
+            self.pop(kind);
+        });
+    }
+
+    fn push_unknown(&mut self, count: usize) {
+        self.stack.extend(std::iter::repeat_n(Item::UNKNOWN, count));
+    }
+
+    /// Returns the item at `depth` from the top of the stack, 0 being the top.
+    fn peek(&mut self, depth: usize) -> Item {
+        self.ensure(depth + 1);
+        self.stack[self.stack.len() - 1 - depth]
+    }
+
+    /// Moves the item at `depth` from the top of the stack to the top.
+    fn roll(&mut self, depth: usize) {
+        self.ensure(depth + 1);
+        let item = self.stack.remove(self.stack.len() - 1 - depth);
+        self.stack.push(item);
+    }
+
+    fn update_depth(&mut self) {
+        let excess = (self.stack.len() + self.alt_stack.len()) as isize - self.required.len() as isize;
+        self.max_excess = self.max_excess.max(excess);
+    }
+
+    fn finish(mut self, sig_op_count: u64, redeem_script_push_size: u64, mass_calculator: &MassCalculator) -> PathAnalysis {
+        let required_stack_items: Vec<_> = self.required.iter().rev().copied().collect();
+        let max_stack_depth = (required_stack_items.len() as isize + self.max_excess.max(1)) as usize;
+
+        let mut issues: Vec<_> = self.issue.take().into_iter().collect();
+        if issues.is_empty() {
+            // The engine enforces both limits during execution, before checking the final stack
+            if self.op_count > MAX_OPS_PER_SCRIPT {
+                issues.push(ScriptIssue::TooManyOperations(self.op_count));
+            } else if max_stack_depth > MAX_STACK_SIZE {
+                issues.push(ScriptIssue::StackSizeExceeded(max_stack_depth));
+            } else {
+                match self.stack.as_slice() {
+                    [] => issues.push(ScriptIssue::EmptyStack),
+                    [Item { value: Some(0), .. }] => issues.push(ScriptIssue::EvalFalse),
+                    [_] => {}
+                    [_, rest @ ..] => issues.push(ScriptIssue::CleanStack(rest.len())),
+                }
+            }
+        }
+
+        let signature_script_size =
+            required_stack_items.iter().map(StackItemKind::max_push_size).sum::<u64>() + redeem_script_push_size;
+        let input = TransactionInput::new(
+            TransactionOutpoint::default(),
+            vec![0; signature_script_size as usize],
+            0,
+            sig_op_count.min(u8::MAX as u64) as u8,
+        );
+        let compute_mass = mass_calculator.calc_input_compute_mass(&input);
+
+        let mut non_standard = vec![];
+        if sig_op_count > MAX_STANDARD_P2SH_SIG_OPS as u64 {
+            non_standard.push(NonStandardReason::TooManySigOps(sig_op_count, MAX_STANDARD_P2SH_SIG_OPS));
+        }
+        if signature_script_size > MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE {
+            non_standard
+                .push(NonStandardReason::SignatureScriptTooLarge(signature_script_size, MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE));
+        }
+
+        PathAnalysis {
+            branches: self.branches,
+            sig_ops: self.sig_ops,
+            op_count: self.op_count,
+            required_stack_items,
+            max_stack_depth,
+            signature_script_size,
+            compute_mass,
+            issues,
+            non_standard,
+        }
+    }
+}
+
+struct Analyzer<'a> {
+    ops: &'a [(usize, Opcode)],
+    flags: EngineFlags,
+    approximate: bool,
+}
+
+impl Analyzer<'_> {
+    /// Walks a path until its end or an issue. At conditionals with an unknown condition the path continues with
+    /// the condition being true, and the state of the path where it is false is added to `pending`.
+    fn walk(&mut self, mut state: PathState, pending: &mut Vec<PathState>) -> PathState {
+        while state.pc < self.ops.len() && state.issue.is_none() {
+            let (offset, opcode) = &self.ops[state.pc];
+            state.pc += 1;
+            let value = opcode.value();
+            match value {
+                codes::OpIf | codes::OpNotIf if state.is_executing() => {
+                    let condition = state.pop(StackItemKind::Condition);
+                    let taken = match condition.value {
+                        Some(0) => Some(false),
+                        Some(1) => Some(true),
+                        Some(_) => {
+                            state.issue = Some(ScriptIssue::InvalidCondition { offset: *offset });
+                            continue;
+                        }
+                        None => None,
+                    };
+                    let cond = |condition: bool| if condition == (value == codes::OpIf) { Cond::True } else { Cond::False };
+                    match taken {
+                        Some(condition) => {
+                            state.branches.push(condition);
+                            state.cond_stack.push(cond(condition));
+                        }
+                        None => {
+                            let mut fork = state.clone();
+                            fork.branches.push(false);
+                            fork.cond_stack.push(cond(false));
+                            pending.push(fork);
+                            state.branches.push(true);
+                            state.cond_stack.push(cond(true));
+                        }
+                    }
+                }
+                codes::OpIf | codes::OpNotIf => state.cond_stack.push(Cond::Skip),
+                codes::OpElse => match state.cond_stack.last_mut() {
+                    Some(cond @ Cond::True) => *cond = Cond::False,
+                    Some(cond @ Cond::False) => *cond = Cond::True,
+                    Some(Cond::Skip) => {}
+                    None => state.issue = Some(ScriptIssue::UnbalancedConditional),
+                },
+                codes::OpEndIf => state.issue = state.cond_stack.pop().is_none().then_some(ScriptIssue::UnbalancedConditional),
+                _ if state.is_executing() => self.execute(&mut state, *offset, opcode),
+                _ => {}
+            }
+            state.update_depth();
+        }
+        state
+    }
+
+    fn execute(&mut self, state: &mut PathState, offset: usize, opcode: &Opcode) {
+        use StackItemKind::*;
+        let value = opcode.value();
+        let kip10 = self.flags.kip10_enabled;
+        let full_introspection = self.flags.full_introspection_enabled;
+        match value {
+            codes::OpFalse..=codes::OpPushData4 | codes::Op1Negate | codes::OpTrue..=codes::Op16 => {
+                if value > 0 && value <= codes::OpPushData4 && opcode.check_minimal_data_push().is_err() {
+                    state.issue = Some(ScriptIssue::NonMinimalPush { offset });
+                    return;
+                }
+                let number = match value {
+                    codes::Op1Negate => Some(-1),
+                    codes::OpTrue..=codes::Op16 => Some((value - codes::OpTrue + 1) as i64),
+                    _ => OpcodeData::<i64>::deserialize(&opcode.get_data().to_vec()).ok(),
+                };
+                state.stack.push(Item { value: number, origin: None });
+            }
+            codes::OpNop => {}
+            codes::OpVerify => {
+                state.pop(Condition);
+            }
+            codes::OpReturn => state.issue = Some(ScriptIssue::OpReturn { offset }),
+            codes::OpToAltStack => {
+                let item = state.pop(Unused);
+                state.alt_stack.push(item);
+            }
+            codes::OpFromAltStack => match state.alt_stack.pop() {
+                Some(item) => state.stack.push(item),
+                None => state.issue = Some(ScriptIssue::InvalidStackOperation { offset }),
+            },
+            codes::Op2Drop => state.pop_n(2, Unused),
+            codes::Op2Dup => (0..2).for_each(|_| {
+                let item = state.peek(1);
+                state.stack.push(item);
+            }),
+            codes::Op3Dup => (0..3).for_each(|_| {
+                let item = state.peek(2);
+                state.stack.push(item);
+            }),
+            codes::Op2Over => (0..2).for_each(|_| {
+                let item = state.peek(3);
+                state.stack.push(item);
+            }),
+            codes::Op2Rot => (0..2).for_each(|_| state.roll(5)),
+            codes::Op2Swap => (0..2).for_each(|_| state.roll(3)),
+            codes::OpIfDup => {
+                let item = state.peek(0);
+                if item.value != Some(0) {
+                    state.stack.push(item);
+                }
+            }
+            codes::OpDepth => state.push_unknown(1),
+            codes::OpDrop => {
+                state.pop(Unused);
+            }
+            codes::OpDup => {
+                let item = state.peek(0);
+                state.stack.push(item);
+            }
+            codes::OpNip => {
+                state.roll(1);
+                state.pop(Unused);
+            }
+            codes::OpOver => {
+                let item = state.peek(1);
+                state.stack.push(item);
+            }
+            codes::OpPick | codes::OpRoll => {
+                let depth =
+                    state.pop(Number).value.and_then(|depth| usize::try_from(depth).ok()).filter(|&depth| depth < MAX_STACK_SIZE);
+                match (depth, value) {
+                    (Some(depth), codes::OpPick) => {
+                        let item = state.peek(depth);
+                        state.stack.push(item);
+                    }
+                    (Some(depth), _) => state.roll(depth),
+                    (None, codes::OpPick) => {
+                        self.approximate = true;
+                        state.push_unknown(1);
+                    }
+                    (None, _) => self.approximate = true,
+                }
+            }
+            codes::OpRot => state.roll(2),
+            codes::OpSwap => state.roll(1),
+            codes::OpTuck => {
+                let item = state.peek(0);
+                state.ensure(2);
+                let position = state.stack.len() - 2;
+                state.stack.insert(position, item);
+            }
+            codes::OpCat | codes::OpAnd | codes::OpOr | codes::OpXor | codes::OpEqual => {
+                state.pop_n(2, Data);
+                state.push_unknown(1);
+            }
+            codes::OpSubStr => {
+                state.pop_n(2, Number);
+                state.pop(Data);
+                state.push_unknown(1);
+            }
+            codes::OpLeft | codes::OpRight => {
+                state.pop(Number);
+                state.pop(Data);
+                state.push_unknown(1);
+            }
+            codes::OpSize => {
+                state.peek(0);
+                state.push_unknown(1);
+            }
+            codes::OpInvert | codes::OpSHA256 | codes::OpBlake3 => {
+                state.pop(Data);
+                state.push_unknown(1);
+            }
+            codes::OpEqualVerify => state.pop_n(2, Data),
+            codes::Op1Add
+            | codes::Op1Sub
+            | codes::Op2Mul
+            | codes::Op2Div
+            | codes::OpNegate
+            | codes::OpAbs
+            | codes::OpNot
+            | codes::Op0NotEqual => {
+                state.pop(Number);
+                state.push_unknown(1);
+            }
+            codes::OpAdd
+            | codes::OpSub
+            | codes::OpMul
+            | codes::OpDiv
+            | codes::OpMod
+            | codes::OpLShift
+            | codes::OpRShift
+            | codes::OpBoolAnd
+            | codes::OpBoolOr
+            | codes::OpNumEqual
+            | codes::OpNumNotEqual
+            | codes::OpLessThan
+            | codes::OpGreaterThan
+            | codes::OpLessThanOrEqual
+            | codes::OpGreaterThanOrEqual
+            | codes::OpMin
+            | codes::OpMax => {
+                state.pop_n(2, Number);
+                state.push_unknown(1);
+            }
+            codes::OpNumEqualVerify => state.pop_n(2, Number),
+            codes::OpWithin => {
+                state.pop_n(3, Number);
+                state.push_unknown(1);
+            }
+            codes::OpCheckSig | codes::OpCheckSigECDSA | codes::OpCheckSigVerify => {
+                state.pop(PublicKey);
+                state.pop(Signature);
+                state.sig_ops += 1;
+                if value != codes::OpCheckSigVerify {
+                    state.push_unknown(1);
+                }
+            }
+            codes::OpCheckMultiSig | codes::OpCheckMultiSigECDSA | codes::OpCheckMultiSigVerify => {
+                let num_keys = self.count(state.pop(Number), MAX_PUB_KEYS_PER_MUTLTISIG as usize);
+                state.pop_n(num_keys, PublicKey);
+                let num_sigs = self.count(state.pop(Number), num_keys);
+                state.pop_n(num_sigs, Signature);
+                state.op_count += num_keys as i32;
+                state.sig_ops += num_keys as u64;
+                if value != codes::OpCheckMultiSigVerify {
+                    state.push_unknown(1);
+                }
+            }
+            codes::OpCheckLockTimeVerify | codes::OpCheckSequenceVerify => {
+                state.pop(Number);
+            }
+            codes::OpTxInputCount | codes::OpTxOutputCount | codes::OpTxInputIndex if kip10 => state.push_unknown(1),
+            codes::OpTxInputAmount | codes::OpTxInputSpk | codes::OpTxOutputAmount | codes::OpTxOutputSpk if kip10 => {
+                state.pop(Number);
+                state.push_unknown(1);
+            }
+            codes::OpTxVersion | codes::OpTxLockTime | codes::OpTxSubnetId | codes::OpTxGas | codes::OpTxPayload
+                if full_introspection =>
+            {
+                state.push_unknown(1)
+            }
+            codes::OpOutpointTxId
+            | codes::OpOutpointIndex
+            | codes::OpTxInputScriptSig
+            | codes::OpTxInputSeq
+            | codes::OpTxInputBlockDaaScore
+            | codes::OpTxInputIsCoinbase
+                if full_introspection =>
+            {
+                state.pop(Number);
+                state.push_unknown(1);
+            }
+            _ => state.issue = Some(ScriptIssue::ReservedOpcode { offset, opcode: value }),
+        }
+    }
+
+    /// Resolves a multisig count operand, falling back to `max` when it is only known at execution time.
+    fn count(&mut self, item: Item, max: usize) -> usize {
+        match item.value.and_then(|value| usize::try_from(value).ok()).filter(|&value| value <= max) {
+            Some(value) => value,
+            None => {
+                self.approximate = true;
+                max
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcodes::codes::*;
+    use crate::{multisig_redeem_script, HtlcScript, RecoveryScript};
+    use StackItemKind::*;
+
+    fn mass_calculator() -> MassCalculator {
+        MassCalculator::new(1, 10, 1000, 10_000)
+    }
+
+    fn analyze(redeem_script: &[u8]) -> ScriptAnalysis {
+        analyze_redeem_script(redeem_script, Default::default(), &mass_calculator())
+    }
+
+    #[test]
+    fn test_multisig() {
+        let keys = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let redeem_script = multisig_redeem_script(keys.iter(), 2).unwrap();
+        let analysis = analyze(&redeem_script);
+        assert!(analysis.is_spendable() && analysis.is_standard());
+        assert!(!analysis.approximate && !analysis.truncated);
+        assert_eq!(analysis.sig_op_count, 3);
+        assert_eq!(analysis.paths.len(), 1);
+
+        let path = &analysis.paths[0];
+        assert_eq!(path.required_stack_items, vec![Signature, Signature]);
+        assert_eq!(path.sig_ops, 3);
+        assert_eq!(path.op_count, 4);
+        assert_eq!(path.max_stack_depth, 7);
+        let signature_script_size = 2 * 66 + ScriptBuilder::canonical_data_size(&redeem_script) as u64;
+        assert_eq!(path.signature_script_size, signature_script_size);
+        let input_size = 36 + 8 + signature_script_size + 8;
+        assert_eq!(path.compute_mass, input_size + 3 * 1000);
+        assert_eq!(analysis.max_compute_mass(), path.compute_mass);
+    }
+
+    #[test]
+    fn test_branches() {
+        let htlc = HtlcScript { payment_hash: [1; 32], receiver: [2; 32], refund: [3; 32], lock_time: 1000 };
+        let analysis = analyze(&htlc.redeem_script().unwrap());
+        assert!(analysis.is_standard());
+        assert_eq!(analysis.sig_op_count, 2);
+        assert_eq!(analysis.paths.len(), 2);
+
+        let claim = analysis.paths.iter().find(|path| path.branches == [true]).unwrap();
+        assert_eq!(claim.required_stack_items, vec![Signature, Data, Condition]);
+        assert_eq!(claim.sig_ops, 1);
+        let refund = analysis.paths.iter().find(|path| path.branches == [false]).unwrap();
+        assert_eq!(refund.required_stack_items, vec![Signature, Condition]);
+        assert!(refund.signature_script_size < claim.signature_script_size);
+        assert_eq!(analysis.max_compute_mass(), claim.compute_mass);
+
+        // Nested conditionals multiply the paths
+        let recovery = RecoveryScript { primary: [1; 32], recovery: [2; 32], daa_score: 10 }.redeem_script().unwrap();
+        let nested: Vec<u8> = [OpIf].into_iter().chain(recovery.clone()).chain([OpElse]).chain(recovery).chain([OpEndIf]).collect();
+        let analysis = analyze(&nested);
+        assert_eq!(analysis.paths.len(), 4);
+        assert!(analysis.paths.iter().all(|path| path.is_valid() && path.branches.len() == 2));
+        assert!(analysis.paths.iter().all(|path| path.required_stack_items == [Signature, Condition, Condition]));
+
+        // A constant condition is resolved
+        let analysis = analyze(&[OpTrue, OpIf, OpTrue, OpElse, OpFalse, OpEndIf]);
+        assert_eq!(analysis.paths.len(), 1);
+        assert!(analysis.paths[0].is_valid() && analysis.paths[0].required_stack_items.is_empty());
+    }
+
+    #[test]
+    fn test_issues() {
+        // Disabled and illegal opcodes fail the script even in a branch which is not executed
+        let analysis = analyze(&[OpIf, OpCat, OpEndIf, OpTrue]);
+        assert_eq!(analysis.issues, vec![ScriptIssue::DisabledOpcode { offset: 1, opcode: OpCat }]);
+        assert!(!analysis.is_spendable());
+        let flags = EngineFlags { splice_and_arithmetic_enabled: true, ..Default::default() };
+        assert!(analyze_redeem_script(&[OpIf, OpCat, OpEndIf, OpTrue], flags, &mass_calculator()).issues.is_empty());
+        assert_eq!(analyze(&[OpVerIf, OpEndIf]).issues[0], ScriptIssue::IllegalOpcode { offset: 0, opcode: OpVerIf });
+
+        // Reserved opcodes only fail the paths executing them
+        let analysis = analyze(&[OpIf, OpReserved, OpEndIf, OpTrue]);
+        assert!(analysis.issues.is_empty() && analysis.is_spendable());
+        let reserved = analysis.paths.iter().find(|path| path.branches == [true]).unwrap();
+        assert_eq!(reserved.issues, vec![ScriptIssue::ReservedOpcode { offset: 1, opcode: OpReserved }]);
+        assert_eq!(analyze(&[OpTxVersion]).paths[0].issues, vec![ScriptIssue::ReservedOpcode { offset: 0, opcode: OpTxVersion }]);
+        let flags = EngineFlags { full_introspection_enabled: true, ..Default::default() };
+        assert!(analyze_redeem_script(&[OpTxVersion], flags, &mass_calculator()).is_spendable());
+
+        assert_eq!(analyze(&[OpIf, OpTrue]).issues, vec![ScriptIssue::UnbalancedConditional]);
+        assert_eq!(analyze(&[OpData2, 1]).issues, vec![ScriptIssue::Malformed { offset: 0 }]);
+        assert_eq!(analyze(&[OpReturn]).paths[0].issues, vec![ScriptIssue::OpReturn { offset: 0 }]);
+        assert_eq!(analyze(&[OpFalse]).paths[0].issues, vec![ScriptIssue::EvalFalse]);
+        assert_eq!(analyze(&[OpTrue, OpTrue]).paths[0].issues, vec![ScriptIssue::CleanStack(1)]);
+        assert_eq!(analyze(&[OpFromAltStack]).paths[0].issues, vec![ScriptIssue::InvalidStackOperation { offset: 0 }]);
+        assert_eq!(analyze(&[Op2, OpIf, OpEndIf]).paths[0].issues, vec![ScriptIssue::InvalidCondition { offset: 1 }]);
+
+        let analysis = analyze(&[OpNop; 202]);
+        assert_eq!(analysis.paths[0].issues, vec![ScriptIssue::TooManyOperations(202)]);
+
+        let deep = vec![OpTrue; MAX_STACK_SIZE];
+        assert_eq!(analyze(&deep).paths[0].issues, vec![ScriptIssue::CleanStack(MAX_STACK_SIZE - 1)]);
+        let deep: Vec<u8> = [OpDup].into_iter().chain(vec![OpTrue; MAX_STACK_SIZE - 1]).collect();
+        let path = &analyze(&deep).paths[0];
+        assert_eq!(path.required_stack_items, vec![Unused]);
+        assert_eq!(path.issues, vec![ScriptIssue::StackSizeExceeded(MAX_STACK_SIZE + 1)]);
+    }
+
+    #[test]
+    fn test_standardness() {
+        let script: Vec<u8> = std::iter::repeat_n([Op1, OpCheckSigVerify], 15).flatten().chain([Op1, OpCheckSig]).collect();
+        let analysis = analyze(&script);
+        assert!(analysis.is_spendable() && !analysis.is_standard());
+        assert_eq!(analysis.paths[0].required_stack_items, vec![Signature; 16]);
+        assert_eq!(analysis.paths[0].non_standard, vec![NonStandardReason::TooManySigOps(16, MAX_STANDARD_P2SH_SIG_OPS)]);
+
+        // Four arbitrary data items exceed the standard signature script size
+        let analysis = analyze(&[OpSHA256, OpDrop, OpSHA256, OpDrop, OpSHA256, OpDrop, OpSHA256, OpDrop, OpTrue]);
+        assert_eq!(analysis.paths[0].required_stack_items, vec![Data; 4]);
+        assert_eq!(analysis.paths[0].non_standard, vec![NonStandardReason::SignatureScriptTooLarge(4 * 523 + 10, 1650)]);
+
+        // Multisig with a count only known at execution time
+        let analysis = analyze(&[OpDepth, OpCheckMultiSig]);
+        assert!(analysis.approximate);
+        assert_eq!(analysis.paths[0].sig_ops, MAX_PUB_KEYS_PER_MUTLTISIG as u64);
+    }
+
+    #[test]
+    fn test_truncated() {
+        let script: Vec<u8> = std::iter::repeat_n([OpIf, OpEndIf], 11).flatten().chain([OpTrue]).collect();
+        let analysis = analyze(&script);
+        assert!(analysis.truncated);
+        assert_eq!(analysis.paths.len(), MAX_ANALYZED_PATHS);
+    }
+}
@@ -0,0 +1,194 @@
+//!
+//! Batch verification of Schnorr signatures.
+//!
+//! A batch of BIP-340 signatures `(R_i, s_i)` over messages `m_i` by keys `P_i` is verified at once by checking
+//! `(Σ a_i·s_i)·G = Σ a_i·R_i + Σ (a_i·e_i)·P_i` with random coefficients `a_i`, where `e_i` is the challenge of
+//! each signature. The single multi-scalar multiplication is considerably cheaper than verifying the signatures
+//! one by one. A failing batch does not tell which signature is invalid, so in this case every signature is
+//! verified individually.
+//!
+//! The engine takes part by deferring the Schnorr signatures it encounters, see
+//! [`TxScriptEngine::execute_deferring_schnorr_checks`](crate::TxScriptEngine::execute_deferring_schnorr_checks).
+//! A passing batch only confirms the executions its checks were deferred from. Since the batch equation is not
+//! evaluated by the `secp256k1` library verifying the signatures individually, its results are never inserted into
+//! the signature cache, which only holds the results of individual verifications.
+//!
+
+use k256::elliptic_curve::group::Group;
+use k256::elliptic_curve::ops::{LinearCombinationExt, Reduce};
+use k256::elliptic_curve::point::DecompressPoint;
+use k256::elliptic_curve::subtle::Choice;
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, FieldBytes, ProjectivePoint, Scalar, U256};
+use secp256k1::{schnorr, Message, XOnlyPublicKey};
+use sha2::{Digest, Sha256};
+
+/// The BIP-340 tag of the hash computing the challenge of a signature
+const CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+/// A Schnorr signature check encountered during script execution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrCheck {
+    pub pub_key: XOnlyPublicKey,
+    pub signature: schnorr::Signature,
+    pub message: Message,
+}
+
+impl SchnorrCheck {
+    pub fn new(pub_key: XOnlyPublicKey, signature: schnorr::Signature, message: Message) -> Self {
+        Self { pub_key, signature, message }
+    }
+
+    /// Verifies the signature on its own
+    pub fn verify(&self) -> bool {
+        self.signature.verify(&self.message, &self.pub_key).is_ok()
+    }
+
+    /// Returns the terms `(R, s, e·P)` of the verification equation `s·G = R + e·P`, or `None` if the
+    /// signature or the public key are malformed, in which case the signature is invalid.
+    fn terms(&self) -> Option<(ProjectivePoint, Scalar, ProjectivePoint, Scalar)> {
+        let signature = self.signature.as_ref();
+        let (r, s) = signature.split_at(32);
+        let pub_key = self.pub_key.serialize();
+        let lift_x = |x: &[u8]| Option::<AffinePoint>::from(AffinePoint::decompress(FieldBytes::from_slice(x), Choice::from(0)));
+        let r_point = lift_x(r)?;
+        let pub_key_point = lift_x(&pub_key)?;
+        let s = Option::<Scalar>::from(Scalar::from_repr(*FieldBytes::from_slice(s)))?;
+
+        let tag = Sha256::digest(CHALLENGE_TAG);
+        let challenge = Sha256::new()
+            .chain_update(tag)
+            .chain_update(tag)
+            .chain_update(r)
+            .chain_update(pub_key)
+            .chain_update(self.message.as_ref());
+        let e = <Scalar as Reduce<U256>>::reduce_bytes(&challenge.finalize());
+        Some((r_point.into(), s, pub_key_point.into(), e))
+    }
+}
+
+/// Returns whether all the signatures of the batch are valid. An empty batch is valid.
+pub fn batch_verify_schnorr(checks: &[SchnorrCheck]) -> bool {
+    match checks {
+        [] => true,
+        [check] => check.verify(),
+        _ => {
+            let mut terms = Vec::with_capacity(2 * checks.len() + 1);
+            let mut s_sum = Scalar::ZERO;
+            for (i, check) in checks.iter().enumerate() {
+                let Some((r_point, s, pub_key_point, e)) = check.terms() else { return false };
+                // The first coefficient may be fixed to one without weakening the check
+                let a = if i == 0 { Scalar::ONE } else { Scalar::from(rand::random::<u128>()) };
+                s_sum += a * s;
+                terms.push((r_point, a));
+                terms.push((pub_key_point, a * e));
+            }
+            terms.push((ProjectivePoint::GENERATOR, -s_sum));
+            bool::from(ProjectivePoint::lincomb_ext(terms.as_slice()).is_identity())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caches::Cache;
+    use crate::opcodes::codes::{OpCheckSig, OpData32};
+    use crate::{EngineFlags, PublicKey, SigCacheKey, Signature, TxScriptEngine};
+    use secp256k1::{Keypair, SECP256K1};
+    use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+    use vecno_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
+    use vecno_consensus_core::sign::sign_input;
+    use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use vecno_consensus_core::tx::{
+        PopulatedTransaction, ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
+        VerifiableTransaction,
+    };
+
+    fn check(seed: u8) -> SchnorrCheck {
+        let keypair = Keypair::from_seckey_slice(SECP256K1, &[seed; 32]).unwrap();
+        let message = Message::from_digest([seed.wrapping_mul(7); 32]);
+        SchnorrCheck::new(keypair.x_only_public_key().0, keypair.sign_schnorr(message), message)
+    }
+
+    fn cache_key(check: &SchnorrCheck) -> SigCacheKey {
+        SigCacheKey {
+            signature: Signature::Secp256k1(check.signature),
+            pub_key: PublicKey::Schnorr(check.pub_key),
+            message: check.message,
+        }
+    }
+
+    #[test]
+    fn test_batch_verify_schnorr() {
+        let checks = (1..=16).map(check).collect::<Vec<_>>();
+        assert!(batch_verify_schnorr(&[]));
+        assert!(batch_verify_schnorr(&checks[..1]));
+        assert!(batch_verify_schnorr(&checks));
+
+        // A signature over another message
+        let mut invalid = checks.clone();
+        invalid[5].message = Message::from_digest([0xff; 32]);
+        assert!(!batch_verify_schnorr(&invalid));
+
+        // A signature by another key
+        let mut invalid = checks.clone();
+        invalid[15].pub_key = checks[14].pub_key;
+        assert!(!batch_verify_schnorr(&invalid));
+
+        // A signature whose R is not on the curve
+        let mut bytes = *checks[0].signature.as_ref();
+        bytes[..32].copy_from_slice(&[0xff; 32]);
+        let mut invalid = checks.clone();
+        invalid[0].signature = schnorr::Signature::from_slice(&bytes).unwrap();
+        assert!(!batch_verify_schnorr(&invalid));
+    }
+
+    #[test]
+    fn test_execute_deferring_schnorr_checks() {
+        let keys = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        let entries = keys
+            .iter()
+            .map(|key| {
+                let (pub_key, _) = Keypair::from_seckey_slice(SECP256K1, key).unwrap().x_only_public_key();
+                let script = [OpData32].into_iter().chain(pub_key.serialize()).chain([OpCheckSig]).collect::<Vec<_>>();
+                UtxoEntry::new(1000, ScriptPublicKey::from_vec(0, script), 0, false)
+            })
+            .collect::<Vec<_>>();
+        let inputs = (0..keys.len())
+            .map(|index| TransactionInput::new(TransactionOutpoint::new(Default::default(), index as u32), vec![], 0, 1))
+            .collect();
+        let outputs = vec![TransactionOutput::new(2000, ScriptPublicKey::from_vec(0, vec![]))];
+        let mut tx = Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let signature_scripts = (0..keys.len())
+            .map(|index| sign_input(&PopulatedTransaction::new(&tx, entries.clone()), index, &keys[index], SIG_HASH_ALL))
+            .collect::<Vec<_>>();
+        for (input, signature_script) in tx.inputs.iter_mut().zip(signature_scripts) {
+            input.signature_script = signature_script;
+        }
+        // Corrupt the signature of the last input
+        tx.inputs[2].signature_script[10] ^= 1;
+        let tx = PopulatedTransaction::new(&tx, entries);
+
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_cache = Cache::new(100);
+        let engine = |index: usize| {
+            let (input, entry) = tx.populated_input(index);
+            TxScriptEngine::from_transaction_input(&tx, input, index, entry, &reused_values, &sig_cache, EngineFlags::default())
+        };
+        // Every input succeeds when assuming its signature is valid, and defers its single check
+        let checks = (0..keys.len()).map(|index| engine(index).execute_deferring_schnorr_checks().unwrap()).collect::<Vec<_>>();
+        assert!(checks.iter().all(|checks| checks.len() == 1));
+        assert!(batch_verify_schnorr(&[checks[0][0].clone(), checks[1][0].clone()]));
+        assert!(!batch_verify_schnorr(&checks.concat()));
+
+        // The deferred checks are kept out of the signature cache, leaving the regular executions to decide on their own
+        assert!(checks.iter().flatten().all(|check| sig_cache.get(&cache_key(check)).is_none()));
+        assert_eq!(engine(0).execute(), Ok(()));
+        assert!(engine(2).execute().is_err());
+
+        // Once cached by the regular executions, the individual results are used instead of being deferred
+        assert_eq!(engine(0).execute_deferring_schnorr_checks(), Ok(vec![]));
+        assert!(engine(2).execute_deferring_schnorr_checks().is_err());
+    }
+}
@@ -0,0 +1,457 @@
+//!
+//! Step-by-step execution of the scripts run by a [`TxScriptEngine`], for debugging scripts offline.
+//!
+//! The [`ScriptDebugger`] drives the engine one opcode at a time, following the exact same rules as
+//! [`TxScriptEngine::execute`], and records the state of the engine after every opcode. Execution can
+//! be paused at breakpoints and single-stepped from there.
+//!
+
+use crate::opcodes::{deserialize_next_opcode, opcode_name, OpCond};
+use crate::{RevealedScript, ScriptSource, TxScriptEngine};
+use std::borrow::Cow;
+use std::collections::{HashSet, VecDeque};
+use vecno_consensus_core::hashing::sighash::SigHashReusedValues;
+use vecno_consensus_core::tx::VerifiableTransaction;
+use vecno_txscript_errors::TxScriptError;
+
+/// The script an opcode belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptKind {
+    /// The signature script of the input
+    SignatureScript,
+    /// The script public key of the spent UTXO entry
+    ScriptPublicKey,
+    /// The redeem script pushed by the signature script when spending a P2SH output
+    RedeemScript,
+    /// The leaf script revealed by the signature script when spending a MAST output
+    LeafScript,
+    /// A script executed without a transaction context
+    StandAlone,
+}
+
+/// A position in the executed scripts: the script and the byte offset of an opcode in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Breakpoint {
+    pub script: ScriptKind,
+    pub pc: usize,
+}
+
+impl Breakpoint {
+    pub fn new(script: ScriptKind, pc: usize) -> Self {
+        Self { script, pc }
+    }
+}
+
+/// A single executed opcode together with the state of the engine right after it.
+#[derive(Clone, Debug)]
+pub struct ScriptStep {
+    pub script: ScriptKind,
+    /// The byte offset of the opcode in its script
+    pub pc: usize,
+    pub opcode: u8,
+    /// The data pushed by the opcode, if any
+    pub data: Vec<u8>,
+    /// Whether the opcode was actually executed, i.e., was not skipped by a non-executing conditional branch
+    pub executed: bool,
+    pub data_stack: Vec<Vec<u8>>,
+    pub alt_stack: Vec<Vec<u8>>,
+    pub cond_stack: Vec<OpCond>,
+}
+
+impl ScriptStep {
+    pub fn opcode_name(&self) -> &'static str {
+        opcode_name(self.opcode)
+    }
+}
+
+struct ScriptFrame<'a> {
+    kind: ScriptKind,
+    script: Cow<'a, [u8]>,
+    pc: usize,
+    verify_only_push: bool,
+    // In P2SH, the data stack prior to executing the script public key is saved for running the redeem script over it
+    save_stack: bool,
+}
+
+/// Executes the scripts of a [`TxScriptEngine`] opcode by opcode, recording a trace of every executed opcode.
+pub struct ScriptDebugger<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> {
+    engine: TxScriptEngine<'a, T, Reused>,
+    pending: VecDeque<ScriptFrame<'a>>,
+    current: Option<ScriptFrame<'a>>,
+    revealed: RevealedScript,
+    saved_stack: Option<Vec<Vec<u8>>>,
+    breakpoints: HashSet<Breakpoint>,
+    trace: Vec<ScriptStep>,
+    outcome: Option<Result<(), TxScriptError>>,
+}
+
+impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> ScriptDebugger<'a, T, Reused> {
+    pub fn new(engine: TxScriptEngine<'a, T, Reused>) -> Self {
+        let mut debugger = Self {
+            engine,
+            pending: Default::default(),
+            current: None,
+            revealed: RevealedScript::None,
+            saved_stack: None,
+            breakpoints: Default::default(),
+            trace: vec![],
+            outcome: None,
+        };
+
+        match debugger.engine.scripts_to_execute() {
+            Ok(Some((scripts, revealed))) => {
+                let is_tx_input = matches!(debugger.engine.script_source, ScriptSource::TxInput { .. });
+                debugger.revealed = revealed;
+                debugger.pending = scripts
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, s)| !s.is_empty())
+                    .map(|(idx, script)| ScriptFrame {
+                        kind: match (is_tx_input, idx) {
+                            (true, 0) => ScriptKind::SignatureScript,
+                            (true, _) => ScriptKind::ScriptPublicKey,
+                            (false, _) => ScriptKind::StandAlone,
+                        },
+                        script: Cow::Borrowed(script),
+                        pc: 0,
+                        verify_only_push: is_tx_input && idx == 0,
+                        save_stack: revealed == RevealedScript::P2sh && idx == 1,
+                    })
+                    .collect();
+                debugger.settle();
+            }
+            Ok(None) => debugger.outcome = Some(Ok(())),
+            Err(err) => debugger.outcome = Some(Err(err)),
+        }
+        debugger
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: &Breakpoint) -> bool {
+        self.breakpoints.remove(breakpoint)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    /// Returns the position of the next opcode to execute, or `None` if execution has finished.
+    pub fn position(&self) -> Option<Breakpoint> {
+        self.current.as_ref().map(|frame| Breakpoint::new(frame.kind, frame.pc))
+    }
+
+    /// Returns the result of the execution, or `None` if it has not finished yet.
+    pub fn outcome(&self) -> Option<&Result<(), TxScriptError>> {
+        self.outcome.as_ref()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Returns all the opcodes executed so far, in execution order.
+    pub fn trace(&self) -> &[ScriptStep] {
+        &self.trace
+    }
+
+    pub fn data_stack(&self) -> &[Vec<u8>] {
+        &self.engine.dstack
+    }
+
+    pub fn alt_stack(&self) -> &[Vec<u8>] {
+        &self.engine.astack
+    }
+
+    pub fn cond_stack(&self) -> &[OpCond] {
+        &self.engine.cond_stack
+    }
+
+    /// Executes the next opcode and returns its trace step, or `None` if execution has already finished
+    /// or the next opcode could not be parsed. In the latter case, the parsing error is the outcome.
+    pub fn step(&mut self) -> Option<&ScriptStep> {
+        if self.outcome.is_some() {
+            return None;
+        }
+        let frame = self.current.as_mut().expect("settled frames are never exhausted");
+        let mut it = frame.script[frame.pc..].iter();
+        let opcode = match deserialize_next_opcode(&mut it).expect("settled frames are never exhausted") {
+            Ok(opcode) => opcode,
+            Err(err) => {
+                let result = self.engine.finish_script(Err(err));
+                self.finish(result);
+                return None;
+            }
+        };
+        let (kind, pc, verify_only_push) = (frame.kind, frame.pc, frame.verify_only_push);
+        frame.pc = frame.script.len() - it.len();
+
+        let executed = self.engine.is_executing() || opcode.is_conditional();
+        let (value, data) = (opcode.value(), opcode.get_data().to_vec());
+        let result = self.engine.execute_step(opcode, verify_only_push);
+        self.trace.push(ScriptStep {
+            script: kind,
+            pc,
+            opcode: value,
+            data,
+            executed,
+            data_stack: self.engine.dstack.clone(),
+            alt_stack: self.engine.astack.clone(),
+            cond_stack: self.engine.cond_stack.clone(),
+        });
+
+        match result {
+            Ok(()) => self.settle(),
+            Err(err) => {
+                let result = self.engine.finish_script(Err(err));
+                self.finish(result);
+            }
+        }
+        self.trace.last()
+    }
+
+    /// Executes opcodes until the next opcode to execute is at a breakpoint, or until execution finishes.
+    /// At least one opcode is executed, so resuming from a breakpoint moves past it. Returns the breakpoint
+    /// which was hit, if any.
+    pub fn resume(&mut self) -> Option<Breakpoint> {
+        while self.step().is_some() {
+            if let Some(position) = self.position().filter(|position| self.breakpoints.contains(position)) {
+                return Some(position);
+            }
+        }
+        None
+    }
+
+    /// Executes all remaining opcodes regardless of breakpoints and returns the outcome, which is identical
+    /// to the result of [`TxScriptEngine::execute`].
+    pub fn run(&mut self) -> Result<(), TxScriptError> {
+        while self.step().is_some() {}
+        self.outcome.clone().expect("execution has finished")
+    }
+
+    fn finish(&mut self, result: Result<(), TxScriptError>) {
+        self.current = None;
+        self.outcome = Some(result);
+    }
+
+    /// Moves across script boundaries until the next opcode to execute is reached or execution finishes,
+    /// applying the checks [`TxScriptEngine::execute`] runs between scripts.
+    fn settle(&mut self) {
+        loop {
+            match self.current.as_ref() {
+                Some(frame) if frame.pc < frame.script.len() => return,
+                Some(_) => {
+                    self.current = None;
+                    if let Err(err) = self.engine.finish_script(Ok(())) {
+                        return self.finish(Err(err));
+                    }
+                }
+                None => {
+                    if let Some(frame) = self.pending.pop_front() {
+                        if frame.save_stack {
+                            self.saved_stack = Some(self.engine.dstack.clone());
+                        }
+                        self.current = Some(frame);
+                    } else if self.revealed != RevealedScript::None {
+                        let revealed = std::mem::replace(&mut self.revealed, RevealedScript::None);
+                        let script = match revealed {
+                            RevealedScript::MastLeaf(merkle_root) => {
+                                self.engine.reveal_mast_leaf_script(merkle_root).map(|script| (ScriptKind::LeafScript, script))
+                            }
+                            _ => self.redeem_script().map(|script| (ScriptKind::RedeemScript, script)),
+                        };
+                        match script {
+                            Ok((kind, script)) => {
+                                self.current = Some(ScriptFrame {
+                                    kind,
+                                    script: Cow::Owned(script),
+                                    pc: 0,
+                                    verify_only_push: false,
+                                    save_stack: false,
+                                })
+                            }
+                            Err(err) => return self.finish(Err(err)),
+                        }
+                    } else {
+                        let result = self.engine.check_error_condition(true);
+                        return self.finish(result);
+                    }
+                }
+            }
+        }
+    }
+
+    fn redeem_script(&mut self) -> Result<Vec<u8>, TxScriptError> {
+        self.engine.check_error_condition(false)?;
+        self.engine.dstack = self.saved_stack.take().ok_or(TxScriptError::EmptyStack)?;
+        self.engine.dstack.pop().ok_or(TxScriptError::EmptyStack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caches::Cache;
+    use crate::opcodes::codes::*;
+    use crate::script_builder::ScriptBuilder;
+    use crate::{pay_to_script_hash_script, pay_to_script_hash_signature_script, EngineFlags, MastTree};
+    use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+    use vecno_consensus_core::tx::{
+        PopulatedTransaction, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput,
+        UtxoEntry,
+    };
+
+    fn spend(signature_script: Vec<u8>, script_public_key: ScriptPublicKey) -> (Transaction, UtxoEntry) {
+        let input = TransactionInput {
+            previous_outpoint: TransactionOutpoint { transaction_id: TransactionId::from_bytes([1; 32]), index: 0 },
+            signature_script,
+            sequence: u64::MAX,
+            sig_op_count: 0,
+        };
+        let output = TransactionOutput { value: 1000, script_public_key: ScriptPublicKey::from_vec(0, vec![OpTrue]) };
+        let tx = Transaction::new(1, vec![input], vec![output], 0, Default::default(), 0, vec![]);
+        (tx, UtxoEntry::new(1000, script_public_key, 0, false))
+    }
+
+    /// Runs the spending of the UTXO entry both through the engine and the debugger, checks that both agree
+    /// and returns the debugger trace
+    fn debug(signature_script: Vec<u8>, script_public_key: ScriptPublicKey) -> (Result<(), TxScriptError>, Vec<ScriptStep>) {
+        debug_with_flags(signature_script, script_public_key, Default::default())
+    }
+
+    fn debug_with_flags(
+        signature_script: Vec<u8>,
+        script_public_key: ScriptPublicKey,
+        flags: EngineFlags,
+    ) -> (Result<(), TxScriptError>, Vec<ScriptStep>) {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let (tx, utxo_entry) = spend(signature_script, script_public_key);
+        let populated_tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+        let input = &tx.inputs[0];
+
+        let expected =
+            TxScriptEngine::from_transaction_input(&populated_tx, input, 0, &utxo_entry, &reused_values, &sig_cache, flags).execute();
+        let mut debugger = ScriptDebugger::new(TxScriptEngine::from_transaction_input(
+            &populated_tx,
+            input,
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            flags,
+        ));
+        let result = debugger.run();
+        assert_eq!(result, expected);
+        assert!(debugger.position().is_none());
+        (result, debugger.trace().to_vec())
+    }
+
+    #[test]
+    fn test_debugger_matches_engine() {
+        let signature_script = ScriptBuilder::new().add_i64(3).unwrap().drain();
+        // Only agreement with the engine matters here, the exact errors are covered by the engine tests
+        let cases = vec![
+            (vec![OpTrue], vec![OpTrue]),
+            (vec![], vec![OpTrue]),
+            (vec![], vec![OpFalse]),
+            (vec![], vec![OpTrue, OpIf, OpTrue]),
+            (vec![OpTrue, OpDrop], vec![OpTrue]),
+            (vec![], vec![OpTrue, OpCat]),
+            (vec![], vec![OpData2, 0x01]),
+            (signature_script.clone(), vec![Op3, OpEqual]),
+            (signature_script, vec![Op4, OpEqual]),
+        ];
+        for (signature_script, script) in cases {
+            let _ = debug(signature_script, ScriptPublicKey::from_vec(0, script));
+        }
+    }
+
+    #[test]
+    fn test_debugger_trace() {
+        // 1 IF 2 ELSE 3 ENDIF 2 EQUAL
+        let script = vec![OpTrue, OpIf, Op2, OpElse, Op3, OpEndIf, Op2, OpEqual];
+        let (result, trace) = debug(vec![], ScriptPublicKey::from_vec(0, script.clone()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(trace.iter().map(|step| step.opcode).collect::<Vec<_>>(), script);
+        assert_eq!(trace.iter().map(|step| step.pc).collect::<Vec<_>>(), (0..script.len()).collect::<Vec<_>>());
+        assert!(trace.iter().all(|step| step.script == ScriptKind::ScriptPublicKey));
+        assert_eq!(trace.iter().map(|step| step.executed).collect::<Vec<_>>(), vec![true, true, true, true, false, true, true, true]);
+        assert_eq!(trace[1].cond_stack, vec![OpCond::True]);
+        assert_eq!(trace[4].cond_stack, vec![OpCond::False]);
+        assert!(trace[5].cond_stack.is_empty());
+        assert_eq!(trace[2].data_stack, vec![vec![2]]);
+        assert_eq!(trace[4].data_stack, vec![vec![2]]);
+        assert_eq!(trace[7].data_stack, vec![vec![1]]);
+        assert_eq!(trace[7].opcode_name(), "OpEqual");
+    }
+
+    #[test]
+    fn test_debugger_p2sh() {
+        let redeem_script = vec![OpToAltStack, OpFromAltStack, Op5, OpEqual];
+        let signature_script =
+            pay_to_script_hash_signature_script(redeem_script.clone(), ScriptBuilder::new().add_i64(5).unwrap().drain()).unwrap();
+        let (result, trace) = debug(signature_script, pay_to_script_hash_script(&redeem_script));
+        assert_eq!(result, Ok(()));
+
+        let kinds = trace.iter().map(|step| step.script).collect::<Vec<_>>();
+        assert_eq!(kinds.iter().filter(|kind| **kind == ScriptKind::SignatureScript).count(), 2);
+        assert_eq!(kinds.iter().filter(|kind| **kind == ScriptKind::ScriptPublicKey).count(), 3);
+        assert_eq!(kinds.iter().filter(|kind| **kind == ScriptKind::RedeemScript).count(), 4);
+        let redeem_steps = trace.iter().filter(|step| step.script == ScriptKind::RedeemScript).collect::<Vec<_>>();
+        // The redeem script runs over the stack left by the signature script, without the redeem script itself
+        assert_eq!(redeem_steps[0].data_stack, Vec::<Vec<u8>>::new());
+        assert_eq!(redeem_steps[0].alt_stack, vec![vec![5]]);
+        assert_eq!(redeem_steps[1].data_stack, vec![vec![5]]);
+    }
+
+    #[test]
+    fn test_debugger_mast() {
+        let tree = MastTree::new(vec![vec![Op5, OpEqual], vec![OpTrue]]).unwrap();
+        let flags = EngineFlags { mast_enabled: true, ..Default::default() };
+        let signature_script = tree.signature_script(0, &ScriptBuilder::new().add_i64(5).unwrap().drain()).unwrap();
+        let (result, trace) = debug_with_flags(signature_script, tree.script_public_key(), flags);
+        assert_eq!(result, Ok(()));
+        let kinds = trace.iter().map(|step| step.script).collect::<Vec<_>>();
+        assert_eq!(kinds, [[ScriptKind::SignatureScript; 3].as_slice(), &[ScriptKind::LeafScript; 2]].concat());
+        // The leaf script runs over the leaf arguments only
+        assert_eq!(trace[3].data_stack, vec![vec![5], vec![5]]);
+
+        // A leaf script which is not part of the tree never runs
+        let reveal = ScriptBuilder::new().add_mast_reveal(&[OpTrue], &tree.control_block(0).unwrap()).unwrap().drain();
+        let (result, trace) = debug_with_flags([vec![OpTrue], reveal].concat(), tree.script_public_key(), flags);
+        assert_eq!(result, Err(TxScriptError::MastProofMismatch));
+        assert!(trace.iter().all(|step| step.script == ScriptKind::SignatureScript));
+    }
+
+    #[test]
+    fn test_debugger_breakpoints() {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let script = vec![Op1, Op2, OpAdd, Op3, OpEqual];
+        let mut debugger = ScriptDebugger::<PopulatedTransaction, _>::new(TxScriptEngine::from_script(
+            &script,
+            &reused_values,
+            &sig_cache,
+            Default::default(),
+        ));
+        assert_eq!(debugger.position(), Some(Breakpoint::new(ScriptKind::StandAlone, 0)));
+        debugger.add_breakpoint(Breakpoint::new(ScriptKind::StandAlone, 2));
+        debugger.add_breakpoint(Breakpoint::new(ScriptKind::StandAlone, 4));
+
+        assert_eq!(debugger.resume(), Some(Breakpoint::new(ScriptKind::StandAlone, 2)));
+        assert_eq!(debugger.data_stack(), &[vec![1], vec![2]]);
+        assert_eq!(debugger.step().unwrap().opcode, OpAdd);
+        assert_eq!(debugger.data_stack(), &[vec![3]]);
+        assert_eq!(debugger.resume(), Some(Breakpoint::new(ScriptKind::StandAlone, 4)));
+        assert!(debugger.outcome().is_none());
+
+        assert!(debugger.remove_breakpoint(&Breakpoint::new(ScriptKind::StandAlone, 2)));
+        assert_eq!(debugger.resume(), None);
+        assert_eq!(debugger.outcome(), Some(&Ok(())));
+        assert_eq!(debugger.trace().len(), script.len());
+        assert!(debugger.step().is_none());
+    }
+}
@@ -1,17 +1,24 @@
 extern crate alloc;
 extern crate core;
 
+pub mod analyzer;
+pub mod batch;
 pub mod caches;
 mod data_stack;
+pub mod debugger;
 pub mod error;
 pub mod opcodes;
+#[cfg(test)]
+mod property_tests;
 pub mod result;
+pub mod script_asm;
 pub mod script_builder;
 pub mod script_class;
 pub mod standard;
 #[cfg(feature = "wasm32-sdk")]
 pub mod wasm;
 
+use crate::batch::SchnorrCheck;
 use crate::caches::Cache;
 use crate::data_stack::{DataStack, Stack};
 use crate::opcodes::{deserialize_next_opcode, OpCodeImplementation};
@@ -23,6 +30,7 @@ use script_class::ScriptClass;
 use vecno_consensus_core::hashing::sighash::{calc_ecdsa_signature_hash, calc_schnorr_signature_hash, SigHashReusedValues};
 use vecno_consensus_core::hashing::sighash_type::SigHashType;
 use vecno_consensus_core::tx::{ScriptPublicKey, TransactionInput, UtxoEntry, VerifiableTransaction};
+use vecno_hashes::Hash;
 use vecno_txscript_errors::TxScriptError;
 
 pub mod prelude {
@@ -47,6 +55,19 @@ pub const NO_COST_OPCODE: u8 = 0x60;
 
 type DynOpcodeImplementation<Tx, Reused> = Box<dyn OpCodeImplementation<Tx, Reused>>;
 
+/// The script revealed by the signature script of an input, which runs after the scripts of the input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RevealedScript {
+    None,
+    /// The redeem script of a P2SH spend, which runs over the stack left by the signature script
+    P2sh,
+    /// A leaf script of a MAST spend, whose control block must prove its inclusion in the committed merkle root
+    MastLeaf(Hash),
+}
+
+// The scripts to execute in order, and the script they reveal
+type ExecutionScripts<'a> = (Vec<&'a [u8]>, RevealedScript);
+
 #[derive(Clone, Hash, PartialEq, Eq)]
 enum Signature {
     Secp256k1(secp256k1::schnorr::Signature),
@@ -67,8 +88,22 @@ pub struct SigCacheKey {
     message: secp256k1::Message,
 }
 
+/// Script engine features which are activated by consensus hard-forks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EngineFlags {
+    /// Whether KIP-10 transaction introspection opcodes and 8-byte script numbers are enabled
+    pub kip10_enabled: bool,
+    /// Whether the splice opcodes OpCat, OpSubStr, OpLeft and OpRight, and the arithmetic
+    /// opcodes OpMul, OpDiv, OpMod, OpLShift and OpRShift are enabled
+    pub splice_and_arithmetic_enabled: bool,
+    /// Whether the introspection opcodes reserved by KIP-10, such as OpTxPayload and OpOutpointTxId, are enabled
+    pub full_introspection_enabled: bool,
+    /// Whether outputs committing to a MAST of scripts can be spent by revealing one of its leaf scripts
+    pub mast_enabled: bool,
+}
+
 enum ScriptSource<'a, T: VerifiableTransaction> {
-    TxInput { tx: &'a T, input: &'a TransactionInput, idx: usize, utxo_entry: &'a UtxoEntry, is_p2sh: bool },
+    TxInput { tx: &'a T, input: &'a TransactionInput, idx: usize, utxo_entry: &'a UtxoEntry, revealed: RevealedScript },
     StandAloneScripts(Vec<&'a [u8]>),
 }
 
@@ -85,7 +120,10 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction, Reused: SigHashReusedVal
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
     num_ops: i32,
-    kip10_enabled: bool,
+    flags: EngineFlags,
+
+    // Schnorr signature checks deferred to a batch verification, if collecting them
+    deferred_schnorr_checks: Option<Vec<SchnorrCheck>>,
 }
 
 fn parse_script<T: VerifiableTransaction, Reused: SigHashReusedValues>(
@@ -100,8 +138,9 @@ pub fn get_sig_op_count<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     prev_script_public_key: &ScriptPublicKey,
 ) -> u64 {
     let is_p2sh = ScriptClass::is_pay_to_script_hash(prev_script_public_key.script());
+    let is_mast = ScriptClass::is_pay_to_mast(prev_script_public_key.script());
     let script_pub_key_ops = parse_script::<T, Reused>(prev_script_public_key.script()).collect_vec();
-    if !is_p2sh {
+    if !is_p2sh && !is_mast {
         return get_sig_op_count_by_opcodes(&script_pub_key_ops);
     }
 
@@ -110,9 +149,15 @@ pub fn get_sig_op_count<T: VerifiableTransaction, Reused: SigHashReusedValues>(
         return 0;
     }
 
-    let p2sh_script = signature_script_ops.last().expect("checked if empty above").as_ref().expect("checked if err above").get_data();
-    let p2sh_ops = parse_script::<T, Reused>(p2sh_script).collect_vec();
-    get_sig_op_count_by_opcodes(&p2sh_ops)
+    // The redeem script is the last push of a P2SH spend, while a MAST leaf script is followed by its control block
+    let revealed_script_index = match is_mast {
+        true if signature_script_ops.len() < 2 => return 0,
+        true => signature_script_ops.len() - 2,
+        false => signature_script_ops.len() - 1,
+    };
+    let revealed_script = signature_script_ops[revealed_script_index].as_ref().expect("checked if err above").get_data();
+    let revealed_ops = parse_script::<T, Reused>(revealed_script).collect_vec();
+    get_sig_op_count_by_opcodes(&revealed_ops)
 }
 
 fn get_sig_op_count_by_opcodes<T: VerifiableTransaction, Reused: SigHashReusedValues>(
@@ -155,7 +200,7 @@ pub fn is_unspendable<T: VerifiableTransaction, Reused: SigHashReusedValues>(scr
 }
 
 impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'a, T, Reused> {
-    pub fn new(reused_values: &'a Reused, sig_cache: &'a Cache<SigCacheKey, bool>, kip10_enabled: bool) -> Self {
+    pub fn new(reused_values: &'a Reused, sig_cache: &'a Cache<SigCacheKey, bool>, flags: EngineFlags) -> Self {
         Self {
             dstack: vec![],
             astack: vec![],
@@ -164,7 +209,8 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             sig_cache,
             cond_stack: vec![],
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            deferred_schnorr_checks: None,
         }
     }
 
@@ -177,7 +223,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
     /// * `utxo_entry` - UTXO entry being spent
     /// * `reused_values` - Reused values for signature hashing
     /// * `sig_cache` - Cache for signature verification
-    /// * `kip10_enabled` - Whether KIP-10 transaction introspection opcodes are enabled
+    /// * `flags` - The hard-fork activated features of the engine
     ///
     /// # Panics
     /// * When input_idx >= number of inputs in transaction (malformed input)
@@ -191,22 +237,29 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         utxo_entry: &'a UtxoEntry,
         reused_values: &'a Reused,
         sig_cache: &'a Cache<SigCacheKey, bool>,
-        kip10_enabled: bool,
+        flags: EngineFlags,
     ) -> Self {
         let script_public_key = utxo_entry.script_public_key.script();
         // The script_public_key in P2SH is just validating the hash on the OpMultiSig script
-        // the user provides
-        let is_p2sh = ScriptClass::is_pay_to_script_hash(script_public_key);
+        // the user provides, and in MAST it only commits to the merkle root of the leaf scripts
+        let revealed = if ScriptClass::is_pay_to_script_hash(script_public_key) {
+            RevealedScript::P2sh
+        } else if let Some(merkle_root) = mast_merkle_root(script_public_key).filter(|_| flags.mast_enabled) {
+            RevealedScript::MastLeaf(merkle_root)
+        } else {
+            RevealedScript::None
+        };
         assert!(input_idx < tx.tx().inputs.len());
         Self {
             dstack: Default::default(),
             astack: Default::default(),
-            script_source: ScriptSource::TxInput { tx, input, idx: input_idx, utxo_entry, is_p2sh },
+            script_source: ScriptSource::TxInput { tx, input, idx: input_idx, utxo_entry, revealed },
             reused_values,
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            deferred_schnorr_checks: None,
         }
     }
 
@@ -214,7 +267,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         script: &'a [u8],
         reused_values: &'a Reused,
         sig_cache: &'a Cache<SigCacheKey, bool>,
-        kip10_enabled: bool,
+        flags: EngineFlags,
     ) -> Self {
         Self {
             dstack: Default::default(),
@@ -224,7 +277,8 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            deferred_schnorr_checks: None,
         }
     }
 
@@ -255,30 +309,29 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         }
     }
 
-    fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
-        let script_result = parse_script(script).try_for_each(|opcode| {
-            let opcode = opcode?;
-            if opcode.is_disabled() {
-                return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
-            }
+    fn execute_step(&mut self, opcode: DynOpcodeImplementation<T, Reused>, verify_only_push: bool) -> Result<(), TxScriptError> {
+        if opcode.is_disabled() && !(self.flags.splice_and_arithmetic_enabled && opcode.is_splice_or_arithmetic()) {
+            return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
+        }
 
-            if opcode.always_illegal() {
-                return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
-            }
+        if opcode.always_illegal() {
+            return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
+        }
 
-            if verify_only_push && !opcode.is_push_opcode() {
-                return Err(TxScriptError::SignatureScriptNotPushOnly);
-            }
+        if verify_only_push && !opcode.is_push_opcode() {
+            return Err(TxScriptError::SignatureScriptNotPushOnly);
+        }
 
-            self.execute_opcode(opcode)?;
+        self.execute_opcode(opcode)?;
 
-            let combined_size = self.astack.len() + self.dstack.len();
-            if combined_size > MAX_STACK_SIZE {
-                return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
-            }
-            Ok(())
-        });
+        let combined_size = self.astack.len() + self.dstack.len();
+        if combined_size > MAX_STACK_SIZE {
+            return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
+        }
+        Ok(())
+    }
 
+    fn finish_script(&mut self, script_result: Result<(), TxScriptError>) -> Result<(), TxScriptError> {
         // Moving between scripts - we can't be inside an if
         if script_result.is_ok() && !self.cond_stack.is_empty() {
             return Err(TxScriptError::ErrUnbalancedConditional);
@@ -291,16 +344,27 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         script_result
     }
 
-    pub fn execute(&mut self) -> Result<(), TxScriptError> {
-        let (scripts, is_p2sh) = match &self.script_source {
-            ScriptSource::TxInput { input, utxo_entry, is_p2sh, .. } => {
+    fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
+        let script_result = parse_script(script).try_for_each(|opcode| self.execute_step(opcode?, verify_only_push));
+        self.finish_script(script_result)
+    }
+
+    /// Returns the scripts to execute in order and the script revealed by the input, or `None` if the
+    /// script public key version is unknown, in which case execution succeeds without running any script.
+    fn scripts_to_execute(&self) -> Result<Option<ExecutionScripts<'a>>, TxScriptError> {
+        let (scripts, revealed) = match &self.script_source {
+            ScriptSource::TxInput { input, utxo_entry, revealed, .. } => {
                 if utxo_entry.script_public_key.version() > MAX_SCRIPT_PUBLIC_KEY_VERSION {
                     trace!("The version of the scriptPublicKey is higher than the known version - the Execute function returns true.");
-                    return Ok(());
+                    return Ok(None);
+                }
+                match revealed {
+                    // The MAST commitment is verified against the revealed leaf script instead of being executed
+                    RevealedScript::MastLeaf(_) => (vec![input.signature_script.as_slice()], *revealed),
+                    _ => (vec![input.signature_script.as_slice(), utxo_entry.script_public_key.script()], *revealed),
                 }
-                (vec![input.signature_script.as_slice(), utxo_entry.script_public_key.script()], *is_p2sh)
             }
-            ScriptSource::StandAloneScripts(scripts) => (scripts.clone(), false),
+            ScriptSource::StandAloneScripts(scripts) => (scripts.clone(), RevealedScript::None),
         };
 
         // TODO: run all in same iterator?
@@ -319,30 +383,65 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             return Err(TxScriptError::ScriptSize(s.len(), MAX_SCRIPTS_SIZE));
         }
 
+        Ok(Some((scripts, revealed)))
+    }
+
+    /// Executes the scripts while assuming that the Schnorr signatures missing from the signature cache are valid, and
+    /// returns these signature checks instead of verifying them. On success, the scripts are valid if the returned checks
+    /// are, which is meant to be verified as a batch by [`batch::batch_verify_schnorr`]. Otherwise, or if the batch fails,
+    /// only a regular [`Self::execute`] decides, since a script may also succeed over an invalid signature.
+    pub fn execute_deferring_schnorr_checks(&mut self) -> Result<Vec<SchnorrCheck>, TxScriptError> {
+        self.deferred_schnorr_checks = Some(vec![]);
+        let result = self.execute();
+        let checks = self.deferred_schnorr_checks.take().unwrap_or_default();
+        result.map(|()| checks)
+    }
+
+    pub fn execute(&mut self) -> Result<(), TxScriptError> {
+        let Some((scripts, revealed)) = self.scripts_to_execute()? else {
+            return Ok(());
+        };
+
         let mut saved_stack: Option<Vec<Vec<u8>>> = None;
         // try_for_each quits only if an error occurred. So, we always run over all scripts if
         // each is successful
         scripts.iter().enumerate().filter(|(_, s)| !s.is_empty()).try_for_each(|(idx, s)| {
-            let verify_only_push =
-                idx == 0 && matches!(self.script_source, ScriptSource::TxInput { tx: _, input: _, idx: _, utxo_entry: _, is_p2sh: _ });
+            let verify_only_push = idx == 0 && matches!(self.script_source, ScriptSource::TxInput { .. });
             // Save script in p2sh
-            if is_p2sh && idx == 1 {
+            if revealed == RevealedScript::P2sh && idx == 1 {
                 saved_stack = Some(self.dstack.clone());
             }
             self.execute_script(s, verify_only_push)
         })?;
 
-        if is_p2sh {
-            self.check_error_condition(false)?;
-            self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
-            let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
-            self.execute_script(script.as_slice(), false)?
+        match revealed {
+            RevealedScript::None => {}
+            RevealedScript::P2sh => {
+                self.check_error_condition(false)?;
+                self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
+                let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
+                self.execute_script(script.as_slice(), false)?
+            }
+            RevealedScript::MastLeaf(merkle_root) => {
+                let script = self.reveal_mast_leaf_script(merkle_root)?;
+                self.execute_script(script.as_slice(), false)?
+            }
         }
 
         self.check_error_condition(true)?;
         Ok(())
     }
 
+    /// Pops the control block and the leaf script pushed by the signature script of a MAST spend, and returns
+    /// the leaf script if the control block proves its inclusion in the tree with the committed merkle root.
+    fn reveal_mast_leaf_script(&mut self, merkle_root: Hash) -> Result<Vec<u8>, TxScriptError> {
+        let [leaf_script, control_block] = self.dstack.pop_raw()?;
+        if mast_root_from_control_block(&leaf_script, &control_block)? != merkle_root {
+            return Err(TxScriptError::MastProofMismatch);
+        }
+        Ok(leaf_script)
+    }
+
     // check_error_condition is called whenever we finish a chunk of the scripts
     // (all original scripts, all scripts including p2sh, and maybe future extensions)
     // returns Ok(()) if the running script has ended and was successful, leaving a true boolean
@@ -483,7 +582,10 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
                 match self.sig_cache.get(&sig_cache_key) {
                     Some(valid) => Ok(valid),
                     None => {
-                        // TODO: Find a way to parallelize this part.
+                        if let Some(deferred) = self.deferred_schnorr_checks.as_mut() {
+                            deferred.push(SchnorrCheck::new(pk, sig, msg));
+                            return Ok(true);
+                        }
                         match sig.verify(&msg, &pk) {
                             Ok(()) => {
                                 self.sig_cache.insert(sig_cache_key, true);
@@ -553,6 +655,7 @@ mod tests {
     use crate::opcodes::codes::{OpBlake3, OpCheckSig, OpData1, OpData2, OpData32, OpDup, OpEqual, OpPushData1, OpTrue};
 
     use super::*;
+    use crate::script_builder::ScriptBuilder;
     use smallvec::SmallVec;
     use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
     use vecno_consensus_core::tx::{
@@ -618,7 +721,7 @@ mod tests {
                     &utxo_entry,
                     &reused_values,
                     &sig_cache,
-                    kip10_enabled,
+                    EngineFlags { kip10_enabled, ..Default::default() },
                 );
                 assert_eq!(vm.execute(), test.expected_result);
             });
@@ -921,6 +1024,12 @@ mod tests {
                 ),
                 expected_sig_ops: 2,
             },
+            TestVector {
+                name: "multisig with 16 keys",
+                signature_script: &[],
+                prev_script_public_key: ScriptPublicKey::new(0, SmallVec::from_slice(&[codes::Op16, codes::OpCheckMultiSig])),
+                expected_sig_ops: 16,
+            },
             TestVector {
                 name: "p2pk",
                 signature_script: &hex::decode("416db0c0ce824a6d076c8e73aae9987416933df768e07760829cb0685dc0a2bbb11e2c0ced0cab806e111a11cbda19784098fd25db176b6a9d7c93e5747674d32301").unwrap(),
@@ -930,6 +1039,18 @@ mod tests {
                 ),
                 expected_sig_ops: 1,
             },
+            TestVector {
+                name: "mast leaf script followed by its control block",
+                signature_script: &[OpTrue, OpData2, OpCheckSig, OpCheckSig, OpData2, 0x00, 0x00],
+                prev_script_public_key: pay_to_mast_script(&Hash::from_bytes([1; 32])),
+                expected_sig_ops: 2,
+            },
+            TestVector {
+                name: "mast spend without a control block",
+                signature_script: &[OpData2, OpCheckSig, OpCheckSig],
+                prev_script_public_key: pay_to_mast_script(&Hash::from_bytes([1; 32])),
+                expected_sig_ops: 0,
+            },
         ];
 
         for test in tests {
@@ -945,6 +1066,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mast_spend() {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let tree = MastTree::new(vec![vec![codes::Op3, OpEqual], vec![codes::Op4, OpEqual], vec![OpTrue]]).unwrap();
+        let execute = |signature_script: Vec<u8>, mast_enabled: bool| {
+            let input = TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: TransactionId::from_bytes([1; 32]), index: 0 },
+                signature_script,
+                sequence: u64::MAX,
+                sig_op_count: 0,
+            };
+            let output = TransactionOutput { value: 1000, script_public_key: ScriptPublicKey::from_vec(0, vec![OpTrue]) };
+            let tx = Transaction::new(1, vec![input.clone()], vec![output], 0, Default::default(), 0, vec![]);
+            let utxo_entry = UtxoEntry::new(1000, tree.script_public_key(), 0, false);
+            let populated_tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+            let flags = EngineFlags { mast_enabled, ..Default::default() };
+            TxScriptEngine::from_transaction_input(&populated_tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, flags).execute()
+        };
+        let reveal = |leaf_script: &[u8], control_block: &[u8]| {
+            ScriptBuilder::new().add_mast_reveal(leaf_script, control_block).unwrap().drain()
+        };
+        let push = |value: i64| ScriptBuilder::new().add_i64(value).unwrap().drain();
+
+        assert_eq!(execute(tree.signature_script(0, &push(3)).unwrap(), true), Ok(()));
+        assert_eq!(execute(tree.signature_script(1, &push(4)).unwrap(), true), Ok(()));
+        assert_eq!(execute(tree.signature_script(2, &[]).unwrap(), true), Ok(()));
+        assert_eq!(execute(tree.signature_script(1, &push(3)).unwrap(), true), Err(TxScriptError::EvalFalse));
+        // The leaf script must be proven against the committed root, at its own index
+        let control_block = tree.control_block(1).unwrap();
+        assert_eq!(
+            execute([push(3), reveal(&[codes::Op3, OpEqual], &control_block)].concat(), true),
+            Err(TxScriptError::MastProofMismatch)
+        );
+        assert_eq!(
+            execute([push(5), reveal(&[codes::Op5, OpEqual], &control_block)].concat(), true),
+            Err(TxScriptError::MastProofMismatch)
+        );
+        assert!(matches!(
+            execute([push(4), reveal(&[codes::Op4, OpEqual], &control_block[..33])].concat(), true),
+            Err(TxScriptError::InvalidMastControlBlock(_))
+        ));
+        assert!(execute(ScriptBuilder::new().add_data(&[OpTrue]).unwrap().drain(), true).is_err());
+        // The signature script must be push only, as anywhere else
+        let mut signature_script = tree.signature_script(2, &[]).unwrap();
+        signature_script.insert(0, OpDup);
+        assert_eq!(execute(signature_script, true), Err(TxScriptError::SignatureScriptNotPushOnly));
+        // Before activation, the commitment itself is executed and leaves an unclean stack
+        assert!(matches!(execute(tree.signature_script(0, &push(3)).unwrap(), false), Err(TxScriptError::CleanStack(_))));
+    }
+
     #[test]
     fn test_is_unspendable() {
         struct Test<'a> {
@@ -1085,7 +1257,7 @@ mod bitcoind_tests {
                 &populated_tx.entries[0],
                 &reused_values,
                 &sig_cache,
-                kip10_enabled,
+                EngineFlags { kip10_enabled, ..Default::default() },
             );
             vm.execute().map_err(UnifiedError::TxScriptError)
         }
@@ -122,6 +122,25 @@ macro_rules! opcode_list {
             }
         }
 
+        /// Returns the name of the opcode with the given value, e.g. `OpCheckSig`.
+        pub fn opcode_name(opcode: u8) -> &'static str {
+            match opcode {
+                $(
+                    $num => stringify!($name),
+                )*
+            }
+        }
+
+        /// Returns the value of the opcode with the given name or alias, e.g. `OpCheckSig` or `Op0`.
+        pub fn opcode_from_name(name: &str) -> Option<u8> {
+            match name {
+                $(
+                    stringify!($name) $(| stringify!($alias))? => Some($num),
+                )*
+                _ => None,
+            }
+        }
+
         #[cfg(test)]
         use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
 
@@ -3,8 +3,8 @@ mod macros;
 
 use crate::{
     data_stack::{DataStack, Kip10I64, OpcodeData},
-    ScriptSource, SpkEncoding, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_TX_IN_SEQUENCE_NUM, NO_COST_OPCODE,
-    SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
+    ScriptSource, SpkEncoding, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_SCRIPT_ELEMENT_SIZE, MAX_TX_IN_SEQUENCE_NUM,
+    NO_COST_OPCODE, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
 };
 
 use sha2::{Digest, Sha256};
@@ -27,8 +27,10 @@ pub const OP_DATA_MAX_VAL: u8 = self::codes::OpData75;
 /// Minus 1 value
 pub const OP_1_NEGATE_VAL: u8 = 0x81;
 
-#[derive(Debug, PartialEq, Eq)]
-pub(crate) enum OpCond {
+/// The state of an open conditional branch: whether it is executed, or skipped as a whole because it is nested
+/// inside a branch which is not executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpCond {
     False,
     True,
     Skip,
@@ -67,6 +69,8 @@ pub trait OpCodeMetadata: Debug {
     fn check_minimal_data_push(&self) -> Result<(), TxScriptError>;
 
     fn is_disabled(&self) -> bool;
+    // Disabled opcodes which are enabled once the splice and arithmetic opcodes are activated
+    fn is_splice_or_arithmetic(&self) -> bool;
     fn always_illegal(&self) -> bool;
     fn is_push_opcode(&self) -> bool;
     fn get_data(&self) -> &[u8];
@@ -128,6 +132,21 @@ impl<const CODE: u8> OpCodeMetadata for OpCode<CODE> {
         )
     }
 
+    fn is_splice_or_arithmetic(&self) -> bool {
+        matches!(
+            CODE,
+            codes::OpCat
+                | codes::OpSubStr
+                | codes::OpLeft
+                | codes::OpRight
+                | codes::OpMul
+                | codes::OpDiv
+                | codes::OpMod
+                | codes::OpLShift
+                | codes::OpRShift
+        )
+    }
+
     fn always_illegal(&self) -> bool {
         matches!(CODE, codes::OpVerIf | codes::OpVerNotIf)
     }
@@ -216,13 +235,42 @@ fn push_number<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     Ok(())
 }
 
+/// Pushes an element taken from the transaction, which must not exceed the maximum element size
+#[inline]
+fn push_element<T: VerifiableTransaction, Reused: SigHashReusedValues>(
+    element: Vec<u8>,
+    vm: &mut TxScriptEngine<T, Reused>,
+) -> OpCodeResult {
+    if element.len() > MAX_SCRIPT_ELEMENT_SIZE {
+        return Err(TxScriptError::ElementTooBig(element.len(), MAX_SCRIPT_ELEMENT_SIZE));
+    }
+    vm.dstack.push(element);
+    Ok(())
+}
+
+/// Returns the byte range `begin..end` of an element of size `len`, or an error if it is not fully contained in the element
+fn splice_range(begin: i64, end: i64, len: usize) -> Result<std::ops::Range<usize>, TxScriptError> {
+    if begin < 0 || begin > end || end > len as i64 {
+        return Err(TxScriptError::SpliceOutOfRange(begin, end, len));
+    }
+    Ok(begin as usize..end as usize)
+}
+
+/// Validates the shift amount of OpLShift and OpRShift, which must be lower than the 64-bit width of script numbers
+fn shift_amount(shift: i64) -> Result<u32, TxScriptError> {
+    match shift {
+        0..=63 => Ok(shift as u32),
+        _ => Err(TxScriptError::InvalidShiftAmount(shift)),
+    }
+}
+
 /// This macro helps to avoid code duplication in numeric opcodes where the only difference
 /// between KIP10_ENABLED and disabled states is the numeric type used (Kip10I64 vs i64).
 /// KIP10I64 deserializator supports 8-byte integers
 // TODO: Remove this macro after KIP-10 activation.
 macro_rules! numeric_op {
     ($vm: expr, $pattern: pat, $count: expr, $block: expr) => {
-        if $vm.kip10_enabled {
+        if $vm.flags.kip10_enabled {
             let $pattern: [Kip10I64; $count] = $vm.dstack.pop_items()?;
             let r = $block;
             $vm.dstack.push_item(r)?;
@@ -536,10 +584,55 @@ opcode_list! {
     }
 
     // Splice opcodes.
-    opcode OpCat<0x7e, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpSubStr<0x7f, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpLeft<0x80, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpRight<0x81, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+    opcode OpCat<0x7e, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            let [mut a, b] = vm.dstack.pop_raw()?;
+            if a.len() + b.len() > MAX_SCRIPT_ELEMENT_SIZE {
+                return Err(TxScriptError::ElementTooBig(a.len() + b.len(), MAX_SCRIPT_ELEMENT_SIZE));
+            }
+            a.extend(b);
+            vm.dstack.push(a);
+            Ok(())
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpSubStr<0x7f, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            let [begin, size]: [i32; 2] = vm.dstack.pop_items()?;
+            let [data] = vm.dstack.pop_raw()?;
+            let range = splice_range(begin as i64, begin as i64 + size as i64, data.len())?;
+            vm.dstack.push(data[range].to_vec());
+            Ok(())
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpLeft<0x80, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            let [size]: [i32; 1] = vm.dstack.pop_items()?;
+            let [data] = vm.dstack.pop_raw()?;
+            let range = splice_range(0, size as i64, data.len())?;
+            vm.dstack.push(data[range].to_vec());
+            Ok(())
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpRight<0x81, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            let [size]: [i32; 1] = vm.dstack.pop_items()?;
+            let [data] = vm.dstack.pop_raw()?;
+            let range = splice_range(data.len() as i64 - size as i64, data.len() as i64, data.len())?;
+            vm.dstack.push(data[range].to_vec());
+            Ok(())
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
 
     opcode OpSize<0x82, 1>(self, vm) {
         match vm.dstack.last() {
@@ -623,11 +716,60 @@ opcode_list! {
         numeric_op!(vm, [a,b], 2, a.checked_sub(b.into()).ok_or_else(|| TxScriptError::NumberTooBig("Difference exceeds 64-bit signed integer range".to_string()))?)
     }
 
-    opcode OpMul<0x95, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpDiv<0x96, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpMod<0x97, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpLShift<0x98, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpRShift<0x99, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+    opcode OpMul<0x95, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            numeric_op!(vm, [a,b], 2, a.checked_mul(b.into()).ok_or_else(|| TxScriptError::NumberTooBig("Product exceeds 64-bit signed integer range".to_string()))?)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpDiv<0x96, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            numeric_op!(vm, [a,b], 2, {
+                let divisor: i64 = b.into();
+                if divisor == 0 {
+                    return Err(TxScriptError::DivisionByZero);
+                }
+                a.checked_div(divisor).ok_or_else(|| TxScriptError::NumberTooBig("Quotient exceeds 64-bit signed integer range".to_string()))?
+            })
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpMod<0x97, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            numeric_op!(vm, [a,b], 2, {
+                let divisor: i64 = b.into();
+                if divisor == 0 {
+                    return Err(TxScriptError::DivisionByZero);
+                }
+                a.checked_rem(divisor).ok_or_else(|| TxScriptError::NumberTooBig("Remainder exceeds 64-bit signed integer range".to_string()))?
+            })
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpLShift<0x98, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            numeric_op!(vm, [a,b], 2, {
+                let shift = shift_amount(b.into())?;
+                i64::try_from((i64::from(a) as i128) << shift).map_err(|_| TxScriptError::NumberTooBig("Left shift result exceeds 64-bit signed integer range".to_string()))?
+            })
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+
+    opcode OpRShift<0x99, 1>(self, vm) {
+        if vm.flags.splice_and_arithmetic_enabled {
+            numeric_op!(vm, [a,b], 2, i64::from(a) >> shift_amount(b.into())?)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
 
     opcode OpBoolAnd<0x9a, 1>(self, vm) {
         numeric_op!(vm, [a,b], 2, ((a != 0) && (b != 0)) as i64)
@@ -642,7 +784,7 @@ opcode_list! {
     }
 
     opcode OpNumEqualVerify<0x9d, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             let [a,b]: [Kip10I64; 2] = vm.dstack.pop_items()?;
             match a == b {
                 true => Ok(()),
@@ -881,9 +1023,20 @@ opcode_list! {
 
     // Introspection opcodes
     // Transaction level opcodes (following Transaction struct field order)
-    opcode OpTxVersion<0xb2, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxVersion<0xb2, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().version as i64, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxVersion only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     opcode OpTxInputCount<0xb3, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     push_number(tx.inputs().len() as i64, vm)
@@ -895,7 +1048,7 @@ opcode_list! {
         }
     }
     opcode OpTxOutputCount<0xb4, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     push_number(tx.outputs().len() as i64, vm)
@@ -906,13 +1059,58 @@ opcode_list! {
             Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
         }
     }
-    opcode OpTxLockTime<0xb5, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxSubnetId<0xb6, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxGas<0xb7, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxPayload<0xb8, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxLockTime<0xb5, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().lock_time.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxLockTime only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxSubnetId<0xb6, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    vm.dstack.push(AsRef::<[u8]>::as_ref(&tx.tx().subnetwork_id).to_vec());
+                    Ok(())
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxSubnetId only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxGas<0xb7, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().gas.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxGas only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxPayload<0xb8, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_element(tx.tx().payload.clone(), vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxPayload only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // Input related opcodes (following TransactionInput struct field order)
     opcode OpTxInputIndex<0xb9, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{idx, ..} => {
                     push_number(idx as i64, vm)
@@ -923,13 +1121,75 @@ opcode_list! {
             Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
         }
     }
-    opcode OpOutpointTxId<0xba, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpOutpointIndex<0xbb, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxInputScriptSig<0xbc, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxInputSeq<0xbd, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpOutpointTxId<0xba, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let input = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.inputs().get(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    vm.dstack.push(input.previous_outpoint.transaction_id.as_bytes().to_vec());
+                    Ok(())
+                },
+                _ => Err(TxScriptError::InvalidSource("OpOutpointTxId only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpOutpointIndex<0xbb, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let input = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.inputs().get(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_number(input.previous_outpoint.index as i64, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpOutpointIndex only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxInputScriptSig<0xbc, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let input = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.inputs().get(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_element(input.signature_script.clone(), vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputScriptSig only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxInputSeq<0xbd, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let input = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.inputs().get(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    vm.dstack.push(input.sequence.to_le_bytes().to_vec());
+                    Ok(())
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputSeq only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // UTXO related opcodes (following UtxoEntry struct field order)
     opcode OpTxInputAmount<0xbe, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -945,7 +1205,7 @@ opcode_list! {
         }
     }
     opcode OpTxInputSpk<0xbf, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -961,11 +1221,41 @@ opcode_list! {
             Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
         }
     }
-    opcode OpTxInputBlockDaaScore<0xc0, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxInputIsCoinbase<0xc1, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxInputBlockDaaScore<0xc0, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let utxo = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.utxo(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_number(utxo.block_daa_score.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputBlockDaaScore only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxInputIsCoinbase<0xc1, 1>(self, vm) {
+        if vm.flags.full_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let utxo = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.utxo(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_number(utxo.is_coinbase as i64, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputIsCoinbase only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // Output related opcodes (following TransactionOutput struct field order)
     opcode OpTxOutputAmount<0xc2, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -981,7 +1271,7 @@ opcode_list! {
         }
     }
     opcode OpTxOutputSpk<0xc3, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -1069,7 +1359,7 @@ pub fn to_small_int<T: VerifiableTransaction, Reused: SigHashReusedValues>(opcod
         return 0;
     }
 
-    assert!((codes::OpTrue..codes::Op16).contains(&value), "expected op codes between from the list of Op0 to Op16");
+    assert!((codes::OpTrue..=codes::Op16).contains(&value), "expected op codes between from the list of Op0 to Op16");
     value - (codes::OpTrue - 1)
 }
 
@@ -1078,7 +1368,7 @@ mod test {
     use crate::caches::Cache;
     use crate::data_stack::Stack;
     use crate::opcodes::{OpCodeExecution, OpCodeImplementation};
-    use crate::{opcodes, pay_to_address_script, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD};
+    use crate::{opcodes, pay_to_address_script, EngineFlags, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD};
     use vecno_addresses::{Address, Prefix, Version};
     use vecno_consensus_core::constants::{SOMPI_PER_VECNO, TX_VERSION};
     use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
@@ -1105,7 +1395,7 @@ mod test {
         let reused_values = SigHashReusedValuesUnsync::new();
         for TestCase { init, code, dstack } in tests {
             [false, true].into_iter().for_each(|kip10_enabled| {
-                let mut vm = TxScriptEngine::new(&reused_values, &cache, kip10_enabled);
+                let mut vm = TxScriptEngine::new(&reused_values, &cache, EngineFlags { kip10_enabled, ..Default::default() });
                 vm.dstack = init.clone();
                 code.execute(&mut vm).unwrap_or_else(|_| panic!("Opcode {} should not fail", code.value()));
                 assert_eq!(*vm.dstack, dstack, "OpCode {} Pushed wrong value", code.value());
@@ -1118,7 +1408,7 @@ mod test {
         let reused_values = SigHashReusedValuesUnsync::new();
         for ErrorTestCase { init, code, error } in tests {
             [false, true].into_iter().for_each(|kip10_enabled| {
-                let mut vm = TxScriptEngine::new(&reused_values, &cache, kip10_enabled);
+                let mut vm = TxScriptEngine::new(&reused_values, &cache, EngineFlags { kip10_enabled, ..Default::default() });
                 vm.dstack.clone_from(&init);
                 assert_eq!(
                     code.execute(&mut vm)
@@ -1154,7 +1444,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -1188,7 +1478,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -1261,7 +1551,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -2856,7 +3146,8 @@ mod test {
         ] {
             let mut tx = base_tx.clone();
             tx.0.lock_time = tx_lock_time;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, false);
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, Default::default());
             vm.dstack = vec![lock_time.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -2898,7 +3189,8 @@ mod test {
         ] {
             let mut input = base_input.clone();
             input.sequence = tx_sequence;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, false);
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, Default::default());
             vm.dstack = vec![sequence.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -3091,7 +3383,7 @@ mod test {
                     tx.utxo(current_idx).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    group.kip10_enabled,
+                    EngineFlags { kip10_enabled: group.kip10_enabled, ..Default::default() },
                 );
 
                 // Check input index opcode first
@@ -3359,7 +3651,7 @@ mod test {
                         tx.utxo(0).unwrap(),
                         &reused_values,
                         &sig_cache,
-                        kip10_enabled,
+                        EngineFlags { kip10_enabled, ..Default::default() },
                     );
 
                     let op_input_count = opcodes::OpTxInputCount::empty().expect("Should accept empty");
@@ -3439,7 +3731,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3463,7 +3755,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3503,7 +3795,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3529,7 +3821,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3551,8 +3843,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // OpInputSpk should push input's SPK onto stack, making it non-empty
             assert_eq!(vm.execute(), Ok(()));
@@ -3575,8 +3874,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // Should succeed because the SPKs are different
             assert_eq!(vm.execute(), Ok(()));
@@ -3600,8 +3906,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // Should succeed because both SPKs are identical
             assert_eq!(vm.execute(), Ok(()));
@@ -3645,7 +3958,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3671,7 +3984,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3704,7 +4017,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3728,7 +4041,7 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 // Should fail because script expects index 0 but we're at index 1
@@ -3774,7 +4087,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3793,7 +4106,7 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3816,7 +4129,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3838,11 +4151,505 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
             }
         }
     }
+
+    mod splice_and_arithmetic {
+        use super::*;
+        use crate::{opcodes::parse_short_form, MAX_SCRIPT_ELEMENT_SIZE};
+
+        struct TestVector {
+            script: String,
+            kip10_enabled: bool,
+            expected: Result<(), TxScriptError>,
+        }
+
+        fn vector(script: &str, expected: Result<(), TxScriptError>) -> TestVector {
+            TestVector { script: script.to_string(), kip10_enabled: true, expected }
+        }
+
+        fn test_vectors() -> Vec<TestVector> {
+            let splice_error = |begin, end, len| Err(TxScriptError::SpliceOutOfRange(begin, end, len));
+            let too_big = |message: &str| Err(TxScriptError::NumberTooBig(message.to_string()));
+            let push_260 = format!("0x4d0401{}", "ab".repeat(260));
+            let push_261 = format!("0x4d0501{}", "ab".repeat(261));
+            vec![
+                // OpCat
+                vector("'abc' 'def' CAT 'abcdef' EQUAL", Ok(())),
+                vector("'abc' '' CAT 'abc' EQUAL", Ok(())),
+                vector("'' 'def' CAT 'def' EQUAL", Ok(())),
+                vector("0 0 CAT 0 EQUAL", Ok(())),
+                vector("'a' CAT", Err(TxScriptError::InvalidStackOperation(2, 1))),
+                TestVector {
+                    script: format!("{push_260} {push_260} CAT SIZE 520 NUMEQUALVERIFY DROP 1"),
+                    kip10_enabled: true,
+                    expected: Ok(()),
+                },
+                TestVector {
+                    script: format!("{push_260} {push_261} CAT"),
+                    kip10_enabled: true,
+                    expected: Err(TxScriptError::ElementTooBig(521, MAX_SCRIPT_ELEMENT_SIZE)),
+                },
+                // OpSubStr
+                vector("'abcdef' 1 3 SUBSTR 'bcd' EQUAL", Ok(())),
+                vector("'abcdef' 0 6 SUBSTR 'abcdef' EQUAL", Ok(())),
+                vector("'abcdef' 6 0 SUBSTR 0 EQUAL", Ok(())),
+                vector("'abcdef' 4 3 SUBSTR", splice_error(4, 7, 6)),
+                vector("'abcdef' 7 0 SUBSTR", splice_error(7, 7, 6)),
+                vector("'abcdef' -1 2 SUBSTR", splice_error(-1, 1, 6)),
+                vector("'abcdef' 2 -1 SUBSTR", splice_error(2, 1, 6)),
+                vector("1 2 SUBSTR", Err(TxScriptError::InvalidStackOperation(1, 0))),
+                // OpLeft
+                vector("'abcdef' 2 LEFT 'ab' EQUAL", Ok(())),
+                vector("'abcdef' 6 LEFT 'abcdef' EQUAL", Ok(())),
+                vector("'abcdef' 0 LEFT 0 EQUAL", Ok(())),
+                vector("'abcdef' 7 LEFT", splice_error(0, 7, 6)),
+                vector("'abcdef' -1 LEFT", splice_error(0, -1, 6)),
+                // OpRight
+                vector("'abcdef' 2 RIGHT 'ef' EQUAL", Ok(())),
+                vector("'abcdef' 6 RIGHT 'abcdef' EQUAL", Ok(())),
+                vector("'abcdef' 0 RIGHT 0 EQUAL", Ok(())),
+                vector("'abcdef' 7 RIGHT", splice_error(-1, 6, 6)),
+                vector("'abcdef' -1 RIGHT", splice_error(7, 6, 6)),
+                // OpMul
+                vector("6 7 MUL 42 EQUAL", Ok(())),
+                vector("-6 7 MUL -42 EQUAL", Ok(())),
+                vector("-6 -7 MUL 42 EQUAL", Ok(())),
+                vector("0 7 MUL 0 EQUAL", Ok(())),
+                vector("3037000499 3037000499 MUL 9223372030926249001 EQUAL", Ok(())),
+                vector("4294967296 4294967296 MUL", too_big("Product exceeds 64-bit signed integer range")),
+                vector("-4294967296 4294967296 MUL", too_big("Product exceeds 64-bit signed integer range")),
+                vector("1 MUL", Err(TxScriptError::InvalidStackOperation(2, 1))),
+                // OpDiv
+                vector("42 5 DIV 8 EQUAL", Ok(())),
+                vector("-42 5 DIV -8 EQUAL", Ok(())),
+                vector("42 -5 DIV -8 EQUAL", Ok(())),
+                vector("0 5 DIV 0 EQUAL", Ok(())),
+                vector("42 0 DIV", Err(TxScriptError::DivisionByZero)),
+                // OpMod
+                vector("42 5 MOD 2 EQUAL", Ok(())),
+                vector("-42 5 MOD -2 EQUAL", Ok(())),
+                vector("42 -5 MOD 2 EQUAL", Ok(())),
+                vector("40 5 MOD 0 EQUAL", Ok(())),
+                vector("42 0 MOD", Err(TxScriptError::DivisionByZero)),
+                // OpLShift
+                vector("1 3 LSHIFT 8 EQUAL", Ok(())),
+                vector("-1 3 LSHIFT -8 EQUAL", Ok(())),
+                vector("5 0 LSHIFT 5 EQUAL", Ok(())),
+                vector("1 62 LSHIFT 4611686018427387904 EQUAL", Ok(())),
+                vector("1 63 LSHIFT", too_big("Left shift result exceeds 64-bit signed integer range")),
+                vector("0 63 LSHIFT 0 EQUAL", Ok(())),
+                vector("1 64 LSHIFT", Err(TxScriptError::InvalidShiftAmount(64))),
+                vector("1 -1 LSHIFT", Err(TxScriptError::InvalidShiftAmount(-1))),
+                // OpRShift
+                vector("8 3 RSHIFT 1 EQUAL", Ok(())),
+                vector("7 1 RSHIFT 3 EQUAL", Ok(())),
+                vector("-7 1 RSHIFT -4 EQUAL", Ok(())),
+                vector("-1 63 RSHIFT -1 EQUAL", Ok(())),
+                vector("4611686018427387904 62 RSHIFT 1 EQUAL", Ok(())),
+                vector("1 64 RSHIFT", Err(TxScriptError::InvalidShiftAmount(64))),
+                vector("1 -1 RSHIFT", Err(TxScriptError::InvalidShiftAmount(-1))),
+                // Operands are limited to 4 bytes before KIP-10, but results are not
+                TestVector { script: "65536 65536 MUL 4294967296 EQUAL".to_string(), kip10_enabled: false, expected: Ok(()) },
+                TestVector { script: "1 40 LSHIFT 1099511627776 EQUAL".to_string(), kip10_enabled: false, expected: Ok(()) },
+                TestVector {
+                    script: "4294967296 2 DIV".to_string(),
+                    kip10_enabled: false,
+                    expected: too_big("numeric value encoded as [0, 0, 0, 0, 1] is 5 bytes which exceeds the max allowed of 4"),
+                },
+                // Activated opcodes are no longer rejected in branches which are not executed
+                vector("0 IF CAT SUBSTR LEFT RIGHT MUL DIV MOD LSHIFT RSHIFT ENDIF 1", Ok(())),
+            ]
+        }
+
+        fn execute(script: &[u8], flags: EngineFlags) -> Result<(), TxScriptError> {
+            let sig_cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            TxScriptEngine::<PopulatedTransaction, _>::from_script(script, &reused_values, &sig_cache, flags).execute()
+        }
+
+        #[test]
+        fn test_before_activation() {
+            for test in test_vectors() {
+                let script = parse_short_form(test.script.clone()).unwrap();
+                for kip10_enabled in [false, true] {
+                    let result =
+                        execute(&script, EngineFlags { kip10_enabled, splice_and_arithmetic_enabled: false, ..Default::default() });
+                    assert!(
+                        matches!(result, Err(TxScriptError::OpcodeDisabled(_))),
+                        "script '{}' should fail with a disabled opcode before activation, got {result:?}",
+                        test.script
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_after_activation() {
+            for test in test_vectors() {
+                let script = parse_short_form(test.script.clone()).unwrap();
+                let flags =
+                    EngineFlags { kip10_enabled: test.kip10_enabled, splice_and_arithmetic_enabled: true, ..Default::default() };
+                assert_eq!(execute(&script, flags), test.expected, "script '{}' returned an unexpected result", test.script);
+            }
+        }
+
+        #[test]
+        fn test_remaining_disabled_opcodes() {
+            let flags = EngineFlags { kip10_enabled: true, splice_and_arithmetic_enabled: true, ..Default::default() };
+            for opcode in ["INVERT", "AND", "OR", "XOR", "2MUL", "2DIV"] {
+                for script in [format!("1 1 {opcode}"), format!("0 IF {opcode} ENDIF 1")] {
+                    let result = execute(&parse_short_form(script.clone()).unwrap(), flags);
+                    assert!(matches!(result, Err(TxScriptError::OpcodeDisabled(_))), "script '{script}' should fail, got {result:?}");
+                }
+            }
+        }
+    }
+
+    mod full_introspection {
+        use super::*;
+        use crate::{
+            data_stack::DataStack, get_sig_op_count, opcodes::codes::*, script_builder::ScriptBuilder, MAX_OPS_PER_SCRIPT,
+            MAX_SCRIPT_ELEMENT_SIZE,
+        };
+        use vecno_consensus_core::subnets::SubnetworkId;
+
+        fn introspection_tx_mock(payload: Vec<u8>) -> (Transaction, Vec<UtxoEntry>) {
+            let inputs = vec![
+                TransactionInput::new(TransactionOutpoint::new(vecno_hashes::Hash::from_u64_word(1), 7), vec![0xab; 65], 10, 1),
+                TransactionInput::new(TransactionOutpoint::new(vecno_hashes::Hash::from_u64_word(2), 0), vec![], u64::MAX, 0),
+            ];
+            let utxos = vec![
+                UtxoEntry::new(1111, ScriptPublicKey::from_vec(0, vec![OpTrue]), 4_000_000_000, false),
+                UtxoEntry::new(2222, ScriptPublicKey::from_vec(0, vec![OpTrue]), 12, true),
+            ];
+            let outputs = vec![TransactionOutput::new(3333, ScriptPublicKey::from_vec(0, vec![OpTrue]))];
+            let subnetwork_id = SubnetworkId::from_byte(7);
+            let tx = Transaction::new(TX_VERSION + 1, inputs, outputs, 1_000_000_000_000, subnetwork_id, 50_000, payload);
+            (tx, utxos)
+        }
+
+        struct IntrospectionTestCase<'a> {
+            opcode: Box<dyn OpCodeImplementation<PopulatedTransaction<'a>, SigHashReusedValuesUnsync>>,
+            init: Stack,
+            expected: Result<Vec<u8>, TxScriptError>,
+        }
+
+        fn execute_opcode<'a>(
+            tx: &PopulatedTransaction<'a>,
+            opcode: &dyn OpCodeImplementation<PopulatedTransaction<'a>, SigHashReusedValuesUnsync>,
+            init: Stack,
+            full_introspection_enabled: bool,
+        ) -> Result<Stack, TxScriptError> {
+            let sig_cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let mut vm = TxScriptEngine::from_transaction_input(
+                tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, full_introspection_enabled, ..Default::default() },
+            );
+            vm.dstack = init;
+            opcode.execute(&mut vm)?;
+            Ok(vm.dstack)
+        }
+
+        fn number(value: i64) -> Vec<u8> {
+            let mut stack = Stack::new();
+            stack.push_item(value).unwrap();
+            stack.pop().unwrap()
+        }
+
+        #[test]
+        fn test_introspection_opcodes() {
+            let (tx, utxos) = introspection_tx_mock(b"covenant state".to_vec());
+            let tx = PopulatedTransaction::new(&tx, utxos);
+            let subnetwork_id = SubnetworkId::from_byte(7);
+
+            let tests = vec![
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxVersion::empty().unwrap(),
+                    init: vec![],
+                    expected: Ok(number(TX_VERSION as i64 + 1)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxLockTime::empty().unwrap(),
+                    init: vec![],
+                    expected: Ok(number(1_000_000_000_000)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxSubnetId::empty().unwrap(),
+                    init: vec![],
+                    expected: Ok(AsRef::<[u8]>::as_ref(&subnetwork_id).to_vec()),
+                },
+                IntrospectionTestCase { opcode: opcodes::OpTxGas::empty().unwrap(), init: vec![], expected: Ok(number(50_000)) },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxPayload::empty().unwrap(),
+                    init: vec![],
+                    expected: Ok(b"covenant state".to_vec()),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpOutpointTxId::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(vecno_hashes::Hash::from_u64_word(2).as_bytes().to_vec()),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpOutpointIndex::empty().unwrap(),
+                    init: vec![number(0)],
+                    expected: Ok(number(7)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpOutpointIndex::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(number(0)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputScriptSig::empty().unwrap(),
+                    init: vec![number(0)],
+                    expected: Ok(vec![0xab; 65]),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputScriptSig::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(vec![]),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputSeq::empty().unwrap(),
+                    init: vec![number(0)],
+                    expected: Ok(10u64.to_le_bytes().to_vec()),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputSeq::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(vec![0xff; 8]),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputBlockDaaScore::empty().unwrap(),
+                    init: vec![number(0)],
+                    expected: Ok(number(4_000_000_000)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputBlockDaaScore::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(number(12)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputIsCoinbase::empty().unwrap(),
+                    init: vec![number(0)],
+                    expected: Ok(vec![]),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputIsCoinbase::empty().unwrap(),
+                    init: vec![number(1)],
+                    expected: Ok(number(1)),
+                },
+                // Out of range and missing input indices
+                IntrospectionTestCase {
+                    opcode: opcodes::OpOutpointTxId::empty().unwrap(),
+                    init: vec![number(2)],
+                    expected: Err(TxScriptError::InvalidInputIndex(2, 2)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpOutpointIndex::empty().unwrap(),
+                    init: vec![number(-1)],
+                    expected: Err(TxScriptError::InvalidInputIndex(-1, 2)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputScriptSig::empty().unwrap(),
+                    init: vec![number(2)],
+                    expected: Err(TxScriptError::InvalidInputIndex(2, 2)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputSeq::empty().unwrap(),
+                    init: vec![],
+                    expected: Err(TxScriptError::InvalidStackOperation(1, 0)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputBlockDaaScore::empty().unwrap(),
+                    init: vec![number(2)],
+                    expected: Err(TxScriptError::InvalidInputIndex(2, 2)),
+                },
+                IntrospectionTestCase {
+                    opcode: opcodes::OpTxInputIsCoinbase::empty().unwrap(),
+                    init: vec![number(-1)],
+                    expected: Err(TxScriptError::InvalidInputIndex(-1, 2)),
+                },
+            ];
+
+            for IntrospectionTestCase { opcode, init, expected } in tests {
+                // Prior to activation the opcodes remain reserved
+                assert!(
+                    matches!(execute_opcode(&tx, opcode.as_ref(), init.clone(), false), Err(TxScriptError::OpcodeReserved(_))),
+                    "{opcode:?} should be reserved before activation"
+                );
+
+                let result = execute_opcode(&tx, opcode.as_ref(), init.clone(), true);
+                match expected {
+                    Ok(item) => assert_eq!(result, Ok(vec![item]), "{opcode:?} pushed a wrong value"),
+                    Err(err) => assert_eq!(result, Err(err), "{opcode:?} returned a wrong error"),
+                }
+            }
+        }
+
+        #[test]
+        fn test_introspection_scripts_activation() {
+            let scripts = [
+                ScriptBuilder::new()
+                    .add_op(OpTxPayload)
+                    .unwrap()
+                    .add_data(b"covenant state")
+                    .unwrap()
+                    .add_op(OpEqual)
+                    .unwrap()
+                    .drain(),
+                ScriptBuilder::new()
+                    .add_op(OpTxVersion)
+                    .unwrap()
+                    .add_i64(TX_VERSION as i64 + 1)
+                    .unwrap()
+                    .add_op(OpEqualVerify)
+                    .unwrap()
+                    .add_op(OpTxGas)
+                    .unwrap()
+                    .add_i64(50_000)
+                    .unwrap()
+                    .add_op(OpEqual)
+                    .unwrap()
+                    .drain(),
+                ScriptBuilder::new()
+                    .add_op(Op0)
+                    .unwrap()
+                    .add_op(OpOutpointIndex)
+                    .unwrap()
+                    .add_i64(7)
+                    .unwrap()
+                    .add_op(OpEqualVerify)
+                    .unwrap()
+                    .add_op(OpTrue)
+                    .unwrap()
+                    .add_op(OpTxInputBlockDaaScore)
+                    .unwrap()
+                    .add_i64(12)
+                    .unwrap()
+                    .add_op(OpEqualVerify)
+                    .unwrap()
+                    .add_op(OpTrue)
+                    .unwrap()
+                    .add_op(OpTxInputIsCoinbase)
+                    .unwrap()
+                    .drain(),
+            ];
+
+            for script in scripts {
+                let (mut tx, utxos) = introspection_tx_mock(b"covenant state".to_vec());
+                tx.inputs[0].signature_script = vec![];
+                let utxos = utxos
+                    .into_iter()
+                    .map(|utxo| UtxoEntry { script_public_key: ScriptPublicKey::from_vec(0, script.clone()), ..utxo })
+                    .collect();
+                let tx = PopulatedTransaction::new(&tx, utxos);
+                let sig_cache = Cache::new(10_000);
+                let reused_values = SigHashReusedValuesUnsync::new();
+
+                for full_introspection_enabled in [false, true] {
+                    let mut vm = TxScriptEngine::from_transaction_input(
+                        &tx,
+                        &tx.inputs()[0],
+                        0,
+                        tx.utxo(0).unwrap(),
+                        &reused_values,
+                        &sig_cache,
+                        EngineFlags { kip10_enabled: true, full_introspection_enabled, ..Default::default() },
+                    );
+                    let result = vm.execute();
+                    if full_introspection_enabled {
+                        assert_eq!(result, Ok(()), "script {script:?} should succeed after activation");
+                    } else {
+                        assert!(
+                            matches!(result, Err(TxScriptError::OpcodeReserved(_))),
+                            "script {script:?} should fail with a reserved opcode before activation, got {result:?}"
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_introspected_element_size() {
+            for (len, expected) in [
+                (MAX_SCRIPT_ELEMENT_SIZE, Ok(())),
+                (MAX_SCRIPT_ELEMENT_SIZE + 1, Err(TxScriptError::ElementTooBig(MAX_SCRIPT_ELEMENT_SIZE + 1, MAX_SCRIPT_ELEMENT_SIZE))),
+            ] {
+                let (tx, utxos) = introspection_tx_mock(vec![1; len]);
+                let tx = PopulatedTransaction::new(&tx, utxos);
+                let result = execute_opcode(&tx, opcodes::OpTxPayload::empty().unwrap().as_ref(), vec![], true);
+                assert_eq!(result.map(|_| ()), expected);
+            }
+        }
+
+        #[test]
+        fn test_introspection_requires_transaction_input() {
+            let sig_cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let script = [OpTxPayload];
+            let mut vm = TxScriptEngine::<PopulatedTransaction, _>::from_script(
+                &script,
+                &reused_values,
+                &sig_cache,
+                EngineFlags { full_introspection_enabled: true, ..Default::default() },
+            );
+            assert_eq!(vm.execute(), Err(TxScriptError::InvalidSource("OpTxPayload only applies to transaction inputs".to_string())));
+        }
+
+        #[test]
+        fn test_introspection_sig_op_and_op_accounting() {
+            // Introspection opcodes are not signature operations, hence they do not add to the sig op count nor to the mass
+            let script = ScriptPublicKey::from_vec(
+                0,
+                vec![
+                    OpTxVersion,
+                    OpDrop,
+                    OpTxPayload,
+                    OpDrop,
+                    OpFalse,
+                    OpTxInputScriptSig,
+                    OpDrop,
+                    OpFalse,
+                    OpTxInputIsCoinbase,
+                    OpCheckSig,
+                ],
+            );
+            assert_eq!(get_sig_op_count::<PopulatedTransaction, SigHashReusedValuesUnsync>(&[], &script), 1);
+
+            // But every one of them counts towards the operation limit of a script
+            let (tx, utxos) = introspection_tx_mock(vec![]);
+            let mut script = [OpTxGas, OpDrop].repeat(100);
+            script.extend([OpTxVersion, OpDrop]);
+            let utxos = utxos
+                .into_iter()
+                .map(|utxo| UtxoEntry { script_public_key: ScriptPublicKey::from_vec(0, script.clone()), ..utxo })
+                .collect();
+            let tx = PopulatedTransaction::new(&tx, utxos);
+            let sig_cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[1],
+                1,
+                tx.utxo(1).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { full_introspection_enabled: true, ..Default::default() },
+            );
+            assert_eq!(vm.execute(), Err(TxScriptError::TooManyOperations(MAX_OPS_PER_SCRIPT)));
+        }
+    }
 }
@@ -0,0 +1,174 @@
+//!
+//! Property-based tests of invariants of the script engine, over randomly generated scripts.
+//!
+//! The fuzz targets in `fuzz/` explore arbitrary bytes, while these tests generate mostly well-formed scripts
+//! biased towards stack manipulation, so that the invariants are checked over long executions.
+//!
+
+use crate::analyzer::analyze_redeem_script;
+use crate::caches::Cache;
+use crate::debugger::{ScriptDebugger, ScriptStep};
+use crate::opcodes::codes::{self, *};
+use crate::script_asm::{assemble, disassemble};
+use crate::{pay_to_script_hash_script, EngineFlags, TxScriptEngine, MAX_SCRIPT_ELEMENT_SIZE, MAX_STACK_SIZE};
+use proptest::prelude::*;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::mass::MassCalculator;
+use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+use vecno_consensus_core::tx::{
+    PopulatedTransaction, ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
+    VerifiableTransaction,
+};
+use vecno_txscript_errors::TxScriptError;
+
+/// Opcodes manipulating the data and alt stacks, or the condition stack, which are more likely than arbitrary
+/// opcodes to keep a random script executing
+const STACK_OPCODES: &[u8] = &[
+    OpDup,
+    Op2Dup,
+    Op3Dup,
+    OpOver,
+    Op2Over,
+    OpPick,
+    OpRoll,
+    OpRot,
+    Op2Rot,
+    OpSwap,
+    Op2Swap,
+    OpTuck,
+    OpIfDup,
+    OpDepth,
+    OpDrop,
+    Op2Drop,
+    OpNip,
+    OpToAltStack,
+    OpFromAltStack,
+    OpSize,
+    OpCat,
+    OpIf,
+    OpNotIf,
+    OpElse,
+    OpEndIf,
+    OpEqual,
+    OpAdd,
+];
+
+/// Encodes a data push with the given push opcode, which may not be the minimal one.
+fn encode_push(opcode: u8, data: &[u8]) -> Vec<u8> {
+    let prefix = match opcode {
+        codes::OpPushData1 => vec![opcode, data.len() as u8],
+        codes::OpPushData2 => [opcode].into_iter().chain((data.len() as u16).to_le_bytes()).collect(),
+        _ => vec![data.len() as u8],
+    };
+    prefix.into_iter().chain(data.iter().copied()).collect()
+}
+
+/// A single push opcode with its data, the data being occasionally larger than the max element size
+fn push() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        4 => prop_oneof![Just(OpFalse), Just(Op1Negate), OpTrue..=Op16].prop_map(|opcode| vec![opcode]),
+        4 => prop::collection::vec(any::<u8>(), 1..=OpData75 as usize).prop_map(|data| encode_push(data.len() as u8, &data)),
+        1 => prop::collection::vec(any::<u8>(), 0..=u8::MAX as usize).prop_map(|data| encode_push(OpPushData1, &data)),
+        1 => prop::collection::vec(any::<u8>(), 0..MAX_SCRIPT_ELEMENT_SIZE + 16).prop_map(|data| encode_push(OpPushData2, &data)),
+    ]
+}
+
+fn push_only_script() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(push(), 0..64).prop_map(|pushes| pushes.concat())
+}
+
+fn script() -> impl Strategy<Value = Vec<u8>> {
+    let opcode = prop_oneof![
+        3 => push(),
+        3 => prop::sample::select(STACK_OPCODES).prop_map(|opcode| vec![opcode]),
+        1 => (Op1Negate..=u8::MAX).prop_map(|opcode| vec![opcode]),
+    ];
+    prop::collection::vec(opcode, 0..64).prop_map(|opcodes| opcodes.concat())
+}
+
+fn engine_flags() -> impl Strategy<Value = EngineFlags> {
+    (any::<bool>(), any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+        |(kip10_enabled, splice_and_arithmetic_enabled, full_introspection_enabled, mast_enabled)| EngineFlags {
+            kip10_enabled,
+            splice_and_arithmetic_enabled,
+            full_introspection_enabled,
+            mast_enabled,
+        },
+    )
+}
+
+/// Executes a stand-alone script with the debugger, returning its outcome and trace.
+fn debug_script(script: &[u8], flags: EngineFlags) -> (Result<(), TxScriptError>, Vec<ScriptStep>) {
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let sig_cache = Cache::new(0);
+    let mut debugger =
+        ScriptDebugger::<PopulatedTransaction, _>::new(TxScriptEngine::from_script(script, &reused_values, &sig_cache, flags));
+    let outcome = debugger.run();
+    (outcome, debugger.trace().to_vec())
+}
+
+proptest! {
+    #[test]
+    fn test_push_only_script_never_mutates_alt_stack(script in push_only_script(), flags in engine_flags()) {
+        let (outcome, trace) = debug_script(&script, flags);
+        // Every push but a failing last one grows the data stack by a single item
+        let completed = if outcome.is_ok() { trace.len() } else { trace.len().saturating_sub(1) };
+        for (index, step) in trace.iter().enumerate() {
+            prop_assert!(step.alt_stack.is_empty());
+            if index < completed {
+                prop_assert_eq!(step.data_stack.len(), index + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_executed_script_never_exceeds_stack_limits(script in script(), flags in engine_flags()) {
+        let (outcome, trace) = debug_script(&script, flags);
+        // The step failing execution, if any, may record the state which made it fail
+        let completed = if outcome.is_ok() { trace.len() } else { trace.len().saturating_sub(1) };
+        for step in &trace[..completed] {
+            prop_assert!(step.data_stack.len() + step.alt_stack.len() <= MAX_STACK_SIZE);
+            prop_assert!(step.data_stack.iter().chain(step.alt_stack.iter()).all(|item| item.len() <= MAX_SCRIPT_ELEMENT_SIZE));
+        }
+    }
+
+    #[test]
+    fn test_debugger_matches_engine(script in script(), flags in engine_flags()) {
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_cache = Cache::new(0);
+        let result = TxScriptEngine::<PopulatedTransaction, _>::from_script(&script, &reused_values, &sig_cache, flags).execute();
+        prop_assert_eq!(debug_script(&script, flags).0, result);
+    }
+
+    #[test]
+    fn test_disassembly_round_trip(script in prop_oneof![script(), prop::collection::vec(any::<u8>(), 0..256)]) {
+        prop_assert_eq!(assemble(&disassemble(&script)).unwrap(), script);
+    }
+
+    #[test]
+    fn test_analyzer_issues_fail_every_spend(
+        redeem_script in script(),
+        signature_script in push_only_script(),
+        flags in engine_flags(),
+    ) {
+        let analysis = analyze_redeem_script(&redeem_script, flags, &MassCalculator::new(0, 0, 0, 0));
+        prop_assume!(!analysis.issues.is_empty());
+
+        let push_opcode = match redeem_script.len() {
+            0..=75 => redeem_script.len() as u8,
+            76..=255 => OpPushData1,
+            _ => OpPushData2,
+        };
+        let signature_script = [signature_script, encode_push(push_opcode, &redeem_script)].concat();
+        let input = TransactionInput::new(TransactionOutpoint::default(), signature_script, 0, 0);
+        let output = TransactionOutput::new(1000, ScriptPublicKey::from_vec(0, vec![OpTrue]));
+        let tx = Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let entry = UtxoEntry::new(1000, pay_to_script_hash_script(&redeem_script), 0, false);
+        let tx = PopulatedTransaction::new(&tx, vec![entry]);
+        let (input, entry) = tx.populated_input(0);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_cache = Cache::new(0);
+        let result = TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &sig_cache, flags).execute();
+        prop_assert!(result.is_err(), "{:?} spent despite {:?}", disassemble(&redeem_script), analysis.issues);
+    }
+}
@@ -0,0 +1,209 @@
+//!
+//! Textual representation of scripts.
+//!
+//! [`disassemble`] renders a script as a whitespace separated list of opcode names, where every data push
+//! opcode is followed by its data as `0x`-prefixed hex, e.g. `OpDup OpBlake3 OpData32 0x1a2b... OpEqualVerify OpCheckSig`.
+//! Trailing bytes which do not form a complete opcode, such as a truncated data push, are rendered verbatim
+//! in square brackets, e.g. `[4c05aabb]`.
+//!
+//! [`assemble`] parses the same syntax back to the exact same bytes. Additionally, it accepts a few literals
+//! which are encoded canonically, as [`ScriptBuilder`] would:
+//! * decimal numbers, e.g. `-1` or `1000`, pushed as script numbers;
+//! * standalone `0x`-prefixed hex, pushed as data with the smallest suitable push opcode.
+//!
+
+use crate::opcodes::{codes, deserialize_next_opcode, opcode_from_name, opcode_name, OpCodeImplementation};
+use crate::script_builder::{ScriptBuilder, ScriptBuilderError};
+use thiserror::Error;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+use vecno_utils::hex::{FromHex, ToHex};
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum ScriptAsmError {
+    #[error("unknown token '{0}'")]
+    UnknownToken(String),
+
+    #[error("{0} must be followed by its data as 0x-prefixed hex")]
+    MissingData(&'static str),
+
+    #[error("invalid hex '{0}'")]
+    InvalidHex(String),
+
+    #[error("{0} pushes {1} bytes of data but {2} were supplied")]
+    DataLengthMismatch(&'static str, usize, usize),
+
+    #[error("{1} bytes of data exceed the capacity of {0}")]
+    DataTooLong(&'static str, usize),
+
+    #[error(transparent)]
+    ScriptBuilder(#[from] ScriptBuilderError),
+}
+
+pub type ScriptAsmResult<T> = std::result::Result<T, ScriptAsmError>;
+
+/// Renders a script in its canonical textual form. Any byte sequence can be rendered, and assembling the
+/// result with [`assemble`] always yields the original bytes.
+pub fn disassemble(script: &[u8]) -> String {
+    let mut tokens = vec![];
+    let mut it = script.iter();
+    while it.len() > 0 {
+        let remaining = it.as_slice();
+        match deserialize_next_opcode::<_, PopulatedTransaction<'static>, SigHashReusedValuesUnsync>(&mut it) {
+            Some(Ok(opcode)) => {
+                tokens.push(opcode_name(opcode.value()).to_string());
+                if is_data_push(opcode.as_ref()) {
+                    tokens.push(format!("0x{}", opcode.get_data().to_hex()));
+                }
+            }
+            _ => {
+                tokens.push(format!("[{}]", remaining.to_hex()));
+                break;
+            }
+        }
+    }
+    tokens.join(" ")
+}
+
+/// Parses the textual form of a script produced by [`disassemble`], or written by hand using the same syntax
+/// and the literals described in the [module documentation](self).
+pub fn assemble(text: &str) -> ScriptAsmResult<Vec<u8>> {
+    let mut script = vec![];
+    let mut tokens = text.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if let Some(opcode) = opcode_from_name(token) {
+            script.push(opcode);
+            if (codes::OpData1..=codes::OpPushData4).contains(&opcode) {
+                let name = opcode_name(opcode);
+                let data = tokens.next().and_then(|token| token.strip_prefix("0x")).ok_or(ScriptAsmError::MissingData(name))?;
+                let data = Vec::<u8>::from_hex(data).map_err(|_| ScriptAsmError::InvalidHex(data.to_string()))?;
+                match opcode {
+                    codes::OpPushData1 => {
+                        script.push(u8::try_from(data.len()).map_err(|_| ScriptAsmError::DataTooLong(name, data.len()))?)
+                    }
+                    codes::OpPushData2 => script
+                        .extend(u16::try_from(data.len()).map_err(|_| ScriptAsmError::DataTooLong(name, data.len()))?.to_le_bytes()),
+                    codes::OpPushData4 => script
+                        .extend(u32::try_from(data.len()).map_err(|_| ScriptAsmError::DataTooLong(name, data.len()))?.to_le_bytes()),
+                    _ if data.len() != opcode as usize => {
+                        return Err(ScriptAsmError::DataLengthMismatch(name, opcode as usize, data.len()));
+                    }
+                    _ => {}
+                }
+                script.extend(data);
+            }
+        } else if let Some(hex) = token.strip_prefix("0x") {
+            let data = Vec::<u8>::from_hex(hex).map_err(|_| ScriptAsmError::InvalidHex(hex.to_string()))?;
+            script.extend(ScriptBuilder::new().add_data(&data)?.drain());
+        } else if let Some(raw) = token.strip_prefix('[').and_then(|token| token.strip_suffix(']')) {
+            script.extend(Vec::<u8>::from_hex(raw).map_err(|_| ScriptAsmError::InvalidHex(raw.to_string()))?);
+        } else if let Ok(value) = token.parse::<i64>() {
+            script.extend(ScriptBuilder::new().add_i64(value)?.drain());
+        } else {
+            return Err(ScriptAsmError::UnknownToken(token.to_string()));
+        }
+    }
+    Ok(script)
+}
+
+fn is_data_push(opcode: &dyn OpCodeImplementation<PopulatedTransaction<'static>, SigHashReusedValuesUnsync>) -> bool {
+    (codes::OpData1..=codes::OpPushData4).contains(&opcode.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcodes::codes::*;
+    use crate::opcodes::{parse_short_form, OP_DATA_MAX_VAL};
+    use serde::Deserialize;
+    use std::{fs::File, io::BufReader, path::Path};
+
+    #[test]
+    fn test_disassemble() {
+        let pub_key = [0x1a; 32];
+        let script = ScriptBuilder::new().add_data(&pub_key).unwrap().add_op(OpCheckSig).unwrap().drain();
+        assert_eq!(disassemble(&script), format!("OpData32 0x{} OpCheckSig", pub_key.as_slice().to_hex()));
+
+        assert_eq!(disassemble(&[]), "");
+        assert_eq!(disassemble(&[Op0, Op1, Op16, Op1Negate]), "OpFalse OpTrue Op16 Op1Negate");
+        assert_eq!(disassemble(&[OpPushData1, 0]), "OpPushData1 0x");
+        assert_eq!(disassemble(&[OpPushData2, 2, 0, 0xaa, 0xbb]), "OpPushData2 0xaabb");
+        assert_eq!(disassemble(&[OpTrue, OpData2, 0xaa]), "OpTrue [02aa]");
+        assert_eq!(disassemble(&[0xa6]), "OpUnknown166");
+    }
+
+    #[test]
+    fn test_assemble() {
+        let tests = vec![
+            ("OpTrue Op1 Op0 OpFalse", Ok(vec![Op1, Op1, Op0, Op0])),
+            ("  OpDup\tOpBlake3\n", Ok(vec![OpDup, OpBlake3])),
+            ("OpData2 0xaabb", Ok(vec![OpData2, 0xaa, 0xbb])),
+            // Explicit push opcodes keep non-canonical encodings
+            ("OpPushData1 0xaabb", Ok(vec![OpPushData1, 2, 0xaa, 0xbb])),
+            ("OpPushData4 0x", Ok(vec![OpPushData4, 0, 0, 0, 0])),
+            // Literals are encoded canonically
+            ("0xaabb", Ok(vec![OpData2, 0xaa, 0xbb])),
+            ("0x 0x05 0x81", Ok(vec![Op0, Op5, Op1Negate])),
+            ("0 -1 16 17 1000", Ok(vec![Op0, Op1Negate, Op16, OpData1, 17, OpData2, 0xe8, 0x03])),
+            ("OpTrue [02aa]", Ok(vec![OpTrue, OpData2, 0xaa])),
+            ("OpData2 0xaa", Err(ScriptAsmError::DataLengthMismatch("OpData2", 2, 1))),
+            ("OpData1", Err(ScriptAsmError::MissingData("OpData1"))),
+            ("OpData1 aa", Err(ScriptAsmError::MissingData("OpData1"))),
+            ("OpData1 0xzz", Err(ScriptAsmError::InvalidHex("zz".to_string()))),
+            ("OpCheckSigg", Err(ScriptAsmError::UnknownToken("OpCheckSigg".to_string()))),
+        ];
+        for (text, expected) in tests {
+            assert_eq!(assemble(text), expected, "failed assembling '{text}'");
+        }
+
+        let text = format!("OpPushData1 0x{}", [0u8; 256].as_slice().to_hex());
+        assert_eq!(assemble(&text), Err(ScriptAsmError::DataTooLong("OpPushData1", 256)));
+    }
+
+    #[test]
+    fn test_round_trip_all_opcodes() {
+        for opcode in 0..=u8::MAX {
+            let script = match opcode {
+                OpData1..=OP_DATA_MAX_VAL => std::iter::once(opcode).chain(std::iter::repeat_n(0xab, opcode as usize)).collect(),
+                codes::OpPushData1 => vec![opcode, 3, 7, 7, 7],
+                codes::OpPushData2 => vec![opcode, 3, 0, 7, 7, 7],
+                codes::OpPushData4 => vec![opcode, 3, 0, 0, 0, 7, 7, 7],
+                _ => vec![opcode],
+            };
+            let text = disassemble(&script);
+            assert_eq!(assemble(&text).unwrap(), script, "opcode {opcode:#x} did not round-trip through '{text}'");
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(untagged)]
+    enum JsonTestRow {
+        Test(String, String, String, String),
+        TestWithComment(String, String, String, String, String),
+        Comment((String,)),
+    }
+
+    #[test]
+    fn test_round_trip_script_test_vectors() {
+        let file = File::open(Path::new(env!("CARGO_MANIFEST_DIR")).join("test-data").join("script_tests.json")).unwrap();
+        let rows: Vec<JsonTestRow> = serde_json::from_reader(BufReader::new(file)).unwrap();
+
+        let mut count = 0;
+        for row in rows {
+            let (sig_script, script_pub_key) = match row {
+                JsonTestRow::Test(sig_script, script_pub_key, ..) | JsonTestRow::TestWithComment(sig_script, script_pub_key, ..) => {
+                    (sig_script, script_pub_key)
+                }
+                JsonTestRow::Comment(_) => continue,
+            };
+            // Some vectors are rejected by the builder by design, there is no script to round-trip in these
+            for script in [sig_script, script_pub_key].into_iter().filter_map(|short_form| parse_short_form(short_form).ok()) {
+                let text = disassemble(&script);
+                assert_eq!(assemble(&text).unwrap(), script, "script did not round-trip through '{text}'");
+                count += 1;
+            }
+        }
+        assert!(count > 1000);
+    }
+}
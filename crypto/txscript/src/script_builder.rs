@@ -7,6 +7,7 @@ use crate::{
 };
 use hexplay::{HexView, HexViewBuilder};
 use thiserror::Error;
+use vecno_hashes::Hash;
 use vecno_txscript_errors::SerializationError;
 
 /// DEFAULT_SCRIPT_ALLOC is the default size used for the backing array
@@ -253,6 +254,18 @@ impl ScriptBuilder {
         self.add_u64(sequence)
     }
 
+    /// Adds the script public key of a MAST output committing to the merkle root of its leaf scripts,
+    /// see [`MastTree`](crate::MastTree).
+    pub fn add_mast_commitment(&mut self, merkle_root: &Hash) -> ScriptBuilderResult<&mut Self> {
+        self.add_op(OpTrue)?.add_data(&merkle_root.as_bytes())
+    }
+
+    /// Adds the pushes revealing a MAST leaf script along with the control block proving its inclusion,
+    /// which must end the signature script spending a MAST output.
+    pub fn add_mast_reveal(&mut self, leaf_script: &[u8], control_block: &[u8]) -> ScriptBuilderResult<&mut Self> {
+        self.add_data(leaf_script)?.add_data(control_block)
+    }
+
     /// Gets a u64 lock time or sequence, converts it to byte array in little-endian, and then used the add_data function.
     fn add_u64(&mut self, val: u64) -> ScriptBuilderResult<&mut Self> {
         let buffer: [u8; 8] = val.to_le_bytes();
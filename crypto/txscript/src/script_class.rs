@@ -1,4 +1,4 @@
-use crate::{opcodes, MAX_SCRIPT_PUBLIC_KEY_VERSION};
+use crate::{opcodes, HtlcScript, RecoveryScript, TimelockVaultScript, MAX_SCRIPT_PUBLIC_KEY_VERSION};
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -28,12 +28,15 @@ pub enum ScriptClass {
     PubKeyECDSA,
     /// Pay to script hash
     ScriptHash,
+    /// Pay to the merkle root of alternative scripts (MAST)
+    Mast,
 }
 
 const NON_STANDARD: &str = "nonstandard";
 const PUB_KEY: &str = "pubkey";
 const PUB_KEY_ECDSA: &str = "pubkeyecdsa";
 const SCRIPT_HASH: &str = "scripthash";
+const MAST: &str = "mast";
 
 impl ScriptClass {
     pub fn from_script(script_public_key: &ScriptPublicKey) -> Self {
@@ -45,6 +48,8 @@ impl ScriptClass {
                 Self::PubKeyECDSA
             } else if Self::is_pay_to_script_hash(script_public_key_) {
                 Self::ScriptHash
+            } else if Self::is_pay_to_mast(script_public_key_) {
+                Self::Mast
             } else {
                 ScriptClass::NonStandard
             }
@@ -81,12 +86,22 @@ impl ScriptClass {
         (script_public_key[34] == opcodes::codes::OpEqual)
     }
 
+    /// Returns true if the script is in the standard
+    /// pay-to-MAST format committing to a merkle root of scripts, false otherwise.
+    #[inline(always)]
+    pub fn is_pay_to_mast(script_public_key: &[u8]) -> bool {
+        (script_public_key.len() == 34) && // 2 opcodes number + 32 data
+        (script_public_key[0] == opcodes::codes::OpTrue) &&
+        (script_public_key[1] == opcodes::codes::OpData32)
+    }
+
     fn as_str(&self) -> &'static str {
         match self {
             ScriptClass::NonStandard => NON_STANDARD,
             ScriptClass::PubKey => PUB_KEY,
             ScriptClass::PubKeyECDSA => PUB_KEY_ECDSA,
             ScriptClass::ScriptHash => SCRIPT_HASH,
+            ScriptClass::Mast => MAST,
         }
     }
 
@@ -96,6 +111,7 @@ impl ScriptClass {
             ScriptClass::PubKey => MAX_SCRIPT_PUBLIC_KEY_VERSION,
             ScriptClass::PubKeyECDSA => MAX_SCRIPT_PUBLIC_KEY_VERSION,
             ScriptClass::ScriptHash => MAX_SCRIPT_PUBLIC_KEY_VERSION,
+            ScriptClass::Mast => MAX_SCRIPT_PUBLIC_KEY_VERSION,
         }
     }
 }
@@ -115,6 +131,7 @@ impl FromStr for ScriptClass {
             PUB_KEY => Ok(ScriptClass::PubKey),
             PUB_KEY_ECDSA => Ok(ScriptClass::PubKeyECDSA),
             SCRIPT_HASH => Ok(ScriptClass::ScriptHash),
+            MAST => Ok(ScriptClass::Mast),
             _ => Err(Error::InvalidScriptClass(script_class.to_string())),
         }
     }
@@ -138,6 +155,68 @@ impl From<Version> for ScriptClass {
     }
 }
 
+/// Standard classes of pay-to-script-hash redeem scripts
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum RedeemScriptClass {
+    /// None of the recognized templates
+    NonStandard = 0,
+    /// Hash-time-locked contract
+    Htlc,
+    /// Owner key after a relative timelock or recovery key at any time
+    TimelockVault,
+    /// Primary key at any time or recovery key from a DAA score
+    Recovery,
+}
+
+const HTLC: &str = "htlc";
+const TIMELOCK_VAULT: &str = "timelockvault";
+const RECOVERY: &str = "recovery";
+
+impl RedeemScriptClass {
+    pub fn from_redeem_script(redeem_script: &[u8]) -> Self {
+        if HtlcScript::parse(redeem_script).is_some() {
+            RedeemScriptClass::Htlc
+        } else if TimelockVaultScript::parse(redeem_script).is_some() {
+            RedeemScriptClass::TimelockVault
+        } else if RecoveryScript::parse(redeem_script).is_some() {
+            RedeemScriptClass::Recovery
+        } else {
+            RedeemScriptClass::NonStandard
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RedeemScriptClass::NonStandard => NON_STANDARD,
+            RedeemScriptClass::Htlc => HTLC,
+            RedeemScriptClass::TimelockVault => TIMELOCK_VAULT,
+            RedeemScriptClass::Recovery => RECOVERY,
+        }
+    }
+}
+
+impl Display for RedeemScriptClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RedeemScriptClass {
+    type Err = Error;
+
+    fn from_str(redeem_script_class: &str) -> Result<Self, Self::Err> {
+        match redeem_script_class {
+            NON_STANDARD => Ok(RedeemScriptClass::NonStandard),
+            HTLC => Ok(RedeemScriptClass::Htlc),
+            TIMELOCK_VAULT => Ok(RedeemScriptClass::TimelockVault),
+            RECOVERY => Ok(RedeemScriptClass::Recovery),
+            _ => Err(Error::InvalidScriptClass(redeem_script_class.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use vecno_consensus_core::tx::ScriptVec;
@@ -173,6 +252,12 @@ mod tests {
                 version: 0,
                 class: ScriptClass::ScriptHash,
             },
+            Test {
+                name: "valid mast script",
+                script: hex::decode("51204a23f5eef4b2dead811c7efb4f1afbd8df845e804b6c36a4001fc096e13f8151").unwrap(),
+                version: 0,
+                class: ScriptClass::Mast,
+            },
             Test {
                 name: "non standard script (unexpected version)",
                 script: hex::decode("204a23f5eef4b2dead811c7efb4f1afbd8df845e804b6c36a4001fc096e13f8151ac").unwrap(),
@@ -199,4 +284,33 @@ mod tests {
             assert_eq!(test.class, ScriptClass::from_script(&script_public_key), "{} wrong script class", test.name);
         }
     }
+
+    #[test]
+    fn test_redeem_script_class_from_redeem_script() {
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        let tests = vec![
+            (
+                "htlc",
+                HtlcScript { payment_hash: [3u8; 32], receiver: a, refund: b, lock_time: 1000 }.redeem_script().unwrap(),
+                RedeemScriptClass::Htlc,
+            ),
+            (
+                "timelock vault",
+                TimelockVaultScript { owner: a, recovery: b, delay: 100 }.redeem_script().unwrap(),
+                RedeemScriptClass::TimelockVault,
+            ),
+            (
+                "recovery",
+                RecoveryScript { primary: a, recovery: b, daa_score: 0 }.redeem_script().unwrap(),
+                RedeemScriptClass::Recovery,
+            ),
+            ("multisig", crate::multisig_redeem_script([a, b].iter(), 1).unwrap(), RedeemScriptClass::NonStandard),
+            ("empty", vec![], RedeemScriptClass::NonStandard),
+        ];
+
+        for (name, redeem_script, class) in tests {
+            assert_eq!(class, RedeemScriptClass::from_redeem_script(&redeem_script), "{name} wrong redeem script class");
+            assert_eq!(Ok(class), class.to_string().parse(), "{name} redeem script class does not round-trip");
+        }
+    }
 }
@@ -9,9 +9,18 @@ use vecno_addresses::{Address, Prefix, Version};
 use vecno_consensus_core::tx::{ScriptPublicKey, ScriptVec};
 use vecno_txscript_errors::TxScriptError;
 
+mod mast;
 mod multisig;
+mod timelock;
 
+pub use mast::{
+    mast_leaf_hash, mast_merkle_root, mast_root_from_control_block, pay_to_mast_script, Error as MastScriptError, MastTree,
+    MAX_MAST_DEPTH, MAX_MAST_LEAVES,
+};
 pub use multisig::{multisig_redeem_script, multisig_redeem_script_ecdsa, Error as MultisigCreateError};
+pub use timelock::{
+    Error as TimelockScriptError, HtlcScript, RecoveryScript, TimelockVaultScript, HTLC_PREIMAGE_SIZE, TIMELOCK_TEMPLATE_SIG_OP_COUNT,
+};
 
 /// Creates a new script to pay a transaction output to a 32-byte pubkey.
 fn pay_to_pub_key(address_payload: &[u8]) -> ScriptVec {
@@ -81,6 +90,8 @@ pub fn extract_script_pub_key_address(script_public_key: &ScriptPublicKey, prefi
         ScriptClass::PubKey => Ok(Address::new(prefix, Version::PubKey, &script[1..33])),
         ScriptClass::PubKeyECDSA => Ok(Address::new(prefix, Version::PubKeyECDSA, &script[1..34])),
         ScriptClass::ScriptHash => Ok(Address::new(prefix, Version::ScriptHash, &script[2..34])),
+        // MAST commitments have no address encoding
+        ScriptClass::Mast => Err(TxScriptError::PubKeyFormat),
     }
 }
 
@@ -112,6 +123,42 @@ pub mod test_helpers {
         let output = TransactionOutput::new(tx_to_spend.outputs[0].value - fee, script_public_key);
         Transaction::new(TX_VERSION, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![])
     }
+
+    // Creates a transaction that spends the outputs of specified indexes (if they exist) of every provided transaction and returns an optional change.
+    // Assumes that the outputs being spent have opTrueScript as their scriptPublicKey.
+    //
+    // If some change is provided, creates two outputs, first one with the value of the spent outputs minus `change`
+    // and `fee` (in sompi) and second one of `change` amount.
+    //
+    // If no change is provided, creates only one output with the value of the spent outputs minus and `fee` (in sompi)
+    pub fn create_transaction_with_change<'a>(
+        txs_to_spend: impl Iterator<Item = &'a Transaction>,
+        output_indexes: Vec<usize>,
+        change: Option<u64>,
+        fee: u64,
+    ) -> Transaction {
+        let (script_public_key, redeem_script) = op_true_script();
+        let signature_script = pay_to_script_hash_signature_script(redeem_script, vec![]).expect("the script is canonical");
+        let mut inputs_value: u64 = 0;
+        let mut inputs = vec![];
+        for tx_to_spend in txs_to_spend {
+            for i in output_indexes.iter().copied() {
+                if i < tx_to_spend.outputs.len() {
+                    let previous_outpoint = TransactionOutpoint::new(tx_to_spend.id(), i as u32);
+                    inputs.push(TransactionInput::new(previous_outpoint, signature_script.clone(), MAX_TX_IN_SEQUENCE_NUM, 1));
+                    inputs_value += tx_to_spend.outputs[i].value;
+                }
+            }
+        }
+        let outputs = match change {
+            Some(change) => vec![
+                TransactionOutput::new(inputs_value - fee - change, script_public_key.clone()),
+                TransactionOutput::new(change, script_public_key),
+            ],
+            None => vec![TransactionOutput::new(inputs_value - fee, script_public_key.clone())],
+        };
+        Transaction::new(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,184 @@
+//!
+//! Merkelized script commitments (MAST).
+//!
+//! Instead of the hash of a single redeem script, a MAST output commits to the merkle root of a list of leaf
+//! scripts, with the script public key `OpTrue OpData32 <merkle root>`. It is spent by revealing a single leaf
+//! script followed by a control block proving its inclusion in the tree:
+//! `<leaf arguments...> <leaf script> <control block>`. The leaf script is then executed over the arguments,
+//! just like the redeem script of a P2SH spend, so the unused leaves are neither revealed nor paid for.
+//!
+//! The control block is the 2-byte little-endian index of the leaf followed by the sibling hashes on its path
+//! to the root, from the bottom level up, as computed by [`calc_merkle_proof`]. Leaf scripts are hashed with
+//! [`MastLeafHash`], which separates them from the inner nodes of the tree.
+//!
+//! MAST spends are only validated once [`EngineFlags::mast_enabled`](crate::EngineFlags::mast_enabled) is set.
+//! Before that, MAST outputs cannot be spent since their script public key leaves two items on the stack.
+//!
+
+use crate::script_builder::{ScriptBuilder, ScriptBuilderError};
+use crate::script_class::ScriptClass;
+use crate::MAX_SCRIPT_ELEMENT_SIZE;
+use thiserror::Error;
+use vecno_consensus_core::tx::ScriptPublicKey;
+use vecno_hashes::{Hash, Hasher, MastLeafHash, HASH_SIZE};
+use vecno_merkle::{calc_merkle_proof, calc_merkle_root, calc_merkle_root_from_proof};
+use vecno_txscript_errors::TxScriptError;
+
+/// The maximum depth of a MAST, bounding its control blocks to a single script element.
+pub const MAX_MAST_DEPTH: usize = 16;
+
+/// The maximum number of leaf scripts of a MAST.
+pub const MAX_MAST_LEAVES: usize = 1 << MAX_MAST_DEPTH;
+
+/// The size of the leaf index which starts a control block.
+const CONTROL_BLOCK_INDEX_SIZE: usize = 2;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error("a MAST must have at least one leaf script")]
+    NoLeafScripts,
+    #[error("a MAST may have at most {MAX_MAST_LEAVES} leaf scripts but has {0}")]
+    TooManyLeafScripts(usize),
+    #[error("leaf script {0} of {1} bytes exceeds the maximum script element size of {MAX_SCRIPT_ELEMENT_SIZE}")]
+    LeafScriptTooLarge(usize, usize),
+    #[error("leaf index {0} is out of range of the {1} leaf scripts")]
+    LeafIndexOutOfRange(usize, usize),
+    #[error(transparent)]
+    ScriptBuilderError(#[from] ScriptBuilderError),
+}
+
+/// Returns the hash of a leaf script, as committed to by the merkle root of a MAST.
+pub fn mast_leaf_hash(leaf_script: &[u8]) -> Hash {
+    MastLeafHash::hash(leaf_script)
+}
+
+/// Returns the merkle root committed to by a MAST output, or `None` if the script public key is not a MAST commitment.
+pub fn mast_merkle_root(script_public_key: &[u8]) -> Option<Hash> {
+    ScriptClass::is_pay_to_mast(script_public_key).then(|| Hash::from_slice(&script_public_key[2..]))
+}
+
+/// Computes the merkle root a MAST must have for the leaf script to be included in it according to the control block.
+/// The control block must be canonical: its index may not have bits beyond the depth of the proof.
+pub fn mast_root_from_control_block(leaf_script: &[u8], control_block: &[u8]) -> Result<Hash, TxScriptError> {
+    let invalid =
+        |reason: &str| TxScriptError::InvalidMastControlBlock(format!("{reason}, control block size {}", control_block.len()));
+    if control_block.len() < CONTROL_BLOCK_INDEX_SIZE || !(control_block.len() - CONTROL_BLOCK_INDEX_SIZE).is_multiple_of(HASH_SIZE) {
+        return Err(invalid("the proof must be a sequence of hashes after the leaf index"));
+    }
+    let (index, proof) = control_block.split_at(CONTROL_BLOCK_INDEX_SIZE);
+    let index = u16::from_le_bytes(index.try_into().expect("split at the index size")) as usize;
+    let proof = proof.chunks_exact(HASH_SIZE).map(Hash::from_slice).collect::<Vec<_>>();
+    if proof.len() > MAX_MAST_DEPTH {
+        return Err(invalid("the proof exceeds the maximum depth"));
+    }
+    if index >> proof.len() != 0 {
+        return Err(invalid("the leaf index exceeds the depth of the proof"));
+    }
+    Ok(calc_merkle_root_from_proof(mast_leaf_hash(leaf_script), index, &proof))
+}
+
+/// A merkle tree of leaf scripts, any of which can spend the MAST output committing to its root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MastTree {
+    leaf_scripts: Vec<Vec<u8>>,
+    merkle_root: Hash,
+}
+
+impl MastTree {
+    pub fn new(leaf_scripts: Vec<Vec<u8>>) -> Result<Self, Error> {
+        if leaf_scripts.is_empty() {
+            return Err(Error::NoLeafScripts);
+        }
+        if leaf_scripts.len() > MAX_MAST_LEAVES {
+            return Err(Error::TooManyLeafScripts(leaf_scripts.len()));
+        }
+        // Leaf scripts are revealed as a single push, and could not be spent if larger
+        if let Some((index, leaf_script)) = leaf_scripts.iter().enumerate().find(|(_, script)| script.len() > MAX_SCRIPT_ELEMENT_SIZE)
+        {
+            return Err(Error::LeafScriptTooLarge(index, leaf_script.len()));
+        }
+        let merkle_root = calc_merkle_root(leaf_scripts.iter().map(|script| mast_leaf_hash(script)));
+        Ok(Self { leaf_scripts, merkle_root })
+    }
+
+    pub fn leaf_scripts(&self) -> &[Vec<u8>] {
+        &self.leaf_scripts
+    }
+
+    pub fn merkle_root(&self) -> Hash {
+        self.merkle_root
+    }
+
+    /// Returns the index of the leaf script, if part of the tree.
+    pub fn position(&self, leaf_script: &[u8]) -> Option<usize> {
+        self.leaf_scripts.iter().position(|script| script == leaf_script)
+    }
+
+    /// Returns the script public key of the output committing to this tree.
+    pub fn script_public_key(&self) -> ScriptPublicKey {
+        pay_to_mast_script(&self.merkle_root)
+    }
+
+    /// Returns the control block proving the inclusion of the leaf script at `index` in the tree.
+    pub fn control_block(&self, index: usize) -> Result<Vec<u8>, Error> {
+        let proof = calc_merkle_proof(self.leaf_scripts.iter().map(|script| mast_leaf_hash(script)), index)
+            .ok_or(Error::LeafIndexOutOfRange(index, self.leaf_scripts.len()))?;
+        Ok((index as u16).to_le_bytes().into_iter().chain(proof.iter().flat_map(|hash| hash.as_bytes())).collect())
+    }
+
+    /// Builds the signature script spending the output with the leaf script at `index`, where `leaf_arguments`
+    /// is the push-only script satisfying the leaf script.
+    pub fn signature_script(&self, index: usize, leaf_arguments: &[u8]) -> Result<Vec<u8>, Error> {
+        let control_block = self.control_block(index)?;
+        let reveal = ScriptBuilder::new().add_mast_reveal(&self.leaf_scripts[index], &control_block)?.drain();
+        Ok(leaf_arguments.iter().copied().chain(reveal).collect())
+    }
+}
+
+/// Returns the script public key of a MAST output committing to the merkle root of its leaf scripts.
+pub fn pay_to_mast_script(merkle_root: &Hash) -> ScriptPublicKey {
+    let script = ScriptBuilder::new().add_mast_commitment(merkle_root).expect("the commitment is far below the size limits").drain();
+    ScriptPublicKey::from_vec(ScriptClass::Mast.version(), script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcodes::codes::OpTrue;
+
+    #[test]
+    fn test_control_blocks() {
+        for count in [1, 2, 3, 5, 8, 13] {
+            let tree = MastTree::new((0..count).map(|i| vec![i as u8; i + 1]).collect()).unwrap();
+            assert_eq!(mast_merkle_root(tree.script_public_key().script()), Some(tree.merkle_root()));
+            for (index, leaf_script) in tree.leaf_scripts().iter().enumerate() {
+                let control_block = tree.control_block(index).unwrap();
+                assert_eq!(mast_root_from_control_block(leaf_script, &control_block), Ok(tree.merkle_root()));
+                assert_eq!(tree.position(leaf_script), Some(index));
+                if count > 1 {
+                    let other = &tree.leaf_scripts()[(index + 1) % count];
+                    assert_ne!(mast_root_from_control_block(other, &control_block), Ok(tree.merkle_root()));
+                }
+            }
+            assert_eq!(tree.control_block(count), Err(Error::LeafIndexOutOfRange(count, count)));
+        }
+
+        // Non-canonical and malformed control blocks
+        let tree = MastTree::new(vec![vec![OpTrue], vec![OpTrue, OpTrue]]).unwrap();
+        let mut control_block = tree.control_block(1).unwrap();
+        control_block[0] |= 2;
+        assert!(matches!(
+            mast_root_from_control_block(&[OpTrue, OpTrue], &control_block),
+            Err(TxScriptError::InvalidMastControlBlock(_))
+        ));
+        for control_block in [vec![], vec![0], vec![0; 3], vec![0; CONTROL_BLOCK_INDEX_SIZE + HASH_SIZE * (MAX_MAST_DEPTH + 1)]] {
+            assert!(matches!(mast_root_from_control_block(&[OpTrue], &control_block), Err(TxScriptError::InvalidMastControlBlock(_))));
+        }
+
+        assert_eq!(MastTree::new(vec![]), Err(Error::NoLeafScripts));
+        assert_eq!(
+            MastTree::new(vec![vec![0; MAX_SCRIPT_ELEMENT_SIZE + 1]]),
+            Err(Error::LeafScriptTooLarge(0, MAX_SCRIPT_ELEMENT_SIZE + 1))
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//!
+//! Timelocked pay-to-script-hash redeem script templates.
+//!
+//! All templates lock funds to x-only Schnorr public keys and offer two spending branches, selected by the
+//! `OpTrue`/`OpFalse` pushed by the signature script right before the redeem script:
+//! * [`HtlcScript`] - a hash-time-locked contract, spendable by the receiver with the preimage of a SHA-256
+//!   payment hash, or by the refund key once the transaction lock time reaches the contract lock time;
+//! * [`TimelockVaultScript`] - spendable by the owner once the input is at least `delay` DAA score old
+//!   (enforced through the input sequence), or by the recovery key at any time;
+//! * [`RecoveryScript`] - spendable by the primary key at any time, or by the recovery key once the
+//!   transaction lock time reaches the given DAA score.
+//!
+//! Each template can be parsed back from its redeem script, which only succeeds for the exact, canonical
+//! encoding produced by the builder.
+//!
+
+use crate::opcodes::codes::{
+    OpCheckLockTimeVerify, OpCheckSequenceVerify, OpCheckSig, OpElse, OpEndIf, OpEqualVerify, OpFalse, OpIf, OpSHA256, OpSize, OpTrue,
+};
+use crate::opcodes::{codes, deserialize_next_opcode};
+use crate::script_builder::{ScriptBuilder, ScriptBuilderError};
+use crate::{LOCK_TIME_THRESHOLD, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
+use thiserror::Error;
+use vecno_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+use vecno_consensus_core::tx::PopulatedTransaction;
+
+/// The sig op count of an input spending any of the timelocked templates, to be used as its `sig_op_count`.
+pub const TIMELOCK_TEMPLATE_SIG_OP_COUNT: u8 = 2;
+
+/// The size of the HTLC preimage, which is enforced by the redeem script.
+pub const HTLC_PREIMAGE_SIZE: usize = 32;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error("the HTLC preimage must be {HTLC_PREIMAGE_SIZE} bytes long but is {0} bytes")]
+    InvalidPreimageLength(usize),
+    #[error("the relative timelock {0:#x} must not exceed {SEQUENCE_LOCK_TIME_MASK:#x}")]
+    InvalidSequence(u64),
+    #[error("the DAA score {0} must be lower than the lock time threshold {LOCK_TIME_THRESHOLD}")]
+    InvalidDaaScore(u64),
+    #[error(transparent)]
+    ScriptBuilderError(#[from] ScriptBuilderError),
+}
+
+/// A hash-time-locked contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HtlcScript {
+    /// SHA-256 hash of the preimage revealed by the receiver
+    pub payment_hash: [u8; 32],
+    pub receiver: [u8; 32],
+    pub refund: [u8; 32],
+    /// Transaction lock time, either a DAA score or a timestamp, from which the refund key can spend
+    pub lock_time: u64,
+}
+
+impl HtlcScript {
+    pub fn redeem_script(&self) -> Result<Vec<u8>, Error> {
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_op(OpIf)?
+            .add_op(OpSize)?
+            .add_i64(HTLC_PREIMAGE_SIZE as i64)?
+            .add_op(OpEqualVerify)?
+            .add_op(OpSHA256)?
+            .add_data(&self.payment_hash)?
+            .add_op(OpEqualVerify)?
+            .add_data(&self.receiver)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_lock_time(self.lock_time)?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(&self.refund)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?;
+        Ok(builder.drain())
+    }
+
+    /// Parses a redeem script built by [`HtlcScript::redeem_script`].
+    pub fn parse(redeem_script: &[u8]) -> Option<Self> {
+        let mut reader = OpReader::new(redeem_script)?;
+        reader.expect(&[OpIf, OpSize, codes::OpData1, OpEqualVerify, OpSHA256])?;
+        let payment_hash = reader.key()?;
+        reader.expect(&[OpEqualVerify])?;
+        let receiver = reader.key()?;
+        reader.expect(&[OpCheckSig, OpElse])?;
+        let lock_time = reader.value()?;
+        reader.expect(&[OpCheckLockTimeVerify])?;
+        let refund = reader.key()?;
+        reader.expect(&[OpCheckSig, OpEndIf])?;
+        reader.finish()?;
+
+        let script = Self { payment_hash, receiver, refund, lock_time };
+        script.redeem_script().is_ok_and(|built| built == redeem_script).then_some(script)
+    }
+
+    /// Builds the signature script spending the contract with the receiver signature and the payment preimage.
+    pub fn claim_signature_script(&self, signature: &[u8], preimage: &[u8]) -> Result<Vec<u8>, Error> {
+        if preimage.len() != HTLC_PREIMAGE_SIZE {
+            return Err(Error::InvalidPreimageLength(preimage.len()));
+        }
+        signature_script(&[signature, preimage], true, &self.redeem_script()?)
+    }
+
+    /// Builds the signature script spending the contract with the refund signature. The spending transaction
+    /// must have a lock time of at least [`HtlcScript::lock_time`] and a non-final input sequence.
+    pub fn refund_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        signature_script(&[signature], false, &self.redeem_script()?)
+    }
+}
+
+/// A vault whose owner can only spend after a relative timelock, while the recovery key can spend at any time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimelockVaultScript {
+    pub owner: [u8; 32],
+    pub recovery: [u8; 32],
+    /// Relative timelock in DAA score, checked against the spending input sequence
+    pub delay: u64,
+}
+
+impl TimelockVaultScript {
+    pub fn redeem_script(&self) -> Result<Vec<u8>, Error> {
+        if self.delay & SEQUENCE_LOCK_TIME_DISABLED != 0 || self.delay > SEQUENCE_LOCK_TIME_MASK {
+            return Err(Error::InvalidSequence(self.delay));
+        }
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_op(OpIf)?
+            .add_sequence(self.delay)?
+            .add_op(OpCheckSequenceVerify)?
+            .add_data(&self.owner)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_data(&self.recovery)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?;
+        Ok(builder.drain())
+    }
+
+    /// Parses a redeem script built by [`TimelockVaultScript::redeem_script`].
+    pub fn parse(redeem_script: &[u8]) -> Option<Self> {
+        let mut reader = OpReader::new(redeem_script)?;
+        reader.expect(&[OpIf])?;
+        let delay = reader.value()?;
+        reader.expect(&[OpCheckSequenceVerify])?;
+        let owner = reader.key()?;
+        reader.expect(&[OpCheckSig, OpElse])?;
+        let recovery = reader.key()?;
+        reader.expect(&[OpCheckSig, OpEndIf])?;
+        reader.finish()?;
+
+        let script = Self { owner, recovery, delay };
+        script.redeem_script().is_ok_and(|built| built == redeem_script).then_some(script)
+    }
+
+    /// Builds the signature script spending the vault with the owner signature. The spending input must have
+    /// a sequence of at least [`TimelockVaultScript::delay`].
+    pub fn owner_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        signature_script(&[signature], true, &self.redeem_script()?)
+    }
+
+    /// Builds the signature script spending the vault with the recovery signature.
+    pub fn recovery_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        signature_script(&[signature], false, &self.redeem_script()?)
+    }
+}
+
+/// A script spendable by the primary key at any time, or by the recovery key from a given DAA score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecoveryScript {
+    pub primary: [u8; 32],
+    pub recovery: [u8; 32],
+    /// Absolute DAA score from which the recovery key can spend
+    pub daa_score: u64,
+}
+
+impl RecoveryScript {
+    pub fn redeem_script(&self) -> Result<Vec<u8>, Error> {
+        if self.daa_score >= LOCK_TIME_THRESHOLD {
+            return Err(Error::InvalidDaaScore(self.daa_score));
+        }
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_op(OpIf)?
+            .add_data(&self.primary)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_lock_time(self.daa_score)?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(&self.recovery)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?;
+        Ok(builder.drain())
+    }
+
+    /// Parses a redeem script built by [`RecoveryScript::redeem_script`].
+    pub fn parse(redeem_script: &[u8]) -> Option<Self> {
+        let mut reader = OpReader::new(redeem_script)?;
+        reader.expect(&[OpIf])?;
+        let primary = reader.key()?;
+        reader.expect(&[OpCheckSig, OpElse])?;
+        let daa_score = reader.value()?;
+        reader.expect(&[OpCheckLockTimeVerify])?;
+        let recovery = reader.key()?;
+        reader.expect(&[OpCheckSig, OpEndIf])?;
+        reader.finish()?;
+
+        let script = Self { primary, recovery, daa_score };
+        script.redeem_script().is_ok_and(|built| built == redeem_script).then_some(script)
+    }
+
+    /// Builds the signature script spending with the primary signature.
+    pub fn primary_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        signature_script(&[signature], true, &self.redeem_script()?)
+    }
+
+    /// Builds the signature script spending with the recovery signature. The spending transaction must have
+    /// a lock time of at least [`RecoveryScript::daa_score`] and a non-final input sequence.
+    pub fn recovery_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        signature_script(&[signature], false, &self.redeem_script()?)
+    }
+}
+
+/// Pushes the branch arguments, the branch selector and the redeem script. Every signature is expected to be
+/// the 64-byte Schnorr signature followed by the sighash type.
+fn signature_script(args: &[&[u8]], branch: bool, redeem_script: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut builder = ScriptBuilder::new();
+    for arg in args {
+        builder.add_data(arg)?;
+    }
+    builder.add_op(if branch { OpTrue } else { OpFalse })?.add_data(redeem_script)?;
+    Ok(builder.drain())
+}
+
+/// Sequential reader of the opcodes of a redeem script.
+struct OpReader {
+    ops: std::vec::IntoIter<(u8, Vec<u8>)>,
+}
+
+impl OpReader {
+    fn new(script: &[u8]) -> Option<Self> {
+        let mut ops = vec![];
+        let mut it = script.iter();
+        while it.len() > 0 {
+            let opcode = deserialize_next_opcode::<_, PopulatedTransaction<'static>, SigHashReusedValuesUnsync>(&mut it)?.ok()?;
+            ops.push((opcode.value(), opcode.get_data().to_vec()));
+        }
+        Some(Self { ops: ops.into_iter() })
+    }
+
+    fn expect(&mut self, opcodes: &[u8]) -> Option<()> {
+        opcodes.iter().all(|&expected| self.ops.next().is_some_and(|(opcode, _)| opcode == expected)).then_some(())
+    }
+
+    fn key(&mut self) -> Option<[u8; 32]> {
+        match self.ops.next()? {
+            (codes::OpData32, data) => data.try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// Decodes a lock time or sequence pushed by [`ScriptBuilder::add_lock_time`] or [`ScriptBuilder::add_sequence`].
+    fn value(&mut self) -> Option<u64> {
+        match self.ops.next()? {
+            (opcode @ codes::Op1..=codes::Op16, _) => Some((opcode - codes::Op1 + 1) as u64),
+            (codes::Op1Negate, _) => Some(0x81),
+            (_, data) if data.len() <= 8 => {
+                let mut bytes = [0u8; 8];
+                bytes[..data.len()].copy_from_slice(&data);
+                Some(u64::from_le_bytes(bytes))
+            }
+            _ => None,
+        }
+    }
+
+    fn finish(mut self) -> Option<()> {
+        self.ops.next().is_none().then_some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{caches::Cache, pay_to_script_hash_script, TxScriptEngine, TxScriptError, MAX_TX_IN_SEQUENCE_NUM};
+    use core::str::FromStr;
+    use rand::thread_rng;
+    use secp256k1::Keypair;
+    use sha2::{Digest, Sha256};
+    use vecno_consensus_core::{
+        hashing::{
+            sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync},
+            sighash_type::SIG_HASH_ALL,
+        },
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::*,
+    };
+
+    fn keys() -> [Keypair; 2] {
+        [Keypair::new(secp256k1::SECP256K1, &mut thread_rng()), Keypair::new(secp256k1::SECP256K1, &mut thread_rng())]
+    }
+
+    fn x_only(kp: &Keypair) -> [u8; 32] {
+        kp.x_only_public_key().0.serialize()
+    }
+
+    /// Spends a UTXO locked to `redeem_script` with the given transaction lock time and input sequence and executes
+    /// the signature script built by `signature_script` from the signature of `kp`.
+    fn spend(
+        redeem_script: &[u8],
+        lock_time: u64,
+        sequence: u64,
+        kp: &Keypair,
+        signature_script: impl FnOnce(&[u8]) -> Result<Vec<u8>, Error>,
+    ) -> Result<(), TxScriptError> {
+        let prev_tx_id = TransactionId::from_str("63020db736215f8b1105a9281f7bcbb6473d965ecc45bb2fb5da59bd35e6ff84").unwrap();
+        let tx = Transaction::new(
+            0,
+            vec![TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: prev_tx_id, index: 0 },
+                signature_script: vec![],
+                sequence,
+                sig_op_count: TIMELOCK_TEMPLATE_SIG_OP_COUNT,
+            }],
+            vec![],
+            lock_time,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entries = vec![UtxoEntry {
+            amount: 12793000000000,
+            script_public_key: pay_to_script_hash_script(redeem_script),
+            block_daa_score: 36151168,
+            is_coinbase: false,
+        }];
+        let mut tx = MutableTransaction::with_entries(tx, entries);
+
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        let signature: Vec<u8> = kp.sign_schnorr(msg).as_ref().iter().copied().chain([SIG_HASH_ALL.to_u8()]).collect();
+        tx.tx.inputs[0].signature_script = signature_script(&signature).unwrap();
+
+        let tx = tx.as_verifiable();
+        let (input, entry) = tx.populated_inputs().next().unwrap();
+        let cache = Cache::new(10_000);
+        let mut engine = TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &cache, Default::default());
+        engine.execute()
+    }
+
+    #[test]
+    fn test_htlc() {
+        let [receiver, refund] = keys();
+        let preimage = [7u8; HTLC_PREIMAGE_SIZE];
+        let htlc = HtlcScript {
+            payment_hash: Sha256::digest(preimage).into(),
+            receiver: x_only(&receiver),
+            refund: x_only(&refund),
+            lock_time: 36_200_000,
+        };
+        let redeem_script = htlc.redeem_script().unwrap();
+        assert_eq!(HtlcScript::parse(&redeem_script), Some(htlc));
+        assert_eq!(TimelockVaultScript::parse(&redeem_script), None);
+        assert_eq!(RecoveryScript::parse(&redeem_script), None);
+
+        // The receiver claims at any time with the preimage
+        assert!(spend(&redeem_script, 0, MAX_TX_IN_SEQUENCE_NUM, &receiver, |sig| htlc.claim_signature_script(sig, &preimage)).is_ok());
+        assert!(spend(&redeem_script, 0, 0, &receiver, |sig| htlc.claim_signature_script(sig, &[8u8; 32])).is_err());
+        assert!(spend(&redeem_script, 0, 0, &refund, |sig| htlc.claim_signature_script(sig, &preimage)).is_err());
+        assert_eq!(htlc.claim_signature_script(&[0; 65], &[7u8; 31]), Err(Error::InvalidPreimageLength(31)));
+
+        // The refund key spends once the lock time is reached
+        assert!(spend(&redeem_script, 36_200_000, 0, &refund, |sig| htlc.refund_signature_script(sig)).is_ok());
+        assert!(matches!(
+            spend(&redeem_script, 36_199_999, 0, &refund, |sig| htlc.refund_signature_script(sig)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(matches!(
+            spend(&redeem_script, 36_200_000, MAX_TX_IN_SEQUENCE_NUM, &refund, |sig| htlc.refund_signature_script(sig)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(spend(&redeem_script, 36_200_000, 0, &receiver, |sig| htlc.refund_signature_script(sig)).is_err());
+    }
+
+    #[test]
+    fn test_timelock_vault() {
+        let [owner, recovery] = keys();
+        let vault = TimelockVaultScript { owner: x_only(&owner), recovery: x_only(&recovery), delay: 1000 };
+        let redeem_script = vault.redeem_script().unwrap();
+        assert_eq!(TimelockVaultScript::parse(&redeem_script), Some(vault));
+        assert_eq!(HtlcScript::parse(&redeem_script), None);
+
+        // The owner spends once the input sequence covers the delay
+        assert!(spend(&redeem_script, 0, 1000, &owner, |sig| vault.owner_signature_script(sig)).is_ok());
+        assert!(matches!(
+            spend(&redeem_script, 0, 999, &owner, |sig| vault.owner_signature_script(sig)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(spend(&redeem_script, 0, 1000, &recovery, |sig| vault.owner_signature_script(sig)).is_err());
+
+        // The recovery key spends at any time
+        assert!(spend(&redeem_script, 0, MAX_TX_IN_SEQUENCE_NUM, &recovery, |sig| vault.recovery_signature_script(sig)).is_ok());
+        assert!(spend(&redeem_script, 0, MAX_TX_IN_SEQUENCE_NUM, &owner, |sig| vault.recovery_signature_script(sig)).is_err());
+
+        let vault = TimelockVaultScript { delay: SEQUENCE_LOCK_TIME_DISABLED, ..vault };
+        assert_eq!(vault.redeem_script(), Err(Error::InvalidSequence(SEQUENCE_LOCK_TIME_DISABLED)));
+    }
+
+    #[test]
+    fn test_recovery() {
+        let [primary, recovery] = keys();
+        let script = RecoveryScript { primary: x_only(&primary), recovery: x_only(&recovery), daa_score: 5 };
+        let redeem_script = script.redeem_script().unwrap();
+        assert_eq!(RecoveryScript::parse(&redeem_script), Some(script));
+        assert_eq!(TimelockVaultScript::parse(&redeem_script), None);
+
+        // The primary key spends at any time
+        assert!(spend(&redeem_script, 0, MAX_TX_IN_SEQUENCE_NUM, &primary, |sig| script.primary_signature_script(sig)).is_ok());
+        assert!(spend(&redeem_script, 0, MAX_TX_IN_SEQUENCE_NUM, &recovery, |sig| script.primary_signature_script(sig)).is_err());
+
+        // The recovery key spends from the DAA score
+        assert!(spend(&redeem_script, 5, 0, &recovery, |sig| script.recovery_signature_script(sig)).is_ok());
+        assert!(matches!(
+            spend(&redeem_script, 4, 0, &recovery, |sig| script.recovery_signature_script(sig)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(matches!(
+            spend(&redeem_script, LOCK_TIME_THRESHOLD, 0, &recovery, |sig| script.recovery_signature_script(sig)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+
+        let script = RecoveryScript { daa_score: LOCK_TIME_THRESHOLD, ..script };
+        assert_eq!(script.redeem_script(), Err(Error::InvalidDaaScore(LOCK_TIME_THRESHOLD)));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_canonical_scripts() {
+        let [a, b] = keys();
+        let script = RecoveryScript { primary: x_only(&a), recovery: x_only(&b), daa_score: 0x0100 };
+        let mut redeem_script = script.redeem_script().unwrap();
+        assert_eq!(RecoveryScript::parse(&redeem_script), Some(script));
+
+        // A non-minimal push of the DAA score
+        let position = redeem_script.iter().position(|&op| op == OpElse).unwrap() + 1;
+        redeem_script.splice(position..position + 3, [codes::OpData3, 0x00, 0x01, 0x00]);
+        assert_eq!(RecoveryScript::parse(&redeem_script), None);
+
+        // Trailing opcodes
+        let mut redeem_script = script.redeem_script().unwrap();
+        redeem_script.push(OpTrue);
+        assert_eq!(RecoveryScript::parse(&redeem_script), None);
+        assert_eq!(RecoveryScript::parse(&[]), None);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{
+    cache::CachePolicy,
+    db::{DbSnapshot, DB},
+    errors::StoreError,
+};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use rocksdb::{Direction, IterateBounds, IteratorMode, ReadOptions};
@@ -75,15 +79,21 @@ where
         let prefix_key = DbKey::prefix_only(&self.prefix);
         let mut read_opts = ReadOptions::default();
         read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
-        self.db.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts).map(move |iter_result| {
-            match iter_result {
-                Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
-                    Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
-                    Err(e) => Err(e.into()),
-                },
-                Err(e) => Err(e.into()),
-            }
-        })
+        let iter = self.db.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts);
+        deserialize_iterator(iter, prefix_key)
+    }
+
+    /// Same as [`Self::iterator`] but reading from the given DB snapshot, hence not observing writes made after it was taken
+    pub fn snapshot_iterator<'a>(&'a self, snapshot: &'a DbSnapshot<'a>) -> impl Iterator<Item = KeyDataResult<TData>> + 'a
+    where
+        TKey: Clone + AsRef<[u8]>,
+        TData: DeserializeOwned,
+    {
+        let prefix_key = DbKey::prefix_only(&self.prefix);
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
+        let iter = snapshot.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts);
+        deserialize_iterator(iter, prefix_key)
     }
 
     pub fn write(&self, mut writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError>
@@ -217,6 +227,23 @@ where
     }
 }
 
+/// Deserializes the data of the entries of a prefix iterator, stripping the prefix from their keys
+fn deserialize_iterator<'a, TData>(
+    iter: impl Iterator<Item = Result<(Box<[u8]>, Box<[u8]>), rocksdb::Error>> + 'a,
+    prefix_key: DbKey,
+) -> impl Iterator<Item = KeyDataResult<TData>> + 'a
+where
+    TData: DeserializeOwned + 'a,
+{
+    iter.map(move |iter_result| match iter_result {
+        Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
+            Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
+            Err(e) => Err(e.into()),
+        },
+        Err(e) => Err(e.into()),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,6 +7,9 @@ use vecno_utils::fd_budget::FDGuard;
 
 mod conn_builder;
 
+/// A point-in-time view of the DB, see [`DBWithThreadMode::snapshot`]
+pub type DbSnapshot<'a> = rocksdb::SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>;
+
 /// The DB type used for Vecnod stores
 pub struct DB {
     inner: DBWithThreadMode<MultiThreaded>,
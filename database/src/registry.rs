@@ -57,6 +57,11 @@ pub enum DatabaseStorePrefixes {
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexTransactions = 195,
+    TxIndexAcceptedTransactions = 196,
+    TxIndexSink = 197,
+    TxIndexAddressHistory = 198,
+    TxIndexAcceptedAddresses = 199,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -1,5 +1,6 @@
 use thiserror::Error;
 use vecno_notify::events::EventType;
+use vecno_txindex::errors::TxIndexError;
 use vecno_utxoindex::errors::UtxoIndexError;
 
 #[derive(Error, Debug)]
@@ -7,6 +8,9 @@ pub enum IndexError {
     #[error("{0}")]
     UtxoIndexError(#[from] UtxoIndexError),
 
+    #[error("{0}")]
+    TxIndexError(#[from] TxIndexError),
+
     #[error("event type {0:?} is not supported")]
     NotSupported(EventType),
 }
@@ -17,11 +17,13 @@ use vecno_notify::{
     notification::Notification as NotificationTrait,
     notifier::DynNotify,
 };
+use vecno_txindex::api::TxIndexProxy;
 use vecno_utils::triggers::SingleTrigger;
 use vecno_utxoindex::api::UtxoIndexProxy;
 
 /// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// notifications submitting them to a UtxoIndex, and VirtualChainChanged notifications
+/// submitting them to a TxIndex.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
@@ -30,6 +32,9 @@ pub struct Processor {
     /// An optional UTXO indexer
     utxoindex: Option<UtxoIndexProxy>,
 
+    /// An optional transaction indexer
+    txindex: Option<TxIndexProxy>,
+
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
     /// Has this collector been started?
@@ -39,9 +44,14 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+    pub fn new(
+        utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
+    ) -> Self {
         Self {
             utxoindex,
+            txindex,
             recv_channel,
             collect_shutdown: Arc::new(SingleTrigger::new()),
             is_started: Arc::new(AtomicBool::new(false)),
@@ -58,12 +68,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +87,18 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the index notification to relay, if any.
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(Some(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?)))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                self.process_virtual_chain_changed(virtual_chain_changed).await?;
+                Ok(None)
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -106,6 +122,24 @@ impl Processor {
         Err(IndexError::NotSupported(EventType::UtxosChanged))
     }
 
+    async fn process_virtual_chain_changed(
+        self: &Arc<Self>,
+        notification: consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<()> {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        if let Some(txindex) = self.txindex.clone() {
+            txindex
+                .update(
+                    notification.removed_chain_block_hashes,
+                    notification.added_chain_block_hashes,
+                    notification.added_chain_blocks_acceptance_data,
+                )
+                .await?;
+            return Ok(());
+        };
+        Err(IndexError::NotSupported(EventType::VirtualChainChanged))
+    }
+
     async fn join_collecting_task(&self) -> Result<()> {
         trace!("[Index processor] joining");
         self.collect_shutdown.listener.clone().await;
@@ -160,7 +194,7 @@ mod tests {
             tc.init();
             let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
             let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
-            let processor = Arc::new(Processor::new(utxoindex, consensus_receiver));
+            let processor = Arc::new(Processor::new(utxoindex, None, consensus_receiver));
             let (processor_sender, processor_receiver) = unbounded();
             let notifier = Arc::new(NotifyMock::new(processor_sender));
             processor.clone().start(notifier);
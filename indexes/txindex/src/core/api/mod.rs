@@ -0,0 +1,110 @@
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+use vecno_consensus_core::{
+    acceptance_data::AcceptanceData,
+    tx::{ScriptPublicKey, TransactionId},
+};
+use vecno_consensusmanager::spawn_blocking;
+use vecno_database::prelude::StoreResult;
+use vecno_hashes::Hash;
+
+use crate::{
+    errors::TxIndexResult,
+    model::{AddressHistoryCursor, AddressHistoryPage, TxIndexEntry},
+};
+
+///Txindex API targeted at retrieval calls.
+pub trait TxIndexApi: Send + Sync + Debug {
+    /// Retrieve the index entry of an accepted transaction, if any.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+
+    /// Retrieve the index entries of accepted transactions, in the order of `transaction_ids`.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transaction_entries(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TxIndexEntry>>>;
+
+    /// Retrieve a page of the transactions which spent from or paid to `script_public_key`, in accepting DAA score order,
+    /// starting at `from` and ending before `end_daa_score`, if supplied.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+
+    /// Retrieve the chain block up to which the txindex is synced.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_txindex_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the txindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> TxIndexResult<bool>;
+
+    /// Update the txindex with a virtual chain change: transactions accepted by the removed chain blocks are
+    /// dropped and those accepted by the added chain blocks are indexed.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()>;
+
+    /// Resync the txindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> TxIndexResult<()>;
+}
+
+/// Async proxy for the transaction index
+#[derive(Debug, Clone)]
+pub struct TxIndexProxy {
+    inner: Arc<RwLock<dyn TxIndexApi>>,
+}
+
+impl TxIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn TxIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_transaction_entry(self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        spawn_blocking(move || self.inner.read().get_transaction_entry(transaction_id)).await.unwrap()
+    }
+
+    pub async fn get_transaction_entries(self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        spawn_blocking(move || self.inner.read().get_transaction_entries(&transaction_ids)).await.unwrap()
+    }
+
+    pub async fn get_address_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_address_history(&script_public_key, from, end_daa_score, limit)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(removed_chain_block_hashes, added_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
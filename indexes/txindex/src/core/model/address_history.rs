@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::TransactionId;
+use vecno_hashes::Hash;
+
+/// An accepted transaction which spent from or paid to an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistoryEntry {
+    pub transaction_id: TransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_block_daa_score: u64,
+}
+
+impl AddressHistoryEntry {
+    pub fn new(transaction_id: TransactionId, accepting_block_hash: Hash, accepting_block_daa_score: u64) -> Self {
+        Self { transaction_id, accepting_block_hash, accepting_block_daa_score }
+    }
+
+    pub fn cursor(&self) -> AddressHistoryCursor {
+        AddressHistoryCursor::new(self.accepting_block_daa_score, self.transaction_id)
+    }
+}
+
+/// The position of an entry within the history of an address.
+///
+/// The history of an address is ordered by the DAA score of the accepting block, and then by transaction id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AddressHistoryCursor {
+    pub accepting_block_daa_score: u64,
+    pub transaction_id: TransactionId,
+}
+
+impl AddressHistoryCursor {
+    pub fn new(accepting_block_daa_score: u64, transaction_id: TransactionId) -> Self {
+        Self { accepting_block_daa_score, transaction_id }
+    }
+}
+
+/// A page of the history of an address, along with the cursor of the entry following it, if any.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressHistoryPage {
+    pub entries: Vec<AddressHistoryEntry>,
+    pub next_cursor: Option<AddressHistoryCursor>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use vecno_hashes::Hash;
+use vecno_utils::mem_size::MemSizeEstimator;
+
+/// Locates an accepted transaction within the DAG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    /// The merged block which contains the transaction
+    pub including_block_hash: Hash,
+    /// The position of the transaction within the including block
+    pub index_within_block: u32,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+}
+
+impl TxIndexEntry {
+    pub fn new(including_block_hash: Hash, index_within_block: u32, accepting_block_hash: Hash) -> Self {
+        Self { including_block_hash, index_within_block, accepting_block_hash }
+    }
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
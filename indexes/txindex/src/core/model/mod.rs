@@ -0,0 +1,5 @@
+mod address_history;
+mod entry;
+
+pub use address_history::*;
+pub use entry::*;
@@ -0,0 +1,442 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::{AddressHistoryCursor, AddressHistoryPage, TxIndexEntry},
+    stores::{
+        address_history::AddressHistoryKey,
+        store_manager::{IndexedChainBlock, Store},
+    },
+    IDENT,
+};
+use parking_lot::RwLock;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+use vecno_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusApi,
+    errors::consensus::ConsensusError,
+    tx::{ScriptPublicKey, TransactionId, TransactionOutpoint},
+};
+use vecno_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use vecno_core::{info, trace, warn};
+use vecno_database::prelude::{StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Chain blocks per committed batch, each carrying the acceptance data of its whole mergeset.
+
+/// TxIndex maps accepted transaction ids to their including and accepting blocks, and addresses to the accepted
+/// transactions which spent from or paid to them, by following the virtual selected parent chain. Entries are rolled
+/// back when chain blocks are removed by a reorg.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.catch_up()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+
+    /// Brings the txindex up to the consensus sink by walking the chain path from the indexed sink.
+    /// Falls back to a full resync if the index is empty or its sink is unknown to consensus.
+    fn catch_up(&mut self) -> TxIndexResult<()> {
+        let txindex_sink = match self.store.get_sink() {
+            Ok(txindex_sink) => txindex_sink,
+            Err(StoreError::KeyNotFound(_)) => return self.resync(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match session.get_virtual_chain_from_block(txindex_sink, None) {
+            Ok(chain_path) => {
+                info!("Catching up the txindex from chain block {} ({} chain blocks to add)", txindex_sink, chain_path.added.len());
+                self.apply_chain_path(&*session, &chain_path.removed, &chain_path.added)
+            }
+            Err(err) => {
+                warn!("[{0}] cannot catch up from chain block {1}: {2}", IDENT, txindex_sink, err);
+                drop(session);
+                self.resync()
+            }
+        }
+    }
+
+    /// Rolls back `removed` and indexes `added` in chunks, fetching the acceptance data from consensus.
+    fn apply_chain_path(&mut self, consensus: &dyn ConsensusApi, removed: &[Hash], added: &[Hash]) -> TxIndexResult<()> {
+        if added.is_empty() {
+            if !removed.is_empty() {
+                self.store.update_chain(removed, &[], consensus.get_sink())?;
+            }
+            return Ok(());
+        }
+
+        let mut removed = removed;
+        for chunk in added.chunks(RESYNC_CHUNK_SIZE) {
+            trace!("[{0}] indexing a chunk of {1} chain blocks from consensus db", IDENT, chunk.len());
+            let acceptance_data = consensus.get_blocks_acceptance_data(chunk, None)?;
+            let added_chunk = chunk
+                .iter()
+                .copied()
+                .zip(acceptance_data)
+                .map(|(hash, acceptance_data)| Self::build_indexed_chain_block(consensus, hash, acceptance_data))
+                .collect::<TxIndexResult<Vec<_>>>()?;
+            self.store.update_chain(removed, &added_chunk, *chunk.last().unwrap())?;
+            removed = &[];
+        }
+
+        Ok(())
+    }
+
+    /// Collects the address history of the transactions accepted by a chain block.
+    ///
+    /// The addresses of a transaction are those of its outputs and of the outputs it spent. The latter are resolved via
+    /// the UTXO diff of the chain block, or via the accepted transactions themselves for outputs which were created and
+    /// spent within the same mergeset, and are thus missing from the diff.
+    fn build_indexed_chain_block(
+        consensus: &dyn ConsensusApi,
+        hash: Hash,
+        acceptance_data: Arc<AcceptanceData>,
+    ) -> TxIndexResult<IndexedChainBlock> {
+        let accepting_block_daa_score = consensus.get_header(hash)?.daa_score;
+
+        let mut spent_script_public_keys: HashMap<TransactionOutpoint, ScriptPublicKey> = match consensus
+            .get_chain_block_utxo_diff(hash)
+        {
+            Ok(utxo_diff) => utxo_diff.remove.iter().map(|(outpoint, entry)| (*outpoint, entry.script_public_key.clone())).collect(),
+            // The UTXO diff of the pruning point is not kept
+            Err(ConsensusError::MissingData(_)) => HashMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut accepted_transactions = Vec::new();
+        for mergeset_block_data in acceptance_data.iter().filter(|x| !x.accepted_transactions.is_empty()) {
+            let block = match consensus.get_block(mergeset_block_data.block_hash) {
+                Ok(block) => block,
+                Err(ConsensusError::BlockNotFound(_)) => {
+                    warn!("[{0}] missing body of block {1}, its address history is skipped", IDENT, mergeset_block_data.block_hash);
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            for accepted_transaction in mergeset_block_data.accepted_transactions.iter() {
+                let transaction = &block.transactions[accepted_transaction.index_within_block as usize];
+                for (index, output) in transaction.outputs.iter().enumerate() {
+                    spent_script_public_keys.insert(
+                        TransactionOutpoint::new(accepted_transaction.transaction_id, index as u32),
+                        output.script_public_key.clone(),
+                    );
+                }
+            }
+            accepted_transactions.push((block.transactions, &mergeset_block_data.accepted_transactions));
+        }
+
+        let mut address_history_keys = Vec::new();
+        for (transactions, accepted) in accepted_transactions.iter() {
+            for accepted_transaction in accepted.iter() {
+                let transaction = &transactions[accepted_transaction.index_within_block as usize];
+                let cursor = AddressHistoryCursor::new(accepting_block_daa_score, accepted_transaction.transaction_id);
+                let script_public_keys = transaction
+                    .outputs
+                    .iter()
+                    .map(|output| &output.script_public_key)
+                    .chain(transaction.inputs.iter().filter_map(|input| spent_script_public_keys.get(&input.previous_outpoint)))
+                    .collect::<HashSet<_>>();
+                address_history_keys
+                    .extend(script_public_keys.into_iter().map(|script_public_key| AddressHistoryKey::new(script_public_key, cursor)));
+            }
+        }
+
+        Ok(IndexedChainBlock { hash, acceptance_data, address_history_keys })
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving transaction {1}", IDENT, transaction_id);
+
+        self.store.get_transaction_entry(transaction_id)
+    }
+
+    fn get_transaction_entries(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        trace!("[{0}] retrieving {1} transactions", IDENT, transaction_ids.len());
+
+        transaction_ids.iter().map(|transaction_id| self.store.get_transaction_entry(*transaction_id)).collect()
+    }
+
+    fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving up to {1} history entries of an address", IDENT, limit);
+
+        self.store.get_address_history(script_public_key, from, end_daa_score, limit)
+    }
+
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the consensus sink.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means the txindex sink is missing i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(TxIndexError::StoreAccessError(err)),
+        }
+    }
+
+    /// Updates the [TxIndex] with a virtual chain change.
+    ///
+    /// If the acceptance data was not supplied along with the added chain blocks, it is fetched from consensus.
+    fn update(
+        &mut self,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        if added_chain_block_hashes.len() != added_chain_blocks_acceptance_data.len() {
+            return self.apply_chain_path(&*session, &removed_chain_block_hashes, &added_chain_block_hashes);
+        }
+
+        let Some(&sink) = added_chain_block_hashes.last() else {
+            return Ok(());
+        };
+        let added = added_chain_block_hashes
+            .iter()
+            .copied()
+            .zip(added_chain_blocks_acceptance_data.iter().cloned())
+            .map(|(hash, acceptance_data)| Self::build_indexed_chain_block(&*session, hash, acceptance_data))
+            .collect::<TxIndexResult<Vec<_>>>()?;
+        self.store.update_chain(&removed_chain_block_hashes, &added, sink)?;
+
+        Ok(())
+    }
+
+    /// Deletes and reinstates the txindex database, syncing it from scratch via the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) Only transactions accepted by the pruning point and its chain descendants are indexed.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        // The acceptance data of the pruning point itself might be missing, e.g. right after a pruning point sync
+        let pruning_point = session.pruning_point();
+        let added = match session.get_block_acceptance_data(pruning_point) {
+            Ok(acceptance_data) => vec![Self::build_indexed_chain_block(&*session, pruning_point, acceptance_data)?],
+            Err(_) => vec![],
+        };
+        self.store.update_chain(&[], &added, pruning_point)?;
+
+        let chain_path = session.get_virtual_chain_from_block(pruning_point, None)?;
+        self.apply_chain_path(&*session, &[], &chain_path.added)?;
+
+        trace!("[{0}] resynced up to sink {1}", IDENT, session.get_sink());
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::TxIndexApi,
+        model::{AddressHistoryCursor, AddressHistoryEntry},
+        TxIndex,
+    };
+    use std::sync::Arc;
+    use vecno_consensus::{consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS};
+    use vecno_consensus_core::{
+        api::ConsensusApi,
+        coinbase::MinerData,
+        config::ConfigBuilder,
+        tx::{ScriptPublicKey, TransactionId},
+    };
+    use vecno_consensusmanager::ConsensusManager;
+    use vecno_database::create_temp_db;
+    use vecno_database::prelude::ConnBuilder;
+    use vecno_hashes::Hash;
+
+    /// Reads the whole history of `script_public_key` by following the page cursors, one entry at a time
+    fn read_history(txindex: &dyn TxIndexApi, script_public_key: &ScriptPublicKey) -> Vec<AddressHistoryEntry> {
+        let mut entries = vec![];
+        let mut cursor = Some(AddressHistoryCursor::new(0, TransactionId::default()));
+        while let Some(from) = cursor {
+            let page = txindex.get_address_history(script_public_key, from, None, 1).unwrap();
+            assert!(page.entries.len() <= 1);
+            entries.extend(page.entries);
+            cursor = page.next_cursor;
+        }
+        entries
+    }
+
+    /// Adds a chain of blocks with the given hashes on top of `parent`, and returns the coinbase transaction ids
+    fn add_chain(tc: &TestConsensus, parent: Hash, hashes: &[Hash], extra_data: u8) -> Vec<TransactionId> {
+        let mut parent = parent;
+        hashes
+            .iter()
+            .map(|&hash| {
+                let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![extra_data]), vec![extra_data]);
+                let block = tc.build_utxo_valid_block_with_parents(hash, vec![parent], miner_data, vec![]).to_immutable();
+                let coinbase_id = block.transactions[0].id();
+                futures::executor::block_on(tc.validate_and_insert_block(block).virtual_state_task).unwrap();
+                parent = hash;
+                coinbase_id
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_txindex() {
+        vecno_core::log::try_init_logger("INFO");
+
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+
+        // Build a chain of 5 blocks, each chain block accepting the coinbase of its selected parent.
+        let chain_a = (1..=5).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let coinbases_a = add_chain(&tc, config.genesis.hash, &chain_a, 1);
+
+        // Sync the txindex from scratch.
+        let (_txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), *chain_a.last().unwrap());
+
+        for i in 0..chain_a.len() - 1 {
+            let entry = txindex.read().get_transaction_entry(coinbases_a[i]).unwrap().expect("expected an accepted coinbase");
+            assert_eq!(entry.including_block_hash, chain_a[i]);
+            assert_eq!(entry.index_within_block, 0);
+            assert_eq!(entry.accepting_block_hash, chain_a[i + 1]);
+        }
+        // The coinbase of the sink is not accepted yet
+        assert!(txindex.read().get_transaction_entry(*coinbases_a.last().unwrap()).unwrap().is_none());
+
+        // Coinbase transactions pay the miners of their merged blocks, so the history of the miner consists of accepted coinbases
+        let script_public_key_a = ScriptPublicKey::from_vec(0, vec![1]);
+        let history_a = read_history(&*txindex.read(), &script_public_key_a);
+        assert!(!history_a.is_empty());
+        assert!(history_a.windows(2).all(|w| w[0].cursor() < w[1].cursor()));
+        for entry in history_a.iter() {
+            assert!(coinbases_a.contains(&entry.transaction_id));
+            assert_eq!(
+                txindex.read().get_transaction_entry(entry.transaction_id).unwrap().unwrap().accepting_block_hash,
+                entry.accepting_block_hash
+            );
+            assert_eq!(tc.get_header(entry.accepting_block_hash).unwrap().daa_score, entry.accepting_block_daa_score);
+        }
+        let page = txindex.read().get_address_history(&script_public_key_a, history_a[0].cursor(), None, usize::MAX).unwrap();
+        assert_eq!(page.entries, history_a);
+        assert!(page.next_cursor.is_none());
+
+        // The DAA score range excludes entries at and beyond its end
+        let end_daa_score = history_a.last().unwrap().accepting_block_daa_score;
+        let page =
+            txindex.read().get_address_history(&script_public_key_a, history_a[0].cursor(), Some(end_daa_score), usize::MAX).unwrap();
+        assert_eq!(page.entries, history_a[..history_a.len() - 1]);
+
+        // Reorg to a longer chain with different coinbases, and feed the virtual chain change to the txindex.
+        let chain_b = (10..=17).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let coinbases_b = add_chain(&tc, config.genesis.hash, &chain_b, 2);
+        let chain_path = tc.get_virtual_chain_from_block(*chain_a.last().unwrap(), None).unwrap();
+        assert_eq!(chain_path.removed.len(), chain_a.len());
+        let acceptance_data = tc.get_blocks_acceptance_data(&chain_path.added, None).unwrap();
+        txindex.write().update(Arc::new(chain_path.removed), Arc::new(chain_path.added), Arc::new(acceptance_data)).unwrap();
+        assert!(txindex.read().is_synced().unwrap());
+
+        let entries = txindex.read().get_transaction_entries(&coinbases_a).unwrap();
+        assert!(entries.iter().all(|entry| entry.is_none()));
+        assert!(read_history(&*txindex.read(), &script_public_key_a).is_empty());
+        let script_public_key_b = ScriptPublicKey::from_vec(0, vec![2]);
+        let history_b = read_history(&*txindex.read(), &script_public_key_b);
+        assert!(!history_b.is_empty());
+        assert!(history_b.iter().all(|entry| coinbases_b.contains(&entry.transaction_id)));
+        let entries = txindex.read().get_transaction_entries(&coinbases_b).unwrap();
+        for i in 0..chain_b.len() - 1 {
+            let entry = entries[i].expect("expected an accepted coinbase");
+            assert_eq!(entry.including_block_hash, chain_b[i]);
+            assert_eq!(entry.accepting_block_hash, chain_b[i + 1]);
+        }
+
+        // A resync must reproduce the same index.
+        txindex.write().resync().unwrap();
+        assert_eq!(txindex.read().get_transaction_entries(&coinbases_b).unwrap(), entries);
+        assert!(txindex.read().get_transaction_entries(&coinbases_a).unwrap().iter().all(|entry| entry.is_none()));
+        assert_eq!(read_history(&*txindex.read(), &script_public_key_b), history_b);
+        assert!(read_history(&*txindex.read(), &script_public_key_a).is_empty());
+
+        // Deconstruct
+        drop(txindex);
+        tc.shutdown(wait_handles);
+    }
+}
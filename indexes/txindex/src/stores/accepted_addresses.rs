@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use vecno_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+use crate::stores::address_history::AddressHistoryKey;
+
+/// Reader API for `TxIndexAcceptedAddressesStore`.
+///
+/// Maps every indexed chain block to the address history keys of the transactions it accepted,
+/// so that the index can roll back chain blocks removed by a reorg.
+pub trait TxIndexAcceptedAddressesStoreReader {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<AddressHistoryKey>>>;
+}
+
+pub trait TxIndexAcceptedAddressesStore: TxIndexAcceptedAddressesStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, accepting_block_hash: Hash, keys: Arc<Vec<AddressHistoryKey>>) -> StoreResult<()>;
+    fn delete(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAcceptedAddressesStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAcceptedAddressesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<AddressHistoryKey>>>,
+}
+
+impl DbTxIndexAcceptedAddressesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptedAddresses.into()),
+        }
+    }
+}
+
+impl TxIndexAcceptedAddressesStoreReader for DbTxIndexAcceptedAddressesStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<AddressHistoryKey>>> {
+        self.access.read(accepting_block_hash)
+    }
+}
+
+impl TxIndexAcceptedAddressesStore for DbTxIndexAcceptedAddressesStore {
+    fn insert(&mut self, writer: impl DbWriter, accepting_block_hash: Hash, keys: Arc<Vec<AddressHistoryKey>>) -> StoreResult<()> {
+        self.access.write(writer, accepting_block_hash, keys)
+    }
+
+    fn delete(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(writer, accepting_block_hash)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
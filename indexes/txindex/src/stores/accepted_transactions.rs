@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use vecno_consensus_core::tx::TransactionId;
+use vecno_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::Hash;
+
+/// Reader API for `TxIndexAcceptedTransactionsStore`.
+///
+/// Maps every indexed chain block to the ids of the transactions it accepted,
+/// so that the index can roll back chain blocks removed by a reorg.
+pub trait TxIndexAcceptedTransactionsStoreReader {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<TransactionId>>>;
+}
+
+pub trait TxIndexAcceptedTransactionsStore: TxIndexAcceptedTransactionsStoreReader {
+    fn insert(
+        &mut self,
+        writer: impl DbWriter,
+        accepting_block_hash: Hash,
+        transaction_ids: Arc<Vec<TransactionId>>,
+    ) -> StoreResult<()>;
+    fn delete(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAcceptedTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAcceptedTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, Arc<Vec<TransactionId>>>,
+}
+
+impl DbTxIndexAcceptedTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptedTransactions.into()),
+        }
+    }
+}
+
+impl TxIndexAcceptedTransactionsStoreReader for DbTxIndexAcceptedTransactionsStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Arc<Vec<TransactionId>>> {
+        self.access.read(accepting_block_hash)
+    }
+}
+
+impl TxIndexAcceptedTransactionsStore for DbTxIndexAcceptedTransactionsStore {
+    fn insert(
+        &mut self,
+        writer: impl DbWriter,
+        accepting_block_hash: Hash,
+        transaction_ids: Arc<Vec<TransactionId>>,
+    ) -> StoreResult<()> {
+        self.access.write(writer, accepting_block_hash, transaction_ids)
+    }
+
+    fn delete(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(writer, accepting_block_hash)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,153 @@
+use std::{fmt::Display, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use vecno_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionId};
+use vecno_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use vecno_hashes::{Hash, HASH_SIZE};
+
+use crate::model::{AddressHistoryCursor, AddressHistoryEntry, AddressHistoryPage};
+
+pub const VERSION_TYPE_SIZE: usize = size_of::<ScriptPublicKeyVersion>();
+
+/// Size of the [AddressHistoryCursor] part of an [AddressHistoryKey] in bytes.
+pub const CURSOR_KEY_SIZE: usize = size_of::<u64>() + HASH_SIZE;
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion] bytes, followed by 8 bytes of little endian
+/// script length and a variable size of script bytes.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> = Vec::with_capacity(VERSION_TYPE_SIZE + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Full address history access key.
+/// Consists of variable amount of bytes of [ScriptPublicKeyBucket], followed by 8 bytes of big endian accepting
+/// block DAA score and 32 bytes of [TransactionId], so that the entries of a bucket are iterated in DAA score order.
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct AddressHistoryKey(Arc<Vec<u8>>);
+
+impl AddressHistoryKey {
+    pub fn new(script_public_key: &ScriptPublicKey, cursor: AddressHistoryCursor) -> Self {
+        Self::from_bucket(&ScriptPublicKeyBucket::from(script_public_key), cursor)
+    }
+
+    fn from_bucket(bucket: &ScriptPublicKeyBucket, cursor: AddressHistoryCursor) -> Self {
+        let mut bytes = Vec::with_capacity(bucket.as_ref().len() + CURSOR_KEY_SIZE);
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(&cursor.accepting_block_daa_score.to_be_bytes());
+        bytes.extend_from_slice(&cursor.transaction_id.as_bytes());
+        Self(Arc::new(bytes))
+    }
+}
+
+impl Display for AddressHistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Decodes the cursor part of a key, as returned by iterating a [ScriptPublicKeyBucket].
+fn cursor_from_bucket_key(key: &[u8]) -> AddressHistoryCursor {
+    let accepting_block_daa_score = u64::from_be_bytes(key[..size_of::<u64>()].try_into().unwrap());
+    let transaction_id = Hash::from_slice(&key[size_of::<u64>()..CURSOR_KEY_SIZE]);
+    AddressHistoryCursor::new(accepting_block_daa_score, transaction_id)
+}
+
+/// Reader API for `TxIndexAddressHistoryStore`.
+pub trait TxIndexAddressHistoryStoreReader {
+    /// Returns up to `limit` entries of the history of `script_public_key`, starting at `from` (inclusive)
+    /// and ending before `end_daa_score` (exclusive), if supplied.
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+}
+
+pub trait TxIndexAddressHistoryStore: TxIndexAddressHistoryStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, key: AddressHistoryKey, accepting_block_hash: Hash) -> StoreResult<()>;
+    fn delete(&mut self, writer: impl DbWriter, key: AddressHistoryKey) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAddressHistoryStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, Hash>,
+}
+
+impl DbTxIndexAddressHistoryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAddressHistory.into()),
+        }
+    }
+}
+
+impl TxIndexAddressHistoryStoreReader for DbTxIndexAddressHistoryStore {
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_key = AddressHistoryKey::from_bucket(&bucket, from);
+
+        // Fetch one more entry than requested, it is the start of the next page
+        let mut entries = Vec::with_capacity(limit.saturating_add(1).min(1024));
+        for res in self.access.seek_iterator(Some(bucket.as_ref()), Some(seek_key), limit.saturating_add(1), false) {
+            let (key, accepting_block_hash) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let cursor = cursor_from_bucket_key(&key);
+            if end_daa_score.is_some_and(|end_daa_score| cursor.accepting_block_daa_score >= end_daa_score) {
+                break;
+            }
+            entries.push(AddressHistoryEntry::new(cursor.transaction_id, accepting_block_hash, cursor.accepting_block_daa_score));
+        }
+
+        let next_cursor = if entries.len() > limit { entries.pop().map(|entry| entry.cursor()) } else { None };
+        Ok(AddressHistoryPage { entries, next_cursor })
+    }
+}
+
+impl TxIndexAddressHistoryStore for DbTxIndexAddressHistoryStore {
+    fn insert(&mut self, writer: impl DbWriter, key: AddressHistoryKey, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.write(writer, key, accepting_block_hash)
+    }
+
+    fn delete(&mut self, writer: impl DbWriter, key: AddressHistoryKey) -> StoreResult<()> {
+        self.access.delete(writer, key)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,6 @@
+mod accepted_addresses;
+mod accepted_transactions;
+pub mod address_history;
+mod sink;
+pub mod store_manager;
+mod transactions;
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use rocksdb::WriteBatch;
+use vecno_consensus_core::{
+    acceptance_data::AcceptanceData,
+    tx::{ScriptPublicKey, TransactionId},
+};
+use vecno_core::trace;
+use vecno_database::prelude::{BatchDbWriter, CachePolicy, StoreError, StoreResult, DB};
+use vecno_hashes::Hash;
+
+use crate::{
+    model::{AddressHistoryCursor, AddressHistoryPage, TxIndexEntry},
+    stores::{
+        accepted_addresses::{DbTxIndexAcceptedAddressesStore, TxIndexAcceptedAddressesStore, TxIndexAcceptedAddressesStoreReader},
+        accepted_transactions::{
+            DbTxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStore, TxIndexAcceptedTransactionsStoreReader,
+        },
+        address_history::{
+            AddressHistoryKey, DbTxIndexAddressHistoryStore, TxIndexAddressHistoryStore, TxIndexAddressHistoryStoreReader,
+        },
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+        transactions::{DbTxIndexTransactionsStore, TxIndexTransactionsStore, TxIndexTransactionsStoreReader},
+    },
+    IDENT,
+};
+
+/// A chain block to be added to the index, along with the data it accepted.
+pub struct IndexedChainBlock {
+    pub hash: Hash,
+    pub acceptance_data: Arc<AcceptanceData>,
+    /// The address history keys of the accepted transactions, one per (address, transaction) pair.
+    pub address_history_keys: Vec<AddressHistoryKey>,
+}
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    transactions_store: DbTxIndexTransactionsStore,
+    accepted_transactions_store: DbTxIndexAcceptedTransactionsStore,
+    address_history_store: DbTxIndexAddressHistoryStore,
+    accepted_addresses_store: DbTxIndexAcceptedAddressesStore,
+    sink_store: DbTxIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            transactions_store: DbTxIndexTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            accepted_transactions_store: DbTxIndexAcceptedTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            address_history_store: DbTxIndexAddressHistoryStore::new(db.clone(), CachePolicy::Empty),
+            accepted_addresses_store: DbTxIndexAcceptedAddressesStore::new(db.clone(), CachePolicy::Empty),
+            sink_store: DbTxIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        match self.transactions_store.get(transaction_id) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn get_accepted_transaction_ids(&self, accepting_block_hash: Hash) -> StoreResult<Option<Arc<Vec<TransactionId>>>> {
+        match self.accepted_transactions_store.get(accepting_block_hash) {
+            Ok(transaction_ids) => Ok(Some(transaction_ids)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        end_daa_score: Option<u64>,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        self.address_history_store.get_page(script_public_key, from, end_daa_score, limit)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically rolls back the `removed` chain blocks, indexes the transactions accepted by the `added` chain blocks
+    /// and sets the new index sink.
+    pub fn update_chain(&mut self, removed: &[Hash], added: &[IndexedChainBlock], sink: Hash) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+
+        for &accepting_block_hash in removed.iter() {
+            match self.accepted_addresses_store.get(accepting_block_hash) {
+                Ok(keys) => {
+                    for key in keys.iter() {
+                        self.address_history_store.delete(BatchDbWriter::new(&mut batch), key.clone())?;
+                    }
+                    self.accepted_addresses_store.delete(BatchDbWriter::new(&mut batch), accepting_block_hash)?;
+                }
+                Err(StoreError::KeyNotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+
+            let Some(transaction_ids) = self.get_accepted_transaction_ids(accepting_block_hash)? else {
+                continue;
+            };
+            for &transaction_id in transaction_ids.iter() {
+                // The transaction might have been re-accepted by a chain block which is still in the index
+                if let Some(entry) = self.get_transaction_entry(transaction_id)? {
+                    if entry.accepting_block_hash == accepting_block_hash {
+                        self.transactions_store.delete(BatchDbWriter::new(&mut batch), transaction_id)?;
+                    }
+                }
+            }
+            self.accepted_transactions_store.delete(BatchDbWriter::new(&mut batch), accepting_block_hash)?;
+        }
+
+        for IndexedChainBlock { hash: accepting_block_hash, acceptance_data, address_history_keys } in added.iter() {
+            let mut transaction_ids = Vec::with_capacity(acceptance_data.iter().map(|x| x.accepted_transactions.len()).sum());
+            for mergeset_block_data in acceptance_data.iter() {
+                for accepted_transaction in mergeset_block_data.accepted_transactions.iter() {
+                    let entry = TxIndexEntry::new(
+                        mergeset_block_data.block_hash,
+                        accepted_transaction.index_within_block,
+                        *accepting_block_hash,
+                    );
+                    self.transactions_store.insert(BatchDbWriter::new(&mut batch), accepted_transaction.transaction_id, entry)?;
+                    transaction_ids.push(accepted_transaction.transaction_id);
+                }
+            }
+            self.accepted_transactions_store.insert(
+                BatchDbWriter::new(&mut batch),
+                *accepting_block_hash,
+                Arc::new(transaction_ids),
+            )?;
+
+            for key in address_history_keys.iter() {
+                self.address_history_store.insert(BatchDbWriter::new(&mut batch), key.clone(), *accepting_block_hash)?;
+            }
+            self.accepted_addresses_store.insert(
+                BatchDbWriter::new(&mut batch),
+                *accepting_block_hash,
+                Arc::new(address_history_keys.clone()),
+            )?;
+        }
+
+        self.sink_store.set(BatchDbWriter::new(&mut batch), sink)?;
+        self.db.write(batch)?;
+
+        Ok(())
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.accepted_transactions_store.delete_all()?;
+        self.accepted_addresses_store.delete_all()?;
+        self.address_history_store.delete_all()?;
+        self.transactions_store.delete_all()?;
+
+        trace!("[{0}] clearing txindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use vecno_consensus_core::tx::TransactionId;
+use vecno_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::TxIndexEntry;
+
+/// Reader API for `TxIndexTransactionsStore`.
+pub trait TxIndexTransactionsStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxIndexEntry>;
+}
+
+pub trait TxIndexTransactionsStore: TxIndexTransactionsStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, transaction_id: TransactionId, entry: TxIndexEntry) -> StoreResult<()>;
+    fn delete(&mut self, writer: impl DbWriter, transaction_id: TransactionId) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry>,
+}
+
+impl DbTxIndexTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexTransactions.into()) }
+    }
+}
+
+impl TxIndexTransactionsStoreReader for DbTxIndexTransactionsStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<TxIndexEntry> {
+        self.access.read(transaction_id)
+    }
+}
+
+impl TxIndexTransactionsStore for DbTxIndexTransactionsStore {
+    fn insert(&mut self, writer: impl DbWriter, transaction_id: TransactionId, entry: TxIndexEntry) -> StoreResult<()> {
+        self.access.write(writer, transaction_id, entry)
+    }
+
+    fn delete(&mut self, writer: impl DbWriter, transaction_id: TransactionId) -> StoreResult<()> {
+        self.access.delete(writer, transaction_id)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
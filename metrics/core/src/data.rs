@@ -3,7 +3,7 @@ use crate::result::Result;
 use borsh::{BorshDeserialize, BorshSerialize};
 use separator::{separated_float, separated_int, separated_uint_with_output, Separatable};
 use serde::{Deserialize, Serialize};
-use vecno_rpc_core::GetMetricsResponse;
+use vecno_rpc_core::{GetMetricsResponse, RpcMempoolStatsSample};
 use workflow_core::enums::Describe;
 
 #[derive(Describe, Debug, Clone, Copy, Eq, PartialEq, Hash, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -13,6 +13,8 @@ pub enum MetricGroup {
     Bandwidth,
     Connections,
     Network,
+    Mempool,
+    Miner,
 }
 
 impl std::fmt::Display for MetricGroup {
@@ -23,6 +25,8 @@ impl std::fmt::Display for MetricGroup {
             MetricGroup::Bandwidth => write!(f, "bandwidth"),
             MetricGroup::Connections => write!(f, "connections"),
             MetricGroup::Network => write!(f, "network"),
+            MetricGroup::Mempool => write!(f, "mempool"),
+            MetricGroup::Miner => write!(f, "miner"),
         }
     }
 }
@@ -35,6 +39,8 @@ impl MetricGroup {
             MetricGroup::Bandwidth => "Bandwidth",
             MetricGroup::Connections => "Connections",
             MetricGroup::Network => "Network",
+            MetricGroup::Mempool => "Mempool",
+            MetricGroup::Miner => "Miner",
         }
     }
 }
@@ -114,6 +120,20 @@ impl MetricGroup {
             ]
             .as_slice()
             .iter(),
+            MetricGroup::Mempool => [
+                Metric::MempoolTotalMass,
+                Metric::MempoolOrphansCount,
+                Metric::MempoolEvictedCount,
+                Metric::MempoolReplacedCount,
+                Metric::MempoolPolicyRejectedCount,
+            ]
+            .as_slice()
+            .iter(),
+            MetricGroup::Miner => {
+                [Metric::MinerHashesPerSecond, Metric::MinerBlocksAccepted, Metric::MinerBlocksRejected, Metric::MinerStaleBlocks]
+                    .as_slice()
+                    .iter()
+            }
         }
     }
 }
@@ -177,6 +197,17 @@ impl From<Metric> for MetricGroup {
             | Metric::NetworkPastMedianTime
             | Metric::NetworkVirtualParentHashesCount
             | Metric::NetworkVirtualDaaScore => MetricGroup::Network,
+            // --
+            Metric::MempoolTotalMass
+            | Metric::MempoolOrphansCount
+            | Metric::MempoolEvictedCount
+            | Metric::MempoolReplacedCount
+            | Metric::MempoolPolicyRejectedCount => MetricGroup::Mempool,
+            // --
+            Metric::MinerHashesPerSecond
+            | Metric::MinerBlocksAccepted
+            | Metric::MinerBlocksRejected
+            | Metric::MinerStaleBlocks => MetricGroup::Miner,
         }
     }
 }
@@ -249,6 +280,17 @@ pub enum Metric {
     NetworkPastMedianTime,
     NetworkVirtualParentHashesCount,
     NetworkVirtualDaaScore,
+    // --
+    MempoolTotalMass,
+    MempoolOrphansCount,
+    MempoolEvictedCount,
+    MempoolReplacedCount,
+    MempoolPolicyRejectedCount,
+    // --
+    MinerHashesPerSecond,
+    MinerBlocksAccepted,
+    MinerBlocksRejected,
+    MinerStaleBlocks,
 }
 
 impl Metric {
@@ -355,6 +397,17 @@ impl Metric {
             Metric::NetworkPastMedianTime => format_as_float(f, false),
             Metric::NetworkVirtualParentHashesCount => format_as_float(f, short),
             Metric::NetworkVirtualDaaScore => format_as_float(f, false),
+            // --
+            Metric::MempoolTotalMass => format_as_float(f.trunc(), short),
+            Metric::MempoolOrphansCount => f.trunc().separated_string(),
+            Metric::MempoolEvictedCount => f.trunc().separated_string(),
+            Metric::MempoolReplacedCount => f.trunc().separated_string(),
+            Metric::MempoolPolicyRejectedCount => f.trunc().separated_string(),
+            // --
+            Metric::MinerHashesPerSecond => format!("{} H/s", format_as_float(f.trunc(), short)),
+            Metric::MinerBlocksAccepted => f.trunc().separated_string(),
+            Metric::MinerBlocksRejected => f.trunc().separated_string(),
+            Metric::MinerStaleBlocks => f.trunc().separated_string(),
         }
     }
 
@@ -419,6 +472,17 @@ impl Metric {
             Metric::NetworkPastMedianTime => ("Past Median Time", "MT"),
             Metric::NetworkVirtualParentHashesCount => ("Virtual Parent Hashes", "Virt Parents"),
             Metric::NetworkVirtualDaaScore => ("Virtual DAA Score", "DAA"),
+            // --
+            Metric::MempoolTotalMass => ("Mempool Total Mass", "Mass"),
+            Metric::MempoolOrphansCount => ("Mempool Orphans", "Orphans"),
+            Metric::MempoolEvictedCount => ("Mempool Evictions", "Evicted"),
+            Metric::MempoolReplacedCount => ("Mempool RBF Replacements", "Replaced"),
+            Metric::MempoolPolicyRejectedCount => ("Mempool Policy Rejections", "Rejected"),
+            // --
+            Metric::MinerHashesPerSecond => ("Miner Hashrate", "Hashrate"),
+            Metric::MinerBlocksAccepted => ("Miner Accepted Blocks", "Accepted"),
+            Metric::MinerBlocksRejected => ("Miner Rejected Blocks", "Rejected"),
+            Metric::MinerStaleBlocks => ("Miner Stale Blocks", "Stale"),
         }
     }
 }
@@ -476,12 +540,61 @@ pub struct MetricsData {
     pub network_past_median_time: u64,
     pub network_virtual_parent_hashes_count: u32,
     pub network_virtual_daa_score: u64,
+    // --
+    pub mempool_total_mass: u64,
+    pub mempool_orphans_count: u64,
+    pub mempool_evicted_count: u64,
+    pub mempool_replaced_count: u64,
+    pub mempool_policy_rejected_count: u64,
+    /// The lower bounds, in sompi/gram, of the buckets of the mempool feerate histogram
+    pub mempool_feerate_buckets: Vec<f64>,
+    /// The number of mempool transactions in each bucket of `mempool_feerate_buckets`
+    pub mempool_feerate_histogram: Vec<u64>,
+    // --
+    pub miner_hashes: u64,
+    pub miner_blocks_accepted: u64,
+    pub miner_blocks_rejected: u64,
+    pub miner_stale_blocks: u64,
 }
 
 impl MetricsData {
     pub fn new(unixtime: f64) -> Self {
         Self { unixtime_millis: unixtime, ..Default::default() }
     }
+
+    pub fn set_miner_metrics(&mut self, miner_metrics: MinerMetrics) {
+        let MinerMetrics { hashes, blocks_accepted, blocks_rejected, stale_blocks } = miner_metrics;
+        self.miner_hashes = hashes;
+        self.miner_blocks_accepted = blocks_accepted;
+        self.miner_blocks_rejected = blocks_rejected;
+        self.miner_stale_blocks = stale_blocks;
+    }
+
+    /// Sets the mempool metrics from the most recent mempool statistics sample of the node.
+    /// Evictions, replacements and policy rejections are counted over the sample interval.
+    pub fn set_mempool_metrics(&mut self, feerate_buckets: &[f64], sample: &RpcMempoolStatsSample) {
+        self.mempool_total_mass = sample.total_mass;
+        self.mempool_orphans_count = sample.orphan_count;
+        self.mempool_evicted_count = sample.evicted_count;
+        self.mempool_replaced_count = sample.replaced_count;
+        self.mempool_policy_rejected_count = sample.policy_rejected_count;
+        self.mempool_feerate_buckets = feerate_buckets.to_vec();
+        self.mempool_feerate_histogram = sample.feerate_histogram.clone();
+    }
+
+    /// Returns the mempool feerate histogram as pairs of bucket lower bound, in sompi/gram, and transaction count
+    pub fn mempool_feerate_distribution(&self) -> Vec<(f64, u64)> {
+        self.mempool_feerate_buckets.iter().copied().zip(self.mempool_feerate_histogram.iter().copied()).collect()
+    }
+}
+
+/// The cumulative counters of a miner running alongside the metrics processor
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinerMetrics {
+    pub hashes: u64,
+    pub blocks_accepted: u64,
+    pub blocks_rejected: u64,
+    pub stale_blocks: u64,
 }
 
 impl TryFrom<GetMetricsResponse> for MetricsData {
@@ -561,6 +674,8 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_disk_io_write_per_sec: process_metrics.disk_io_write_per_sec,
 
             node_storage_size_bytes: storage_metrics.storage_size_bytes,
+
+            ..Default::default()
         })
     }
 }
@@ -632,7 +747,20 @@ pub struct MetricsSnapshot {
     pub network_virtual_parent_hashes_count: f64,
     pub network_virtual_daa_score: f64,
     // ---
+    pub mempool_total_mass: f64,
+    pub mempool_orphans_count: f64,
+    pub mempool_evicted_count: f64,
+    pub mempool_replaced_count: f64,
+    pub mempool_policy_rejected_count: f64,
+    /// The mempool feerate histogram, see [`MetricsData::mempool_feerate_distribution`]
+    pub mempool_feerate_histogram: Vec<(f64, u64)>,
+    // ---
     pub node_storage_size_bytes: f64,
+    // ---
+    pub miner_hashes_per_second: f64,
+    pub miner_blocks_accepted: f64,
+    pub miner_blocks_rejected: f64,
+    pub miner_stale_blocks: f64,
 }
 
 impl MetricsSnapshot {
@@ -697,6 +825,17 @@ impl MetricsSnapshot {
             Metric::NetworkPastMedianTime => self.network_past_median_time,
             Metric::NetworkVirtualParentHashesCount => self.network_virtual_parent_hashes_count,
             Metric::NetworkVirtualDaaScore => self.network_virtual_daa_score,
+            // --
+            Metric::MempoolTotalMass => self.mempool_total_mass,
+            Metric::MempoolOrphansCount => self.mempool_orphans_count,
+            Metric::MempoolEvictedCount => self.mempool_evicted_count,
+            Metric::MempoolReplacedCount => self.mempool_replaced_count,
+            Metric::MempoolPolicyRejectedCount => self.mempool_policy_rejected_count,
+            // --
+            Metric::MinerHashesPerSecond => self.miner_hashes_per_second,
+            Metric::MinerBlocksAccepted => self.miner_blocks_accepted,
+            Metric::MinerBlocksRejected => self.miner_blocks_rejected,
+            Metric::MinerStaleBlocks => self.miner_stale_blocks,
         }
     }
 
@@ -730,6 +869,7 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
         let node_grpc_user_bytes_rx_per_second = per_sec(a.node_grpc_user_bytes_rx, b.node_grpc_user_bytes_rx, duration_millis);
         let node_total_bytes_tx_per_second = per_sec(a.node_total_bytes_tx, b.node_total_bytes_tx, duration_millis);
         let node_total_bytes_rx_per_second = per_sec(a.node_total_bytes_rx, b.node_total_bytes_rx, duration_millis);
+        let miner_hashes_per_second = per_sec(a.miner_hashes, b.miner_hashes, duration_millis);
 
         Self {
             unixtime_millis: b.unixtime_millis,
@@ -794,6 +934,18 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             network_past_median_time: b.network_past_median_time as f64,
             network_virtual_parent_hashes_count: b.network_virtual_parent_hashes_count as f64,
             network_virtual_daa_score: b.network_virtual_daa_score as f64,
+            // --
+            mempool_total_mass: b.mempool_total_mass as f64,
+            mempool_orphans_count: b.mempool_orphans_count as f64,
+            mempool_evicted_count: b.mempool_evicted_count as f64,
+            mempool_replaced_count: b.mempool_replaced_count as f64,
+            mempool_policy_rejected_count: b.mempool_policy_rejected_count as f64,
+            mempool_feerate_histogram: b.mempool_feerate_distribution(),
+            // --
+            miner_hashes_per_second,
+            miner_blocks_accepted: b.miner_blocks_accepted as f64,
+            miner_blocks_rejected: b.miner_blocks_rejected as f64,
+            miner_stale_blocks: b.miner_stale_blocks as f64,
 
             data: b.clone(),
         }
@@ -2,7 +2,7 @@ pub mod data;
 pub mod error;
 pub mod result;
 
-pub use data::{Metric, MetricGroup, MetricsData, MetricsSnapshot};
+pub use data::{Metric, MetricGroup, MetricsData, MetricsSnapshot, MinerMetrics};
 
 use crate::result::Result;
 use futures::{pin_mut, select, FutureExt, StreamExt};
@@ -22,10 +22,13 @@ use workflow_log::*;
 pub type MetricsSinkFn =
     Arc<Box<dyn Send + Sync + Fn(MetricsSnapshot) -> Option<Pin<Box<(dyn Send + 'static + Future<Output = Result<()>>)>>> + 'static>>;
 
+pub type MinerMetricsFn = Arc<Box<dyn Send + Sync + Fn() -> Option<MinerMetrics> + 'static>>;
+
 pub struct Metrics {
     task_ctl: DuplexChannel,
     rpc: Arc<Mutex<Option<Arc<dyn RpcApi>>>>,
     sink: Arc<Mutex<Option<MetricsSinkFn>>>,
+    miner: Arc<Mutex<Option<MinerMetricsFn>>>,
     data: Arc<Mutex<Option<MetricsData>>>,
 }
 
@@ -35,6 +38,7 @@ impl Default for Metrics {
             task_ctl: DuplexChannel::oneshot(),
             rpc: Arc::new(Mutex::new(None)),
             sink: Arc::new(Mutex::new(None)),
+            miner: Arc::new(Mutex::new(None)),
             data: Arc::new(Mutex::new(None)),
         }
     }
@@ -61,6 +65,19 @@ impl Metrics {
         self.sink.lock().unwrap().clone()
     }
 
+    /// Registers a source of miner counters, sampled along with the node metrics
+    pub fn register_miner(&self, source: MinerMetricsFn) {
+        self.miner.lock().unwrap().replace(source);
+    }
+
+    pub fn unregister_miner(&self) {
+        self.miner.lock().unwrap().take();
+    }
+
+    fn miner(&self) -> Option<MinerMetricsFn> {
+        self.miner.lock().unwrap().clone()
+    }
+
     pub async fn start_task(self: &Arc<Self>) -> Result<()> {
         let this = self.clone();
 
@@ -121,6 +138,16 @@ impl Metrics {
     }
 
     async fn sample_metrics(self: &Arc<Self>, rpc: Arc<dyn RpcApi>) -> Result<MetricsData> {
-        MetricsData::try_from(rpc.get_metrics(true, true, true, true, true, false).await?)
+        let mut data = MetricsData::try_from(rpc.get_metrics(true, true, true, true, true, false).await?)?;
+        if let Some(miner_metrics) = self.miner().and_then(|miner| miner()) {
+            data.set_miner_metrics(miner_metrics);
+        }
+        // Only the most recent sample is requested; nodes without mempool statistics leave the mempool metrics unset
+        if let Ok(stats) = rpc.get_mempool_stats(1).await {
+            if let Some(sample) = stats.samples.last() {
+                data.set_mempool_metrics(&stats.feerate_buckets, sample);
+            }
+        }
+        Ok(data)
     }
 }
@@ -69,6 +69,20 @@ pub enum RuleError {
     #[error("number of high-priority transactions in orphan pool ({0}) has reached the maximum allowed ({1})")]
     RejectOrphanPoolIsFull(usize, u64),
 
+    #[error("transaction {0} has more than the allowed max amount of {1} ancestors in the mempool")]
+    RejectTooManyAncestors(TransactionId, usize),
+
+    #[error("transaction {0} and its ancestors in the mempool have a total mass of {1} which is more than the allowed max of {2}")]
+    RejectAncestorMassTooHigh(TransactionId, u64, u64),
+
+    #[error("transaction {0} would have more than the allowed max amount of {1} descendants in the mempool")]
+    RejectTooManyDescendants(TransactionId, usize),
+
+    #[error(
+        "transaction {0} and its descendants in the mempool would have a total mass of {1} which is more than the allowed max of {2}"
+    )]
+    RejectDescendantMassTooHigh(TransactionId, u64, u64),
+
     #[error("transactions in mempool form a cycle")]
     RejectCycleInMempoolTransactions,
 
@@ -81,6 +95,32 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("transaction package is empty")]
+    RejectEmptyPackage,
+
+    #[error("transaction package has {0} transactions which is more than the allowed max amount of {1}")]
+    RejectPackageTooLarge(usize, usize),
+
+    #[error("transaction {0} appears more than once in the package")]
+    RejectPackageDuplicate(TransactionId),
+
+    #[error("transaction {0} spends an output of transaction {1} which comes later in the package")]
+    RejectPackageNotSorted(TransactionId, TransactionId),
+
+    #[error("output {0} is spent by more than one transaction of the package, including {1}")]
+    RejectPackageDoubleSpend(TransactionOutpoint, TransactionId),
+
+    #[error("transaction package has {0} fees which is under the required amount of {1}")]
+    RejectPackageInsufficientFee(u64, u64),
+
+    /// A package is accepted or rejected as a whole, so the error of a single transaction rejects the package
+    #[error("transaction {0} of the package was rejected: {1}")]
+    RejectPackageTransaction(TransactionId, Box<RuleError>),
+
+    /// An error emitted by a rule of the mempool policy configured by the node operator
+    #[error("transaction {0} was rejected by the mempool policy: {1}")]
+    RejectPolicy(TransactionId, PolicyRejectReason),
 }
 
 impl From<NonStandardError> for RuleError {
@@ -101,6 +141,76 @@ impl From<TxRuleError> for RuleError {
 
 pub type RuleResult<T> = std::result::Result<T, RuleError>;
 
+/// The reason of a rejection by a rule of the mempool policy
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyRejectReason {
+    #[error("output #{0} of {1} sompi is below the dust threshold of {2} sompi")]
+    DustOutput(usize, u64, u64),
+
+    #[error("payload of {0} bytes is larger than the max allowed size of {1} bytes")]
+    PayloadTooLarge(usize, usize),
+
+    #[error("output #{0} pays to the blocked script class {1}")]
+    BlockedScriptClass(usize, String),
+
+    #[error("the source peer exceeded its rate limit of {0} transactions per second")]
+    PeerRateLimited(u32),
+
+    /// A rejection by a rule plugged into the policy by the node itself
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl PolicyRejectReason {
+    pub fn code(&self) -> PolicyReasonCode {
+        match self {
+            PolicyRejectReason::DustOutput(..) => PolicyReasonCode::Dust,
+            PolicyRejectReason::PayloadTooLarge(..) => PolicyReasonCode::PayloadSize,
+            PolicyRejectReason::BlockedScriptClass(..) => PolicyReasonCode::ScriptClass,
+            PolicyRejectReason::PeerRateLimited(..) => PolicyReasonCode::PeerRateLimit,
+            PolicyRejectReason::Custom(..) => PolicyReasonCode::Custom,
+        }
+    }
+}
+
+/// A stable code identifying the kind of a mempool policy rejection, suited for metrics and RPC clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum PolicyReasonCode {
+    Dust = 0,
+    PayloadSize,
+    ScriptClass,
+    PeerRateLimit,
+    Custom,
+}
+
+impl PolicyReasonCode {
+    pub const COUNT: usize = 5;
+    pub const ALL: [PolicyReasonCode; Self::COUNT] = [
+        PolicyReasonCode::Dust,
+        PolicyReasonCode::PayloadSize,
+        PolicyReasonCode::ScriptClass,
+        PolicyReasonCode::PeerRateLimit,
+        PolicyReasonCode::Custom,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyReasonCode::Dust => "dust",
+            PolicyReasonCode::PayloadSize => "payload-size",
+            PolicyReasonCode::ScriptClass => "script-class",
+            PolicyReasonCode::PeerRateLimit => "peer-rate-limit",
+            PolicyReasonCode::Custom => "custom",
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyReasonCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum NonStandardError {
     #[error("transaction version {1} is not in the valid range of {2}-{3}")]
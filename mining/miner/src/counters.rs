@@ -0,0 +1,85 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+pub struct MinerCounters {
+    pub creation_time: Instant,
+
+    // Counters
+    pub hashes: AtomicU64,
+    pub templates_received: AtomicU64,
+    pub blocks_found: AtomicU64,
+    pub blocks_accepted: AtomicU64,
+    pub blocks_rejected: AtomicU64,
+
+    /// Blocks found on a template which was already replaced by a newer one when the block was submitted
+    pub stale_blocks: AtomicU64,
+}
+
+impl Default for MinerCounters {
+    fn default() -> Self {
+        Self {
+            creation_time: Instant::now(),
+            hashes: Default::default(),
+            templates_received: Default::default(),
+            blocks_found: Default::default(),
+            blocks_accepted: Default::default(),
+            blocks_rejected: Default::default(),
+            stale_blocks: Default::default(),
+        }
+    }
+}
+
+impl MinerCounters {
+    pub fn snapshot(&self) -> MinerCountersSnapshot {
+        MinerCountersSnapshot {
+            elapsed_time: (Instant::now() - self.creation_time),
+            hashes: self.hashes.load(Ordering::Relaxed),
+            templates_received: self.templates_received.load(Ordering::Relaxed),
+            blocks_found: self.blocks_found.load(Ordering::Relaxed),
+            blocks_accepted: self.blocks_accepted.load(Ordering::Relaxed),
+            blocks_rejected: self.blocks_rejected.load(Ordering::Relaxed),
+            stale_blocks: self.stale_blocks.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MinerCountersSnapshot {
+    pub elapsed_time: Duration,
+    pub hashes: u64,
+    pub templates_received: u64,
+    pub blocks_found: u64,
+    pub blocks_accepted: u64,
+    pub blocks_rejected: u64,
+    pub stale_blocks: u64,
+}
+
+impl MinerCountersSnapshot {
+    /// Returns the average amount of hashes per second over the elapsed time of the snapshot
+    pub fn hashrate(&self) -> f64 {
+        let elapsed = self.elapsed_time.as_secs_f64();
+        if elapsed != 0f64 {
+            self.hashes as f64 / elapsed
+        } else {
+            0f64
+        }
+    }
+}
+
+impl core::ops::Sub for &MinerCountersSnapshot {
+    type Output = MinerCountersSnapshot;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            elapsed_time: self.elapsed_time.saturating_sub(rhs.elapsed_time),
+            hashes: self.hashes.saturating_sub(rhs.hashes),
+            templates_received: self.templates_received.saturating_sub(rhs.templates_received),
+            blocks_found: self.blocks_found.saturating_sub(rhs.blocks_found),
+            blocks_accepted: self.blocks_accepted.saturating_sub(rhs.blocks_accepted),
+            blocks_rejected: self.blocks_rejected.saturating_sub(rhs.blocks_rejected),
+            stale_blocks: self.stale_blocks.saturating_sub(rhs.stale_blocks),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::nonce::MAX_THREADS;
+use thiserror::Error;
+use vecno_rpc_core::RpcError;
+
+#[derive(Error, Debug, Clone)]
+pub enum MinerError {
+    #[error("the amount of mining threads must be between 1 and {MAX_THREADS}, got {0}")]
+    InvalidThreadCount(usize),
+
+    #[error("the miner is already running")]
+    AlreadyRunning,
+
+    #[error("the miner is not running")]
+    NotRunning,
+
+    #[error(transparent)]
+    RpcError(#[from] RpcError),
+}
+
+pub type MinerResult<T> = std::result::Result<T, MinerError>;
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use vecno_consensus_core::{block::Block, header::Header, tx::Transaction};
+
+/// A block template published to the worker threads, tagged with the generation it was published at
+pub struct Job {
+    generation: u64,
+    header: Header,
+    transactions: Arc<Vec<Transaction>>,
+}
+
+impl Job {
+    pub fn new(generation: u64, block: Block) -> Self {
+        Self { generation, header: (*block.header).clone(), transactions: block.transactions }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the block formed by this template and the given nonce
+    pub fn to_block(&self, nonce: u64) -> Block {
+        let mut header = self.header.clone();
+        header.nonce = nonce;
+        header.finalize();
+        Block::from_arcs(Arc::new(header), self.transactions.clone())
+    }
+}
+
+/// A nonce passing the network target of the job it was found for
+pub struct Solution {
+    pub job: Arc<Job>,
+    pub nonce: u64,
+}
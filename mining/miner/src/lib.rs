@@ -0,0 +1,16 @@
+//! A native multi-threaded CPU miner.
+//!
+//! The miner mines the block templates of a node through its RPC API. It subscribes to the
+//! `NewBlockTemplate` notifications of the node, splits the nonce space between a configurable
+//! amount of worker threads and keeps counters of its hashrate and of the blocks it submitted.
+
+pub mod counters;
+pub mod errors;
+pub mod job;
+pub mod miner;
+pub mod nonce;
+pub mod workers;
+
+pub use miner::{Miner, MinerConfig};
+
+pub const IDENT: &str = "cpu-miner";
@@ -0,0 +1,255 @@
+use crate::{
+    counters::MinerCounters,
+    errors::{MinerError, MinerResult},
+    job::Solution,
+    nonce::{NonceSpace, MAX_THREADS},
+    workers::WorkerPool,
+    IDENT,
+};
+use parking_lot::Mutex;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    task::JoinHandle,
+};
+use vecno_addresses::Address;
+use vecno_consensus_core::block::Block;
+use vecno_core::{debug, info, vecnod_env::version, warn};
+use vecno_notify::{listener::ListenerId, scope::NewBlockTemplateScope};
+use vecno_rpc_core::{
+    api::rpc::RpcApi,
+    notify::connection::{ChannelConnection, ChannelType},
+    Notification, SubmitBlockReport,
+};
+use vecno_utils::{
+    channel::Channel,
+    triggers::{Listener, SingleTrigger},
+};
+
+#[derive(Debug, Clone)]
+pub struct MinerConfig {
+    /// The amount of hashing threads
+    pub threads: usize,
+
+    /// The address the coinbase of the mined blocks pays to
+    pub pay_address: Address,
+
+    /// The extra data of the coinbase of the mined blocks
+    pub extra_data: Vec<u8>,
+
+    /// The extranonce of this miner instance, chosen at random if not set
+    pub extranonce: Option<u16>,
+}
+
+impl MinerConfig {
+    pub fn new(threads: usize, pay_address: Address) -> Self {
+        Self { threads, pay_address, extra_data: format!("{}/cpu-miner", version()).into_bytes(), extranonce: None }
+    }
+
+    pub fn with_extra_data(mut self, extra_data: Vec<u8>) -> Self {
+        self.extra_data = extra_data;
+        self
+    }
+
+    pub fn with_extranonce(mut self, extranonce: u16) -> Self {
+        self.extranonce = Some(extranonce);
+        self
+    }
+}
+
+struct Running {
+    listener_id: ListenerId,
+    shutdown: SingleTrigger,
+    task: JoinHandle<()>,
+}
+
+/// A CPU miner mining the block templates of a node reached through its RPC API.
+///
+/// The miner fetches a new template whenever the node notifies one and hashes it on a pool of
+/// worker threads. Found blocks are submitted back to the node.
+pub struct Miner {
+    rpc: Arc<dyn RpcApi>,
+    config: MinerConfig,
+    nonce_space: NonceSpace,
+    counters: Arc<MinerCounters>,
+    is_synced: AtomicBool,
+    running: Mutex<Option<Running>>,
+}
+
+impl Miner {
+    pub fn try_new(rpc: Arc<dyn RpcApi>, config: MinerConfig) -> MinerResult<Self> {
+        if config.threads == 0 || config.threads > MAX_THREADS {
+            return Err(MinerError::InvalidThreadCount(config.threads));
+        }
+        let nonce_space = NonceSpace::new(config.extranonce.unwrap_or_else(rand::random));
+        Ok(Self {
+            rpc,
+            config,
+            nonce_space,
+            counters: Default::default(),
+            is_synced: AtomicBool::new(true),
+            running: Mutex::new(None),
+        })
+    }
+
+    pub fn config(&self) -> &MinerConfig {
+        &self.config
+    }
+
+    pub fn counters(&self) -> &Arc<MinerCounters> {
+        &self.counters
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.lock().is_some()
+    }
+
+    /// Subscribes to the block template notifications of the node and starts hashing
+    pub async fn start(self: &Arc<Self>) -> MinerResult<()> {
+        if self.is_running() {
+            return Err(MinerError::AlreadyRunning);
+        }
+
+        let channel = Channel::<Notification>::default();
+        let listener_id = self.rpc.register_new_listener(ChannelConnection::new(IDENT, channel.sender(), ChannelType::Closable));
+        if let Err(err) = self.rpc.start_notify(listener_id, NewBlockTemplateScope {}.into()).await {
+            self.rpc.unregister_listener(listener_id).await.ok();
+            return Err(err.into());
+        }
+
+        let (solution_sender, solution_receiver) = unbounded_channel();
+        let pool = WorkerPool::new(self.config.threads, self.nonce_space, self.counters.clone(), solution_sender);
+        let shutdown = SingleTrigger::new();
+        let task = tokio::spawn(self.clone().worker(pool, channel, solution_receiver, shutdown.listener.clone()));
+
+        let raced = {
+            let mut running = self.running.lock();
+            // Another start call may have raced this one
+            let raced = running.is_some();
+            if !raced {
+                running.replace(Running { listener_id, shutdown: shutdown.clone(), task });
+            }
+            raced
+        };
+        if raced {
+            shutdown.trigger.trigger();
+            self.rpc.unregister_listener(listener_id).await.ok();
+            return Err(MinerError::AlreadyRunning);
+        }
+        info!(
+            "CPU miner started with {} thread{} (extranonce {:04x})",
+            self.config.threads,
+            if self.config.threads == 1 { "" } else { "s" },
+            self.nonce_space.extranonce()
+        );
+        Ok(())
+    }
+
+    /// Stops hashing and unsubscribes from the notifications of the node
+    pub async fn stop(&self) -> MinerResult<()> {
+        let Some(running) = self.running.lock().take() else {
+            return Err(MinerError::NotRunning);
+        };
+        running.shutdown.trigger.trigger();
+        running.task.await.ok();
+        self.rpc.unregister_listener(running.listener_id).await?;
+        info!("CPU miner stopped");
+        Ok(())
+    }
+
+    async fn worker(
+        self: Arc<Self>,
+        pool: WorkerPool,
+        notifications: Channel<Notification>,
+        mut solutions: UnboundedReceiver<Solution>,
+        shutdown: Listener,
+    ) {
+        self.refresh_template(&pool).await;
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.clone() => {
+                    break;
+                }
+                Some(solution) = solutions.recv() => {
+                    self.submit(&pool, solution).await;
+                }
+                notification = notifications.recv() => {
+                    match notification {
+                        Ok(Notification::NewBlockTemplate(_)) => {
+                            // Drain the channel, a single fresh template covers all pending notifications
+                            while notifications.try_recv().is_ok() {}
+                            self.refresh_template(&pool).await;
+                        }
+                        Ok(_) => {}
+                        Err(_) => {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        tokio::task::spawn_blocking(move || pool.shutdown()).await.ok();
+    }
+
+    async fn refresh_template(&self, pool: &WorkerPool) {
+        let response = match self.rpc.get_block_template(self.config.pay_address.clone(), self.config.extra_data.clone()).await {
+            Ok(response) => response,
+            Err(err) => {
+                warn!("CPU miner failed to get a block template: {}", err);
+                return;
+            }
+        };
+
+        // Blocks mined while the node is not synced are unlikely to ever be merged, so the workers are paused
+        let was_synced = self.is_synced.swap(response.is_synced, Ordering::Relaxed);
+        if !response.is_synced {
+            if was_synced {
+                warn!("CPU miner is paused until the node is synced");
+            }
+            pool.publish(None);
+            return;
+        }
+        if !was_synced {
+            info!("CPU miner resumed, the node is synced");
+        }
+
+        match Block::try_from(response.block) {
+            Ok(block) => {
+                self.counters.templates_received.fetch_add(1, Ordering::Relaxed);
+                let generation = pool.publish(Some(block));
+                debug!("CPU miner is mining template #{}", generation);
+            }
+            Err(err) => warn!("CPU miner received an invalid block template: {}", err),
+        }
+    }
+
+    async fn submit(&self, pool: &WorkerPool, solution: Solution) {
+        // A block found on a replaced template may still be merged by the DAG, so it is submitted anyway
+        if solution.job.generation() != pool.generation() {
+            self.counters.stale_blocks.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let block = solution.job.to_block(solution.nonce);
+        let hash = block.hash();
+        match self.rpc.submit_block((&block).into(), false).await {
+            Ok(response) => match response.report {
+                SubmitBlockReport::Success => {
+                    self.counters.blocks_accepted.fetch_add(1, Ordering::Relaxed);
+                    info!("CPU miner found block {}", hash);
+                }
+                SubmitBlockReport::Reject(reason) => {
+                    self.counters.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+                    warn!("CPU miner block {} was rejected: {}", hash, reason);
+                }
+            },
+            Err(err) => {
+                self.counters.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+                warn!("CPU miner failed to submit block {}: {}", hash, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Partitioning of the 64 bit nonce space between miner instances and worker threads.
+//!
+//! A nonce is laid out as `extranonce (16 bits) | thread index (8 bits) | counter (40 bits)`, so that
+//! miners mining the same template with the same pay address, as well as the threads of a single miner,
+//! never hash the same nonce.
+
+use std::ops::Range;
+
+/// The amount of bits of the nonce reserved for the extranonce of the miner instance
+pub const EXTRANONCE_BITS: u32 = 16;
+
+/// The amount of bits of the nonce reserved for the index of the worker thread
+pub const THREAD_INDEX_BITS: u32 = 8;
+
+/// The amount of bits of the nonce iterated by a single worker thread
+pub const COUNTER_BITS: u32 = u64::BITS - EXTRANONCE_BITS - THREAD_INDEX_BITS;
+
+/// The maximum amount of worker threads a single miner can run
+pub const MAX_THREADS: usize = 1 << THREAD_INDEX_BITS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceSpace {
+    extranonce: u16,
+}
+
+impl NonceSpace {
+    pub fn new(extranonce: u16) -> Self {
+        Self { extranonce }
+    }
+
+    pub fn extranonce(&self) -> u16 {
+        self.extranonce
+    }
+
+    /// Returns the range of nonces iterated by the worker thread of the given index
+    pub fn range(&self, thread_index: usize) -> Range<u64> {
+        assert!(thread_index < MAX_THREADS, "thread index {thread_index} exceeds the nonce space");
+        let start = ((self.extranonce as u64) << (THREAD_INDEX_BITS + COUNTER_BITS)) | ((thread_index as u64) << COUNTER_BITS);
+        // The last nonce of the range is left out so that the end never overflows
+        start..start + ((1 << COUNTER_BITS) - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_space_ranges() {
+        let space = NonceSpace::new(0xabcd);
+        let first = space.range(0);
+        assert_eq!(first.start, 0xabcd_0000_0000_0000);
+        assert_eq!(first.end, 0xabcd_00ff_ffff_ffff);
+
+        let last = space.range(MAX_THREADS - 1);
+        assert_eq!(last.start, 0xabcd_ff00_0000_0000);
+        assert_eq!(last.end, 0xabcd_ffff_ffff_ffff);
+
+        // Ranges of distinct threads and of distinct extranonces never overlap
+        let ranges: Vec<_> = (0..4).map(|i| space.range(i)).chain((0..4).map(|i| NonceSpace::new(0xabce).range(i))).collect();
+        for (i, a) in ranges.iter().enumerate() {
+            for b in ranges.iter().skip(i + 1) {
+                assert!(a.end <= b.start || b.end <= a.start, "{a:?} overlaps {b:?}");
+            }
+        }
+
+        let max = NonceSpace::new(u16::MAX).range(MAX_THREADS - 1);
+        assert_eq!(max.end, u64::MAX);
+    }
+}
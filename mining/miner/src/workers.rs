@@ -0,0 +1,209 @@
+use crate::{
+    counters::MinerCounters,
+    job::{Job, Solution},
+    nonce::NonceSpace,
+};
+use parking_lot::RwLock;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tokio::sync::mpsc::UnboundedSender;
+use vecno_consensus_core::block::Block;
+use vecno_pow::State;
+
+/// The amount of nonces hashed by a worker between two checks for a newer job
+const HASH_BATCH_SIZE: u64 = 1024;
+
+/// The time an idle worker waits before checking again for a job
+const IDLE_INTERVAL: Duration = Duration::from_millis(50);
+
+struct Shared {
+    job: RwLock<Option<Arc<Job>>>,
+    generation: AtomicU64,
+    shutdown: AtomicBool,
+    nonce_space: NonceSpace,
+    counters: Arc<MinerCounters>,
+    solutions: UnboundedSender<Solution>,
+}
+
+/// A pool of hashing threads, each iterating its own part of the nonce space of the latest published job.
+/// A worker which solved the current job idles until a newer job is published.
+pub struct WorkerPool {
+    shared: Arc<Shared>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub fn new(threads: usize, nonce_space: NonceSpace, counters: Arc<MinerCounters>, solutions: UnboundedSender<Solution>) -> Self {
+        let shared = Arc::new(Shared {
+            job: RwLock::new(None),
+            generation: AtomicU64::new(0),
+            shutdown: AtomicBool::new(false),
+            nonce_space,
+            counters,
+            solutions,
+        });
+        let handles = (0..threads)
+            .map(|index| {
+                let shared = shared.clone();
+                thread::Builder::new()
+                    .name(format!("miner-worker-{index}"))
+                    .spawn(move || worker(index, shared))
+                    .expect("failed to spawn a miner worker thread")
+            })
+            .collect();
+        Self { shared, handles }
+    }
+
+    /// Publishes a new block template to the workers, or pauses them if `None` is provided.
+    /// Returns the generation of the published job.
+    pub fn publish(&self, block: Option<Block>) -> u64 {
+        let mut job = self.shared.job.write();
+        let generation = self.shared.generation.load(Ordering::Relaxed) + 1;
+        *job = block.map(|block| Arc::new(Job::new(generation, block)));
+        self.shared.generation.store(generation, Ordering::Release);
+        generation
+    }
+
+    /// The generation of the latest published job
+    pub fn generation(&self) -> u64 {
+        self.shared.generation.load(Ordering::Acquire)
+    }
+
+    /// Signals all workers to exit and waits for them to do so
+    pub fn shutdown(self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        for handle in self.handles {
+            handle.join().expect("a miner worker thread panicked");
+        }
+    }
+}
+
+fn worker(index: usize, shared: Arc<Shared>) {
+    let range = shared.nonce_space.range(index);
+    let mut current: Option<(Arc<Job>, State)> = None;
+    let mut next_nonce = range.start;
+
+    while !shared.shutdown.load(Ordering::Relaxed) {
+        let generation = shared.generation.load(Ordering::Acquire);
+        if current.as_ref().map(|(job, _)| job.generation()) != Some(generation) {
+            current = shared.job.read().clone().map(|job| {
+                let state = State::new(job.header());
+                (job, state)
+            });
+            next_nonce = range.start;
+        }
+
+        // Wait for a job if there is none or if this worker exhausted its part of the nonce space
+        let Some((job, state)) = current.as_ref().filter(|_| next_nonce < range.end) else {
+            thread::sleep(IDLE_INTERVAL);
+            continue;
+        };
+
+        let batch_end = range.end.min(next_nonce + HASH_BATCH_SIZE);
+        let mut solved_at = None;
+        for nonce in next_nonce..batch_end {
+            if state.check_pow(nonce).0 {
+                solved_at = Some(nonce);
+                break;
+            }
+        }
+        let hashed_until = solved_at.map_or(batch_end, |nonce| nonce + 1);
+        shared.counters.hashes.fetch_add(hashed_until - next_nonce, Ordering::Relaxed);
+        next_nonce = hashed_until;
+
+        if let Some(nonce) = solved_at {
+            shared.counters.blocks_found.fetch_add(1, Ordering::Relaxed);
+            // The receiver is dropped only once the miner is shutting down
+            let _ = shared.solutions.send(Solution { job: job.clone(), nonce });
+            // The solved template is about to be replaced, so the worker idles until the next one is published
+            next_nonce = range.end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc::unbounded_channel;
+    use vecno_consensus_core::header::Header;
+    use vecno_hashes::Hash;
+
+    fn block(bits: u32, daa_score: u64) -> Block {
+        let header = Header::new_finalized(
+            1,
+            vec![vec![Hash::from_u64_word(daa_score)]],
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            1_700_000_000_000 + daa_score,
+            bits,
+            0,
+            daa_score,
+            Default::default(),
+            daa_score,
+            Default::default(),
+        );
+        Block::from_header(header)
+    }
+
+    #[test]
+    fn test_worker_pool() {
+        const EASY_BITS: u32 = 0x207fffff;
+        let threads = 3;
+        let nonce_space = NonceSpace::new(0x1234);
+        let counters = Arc::new(MinerCounters::default());
+        let (sender, mut receiver) = unbounded_channel();
+        let pool = WorkerPool::new(threads, nonce_space, counters.clone(), sender);
+
+        // No job is published yet so no work is done
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(counters.snapshot().hashes, 0);
+
+        let first_generation = pool.publish(Some(block(EASY_BITS, 1)));
+        let solution = receiver.blocking_recv().unwrap();
+        assert_eq!(solution.job.generation(), first_generation);
+        assert!(State::new(solution.job.header()).check_pow(solution.nonce).0);
+        assert!((0..threads).any(|index| nonce_space.range(index).contains(&solution.nonce)));
+
+        // The mined block carries the nonce and its hash reflects it
+        let mined = solution.job.to_block(solution.nonce);
+        assert_eq!(mined.header.nonce, solution.nonce);
+        assert_eq!(mined.hash(), {
+            let mut header = solution.job.header().clone();
+            header.nonce = solution.nonce;
+            header.finalize();
+            header.hash
+        });
+
+        // Every worker solves the template at most once
+        thread::sleep(Duration::from_millis(100));
+        let mut first_generation_solutions = 1;
+        while receiver.try_recv().is_ok() {
+            first_generation_solutions += 1;
+        }
+        assert!(first_generation_solutions <= threads);
+
+        // Once a new template is published, solutions of the new generation show up
+        let second_generation = pool.publish(Some(block(EASY_BITS, 2)));
+        assert!(second_generation > first_generation);
+        let solution = receiver.blocking_recv().unwrap();
+        assert_eq!(solution.job.generation(), second_generation);
+        assert_eq!(solution.job.header().daa_score, 2);
+
+        // Pausing the workers stops the hashing
+        pool.publish(None);
+        thread::sleep(Duration::from_millis(100));
+        let hashes = counters.snapshot().hashes;
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(counters.snapshot().hashes, hashes);
+        assert!(counters.snapshot().blocks_found >= 2);
+
+        pool.shutdown();
+    }
+}
@@ -213,14 +213,14 @@ impl RebalancingWeightedTransactionSelector {
     /// included in the block.
     fn calc_tx_value(&self, transaction: &CandidateTransaction) -> f64 {
         let mass_limit = self.policy.max_block_mass as f64;
-        let mass = transaction.calculated_mass as f64;
-        let fee = transaction.calculated_fee as f64;
+        // Transactions are valued by their package feerate so that a high-fee child can bump its parent
+        let feerate = transaction.effective_feerate();
         if transaction.tx.subnetwork_id.is_builtin_or_native() {
-            fee / mass / mass_limit
+            feerate / mass_limit
         } else {
             // TODO: Replace with real gas once implemented
             let gas_limit = u64::MAX as f64;
-            fee / mass / mass_limit + transaction.tx.gas as f64 / gas_limit
+            feerate / mass_limit + transaction.tx.gas as f64 / gas_limit
         }
     }
 }
@@ -275,6 +275,7 @@ mod tests {
             model::frontier::selectors::{SequenceSelector, SequenceSelectorInput, SequenceSelectorTransaction},
         },
         model::candidate_tx::CandidateTransaction,
+        DescendantPackage,
     };
 
     #[test]
@@ -334,6 +335,8 @@ mod tests {
         let calculated_mass = transaction_estimated_serialized_size(&tx);
         let calculated_fee = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
 
-        CandidateTransaction { tx, calculated_fee, calculated_mass }
+        let package = DescendantPackage::new(calculated_fee, calculated_mass);
+
+        CandidateTransaction { tx, calculated_fee, calculated_mass, package }
     }
 }
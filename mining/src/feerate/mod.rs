@@ -77,7 +77,8 @@ impl FeerateEstimatorArgs {
 
 #[derive(Debug, Clone)]
 pub struct FeerateEstimator {
-    /// The total probability weight of current mempool ready transactions, i.e., `Σ_{tx in mempool}(tx.fee/tx.mass)^alpha`.
+    /// The total probability weight of current mempool ready transactions, i.e., `Σ_{tx in mempool}(tx.fee/tx.mass)^alpha`,
+    /// where the feerate of a ready transaction is the one of the package it forms with its descendants, if higher.
     /// Note that some estimators might consider a reduced weight which excludes outliers. See [`Frontier::build_feerate_estimator`]
     total_weight: f64,
 
@@ -3,7 +3,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use mempool::tx::Priority;
+use mempool::{errors::PolicyReasonCode, tx::Priority};
 
 mod block_template;
 pub(crate) mod cache;
@@ -14,10 +14,16 @@ mod manager_tests;
 pub mod mempool;
 pub mod model;
 pub mod monitor;
+pub mod persistence;
+pub mod stats;
 
 // Exposed for benchmarks
 pub use block_template::{policy::Policy, selector::RebalancingWeightedTransactionSelector};
-pub use mempool::model::frontier::{feerate_key::FeerateTransactionKey, search_tree::SearchTree, Frontier};
+pub use mempool::model::frontier::{
+    feerate_key::{DescendantPackage, FeerateTransactionKey},
+    search_tree::SearchTree,
+    Frontier,
+};
 
 #[cfg(test)]
 pub mod testutils;
@@ -31,8 +37,11 @@ pub struct MiningCounters {
     pub block_tx_counts: AtomicU64,
     pub tx_accepted_counts: AtomicU64,
     pub tx_evicted_counts: AtomicU64,
+    pub tx_replaced_counts: AtomicU64,
     pub input_counts: AtomicU64,
     pub output_counts: AtomicU64,
+    /// Transactions rejected by the mempool policy, indexed by [`PolicyReasonCode`]
+    pub policy_rejected_counts: [AtomicU64; PolicyReasonCode::COUNT],
 
     // Samples
     pub ready_txs_sample: AtomicU64,
@@ -50,8 +59,10 @@ impl Default for MiningCounters {
             block_tx_counts: Default::default(),
             tx_accepted_counts: Default::default(),
             tx_evicted_counts: Default::default(),
+            tx_replaced_counts: Default::default(),
             input_counts: Default::default(),
             output_counts: Default::default(),
+            policy_rejected_counts: Default::default(),
             ready_txs_sample: Default::default(),
             txs_sample: Default::default(),
             orphans_sample: Default::default(),
@@ -69,8 +80,10 @@ impl MiningCounters {
             block_tx_counts: self.block_tx_counts.load(Ordering::Relaxed),
             tx_accepted_counts: self.tx_accepted_counts.load(Ordering::Relaxed),
             tx_evicted_counts: self.tx_evicted_counts.load(Ordering::Relaxed),
+            tx_replaced_counts: self.tx_replaced_counts.load(Ordering::Relaxed),
             input_counts: self.input_counts.load(Ordering::Relaxed),
             output_counts: self.output_counts.load(Ordering::Relaxed),
+            policy_rejected_counts: self.policy_rejected_counts.each_ref().map(|count| count.load(Ordering::Relaxed)),
             ready_txs_sample: self.ready_txs_sample.load(Ordering::Relaxed),
             txs_sample: self.txs_sample.load(Ordering::Relaxed),
             orphans_sample: self.orphans_sample.load(Ordering::Relaxed),
@@ -95,6 +108,10 @@ impl MiningCounters {
             }
         }
     }
+
+    pub fn increase_policy_rejected_counts(&self, code: PolicyReasonCode) {
+        self.policy_rejected_counts[code as usize].fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -105,8 +122,10 @@ pub struct MempoolCountersSnapshot {
     pub block_tx_counts: u64,
     pub tx_accepted_counts: u64,
     pub tx_evicted_counts: u64,
+    pub tx_replaced_counts: u64,
     pub input_counts: u64,
     pub output_counts: u64,
+    pub policy_rejected_counts: [u64; PolicyReasonCode::COUNT],
     pub ready_txs_sample: u64,
     pub txs_sample: u64,
     pub orphans_sample: u64,
@@ -118,6 +137,15 @@ impl MempoolCountersSnapshot {
         self.high_priority_tx_counts + self.low_priority_tx_counts
     }
 
+    /// Returns the number of transactions rejected by the mempool policy for the given reason
+    pub fn policy_rejected_count(&self, code: PolicyReasonCode) -> u64 {
+        self.policy_rejected_counts[code as usize]
+    }
+
+    pub fn policy_rejected_total_count(&self) -> u64 {
+        self.policy_rejected_counts.iter().sum()
+    }
+
     /// Indicates whether this snapshot has any TPS activity which is worth logging
     pub fn has_tps_activity(&self) -> bool {
         self.tx_accepted_counts > 0 || self.block_tx_counts > 0 || self.low_priority_tx_counts > 0 || self.high_priority_tx_counts > 0
@@ -161,8 +189,12 @@ impl core::ops::Sub for &MempoolCountersSnapshot {
             block_tx_counts: self.block_tx_counts.saturating_sub(rhs.block_tx_counts),
             tx_accepted_counts: self.tx_accepted_counts.saturating_sub(rhs.tx_accepted_counts),
             tx_evicted_counts: self.tx_evicted_counts.saturating_sub(rhs.tx_evicted_counts),
+            tx_replaced_counts: self.tx_replaced_counts.saturating_sub(rhs.tx_replaced_counts),
             input_counts: self.input_counts.saturating_sub(rhs.input_counts),
             output_counts: self.output_counts.saturating_sub(rhs.output_counts),
+            policy_rejected_counts: std::array::from_fn(|i| {
+                self.policy_rejected_counts[i].saturating_sub(rhs.policy_rejected_counts[i])
+            }),
             ready_txs_sample: (self.ready_txs_sample + rhs.ready_txs_sample) / 2,
             txs_sample: (self.txs_sample + rhs.txs_sample) / 2,
             orphans_sample: (self.orphans_sample + rhs.orphans_sample) / 2,
@@ -6,22 +6,25 @@ use crate::{
     mempool::{
         config::Config,
         model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        policy::MempoolPolicy,
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority, RbfPolicy},
+        tx::{Orphan, Priority, RbfPolicy, TransactionSource},
         Mempool,
     },
     model::{
+        mempool_dump::MempoolDump,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
-        topological_sort::IntoIterTopologically,
-        tx_insert::TransactionInsertion,
+        topological_sort::{IntoIterTopologically, TopologicalSort},
+        tx_insert::{TransactionInsertion, TransactionPackageInsertion},
         tx_query::TransactionQuery,
     },
+    stats::{MempoolStatsHistory, MempoolStatsSample, MEMPOOL_STATS_HISTORY_SIZE},
     MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
 };
 use itertools::Itertools;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
 use vecno_consensus_core::{
@@ -31,17 +34,27 @@ use vecno_consensus_core::{
     },
     block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
     coinbase::MinerData,
+    config::params::ForkActivation,
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
 use vecno_consensusmanager::{spawn_blocking, ConsensusProxy};
-use vecno_core::{debug, error, info, time::Stopwatch, warn};
-use vecno_mining_errors::{manager::MiningManagerError, mempool::RuleError};
+use vecno_core::{
+    debug, error, info,
+    time::{unix_now, Stopwatch},
+    warn,
+};
+use vecno_mining_errors::{
+    manager::MiningManagerError,
+    mempool::{RuleError, RuleResult},
+};
 
 pub struct MiningManager {
     config: Arc<Config>,
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
+    policy: MempoolPolicy,
+    stats: Mutex<MempoolStatsHistory>,
     counters: Arc<MiningCounters>,
 }
 
@@ -62,11 +75,13 @@ impl MiningManager {
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        mast_activation: ForkActivation,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .apply_mast_activation(mast_activation);
         Self::with_config(config, cache_lifetime, counters)
     }
 
@@ -74,7 +89,14 @@ impl MiningManager {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        let stats = Mutex::new(MempoolStatsHistory::new(MEMPOOL_STATS_HISTORY_SIZE));
+        Self { config, block_template_cache, mempool, policy: MempoolPolicy::default(), stats, counters }
+    }
+
+    /// Sets the mempool policy checked against every incoming transaction
+    pub fn with_policy(mut self, policy: MempoolPolicy) -> Self {
+        self.policy = policy;
+        self
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -299,6 +321,7 @@ impl MiningManager {
         orphan: Orphan,
         rbf_policy: RbfPolicy,
     ) -> MiningManagerResult<TransactionInsertion> {
+        self.check_policy(&transaction, TransactionSource::Local)?;
         // read lock on mempool
         let TransactionPreValidation { mut transaction, feerate_threshold } =
             self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
@@ -408,6 +431,7 @@ impl MiningManager {
         priority: Priority,
         orphan: Orphan,
         rbf_policy: RbfPolicy,
+        source: TransactionSource,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         const TRANSACTION_CHUNK_SIZE: usize = 250;
 
@@ -426,7 +450,10 @@ impl MiningManager {
             let mempool = self.mempool.read();
             let txs = chunk.filter_map(|tx| {
                 let transaction_id = tx.id();
-                match mempool.pre_validate_and_populate_transaction(consensus, tx, rbf_policy) {
+                match self
+                    .check_policy(&tx, source)
+                    .and_then(|_| mempool.pre_validate_and_populate_transaction(consensus, tx, rbf_policy))
+                {
                     Ok(TransactionPreValidation { transaction, feerate_threshold }) => {
                         if let Some(threshold) = feerate_threshold {
                             args.set_feerate_threshold(transaction.id(), threshold);
@@ -508,6 +535,60 @@ impl MiningManager {
         insert_results
     }
 
+    /// Validates a package of dependent transactions, sorted topologically, and adds them to the set of known
+    /// transactions that have not yet been added to any block, all or none of them.
+    ///
+    /// The fee of the package is considered as a whole, allowing a transaction with an insufficient fee to
+    /// be accepted along with descendants paying for it. The transactions of a package may neither be orphans
+    /// nor replace mempool transactions by fee.
+    ///
+    /// On success, returns the accepted transactions, in package order, followed by the transactions
+    /// that where unorphaned following their insertion, along with whether the package requires package relay.
+    pub fn validate_and_insert_transaction_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+        source: TransactionSource,
+    ) -> MiningManagerResult<TransactionPackageInsertion> {
+        let transactions = transactions.into_iter().map(MutableTransaction::from_tx).collect::<Vec<_>>();
+        for transaction in transactions.iter() {
+            self.check_policy(transaction, source)
+                .map_err(|err| RuleError::RejectPackageTransaction(transaction.id(), Box::new(err)))?;
+        }
+        // read lock on mempool
+        let mut transactions = self.mempool.read().pre_validate_and_populate_package(consensus, transactions)?;
+        // no lock on mempool
+        // The transactions are validated in package order since each may spend the outputs of the preceding ones
+        let args = TransactionValidationArgs::default();
+        for transaction in transactions.iter_mut() {
+            let transaction_id = transaction.id();
+            validate_mempool_transaction(consensus, transaction, &args)
+                .map_err(|err| RuleError::RejectPackageTransaction(transaction_id, Box::new(err)))?;
+        }
+        // write lock on mempool
+        let mut mempool = self.mempool.write();
+        let requires_package_relay = mempool.requires_package_relay(&transactions);
+        let mut accepted_transactions = mempool.post_validate_and_insert_package(consensus, transactions, priority)?;
+        let unorphaned_transactions = accepted_transactions
+            .iter()
+            .flat_map(|transaction| mempool.get_unorphaned_transactions_after_accepted_transaction(transaction))
+            .collect::<Vec<_>>();
+        drop(mempool);
+
+        self.counters.increase_tx_counts(accepted_transactions.len() as u64, priority);
+        accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
+        Ok(TransactionPackageInsertion::new(accepted_transactions, requires_package_relay))
+    }
+
+    /// Checks the transaction against the mempool policy, counting the rejections by reason
+    fn check_policy(&self, transaction: &MutableTransaction, source: TransactionSource) -> RuleResult<()> {
+        self.policy.check(transaction, source).map_err(|reason| {
+            self.counters.increase_policy_rejected_counts(reason.code());
+            RuleError::RejectPolicy(transaction.id(), reason)
+        })
+    }
+
     fn next_transaction_chunk_upper_bound(&self, transactions: &[MutableTransaction], lower_bound: usize) -> Option<usize> {
         if lower_bound >= transactions.len() {
             return None;
@@ -809,6 +890,67 @@ impl MiningManager {
         }
     }
 
+    /// Returns a snapshot of the mempool, including its orphans and the cache of accepted transactions,
+    /// which can be persisted and restored by [`Self::restore_mempool`] once the node restarts.
+    pub fn dump_mempool(&self) -> MempoolDump {
+        self.mempool.read().dump()
+    }
+
+    /// Restores a mempool snapshot taken by [`Self::dump_mempool`].
+    ///
+    /// Entries which would have expired by now are dropped. Every other transaction is revalidated
+    /// against the current virtual UTXO set, exactly like a newly submitted one, and keeps the DAA
+    /// score at which it originally entered the mempool.
+    ///
+    /// Returns the number of restored transactions.
+    pub fn restore_mempool(&self, consensus: &dyn ConsensusApi, mut dump: MempoolDump) -> usize {
+        let expired_count = dump.drop_expired(&self.config, consensus.get_virtual_daa_score());
+        if expired_count > 0 {
+            debug!("Dropped {} expired entries of the mempool dump", expired_count);
+        }
+        self.mempool.write().restore_accepted_transactions(dump.accepted_transactions);
+
+        let mut restored_count = 0;
+        for entry in dump.transactions.topological_sort() {
+            let transaction_id = entry.transaction.id();
+            match self.validate_and_insert_transaction(
+                consensus,
+                entry.transaction,
+                entry.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            ) {
+                Ok(_) => {
+                    self.mempool.write().restore_added_at_daa_score(&transaction_id, entry.added_at_daa_score);
+                    restored_count += 1;
+                }
+                Err(err) => debug!("Dropped transaction {} of the mempool dump: {}", transaction_id, err),
+            }
+        }
+        restored_count
+    }
+
+    /// Samples the mempool statistics and records the sample in the statistics history.
+    ///
+    /// Returns the recorded sample.
+    pub fn sample_mempool_stats(&self) -> MempoolStatsSample {
+        let sample = self.mempool.read().stats_sample(unix_now());
+        let counters = self.counters.snapshot();
+        self.stats.lock().record(
+            sample,
+            counters.tx_evicted_counts,
+            counters.tx_replaced_counts,
+            counters.policy_rejected_total_count(),
+        )
+    }
+
+    /// Returns the history of the mempool statistics, from the oldest to the most recent sample.
+    ///
+    /// If `limit` is provided, only the `limit` most recent samples are returned.
+    pub fn get_mempool_stats(&self, limit: Option<usize>) -> Vec<MempoolStatsSample> {
+        self.stats.lock().samples(limit)
+    }
+
     /// is_transaction_output_dust returns whether or not the passed transaction output
     /// amount is considered dust or not based on the configured minimum transaction
     /// relay fee.
@@ -836,6 +978,11 @@ impl MiningManager {
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.mempool.read().get_estimated_size()
     }
+
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_package(&self, transaction_id: &TransactionId) -> Option<crate::DescendantPackage> {
+        self.mempool.read().get_ready_transaction_package(transaction_id)
+    }
 }
 
 /// Async proxy for the mining manager
@@ -903,10 +1050,31 @@ impl MiningManagerProxy {
         priority: Priority,
         orphan: Orphan,
         rbf_policy: RbfPolicy,
+        source: TransactionSource,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         consensus
             .clone()
-            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan, rbf_policy))
+            .spawn_blocking(move |c| {
+                self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan, rbf_policy, source)
+            })
+            .await
+    }
+
+    /// Validates a package of dependent transactions, sorted topologically, and adds them to the set of known
+    /// transactions that have not yet been added to any block, all or none of them.
+    ///
+    /// The fee of the package is considered as a whole. For more information, see
+    /// [`MiningManager::validate_and_insert_transaction_package`].
+    pub async fn validate_and_insert_transaction_package(
+        self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+        source: TransactionSource,
+    ) -> MiningManagerResult<TransactionPackageInsertion> {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_package(c, transactions, priority, source))
             .await
     }
 
@@ -990,6 +1158,26 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    /// Returns a snapshot of the mempool, see [`MiningManager::dump_mempool`].
+    pub async fn dump_mempool(self) -> MempoolDump {
+        spawn_blocking(move || self.inner.dump_mempool()).await.unwrap()
+    }
+
+    /// Restores a mempool snapshot, see [`MiningManager::restore_mempool`].
+    pub async fn restore_mempool(self, consensus: &ConsensusProxy, dump: MempoolDump) -> usize {
+        consensus.clone().spawn_blocking(move |c| self.inner.restore_mempool(c, dump)).await
+    }
+
+    /// Samples the mempool statistics, see [`MiningManager::sample_mempool_stats`].
+    pub async fn sample_mempool_stats(self) -> MempoolStatsSample {
+        spawn_blocking(move || self.inner.sample_mempool_stats()).await.unwrap()
+    }
+
+    /// Returns the history of the mempool statistics, see [`MiningManager::get_mempool_stats`].
+    pub fn get_mempool_stats(&self, limit: Option<usize>) -> Vec<MempoolStatsSample> {
+        self.inner.get_mempool_stats(limit)
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
@@ -6,13 +6,18 @@ mod tests {
         manager::MiningManager,
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
-            errors::RuleError,
+            errors::{PolicyReasonCode, PolicyRejectReason, RuleError},
             model::frontier::selectors::TakeAllSelector,
-            tx::{Orphan, Priority, RbfPolicy},
+            policy::{
+                rules::{DustRule, PeerRateLimitRule},
+                MempoolPolicy,
+            },
+            tx::{Orphan, Priority, RbfPolicy, TransactionSource},
         },
         model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        stats::{MempoolStatsSample, MEMPOOL_STATS_FEERATE_BUCKETS},
         testutils::consensus_mock::ConsensusMock,
-        MiningCounters,
+        DescendantPackage, MiningCounters,
     };
     use itertools::Itertools;
     use std::{iter::once, sync::Arc};
@@ -1065,6 +1070,62 @@ mod tests {
         assert!(orphan_txs.is_empty(), "orphan pool should be empty");
     }
 
+    /// test_restore_mempool verifies that a mempool dump is restored with its transactions revalidated.
+    #[test]
+    fn test_restore_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        // Fill the mempool with chained transactions and an orphan
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 3);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            parent_txs.iter().chain(child_txs.iter()),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        let orphan_tx = create_transaction(&create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]), 1000);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&orphan_tx),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+
+        // Simulate: Mine a block accepting the first parent
+        consensus.add_transaction(parent_txs[0].clone(), 2);
+        let result =
+            mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&parent_txs[0])));
+        assert!(result.is_ok(), "the handling by the mempool of the transactions of a block should succeed");
+
+        let dump = mining_manager.dump_mempool();
+        assert_eq!(6, dump.transactions.len(), "the mempool dump should contain all the mempool transactions and the orphan");
+        assert_eq!(1, dump.accepted_transactions.len(), "the mempool dump should contain the accepted transaction");
+
+        // Simulate: The last child transaction became invalid while the node was down
+        consensus.set_status(child_txs[2].id(), Err(TxRuleError::TxHasGas));
+
+        let counters = Arc::new(MiningCounters::default());
+        let restored_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let restored_count = restored_manager.restore_mempool(consensus.as_ref(), dump);
+        assert_eq!(5, restored_count, "all the valid transactions of the mempool dump should be restored");
+        assert_transaction_count(&restored_manager, 4, "after restoring the mempool dump,");
+        assert!(
+            restored_manager.get_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly).is_some(),
+            "the orphan transaction should be restored in the orphan pool"
+        );
+        assert!(
+            restored_manager.get_transaction(&child_txs[2].id(), TransactionQuery::All).is_none(),
+            "the invalid transaction should not be restored"
+        );
+        assert!(restored_manager.has_accepted_transaction(&parent_txs[0].id()), "the accepted transaction cache should be restored");
+    }
+
     /// test_modify_block_template verifies that modifying a block template changes coinbase data correctly.
     #[test]
     fn test_modify_block_template() {
@@ -1174,6 +1235,497 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    /// test_package_feerate verifies that ready transactions are prioritized by the feerate of the package they
+    /// form with their mempool descendants, and that the packages follow the changes of the mempool.
+    #[test]
+    fn test_package_feerate() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        // A low-fee parent with a high-fee child and a low-fee grandchild
+        let funding_tx = create_and_add_funding_transactions(&consensus, 1).remove(0);
+        let parent_tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let grandchild_tx = create_transaction(&child_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            [&parent_tx, &child_tx, &grandchild_tx].into_iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let package_of = |transactions: &[&Transaction]| {
+            transactions.iter().fold(DescendantPackage::default(), |mut package, transaction| {
+                let mtx = mining_manager.get_transaction(&transaction.id(), TransactionQuery::TransactionsOnly).unwrap();
+                package.add(mtx.calculated_fee.unwrap(), mtx.tx.mass());
+                package
+            })
+        };
+        let parent_package = mining_manager.get_ready_transaction_package(&parent_tx.id());
+        assert_eq!(
+            Some(package_of(&[&parent_tx, &child_tx, &grandchild_tx])),
+            parent_package,
+            "the package of the ready parent should include all its descendants"
+        );
+        assert!(
+            parent_package.unwrap().feerate() > package_of(&[&parent_tx]).feerate(),
+            "the high-fee child should bump the feerate of the parent"
+        );
+        assert!(mining_manager.get_ready_transaction_package(&child_tx.id()).is_none(), "the child should not be ready");
+
+        // Replace the grandchild by a transaction paying a higher fee
+        let replacement_tx = create_transaction(&child_tx, 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&replacement_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Allowed,
+        );
+        assert_eq!(
+            Some(package_of(&[&parent_tx, &child_tx, &replacement_tx])),
+            mining_manager.get_ready_transaction_package(&parent_tx.id()),
+            "the package of the parent should follow the replacement of its descendants"
+        );
+
+        // Simulate: Mine a block accepting the parent
+        let child_package = package_of(&[&child_tx, &replacement_tx]);
+        consensus.add_transaction(parent_tx.clone(), 2);
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&parent_tx)));
+        assert!(result.is_ok(), "the handling by the mempool of the transactions of a block should succeed");
+        assert!(mining_manager.get_ready_transaction_package(&parent_tx.id()).is_none(), "the accepted parent should be removed");
+        assert_eq!(
+            Some(child_package),
+            mining_manager.get_ready_transaction_package(&child_tx.id()),
+            "the child should become ready along with its own package"
+        );
+    }
+
+    /// test_package_eviction verifies that a low-fee parent bumped by a high-fee child is evicted after
+    /// transactions having a lower feerate than their package.
+    #[test]
+    fn test_package_eviction() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_transaction_count = 3;
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let funding_txs = create_and_add_funding_transactions(&consensus, 4);
+        let standalone_tx = create_transaction(&funding_txs[0], 3 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let parent_tx = create_transaction(&funding_txs[1], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, 50 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            [&standalone_tx, &parent_tx, &child_tx].into_iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        // The parent has the lowest feerate of the mempool but its package has the highest one,
+        // so the standalone transaction gets evicted instead
+        let pending_tx = create_transaction(&funding_txs[2], 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&pending_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(
+            !mining_manager.has_transaction(&standalone_tx.id(), TransactionQuery::All),
+            "the standalone transaction should have been evicted"
+        );
+        for transaction in [&parent_tx, &child_tx, &pending_tx] {
+            assert!(
+                mining_manager.has_transaction(&transaction.id(), TransactionQuery::TransactionsOnly),
+                "the transaction {} should remain in the mempool",
+                transaction.id()
+            );
+        }
+
+        // A transaction with a lower feerate than any ready package cannot enter the full mempool
+        let low_fee_tx = create_transaction(&funding_txs[3], 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            low_fee_tx,
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert_eq!(Err(RuleError::RejectMempoolIsFull), result, "the low-fee transaction should be rejected");
+        assert_transaction_count(&mining_manager, 3, "after rejecting the low-fee transaction,");
+    }
+
+    /// test_package_eviction_is_all_or_nothing verifies that a package which does not fit in a full mempool
+    /// as a whole is rejected without evicting any transaction.
+    #[test]
+    fn test_package_eviction_is_all_or_nothing() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_transaction_count = 3;
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        let funding_txs = create_and_add_funding_transactions(&consensus, 4);
+        let mempool_txs = [1, 5, 50]
+            .into_iter()
+            .zip(funding_txs.iter())
+            .map(|(fee_factor, funding_tx)| create_transaction(funding_tx, fee_factor * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE))
+            .collect_vec();
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            mempool_txs.iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        // The package needs two slots, but only the first mempool transaction has a lower feerate than the package
+        let parent_tx = create_transaction(&funding_txs[3], 0);
+        let child_tx = create_transaction(&parent_tx, 6 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone()],
+            Priority::Low,
+            TransactionSource::Local,
+        ));
+        assert_eq!(Err(RuleError::RejectMempoolIsFull), result, "the package should not fit in the mempool");
+        for transaction in mempool_txs.iter() {
+            assert!(
+                mining_manager.has_transaction(&transaction.id(), TransactionQuery::TransactionsOnly),
+                "the transaction {} should not have been evicted",
+                transaction.id()
+            );
+        }
+        assert_transaction_count(&mining_manager, 3, "after rejecting the package,");
+    }
+
+    /// test_chain_limits verifies that a transaction is rejected if it has too many ancestors in the mempool
+    /// or if it gives one of its ancestors too many descendants.
+    #[test]
+    fn test_chain_limits() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_ancestor_count = 2;
+        config.maximum_descendant_count = 3;
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        // A root with two children, the first one having a child too
+        let funding_tx = create_and_add_funding_transactions(&consensus, 1).remove(0);
+        let root_tx = create_transaction_with_change(once(&funding_tx), vec![0], Some(500 * SOMPI_PER_VECNO), 1_000);
+        let children_txs = create_children_tree(&root_tx, 1);
+        let grandchild_tx = create_transaction(&children_txs[0], 1_000);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&root_tx).chain(children_txs.iter()).chain(once(&grandchild_tx)),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let great_grandchild_tx = create_transaction(&grandchild_tx, 1_000);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            great_grandchild_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert_eq!(
+            Err(RuleError::RejectTooManyAncestors(great_grandchild_tx.id(), 2)),
+            result,
+            "a transaction with too many ancestors should be rejected"
+        );
+
+        let second_grandchild_tx = create_transaction(&children_txs[1], 1_000);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            second_grandchild_tx,
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert_eq!(
+            Err(RuleError::RejectTooManyDescendants(root_tx.id(), 3)),
+            result,
+            "a transaction giving its root too many descendants should be rejected"
+        );
+        assert_transaction_count(&mining_manager, 4, "after rejecting the transactions exceeding the limits,");
+
+        // The mass of the ancestors is limited as well
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_ancestor_mass = 1;
+        let mining_manager = MiningManager::with_config(config, None, counters);
+        let funding_tx = create_and_add_funding_transactions(&consensus, 1).remove(0);
+        let parent_tx = create_transaction(&funding_tx, 1_000);
+        let child_tx = create_transaction(&parent_tx, 1_000);
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&parent_tx),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            child_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert!(
+            matches!(result, Err(RuleError::RejectAncestorMassTooHigh(id, _, 1)) if id == child_tx.id()),
+            "a transaction with too heavy ancestors should be rejected, got {result:?}"
+        );
+    }
+
+    /// test_transaction_package verifies that a package is accepted or rejected as a whole and that its fee
+    /// is considered as a whole.
+    #[test]
+    fn test_transaction_package() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let funding_txs = create_and_add_funding_transactions(&consensus, 4);
+
+        // A parent paying no fee is rejected alone but accepted along with a child paying for both
+        let parent_tx = create_transaction(&funding_txs[0], 0);
+        let child_tx = create_transaction(&parent_tx, 3 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_tx.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert!(matches!(result, Err(RuleError::RejectNonStandard(..))), "the parent alone should not pay enough fee");
+        let insertion = mining_manager
+            .validate_and_insert_transaction_package(
+                consensus.as_ref(),
+                vec![parent_tx.clone(), child_tx.clone()],
+                Priority::High,
+                TransactionSource::Local,
+            )
+            .unwrap();
+        assert_eq!(
+            insertion.accepted.iter().map(|tx| tx.id()).collect_vec(),
+            vec![parent_tx.id(), child_tx.id()],
+            "the package should be accepted in order"
+        );
+        assert!(insertion.requires_package_relay, "the parent should only be relayed along with its package");
+        assert_transaction_count(&mining_manager, 2, "after accepting the package,");
+
+        // A package whose transactions all pay enough fee on their own does not require package relay
+        let parent_tx = create_transaction(&funding_txs[2], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let insertion = mining_manager
+            .validate_and_insert_transaction_package(
+                consensus.as_ref(),
+                vec![parent_tx.clone(), child_tx.clone()],
+                Priority::High,
+                TransactionSource::Local,
+            )
+            .unwrap();
+        assert_eq!(insertion.accepted.len(), 2, "the package should be accepted");
+        assert!(!insertion.requires_package_relay, "the package transactions should be relayed on their own");
+        assert_transaction_count(&mining_manager, 4, "after accepting the packages,");
+
+        // Malformed packages
+        let parent_tx = create_transaction(&funding_txs[1], 0);
+        let child_tx = create_transaction(&parent_tx, 3 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let double_spend_tx = create_transaction(&funding_txs[1], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let tests = [
+            (vec![], RuleError::RejectEmptyPackage),
+            (vec![child_tx.clone(), parent_tx.clone()], RuleError::RejectPackageNotSorted(child_tx.id(), parent_tx.id())),
+            (vec![parent_tx.clone(), parent_tx.clone()], RuleError::RejectPackageDuplicate(parent_tx.id())),
+            (
+                vec![parent_tx.clone(), double_spend_tx.clone()],
+                RuleError::RejectPackageDoubleSpend(parent_tx.inputs[0].previous_outpoint, double_spend_tx.id()),
+            ),
+        ];
+        for (package, expected) in tests {
+            let result = into_mempool_result(mining_manager.validate_and_insert_transaction_package(
+                consensus.as_ref(),
+                package,
+                Priority::High,
+                TransactionSource::Local,
+            ));
+            assert_eq!(Err(expected), result, "the malformed package should be rejected");
+        }
+
+        // A package not paying enough fee as a whole is rejected
+        let underpaying_child_tx = create_transaction(&parent_tx, 0);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), underpaying_child_tx],
+            Priority::High,
+            TransactionSource::Local,
+        ));
+        assert!(matches!(result, Err(RuleError::RejectPackageInsufficientFee(0, _))), "the underpaying package should be rejected");
+
+        // A package whose child spends a nonexistent output of its parent is rejected
+        let mut out_of_range_child_tx = child_tx.clone();
+        out_of_range_child_tx.inputs[0].previous_outpoint.index = parent_tx.outputs.len() as u32;
+        out_of_range_child_tx.finalize();
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), out_of_range_child_tx.clone()],
+            Priority::High,
+            TransactionSource::Local,
+        ));
+        assert_eq!(
+            Err(RuleError::RejectPackageTransaction(out_of_range_child_tx.id(), Box::new(RuleError::RejectMissingOutpoint))),
+            result,
+            "the package spending an out-of-range output should be rejected"
+        );
+
+        // A package with an invalid transaction is rejected as a whole
+        consensus.set_status(child_tx.id(), Err(TxRuleError::TxHasGas));
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![parent_tx.clone(), child_tx.clone()],
+            Priority::High,
+            TransactionSource::Local,
+        ));
+        assert!(
+            matches!(result, Err(RuleError::RejectPackageTransaction(id, _)) if id == child_tx.id()),
+            "the package with an invalid transaction should be rejected"
+        );
+        assert!(!mining_manager.has_transaction(&parent_tx.id(), TransactionQuery::All), "the valid parent should not be inserted");
+        assert_transaction_count(&mining_manager, 4, "after rejecting the packages,");
+    }
+
+    /// test_mempool_policy verifies that the transactions breaking a rule of the mempool policy are rejected
+    /// and counted by reason.
+    #[test]
+    fn test_mempool_policy() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let dust_threshold = 1_500 * SOMPI_PER_VECNO;
+        let policy = MempoolPolicy::new().with_rule(DustRule::new(dust_threshold)).with_rule(PeerRateLimitRule::new(1, 1));
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters.clone()).with_policy(policy);
+        let funding_txs = create_and_add_funding_transactions(&consensus, 3);
+
+        // A local transaction paying an output below the dust threshold of the policy
+        let dust_tx = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let result = into_mempool_result(mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            dust_tx.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        ));
+        assert_eq!(
+            Err(RuleError::RejectPolicy(dust_tx.id(), PolicyRejectReason::DustOutput(0, dust_tx.outputs[0].value, dust_threshold))),
+            result,
+            "the dust transaction should be rejected"
+        );
+
+        // A peer relaying transactions above its rate limit
+        let transactions = funding_txs[1..]
+            .iter()
+            .map(|tx| create_transaction_with_change(once(tx), vec![1], None, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE))
+            .collect_vec();
+        let peer = TransactionSource::Peer([1, 2, 3, 4].into());
+        let results = mining_manager.validate_and_insert_transaction_batch(
+            consensus.as_ref(),
+            transactions,
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+            peer,
+        );
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1, "the first transaction should be accepted");
+        assert!(
+            results.iter().any(|result| matches!(
+                result,
+                Err(MiningManagerError::MempoolError(RuleError::RejectPolicy(_, PolicyRejectReason::PeerRateLimited(1))))
+            )),
+            "the second transaction should be rate limited"
+        );
+        assert_transaction_count(&mining_manager, 1, "after applying the policy,");
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.policy_rejected_count(PolicyReasonCode::Dust), 1);
+        assert_eq!(snapshot.policy_rejected_count(PolicyReasonCode::PeerRateLimit), 1);
+        assert_eq!(snapshot.policy_rejected_total_count(), 2);
+
+        // The rejections are reported by the mempool statistics, counted since the previous sample
+        assert_eq!(mining_manager.sample_mempool_stats().policy_rejected_count, 2);
+        assert_eq!(mining_manager.sample_mempool_stats().policy_rejected_count, 0);
+    }
+
+    // test_mempool_stats verifies that the mempool statistics samples reflect the state of the mempool,
+    // including the transactions replaced by fee between two samples.
+    #[test]
+    fn test_mempool_stats() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+        let funding_txs = create_and_add_funding_transactions(&consensus, 1);
+
+        let transaction = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let orphan = create_transaction(&create_transaction_without_input(vec![500 * SOMPI_PER_VECNO]), 1000);
+        for tx in [transaction.clone(), orphan] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx,
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept a valid transaction or orphan");
+        }
+
+        let sample = mining_manager.sample_mempool_stats();
+        let mass = mining_manager.get_transaction(&transaction.id(), TransactionQuery::TransactionsOnly).unwrap().tx.mass();
+        assert_eq!(sample.transaction_count, 1);
+        assert_eq!(sample.orphan_count, 1);
+        assert_eq!(sample.total_mass, mass);
+        assert_eq!((sample.evicted_count, sample.replaced_count), (0, 0));
+        assert_eq!(sample.feerate_histogram.len(), MEMPOOL_STATS_FEERATE_BUCKETS.len());
+        assert_eq!(sample.feerate_histogram.iter().sum::<u64>(), 1, "only the transaction pool should be in the feerate histogram");
+
+        // Replace the transaction by a higher feerate one
+        let replacement = create_transaction(&funding_txs[0], DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * 100);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement,
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Mandatory,
+        );
+        assert!(result.is_ok(), "the replacement transaction should be accepted");
+
+        let replacement_sample = mining_manager.sample_mempool_stats();
+        assert_eq!(replacement_sample.transaction_count, 1);
+        assert_eq!(replacement_sample.replaced_count, 1);
+        let feerate_bucket = |sample: &MempoolStatsSample| sample.feerate_histogram.iter().position(|count| *count > 0).unwrap();
+        assert!(feerate_bucket(&replacement_sample) > feerate_bucket(&sample), "the replacement should have a higher feerate");
+
+        // A sample without activity reports no replacement
+        assert_eq!(mining_manager.sample_mempool_stats().replaced_count, 0);
+        assert_eq!(mining_manager.get_mempool_stats(None).len(), 3);
+        let latest = mining_manager.get_mempool_stats(Some(1));
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].replaced_count, 0);
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
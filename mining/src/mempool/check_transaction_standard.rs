@@ -8,30 +8,11 @@ use vecno_consensus_core::{
     mass,
     tx::{MutableTransaction, PopulatedTransaction, TransactionOutput},
 };
-use vecno_txscript::{get_sig_op_count, is_unspendable, script_class::ScriptClass};
-
-/// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
-/// that are considered standard in a pay-to-script-hash script.
-const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
-
-/// MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE is the maximum size allowed for a
-/// transaction input signature script to be considered standard. This
-/// value allows for a 15-of-15 CHECKMULTISIG pay-to-script-hash with
-/// compressed keys.
-///
-/// The form of the overall script is: OP_0 <15 signatures> OP_PUSHDATA2
-/// <2 bytes len> [OP_15 <15 pubkeys> OP_15 OP_CHECKMULTISIG]
-///
-/// For the p2sh script portion, each of the 15 compressed pubkeys are
-/// 33 bytes (plus one for the OP_DATA_33 opcode), and the thus it totals
-/// to (15*34)+3 = 513 bytes. Next, each of the 15 signatures is a max
-/// of 73 bytes (plus one for the OP_DATA_73 opcode). Also, there is one
-/// extra byte for the initial extra OP_0 push and 3 bytes for the
-/// OP_PUSHDATA2 needed to specify the 513 bytes for the script push.
-/// That brings the total to 1+(15*74)+3+513 = 1627. This value also
-/// adds a few extra bytes to provide a little buffer.
-/// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
-const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
+use vecno_txscript::{
+    analyzer::{MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE, MAX_STANDARD_P2SH_SIG_OPS},
+    get_sig_op_count, is_unspendable,
+    script_class::ScriptClass,
+};
 
 /// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
 /// are considered standard and will therefore be relayed and considered for mining.
@@ -167,15 +148,28 @@ impl Mempool {
     /// context of this function is one whose referenced public key script is of a
     /// standard form and, for pay-to-script-hash, does not have more than
     /// maxStandardP2SHSigOps signature operations.
-    /// In addition, makes sure that the transaction's fee is above the minimum for acceptance
-    /// into the mempool and relay.
-    pub(crate) fn check_transaction_standard_in_context(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+    ///
+    /// MAST outputs and inputs are standard only once MAST is active for `virtual_daa_score`, since the
+    /// consensus considers a MAST output as anyone-can-spend until then.
+    pub(crate) fn check_transaction_standard_in_context(
+        &self,
+        transaction: &MutableTransaction,
+        virtual_daa_score: u64,
+    ) -> NonStandardResult<()> {
         let transaction_id = transaction.id();
         let contextual_mass = transaction.tx.mass();
         assert!(contextual_mass > 0, "expected to be set by consensus");
         if contextual_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
             return Err(NonStandardError::RejectContextualMass(transaction_id, contextual_mass, MAXIMUM_STANDARD_TRANSACTION_MASS));
         }
+        let mast_active = self.config.mast_activation.is_active(virtual_daa_score);
+        if !mast_active {
+            for (i, output) in transaction.tx.outputs.iter().enumerate() {
+                if ScriptClass::from_script(&output.script_public_key) == ScriptClass::Mast {
+                    return Err(NonStandardError::RejectOutputScriptClass(transaction_id, i));
+                }
+            }
+        }
         for (i, input) in transaction.tx.inputs.iter().enumerate() {
             // It is safe to elide existence and index checks here since
             // they have already been checked prior to calling this
@@ -185,9 +179,12 @@ impl Mempool {
                 ScriptClass::NonStandard => {
                     return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
                 }
+                ScriptClass::Mast if !mast_active => {
+                    return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
+                }
                 ScriptClass::PubKey => {}
                 ScriptClass::PubKeyECDSA => {}
-                ScriptClass::ScriptHash => {
+                ScriptClass::ScriptHash | ScriptClass::Mast => {
                     let num_sig_ops = get_sig_op_count::<PopulatedTransaction, SigHashReusedValuesUnsync>(
                         &input.signature_script,
                         &entry.script_public_key,
@@ -197,20 +194,28 @@ impl Mempool {
                     }
                 }
             }
-
-            // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
-            let minimum_fee = self.minimum_required_transaction_relay_fee(transaction.calculated_compute_mass.unwrap());
-            if transaction.calculated_fee.unwrap() < minimum_fee {
-                return Err(NonStandardError::RejectInsufficientFee(transaction_id, transaction.calculated_fee.unwrap(), minimum_fee));
-            }
         }
 
         Ok(())
     }
 
+    /// check_transaction_relay_fee makes sure that the transaction's fee is above the minimum
+    /// for acceptance into the mempool and relay.
+    ///
+    /// The transactions of a package are exempted from this check since the fee is then required
+    /// from the package as a whole.
+    pub(crate) fn check_transaction_relay_fee(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
+        let minimum_fee = self.minimum_required_transaction_relay_fee(transaction.calculated_compute_mass.unwrap());
+        if transaction.calculated_fee.unwrap() < minimum_fee {
+            return Err(NonStandardError::RejectInsufficientFee(transaction.id(), transaction.calculated_fee.unwrap(), minimum_fee));
+        }
+        Ok(())
+    }
+
     /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
     /// for a transaction with the passed mass to be accepted into the mempool and relayed.
-    fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
+    pub(crate) fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
         // Calculate the minimum fee for a transaction to be allowed into the
         // mempool and relayed by scaling the base fee. MinimumRelayTransactionFee is in
         // sompi/kg so multiply by mass (which is in grams) and divide by 1000 to get
@@ -240,15 +245,16 @@ mod tests {
     use std::sync::Arc;
     use vecno_addresses::{Address, Prefix, Version};
     use vecno_consensus_core::{
-        config::params::Params,
+        config::params::{ForkActivation, Params},
         constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_VECNO, TX_VERSION},
         network::NetworkType,
         subnets::SUBNETWORK_ID_NATIVE,
-        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
+        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
     };
     use vecno_txscript::{
-        opcodes::codes::{OpReturn, OpTrue},
+        opcodes::codes::{OpEqual, OpReturn, OpTrue},
         script_builder::ScriptBuilder,
+        MastTree,
     };
 
     #[test]
@@ -581,4 +587,41 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_check_transaction_standard_in_context_mast_activation() {
+        const MAST_ACTIVATION_DAA_SCORE: u64 = 100;
+        let params: Params = NetworkType::Devnet.into();
+        let config = Config::build_default(params.target_time_per_block, false, params.max_block_mass)
+            .apply_mast_activation(ForkActivation::new(MAST_ACTIVATION_DAA_SCORE));
+        let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+
+        let mast_script_public_key = MastTree::new(vec![vec![OpTrue], vec![OpTrue, OpTrue, OpEqual]]).unwrap().script_public_key();
+        let p2pk_script_public_key = vecno_txscript::pay_to_address_script(&Address::new(Prefix::Devnet, Version::PubKey, &[1u8; 32]));
+        let new_mtx = |entry_script_public_key: ScriptPublicKey, output_script_public_key: ScriptPublicKey| {
+            let input = TransactionInput::new(TransactionOutpoint::new(vecno_hashes::Hash::from_u64_word(1), 0), vec![], 0, 1);
+            let output = TransactionOutput::new(SOMPI_PER_VECNO, output_script_public_key);
+            let tx = Transaction::new(TX_VERSION, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+            tx.set_mass(1000);
+            let entry = UtxoEntry::new(2 * SOMPI_PER_VECNO, entry_script_public_key, 0, false);
+            MutableTransaction::with_entries(Arc::new(tx), vec![entry])
+        };
+
+        let spend_mast = new_mtx(mast_script_public_key.clone(), p2pk_script_public_key.clone());
+        let pay_to_mast = new_mtx(p2pk_script_public_key, mast_script_public_key);
+        for daa_score in [0, MAST_ACTIVATION_DAA_SCORE - 1] {
+            assert!(matches!(
+                mempool.check_transaction_standard_in_context(&spend_mast, daa_score),
+                Err(NonStandardError::RejectInputScriptClass(_, 0))
+            ));
+            assert!(matches!(
+                mempool.check_transaction_standard_in_context(&pay_to_mast, daa_score),
+                Err(NonStandardError::RejectOutputScriptClass(_, 0))
+            ));
+        }
+        for daa_score in [MAST_ACTIVATION_DAA_SCORE, MAST_ACTIVATION_DAA_SCORE + 1] {
+            assert!(mempool.check_transaction_standard_in_context(&spend_mast, daa_score).is_ok());
+            assert!(mempool.check_transaction_standard_in_context(&pay_to_mast, daa_score).is_ok());
+        }
+    }
 }
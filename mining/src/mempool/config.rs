@@ -1,4 +1,4 @@
-use vecno_consensus_core::constants::TX_VERSION;
+use vecno_consensus_core::{config::params::ForkActivation, constants::TX_VERSION};
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: usize = 1_000_000;
 pub(crate) const DEFAULT_MEMPOOL_SIZE_LIMIT: usize = 1_000_000_000;
@@ -14,6 +14,21 @@ pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 500;
 
+/// DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT is the maximum number of transactions of a package submitted to
+/// the mempool as a unit.
+pub(crate) const DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT: usize = 25;
+
+/// DEFAULT_MAXIMUM_ANCESTOR_COUNT and DEFAULT_MAXIMUM_DESCENDANT_COUNT are the maximum numbers of in-mempool ancestors
+/// of a transaction and of in-mempool descendants of a transaction. Along with the matching mass limits, they bound
+/// the cost of maintaining the descendant packages by which ready transactions are prioritized.
+pub(crate) const DEFAULT_MAXIMUM_ANCESTOR_COUNT: usize = 25;
+pub(crate) const DEFAULT_MAXIMUM_DESCENDANT_COUNT: usize = 101;
+
+/// DEFAULT_MAXIMUM_ANCESTOR_MASS and DEFAULT_MAXIMUM_DESCENDANT_MASS are the maximum total masses of a transaction along
+/// with its in-mempool ancestors and of a transaction along with its in-mempool descendants.
+pub(crate) const DEFAULT_MAXIMUM_ANCESTOR_MASS: u64 = 1_000_000;
+pub(crate) const DEFAULT_MAXIMUM_DESCENDANT_MASS: u64 = 1_000_000;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
@@ -40,12 +55,18 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_package_transaction_count: usize,
+    pub maximum_ancestor_count: usize,
+    pub maximum_descendant_count: usize,
+    pub maximum_ancestor_mass: u64,
+    pub maximum_descendant_mass: u64,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
     pub network_blocks_per_second: u64,
+    pub mast_activation: ForkActivation,
 }
 
 impl Config {
@@ -64,12 +85,18 @@ impl Config {
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_package_transaction_count: usize,
+        maximum_ancestor_count: usize,
+        maximum_descendant_count: usize,
+        maximum_ancestor_mass: u64,
+        maximum_descendant_mass: u64,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
         network_blocks_per_second: u64,
+        mast_activation: ForkActivation,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -85,12 +112,18 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_package_transaction_count,
+            maximum_ancestor_count,
+            maximum_descendant_count,
+            maximum_ancestor_mass,
+            maximum_descendant_mass,
             accept_non_standard,
             maximum_mass_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
             network_blocks_per_second,
+            mast_activation,
         }
     }
 
@@ -114,15 +147,27 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_package_transaction_count: DEFAULT_MAXIMUM_PACKAGE_TRANSACTION_COUNT,
+            maximum_ancestor_count: DEFAULT_MAXIMUM_ANCESTOR_COUNT,
+            maximum_descendant_count: DEFAULT_MAXIMUM_DESCENDANT_COUNT,
+            maximum_ancestor_mass: DEFAULT_MAXIMUM_ANCESTOR_MASS,
+            maximum_descendant_mass: DEFAULT_MAXIMUM_DESCENDANT_MASS,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
             network_blocks_per_second: 1000 / target_milliseconds_per_block,
+            mast_activation: ForkActivation::never(),
         }
     }
 
+    /// Sets the consensus activation of MAST, before which MAST outputs and inputs are not standard
+    pub fn apply_mast_activation(mut self, mast_activation: ForkActivation) -> Self {
+        self.mast_activation = mast_activation;
+        self
+    }
+
     pub fn apply_ram_scale(mut self, ram_scale: f64) -> Self {
         // Allow only scaling down
         self.maximum_transaction_count = (self.maximum_transaction_count as f64 * ram_scale.min(1.0)) as usize;
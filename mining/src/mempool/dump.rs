@@ -0,0 +1,40 @@
+use crate::{
+    mempool::{model::pool::Pool, Mempool},
+    model::mempool_dump::{DumpedTransaction, MempoolDump},
+};
+use std::sync::atomic::Ordering;
+use vecno_consensus_core::tx::TransactionId;
+
+impl Mempool {
+    pub(crate) fn dump(&self) -> MempoolDump {
+        let transactions = self
+            .transaction_pool
+            .all()
+            .values()
+            .map(|tx| (tx, false))
+            .chain(self.orphan_pool.all().values().map(|tx| (tx, true)))
+            .map(|(tx, is_orphan)| DumpedTransaction {
+                transaction: (*tx.mtx.tx).clone(),
+                priority: tx.priority,
+                is_orphan,
+                added_at_daa_score: tx.added_at_daa_score,
+            })
+            .collect();
+        let accepted_transactions = self.accepted_transactions.iter().collect();
+        MempoolDump { transactions, accepted_transactions }
+    }
+
+    pub(crate) fn restore_accepted_transactions(&mut self, accepted_transactions: Vec<(TransactionId, u64)>) {
+        accepted_transactions.into_iter().for_each(|(transaction_id, daa_score)| {
+            self.accepted_transactions.add(transaction_id, daa_score);
+        });
+        self.counters.accepted_sample.store(self.accepted_transactions.len() as u64, Ordering::Relaxed);
+    }
+
+    /// Restores the DAA score at which a restored transaction first entered the mempool, so that
+    /// a node restart does not extend its expiration delay
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) {
+        self.transaction_pool.restore_added_at_daa_score(transaction_id, added_at_daa_score);
+        self.orphan_pool.restore_added_at_daa_score(transaction_id, added_at_daa_score);
+    }
+}
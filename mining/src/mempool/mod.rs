@@ -4,9 +4,13 @@ use crate::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
+    stats::{feerate_bucket_index, MempoolStatsSample, MEMPOOL_STATS_FEERATE_BUCKETS},
     MiningCounters,
 };
 
+#[cfg(test)]
+use crate::DescendantPackage;
+
 use self::{
     config::Config,
     model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
@@ -21,12 +25,15 @@ use vecno_core::time::Stopwatch;
 
 pub(crate) mod check_transaction_standard;
 pub mod config;
+pub(crate) mod dump;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
+pub mod policy;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub(crate) mod validate_and_insert_package;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -123,6 +130,24 @@ impl Mempool {
         self.transaction_pool.ready_transaction_total_mass()
     }
 
+    /// Samples the current state of the mempool. The eviction and replacement counts are left to the caller.
+    pub(crate) fn stats_sample(&self, timestamp: u64) -> MempoolStatsSample {
+        let mut total_mass = 0;
+        let mut feerate_histogram = vec![0; MEMPOOL_STATS_FEERATE_BUCKETS.len()];
+        for transaction in self.transaction_pool.all().values() {
+            total_mass += transaction.mtx.tx.mass();
+            feerate_histogram[feerate_bucket_index(transaction.mtx.calculated_feerate().unwrap_or_default())] += 1;
+        }
+        MempoolStatsSample {
+            timestamp,
+            transaction_count: self.transaction_pool.len() as u64,
+            total_mass,
+            orphan_count: self.orphan_pool.len() as u64,
+            feerate_histogram,
+            ..Default::default()
+        }
+    }
+
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
         let _sw = Stopwatch::<10>::with_threshold("build_selector op");
@@ -162,10 +187,18 @@ impl Mempool {
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.transaction_pool.get_estimated_size()
     }
+
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_package(&self, transaction_id: &TransactionId) -> Option<DescendantPackage> {
+        self.transaction_pool.get_ready_transaction_package(transaction_id)
+    }
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use serde::{Deserialize, Serialize};
+    use std::net::IpAddr;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
     pub enum Priority {
         Low,
         High,
@@ -177,6 +210,15 @@ pub mod tx {
         Allowed,
     }
 
+    /// The origin of a transaction submitted to the mempool, as considered by the mempool policy
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TransactionSource {
+        /// Submitted by the node itself or through RPC
+        Local,
+        /// Relayed by the peer having this IP address
+        Peer(IpAddr),
+    }
+
     /// Replace by Fee (RBF) policy
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub enum RbfPolicy {
@@ -33,6 +33,10 @@ impl AcceptedTransactions {
         self.transactions.contains_key(transaction_id)
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (TransactionId, u64)> + '_ {
+        self.transactions.iter().map(|(transaction_id, daa_score)| (*transaction_id, *daa_score))
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.transactions.len()
     }
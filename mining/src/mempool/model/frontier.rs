@@ -35,7 +35,8 @@ const AVG_MASS_DECAY_FACTOR: f64 = 0.99999;
 /// the transaction pool which have no mempool ancestors and are essentially ready
 /// to enter the next block template.
 pub struct Frontier {
-    /// Frontier transactions sorted by feerate order and searchable for weight sampling
+    /// Frontier transactions sorted by effective feerate order (see [`FeerateTransactionKey::effective_feerate`])
+    /// and searchable for weight sampling
     search_tree: SearchTree,
 
     /// Total masses: Σ_{tx in frontier} tx.mass
@@ -93,6 +94,20 @@ impl Frontier {
         }
     }
 
+    /// Replaces the key of a frontier transaction whose descendant package has changed.
+    ///
+    /// Unlike a removal followed by an insertion, this does not count the transaction twice in the average transaction mass.
+    pub fn update(&mut self, key: &FeerateTransactionKey, updated: FeerateTransactionKey) -> bool {
+        debug_assert_eq!(key.tx.id(), updated.tx.id());
+        debug_assert_eq!(key.mass, updated.mass);
+        if self.search_tree.remove(key) {
+            self.search_tree.insert(updated);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Samples the frontier in-place based on the provided policy and returns a SequenceSelector.
     ///
     /// This sampling algorithm should be used when frontier total mass is high enough compared to
@@ -268,6 +283,11 @@ impl Frontier {
     pub fn ascending_iter(&self) -> impl DoubleEndedIterator<Item = &Arc<Transaction>> + ExactSizeIterator + FusedIterator {
         self.search_tree.ascending_iter().map(|key| &key.tx)
     }
+
+    /// Returns an iterator to the keys of the frontier in increasing effective feerate order
+    pub fn ascending_keys_iter(&self) -> impl DoubleEndedIterator<Item = &FeerateTransactionKey> + ExactSizeIterator + FusedIterator {
+        self.search_tree.ascending_iter()
+    }
 }
 
 #[cfg(test)]
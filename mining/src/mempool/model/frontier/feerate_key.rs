@@ -2,10 +2,40 @@ use crate::{block_template::selector::ALPHA, mempool::model::tx::MempoolTransact
 use std::sync::Arc;
 use vecno_consensus_core::tx::Transaction;
 
+/// The aggregated fee and mass of a transaction along with all its mempool descendants.
+///
+/// A descendant can only enter a block once all its ancestors did, so its fee is effectively
+/// paid for the whole package (child-pays-for-parent).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DescendantPackage {
+    pub fee: u64,
+    pub mass: u64,
+}
+
+impl DescendantPackage {
+    pub fn new(fee: u64, mass: u64) -> Self {
+        Self { fee, mass }
+    }
+
+    pub fn feerate(&self) -> f64 {
+        self.fee as f64 / self.mass as f64
+    }
+
+    /// Adds a descendant transaction to the package
+    pub fn add(&mut self, fee: u64, mass: u64) {
+        self.fee += fee;
+        self.mass += mass;
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FeerateTransactionKey {
+    /// The fee of the transaction itself
     pub fee: u64,
+    /// The mass of the transaction itself, which is the mass it occupies in a block
     pub mass: u64,
+    /// The package formed by the transaction and its mempool descendants
+    pub package: DescendantPackage,
     weight: f64,
     pub tx: Arc<Transaction>,
 }
@@ -20,16 +50,29 @@ impl PartialEq for FeerateTransactionKey {
 
 impl FeerateTransactionKey {
     pub fn new(fee: u64, mass: u64, tx: Arc<Transaction>) -> Self {
+        Self::with_package(fee, mass, DescendantPackage::new(fee, mass), tx)
+    }
+
+    pub fn with_package(fee: u64, mass: u64, package: DescendantPackage, tx: Arc<Transaction>) -> Self {
         // NOTE: any change to the way this weight is calculated (such as scaling by some factor)
         // requires a reversed update to total_weight in `Frontier::build_feerate_estimator`. This
         // is because the math methods in FeeEstimator assume this specific weight function.
-        Self { fee, mass, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
+        let weight = (fee as f64 / mass as f64).max(package.feerate()).powi(ALPHA);
+        Self { fee, mass, package, weight, tx }
     }
 
+    /// The feerate of the transaction itself
     pub fn feerate(&self) -> f64 {
         self.fee as f64 / self.mass as f64
     }
 
+    /// The feerate by which the transaction is prioritized, that is, the feerate of its descendant
+    /// package, unless its own feerate is higher (in which case low-fee descendants are not
+    /// allowed to drag it down)
+    pub fn effective_feerate(&self) -> f64 {
+        self.feerate().max(self.package.feerate())
+    }
+
     pub fn weight(&self) -> f64 {
         self.weight
     }
@@ -59,28 +102,34 @@ impl Ord for FeerateTransactionKey {
         }
 
         // If feerates (and thus weights) are equal, prefer the higher fee in absolute value
+        match self.package.fee.cmp(&other.package.fee) {
+            core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
         match self.fee.cmp(&other.fee) {
             core::cmp::Ordering::Equal => {}
             ord => return ord,
         }
 
-        //
-        // At this point we don't compare the mass fields since if both feerate
-        // and fee are equal, mass must be equal as well
-        //
-
         // Finally, we compare transaction ids in order to allow multiple transactions with
         // the same fee and mass to exist within the same sorted container
         self.tx.id().cmp(&other.tx.id())
     }
 }
 
-impl From<&MempoolTransaction> for FeerateTransactionKey {
+impl From<&MempoolTransaction> for DescendantPackage {
     fn from(tx: &MempoolTransaction) -> Self {
         let mass = tx.mtx.tx.mass();
         let fee = tx.mtx.calculated_fee.expect("fee is expected to be populated");
         assert_ne!(mass, 0, "mass field is expected to be set when inserting to the mempool");
-        Self::new(fee, mass, tx.mtx.tx.clone())
+        Self::new(fee, mass)
+    }
+}
+
+impl From<&MempoolTransaction> for FeerateTransactionKey {
+    fn from(tx: &MempoolTransaction) -> Self {
+        let own = DescendantPackage::from(tx);
+        Self::new(own.fee, own.mass, tx.mtx.tx.clone())
     }
 }
 
@@ -105,4 +154,17 @@ pub(crate) mod tests {
     pub(crate) fn build_feerate_key(fee: u64, mass: u64, id: u64) -> FeerateTransactionKey {
         FeerateTransactionKey::new(fee, mass, generate_unique_tx(id))
     }
+
+    #[test]
+    fn test_package_feerate_ordering() {
+        let standalone = build_feerate_key(3_000, 1_000, 0);
+        // A low-fee parent bumped by a high-fee child
+        let bumped = FeerateTransactionKey::with_package(1_000, 1_000, DescendantPackage::new(11_000, 2_000), generate_unique_tx(1));
+        // A high-fee parent with a low-fee child
+        let dragged = FeerateTransactionKey::with_package(4_000, 1_000, DescendantPackage::new(4_500, 2_000), generate_unique_tx(2));
+
+        assert_eq!(bumped.effective_feerate(), 5.5);
+        assert_eq!(dragged.effective_feerate(), 4.0);
+        assert!(standalone < dragged && dragged < bumped);
+    }
 }
@@ -59,6 +59,12 @@ impl OrphanPool {
         self.outpoint_owner_id.get(outpoint).and_then(|id| self.all_orphans.get_mut(id))
     }
 
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) {
+        if let Some(tx) = self.all_orphans.get_mut(transaction_id) {
+            tx.added_at_daa_score = tx.added_at_daa_score.min(added_at_daa_score);
+        }
+    }
+
     pub(crate) fn try_add_orphan(
         &mut self,
         virtual_daa_score: u64,
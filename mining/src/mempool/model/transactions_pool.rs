@@ -15,7 +15,7 @@ use crate::{
     Policy,
 };
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter},
+    collections::{hash_map::Keys, hash_set::Iter, HashMap, VecDeque},
     iter::once,
     sync::Arc,
 };
@@ -25,7 +25,10 @@ use vecno_consensus_core::{
 };
 use vecno_core::{debug, time::unix_now, trace};
 
-use super::frontier::Frontier;
+use super::frontier::{
+    feerate_key::{DescendantPackage, FeerateTransactionKey},
+    Frontier,
+};
 
 /// Pool of transactions to be included in a block template
 ///
@@ -67,6 +70,10 @@ pub(crate) struct TransactionsPool {
     /// Transactions with no parents in the mempool -- ready to be inserted into a block template
     ready_transactions: Frontier,
 
+    /// The packages formed by each ready transaction and its descendants, by which the ready transactions
+    /// are prioritized in the frontier so that a child can pay for its parents
+    ready_packages: HashMap<TransactionId, DescendantPackage>,
+
     last_expire_scan_daa_score: u64,
 
     /// last expire scan time in milliseconds
@@ -87,6 +94,7 @@ impl TransactionsPool {
             parent_transactions: TransactionsEdges::default(),
             chained_transactions: TransactionsEdges::default(),
             ready_transactions: Default::default(),
+            ready_packages: Default::default(),
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
@@ -120,9 +128,12 @@ impl TransactionsPool {
         // The transactions chained to the added transaction cannot be stored
         // here yet since, by definition, they would have been orphans.
         let parents = self.get_parent_transaction_ids_in_pool(&transaction.mtx);
+        self.check_chain_limits(&transaction, &parents)?;
         self.parent_transactions.insert(id, parents.clone());
+        let package = DescendantPackage::from(&transaction);
         if parents.is_empty() {
-            self.ready_transactions.insert((&transaction).into());
+            self.ready_packages.insert(id, package);
+            self.ready_transactions.insert(Self::ready_key(&transaction, package));
         }
         for parent_id in parents {
             let entry = self.chained_transactions.entry(parent_id).or_default();
@@ -132,13 +143,72 @@ impl TransactionsPool {
         self.utxo_set.add_transaction(&transaction.mtx);
         self.estimated_size += transaction_size;
         self.all_transactions.insert(id, transaction);
+
+        // The added transaction has no descendants yet, so it simply joins the package of each of its ready ancestors
+        for ancestor_id in self.get_ready_ancestor_ids(&id) {
+            let mut ancestor_package = self.ready_packages[&ancestor_id];
+            ancestor_package.add(package.fee, package.mass);
+            self.update_ready_package(&ancestor_id, ancestor_package);
+        }
         trace!("Added transaction {}", id);
         Ok(())
     }
 
+    /// Checks that adding `transaction` keeps its in-pool ancestors, and the in-pool descendants of each of them,
+    /// within the configured count and mass limits.
+    ///
+    /// Since every transaction of the pool went through this check, all the traversals below, as well as the ones
+    /// maintaining the packages of the ready transactions, are bounded by these limits.
+    fn check_chain_limits(&self, transaction: &MempoolTransaction, parents: &TransactionIdSet) -> RuleResult<()> {
+        let id = transaction.id();
+        let mass = transaction.mtx.tx.mass();
+
+        let mut ancestors = TransactionIdSet::default();
+        let mut ancestor_mass = mass;
+        let mut queue = VecDeque::from_iter(parents.iter().copied());
+        while let Some(ancestor_id) = queue.pop_front() {
+            if !ancestors.insert(ancestor_id) {
+                continue;
+            }
+            if ancestors.len() > self.config.maximum_ancestor_count {
+                return Err(RuleError::RejectTooManyAncestors(id, self.config.maximum_ancestor_count));
+            }
+            ancestor_mass += self.all_transactions[&ancestor_id].mtx.tx.mass();
+            if ancestor_mass > self.config.maximum_ancestor_mass {
+                return Err(RuleError::RejectAncestorMassTooHigh(id, ancestor_mass, self.config.maximum_ancestor_mass));
+            }
+            queue.extend(self.parent_transactions.get(&ancestor_id).into_iter().flatten().copied());
+        }
+
+        // The added transaction becomes a descendant of each of its ancestors
+        for ancestor_id in ancestors.iter() {
+            let descendant_ids = self.get_redeemer_ids_in_pool(ancestor_id);
+            if descendant_ids.len() + 1 > self.config.maximum_descendant_count {
+                return Err(RuleError::RejectTooManyDescendants(*ancestor_id, self.config.maximum_descendant_count));
+            }
+            let descendant_mass = once(*ancestor_id)
+                .chain(descendant_ids)
+                .map(|descendant_id| self.all_transactions[&descendant_id].mtx.tx.mass())
+                .sum::<u64>()
+                + mass;
+            if descendant_mass > self.config.maximum_descendant_mass {
+                return Err(RuleError::RejectDescendantMassTooHigh(
+                    *ancestor_id,
+                    descendant_mass,
+                    self.config.maximum_descendant_mass,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Fully removes the transaction from all relational sets, as well as from the UTXO set
     pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // Collect the ready ancestors whose package is about to change
+        let ready_ancestor_ids = self.get_ready_ancestor_ids(transaction_id);
+
         // Remove all bijective parent/chained relations
+        let mut new_ready_ids = vec![];
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
                 if let Some(chains) = self.chained_transactions.get_mut(parent) {
@@ -151,8 +221,7 @@ impl TransactionsPool {
                 if let Some(parents) = self.parent_transactions.get_mut(chain) {
                     parents.remove(transaction_id);
                     if parents.is_empty() {
-                        let tx = self.all_transactions.get(chain).unwrap();
-                        self.ready_transactions.insert(tx.into());
+                        new_ready_ids.push(*chain);
                     }
                 }
             }
@@ -163,7 +232,21 @@ impl TransactionsPool {
         // Remove the transaction itself
         let removed_tx = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
 
-        self.ready_transactions.remove(&(&removed_tx).into());
+        if let Some(package) = self.ready_packages.remove(transaction_id) {
+            self.ready_transactions.remove(&Self::ready_key(&removed_tx, package));
+        }
+
+        // The descendants of the removed transaction might still be reachable from its ready ancestors
+        // through other paths, so their packages are fully recalculated
+        for ancestor_id in ready_ancestor_ids {
+            let package = self.calc_descendant_package(&ancestor_id);
+            self.update_ready_package(&ancestor_id, package);
+        }
+        for ready_id in new_ready_ids {
+            let package = self.calc_descendant_package(&ready_id);
+            self.ready_packages.insert(ready_id, package);
+            self.ready_transactions.insert(Self::ready_key(&self.all_transactions[&ready_id], package));
+        }
 
         // TODO: consider using `self.parent_transactions.get(transaction_id)`
         // The tradeoff to consider is whether it might be possible that a parent tx exists in the pool
@@ -182,6 +265,49 @@ impl TransactionsPool {
         Ok(removed_tx)
     }
 
+    fn ready_key(transaction: &MempoolTransaction, package: DescendantPackage) -> FeerateTransactionKey {
+        let own = DescendantPackage::from(transaction);
+        FeerateTransactionKey::with_package(own.fee, own.mass, package, transaction.mtx.tx.clone())
+    }
+
+    /// Sets the package of a ready transaction, updating its position in the frontier accordingly
+    fn update_ready_package(&mut self, transaction_id: &TransactionId, package: DescendantPackage) {
+        let transaction = &self.all_transactions[transaction_id];
+        let previous = self.ready_packages.insert(*transaction_id, package).expect("the transaction is expected to be ready");
+        self.ready_transactions.update(&Self::ready_key(transaction, previous), Self::ready_key(transaction, package));
+    }
+
+    /// Returns the package formed by a transaction and all its descendants in the pool
+    fn calc_descendant_package(&self, transaction_id: &TransactionId) -> DescendantPackage {
+        once(*transaction_id).chain(self.get_redeemer_ids_in_pool(transaction_id)).fold(
+            DescendantPackage::default(),
+            |mut package, id| {
+                let transaction = DescendantPackage::from(&self.all_transactions[&id]);
+                package.add(transaction.fee, transaction.mass);
+                package
+            },
+        )
+    }
+
+    /// Returns the ids of the ready transactions `transaction_id` descends from, excluding itself
+    fn get_ready_ancestor_ids(&self, transaction_id: &TransactionId) -> TransactionIdSet {
+        let mut ready_ancestors = TransactionIdSet::default();
+        let mut visited = TransactionIdSet::default();
+        let mut queue = VecDeque::from_iter(self.parent_transactions.get(transaction_id).into_iter().flatten().copied());
+        while let Some(id) = queue.pop_front() {
+            if !visited.insert(id) {
+                continue;
+            }
+            match self.parent_transactions.get(&id) {
+                Some(parents) if !parents.is_empty() => queue.extend(parents.iter().copied()),
+                _ => {
+                    ready_ancestors.insert(id);
+                }
+            }
+        }
+        ready_ancestors
+    }
+
     pub(crate) fn update_revalidated_transaction(&mut self, transaction: MutableTransaction) -> bool {
         if let Some(tx) = self.all_transactions.get_mut(&transaction.id()) {
             // Make sure to update the overall estimated size since the updated transaction might have a different size
@@ -194,6 +320,12 @@ impl TransactionsPool {
         }
     }
 
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) {
+        if let Some(tx) = self.all_transactions.get_mut(transaction_id) {
+            tx.added_at_daa_score = tx.added_at_daa_score.min(added_at_daa_score);
+        }
+    }
+
     pub(crate) fn ready_transaction_count(&self) -> usize {
         self.ready_transactions.len()
     }
@@ -202,6 +334,11 @@ impl TransactionsPool {
         self.ready_transactions.total_mass()
     }
 
+    #[cfg(test)]
+    pub(crate) fn get_ready_transaction_package(&self, transaction_id: &TransactionId) -> Option<DescendantPackage> {
+        self.ready_packages.get(transaction_id).copied()
+    }
+
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
         self.ready_transactions.build_selector(&Policy::new(self.config.maximum_mass_per_block))
@@ -213,52 +350,54 @@ impl TransactionsPool {
     }
 
     /// Returns the exceeding low-priority transactions having the lowest fee rates in order
-    /// to make room for `transaction`. The returned transactions
+    /// to make room for `transactions`, of overall size `transactions_size`. The returned transactions
     /// are guaranteed to be unchained (no successor in mempool) and to not be parent of
-    /// `transaction`.
+    /// any of `transactions`.
     ///
     /// An error is returned if the mempool is filled with high priority transactions, or
-    /// there are not enough lower feerate transactions that can be removed to accommodate `transaction`
+    /// there are not enough transactions with a feerate lower than `feerate_threshold` that
+    /// can be removed to accommodate `transactions`
     pub(crate) fn limit_transaction_count(
         &self,
-        transaction: &MutableTransaction,
-        transaction_size: usize,
+        transactions: &[MutableTransaction],
+        transactions_size: usize,
+        feerate_threshold: f64,
     ) -> RuleResult<Vec<TransactionId>> {
         // No eviction needed -- return
-        if self.len() < self.config.maximum_transaction_count
-            && self.estimated_size + transaction_size <= self.config.mempool_size_limit
+        if self.len() + transactions.len() <= self.config.maximum_transaction_count
+            && self.estimated_size + transactions_size <= self.config.mempool_size_limit
         {
             return Ok(Default::default());
         }
 
         // Returns a vector of transactions to be removed (the caller has to actually remove)
-        let feerate_threshold = transaction.calculated_feerate().unwrap();
         let mut txs_to_remove = Vec::with_capacity(1); // Normally we expect a single removal
         let mut selection_overall_size = 0;
-        for tx in self
+        for (key, tx) in self
             .ready_transactions
-            .ascending_iter()
-            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
-            .filter(|mtx| mtx.priority == Priority::Low)
+            .ascending_keys_iter()
+            .map(|key| (key, self.all_transactions.get(&key.tx.id()).unwrap()))
+            .filter(|(_, mtx)| mtx.priority == Priority::Low)
         {
             // TODO (optimization): inline the `has_parent_in_set` check within the redeemer traversal and exit early if possible
             let redeemers = self.get_redeemer_ids_in_pool(&tx.id()).into_iter().chain(once(tx.id())).collect::<TransactionIdSet>();
-            if transaction.has_parent_in_set(&redeemers) {
+            if transactions.iter().any(|transaction| transaction.has_parent_in_set(&redeemers)) {
                 continue;
             }
 
-            // We are iterating ready txs by ascending feerate so the pending tx has lower feerate than all remaining txs
-            if tx.fee_rate() > feerate_threshold {
+            // We are iterating ready txs by ascending package feerate so the pending tx has lower feerate than all remaining
+            // packages. Note that a ready tx is removed along with its descendants, hence the comparison with its package
+            if key.effective_feerate() > feerate_threshold {
                 let err = RuleError::RejectMempoolIsFull;
-                debug!("Transaction {} with feerate {} has been rejected: {}", transaction.id(), feerate_threshold, err);
+                debug!("Transaction {} with feerate {} has been rejected: {}", transaction_ids(transactions), feerate_threshold, err);
                 return Err(err);
             }
 
             txs_to_remove.push(tx.id());
             selection_overall_size += tx.mtx.mempool_estimated_bytes();
 
-            if self.len() + 1 - txs_to_remove.len() <= self.config.maximum_transaction_count
-                && self.estimated_size + transaction_size - selection_overall_size <= self.config.mempool_size_limit
+            if self.len() + transactions.len() - txs_to_remove.len() <= self.config.maximum_transaction_count
+                && self.estimated_size + transactions_size - selection_overall_size <= self.config.mempool_size_limit
             {
                 return Ok(txs_to_remove);
             }
@@ -269,9 +408,9 @@ impl TransactionsPool {
             "Mempool is filled with high-priority/ancestor txs (count: {}, bytes: {}). Transaction {} with feerate {} and size {} has been rejected: {}",
             self.len(),
             self.estimated_size,
-            transaction.id(),
+            transaction_ids(transactions),
             feerate_threshold,
-            transaction_size,
+            transactions_size,
             RuleError::RejectMempoolIsFull
         );
         Err(RuleError::RejectMempoolIsFull)
@@ -364,3 +503,8 @@ impl Pool for TransactionsPool {
         &self.chained_transactions
     }
 }
+
+/// Formats the ids of the transactions for logging
+fn transaction_ids(transactions: &[MutableTransaction]) -> String {
+    transactions.iter().map(|transaction| transaction.id().to_string()).collect::<Vec<_>>().join(", ")
+}
@@ -21,12 +21,6 @@ impl MempoolTransaction {
     pub(crate) fn id(&self) -> TransactionId {
         self.mtx.tx.id()
     }
-
-    pub(crate) fn fee_rate(&self) -> f64 {
-        let contextual_mass = self.mtx.tx.mass();
-        assert!(contextual_mass > 0, "expected to be called for validated txs only");
-        self.mtx.calculated_fee.unwrap() as f64 / contextual_mass as f64
-    }
 }
 
 impl RbfPolicy {
@@ -0,0 +1,213 @@
+//!
+//! Mempool policy configured by the node operator.
+//!
+//! On top of the standardness rules enforced by every node, an operator may define a chain of policy rules
+//! rejecting some transactions from the mempool of its own node, for instance outputs below a custom dust
+//! threshold or transactions relayed by a peer at too high a rate. The rules are checked in order against
+//! every incoming transaction before its validation and the first broken rule rejects the transaction with
+//! a [`RuleError::RejectPolicy`](crate::mempool::errors::RuleError::RejectPolicy) error.
+//!
+//! The chain is usually loaded from a TOML file listing the rules:
+//!
+//! ```toml
+//! [[rules]]
+//! type = "dust"
+//! threshold = 1000
+//!
+//! [[rules]]
+//! type = "max-payload-size"
+//! max-size = 256
+//!
+//! [[rules]]
+//! type = "blocked-script-classes"
+//! classes = ["pubkeyecdsa"]
+//!
+//! [[rules]]
+//! type = "peer-rate-limit"
+//! transactions-per-second = 50
+//! burst = 500
+//! ```
+//!
+//! Additional rules can be plugged into the chain by implementing [`PolicyRule`].
+//!
+
+use crate::mempool::{errors::PolicyRejectReason, tx::TransactionSource};
+use serde::Deserialize;
+use std::{fs, io, path::Path, str::FromStr};
+use thiserror::Error;
+use vecno_consensus_core::tx::MutableTransaction;
+use vecno_txscript::script_class::ScriptClass;
+
+pub mod rules;
+
+use rules::{DustRule, PayloadSizeRule, PeerRateLimitRule, ScriptClassRule};
+
+/// A rule of the mempool policy
+pub trait PolicyRule: Send + Sync {
+    /// Checks an incoming transaction, returning the reason of its rejection if it breaks the rule.
+    ///
+    /// The rule is checked before any validation, so the UTXO entries of the transaction are not populated yet.
+    fn check(&self, transaction: &MutableTransaction, source: TransactionSource) -> Result<(), PolicyRejectReason>;
+}
+
+/// A chain of policy rules checked in order, the first broken rule rejecting the transaction.
+///
+/// The default policy has no rule and accepts all transactions.
+#[derive(Default)]
+pub struct MempoolPolicy {
+    rules: Vec<Box<dyn PolicyRule>>,
+}
+
+impl MempoolPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the chain of rules defined by the configuration
+    pub fn from_config(config: &PolicyConfig) -> PolicyConfigResult<Self> {
+        let mut policy = Self::new();
+        for (i, rule) in config.rules.iter().enumerate() {
+            let invalid = |reason: String| PolicyConfigError::InvalidRule(i, reason);
+            policy = match rule {
+                PolicyRuleConfig::Dust { threshold } => policy.with_rule(DustRule::new(*threshold)),
+                PolicyRuleConfig::MaxPayloadSize { max_size } => policy.with_rule(PayloadSizeRule::new(*max_size)),
+                PolicyRuleConfig::BlockedScriptClasses { classes } => {
+                    let classes = classes
+                        .iter()
+                        .map(|class| ScriptClass::from_str(class).map_err(|err| invalid(err.to_string())))
+                        .collect::<PolicyConfigResult<Vec<_>>>()?;
+                    policy.with_rule(ScriptClassRule::new(classes))
+                }
+                PolicyRuleConfig::PeerRateLimit { transactions_per_second, burst } => {
+                    if *transactions_per_second == 0 || *burst == 0 {
+                        return Err(invalid("the rate and the burst of a peer rate limit must be positive".to_string()));
+                    }
+                    policy.with_rule(PeerRateLimitRule::new(*transactions_per_second, *burst))
+                }
+            };
+        }
+        Ok(policy)
+    }
+
+    /// Appends a rule to the chain
+    pub fn with_rule(mut self, rule: impl PolicyRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks the transaction against every rule of the chain, in order
+    pub fn check(&self, transaction: &MutableTransaction, source: TransactionSource) -> Result<(), PolicyRejectReason> {
+        self.rules.iter().try_for_each(|rule| rule.check(transaction, source))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PolicyConfigError {
+    #[error("mempool policy i/o error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("failed parsing the mempool policy: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("invalid mempool policy rule #{0}: {1}")]
+    InvalidRule(usize, String),
+}
+
+pub type PolicyConfigResult<T> = std::result::Result<T, PolicyConfigError>;
+
+/// The rules of a mempool policy, in the order they are checked
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    pub rules: Vec<PolicyRuleConfig>,
+}
+
+impl PolicyConfig {
+    /// Loads the policy defined by the TOML file at `path`
+    pub fn load(path: &Path) -> PolicyConfigResult<Self> {
+        Ok(fs::read_to_string(path)?.parse()?)
+    }
+}
+
+impl FromStr for PolicyConfig {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", rename_all_fields = "kebab-case", deny_unknown_fields)]
+pub enum PolicyRuleConfig {
+    /// Rejects transactions with an output paying less than `threshold` sompi
+    Dust { threshold: u64 },
+    /// Rejects transactions with a payload larger than `max_size` bytes
+    MaxPayloadSize { max_size: usize },
+    /// Rejects transactions with an output paying to one of the script `classes`
+    BlockedScriptClasses { classes: Vec<String> },
+    /// Rejects the transactions relayed by a peer above `transactions_per_second`,
+    /// allowing bursts of up to `burst` transactions
+    PeerRateLimit { transactions_per_second: u32, burst: u32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_config() {
+        let config: PolicyConfig = r#"
+            [[rules]]
+            type = "dust"
+            threshold = 1000
+
+            [[rules]]
+            type = "max-payload-size"
+            max-size = 256
+
+            [[rules]]
+            type = "blocked-script-classes"
+            classes = ["pubkeyecdsa", "mast"]
+
+            [[rules]]
+            type = "peer-rate-limit"
+            transactions-per-second = 50
+            burst = 500
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            config.rules,
+            vec![
+                PolicyRuleConfig::Dust { threshold: 1000 },
+                PolicyRuleConfig::MaxPayloadSize { max_size: 256 },
+                PolicyRuleConfig::BlockedScriptClasses { classes: vec!["pubkeyecdsa".to_string(), "mast".to_string()] },
+                PolicyRuleConfig::PeerRateLimit { transactions_per_second: 50, burst: 500 },
+            ]
+        );
+        assert_eq!(MempoolPolicy::from_config(&config).unwrap().len(), 4);
+        assert!(MempoolPolicy::from_config(&"".parse().unwrap()).unwrap().is_empty());
+
+        // Unknown rules and fields
+        assert!("[[rules]]\ntype = \"unknown\"".parse::<PolicyConfig>().is_err());
+        assert!("[[rules]]\ntype = \"dust\"\nthreshold = 1\nlimit = 2".parse::<PolicyConfig>().is_err());
+
+        // Invalid rule parameters
+        let config: PolicyConfig =
+            "[[rules]]\ntype = \"dust\"\nthreshold = 1\n[[rules]]\ntype = \"blocked-script-classes\"\nclasses = [\"p2pkh\"]"
+                .parse()
+                .unwrap();
+        assert!(matches!(MempoolPolicy::from_config(&config), Err(PolicyConfigError::InvalidRule(1, _))));
+        let config: PolicyConfig = "[[rules]]\ntype = \"peer-rate-limit\"\ntransactions-per-second = 0\nburst = 1".parse().unwrap();
+        assert!(matches!(MempoolPolicy::from_config(&config), Err(PolicyConfigError::InvalidRule(0, _))));
+    }
+}
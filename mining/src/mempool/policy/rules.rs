@@ -0,0 +1,189 @@
+use super::PolicyRule;
+use crate::mempool::{errors::PolicyRejectReason, tx::TransactionSource};
+use parking_lot::Mutex;
+use std::{collections::HashMap, net::IpAddr, time::Instant};
+use vecno_consensus_core::tx::MutableTransaction;
+use vecno_txscript::script_class::ScriptClass;
+
+/// Rejects transactions with an output paying less than a threshold
+pub struct DustRule {
+    threshold: u64,
+}
+
+impl DustRule {
+    pub fn new(threshold: u64) -> Self {
+        Self { threshold }
+    }
+}
+
+impl PolicyRule for DustRule {
+    fn check(&self, transaction: &MutableTransaction, _source: TransactionSource) -> Result<(), PolicyRejectReason> {
+        match transaction.tx.outputs.iter().enumerate().find(|(_, output)| output.value < self.threshold) {
+            Some((i, output)) => Err(PolicyRejectReason::DustOutput(i, output.value, self.threshold)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Rejects transactions with a payload larger than a maximum size
+pub struct PayloadSizeRule {
+    max_size: usize,
+}
+
+impl PayloadSizeRule {
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl PolicyRule for PayloadSizeRule {
+    fn check(&self, transaction: &MutableTransaction, _source: TransactionSource) -> Result<(), PolicyRejectReason> {
+        let payload_size = transaction.tx.payload.len();
+        if payload_size > self.max_size {
+            return Err(PolicyRejectReason::PayloadTooLarge(payload_size, self.max_size));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with an output paying to a blocked script class
+pub struct ScriptClassRule {
+    blocked_classes: Vec<ScriptClass>,
+}
+
+impl ScriptClassRule {
+    pub fn new(blocked_classes: Vec<ScriptClass>) -> Self {
+        Self { blocked_classes }
+    }
+}
+
+impl PolicyRule for ScriptClassRule {
+    fn check(&self, transaction: &MutableTransaction, _source: TransactionSource) -> Result<(), PolicyRejectReason> {
+        for (i, output) in transaction.tx.outputs.iter().enumerate() {
+            let class = ScriptClass::from_script(&output.script_public_key);
+            if self.blocked_classes.contains(&class) {
+                return Err(PolicyRejectReason::BlockedScriptClass(i, class.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The maximum number of peers whose rate is tracked before forgetting the idle ones
+const MAX_TRACKED_PEERS: usize = 4096;
+
+/// A token bucket refilled at a constant rate
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Limits the rate of the transactions relayed by each peer, identified by its IP address.
+///
+/// Every peer is granted a bucket of `burst` transactions, refilled by `transactions_per_second`.
+/// Local transactions are not limited.
+pub struct PeerRateLimitRule {
+    transactions_per_second: u32,
+    burst: u32,
+    buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+}
+
+impl PeerRateLimitRule {
+    pub fn new(transactions_per_second: u32, burst: u32) -> Self {
+        Self { transactions_per_second, burst, buckets: Default::default() }
+    }
+
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.transactions_per_second as f64).min(self.burst as f64);
+        bucket.last_refill = now;
+    }
+
+    fn check_at(&self, peer: IpAddr, now: Instant) -> Result<(), PolicyRejectReason> {
+        let mut buckets = self.buckets.lock();
+        if buckets.len() >= MAX_TRACKED_PEERS && !buckets.contains_key(&peer) {
+            // Peers with a full bucket are indistinguishable from untracked ones
+            buckets.retain(|_, bucket| {
+                self.refill(bucket, now);
+                bucket.tokens < self.burst as f64
+            });
+        }
+        let bucket = buckets.entry(peer).or_insert_with(|| TokenBucket { tokens: self.burst as f64, last_refill: now });
+        self.refill(bucket, now);
+        if bucket.tokens < 1.0 {
+            return Err(PolicyRejectReason::PeerRateLimited(self.transactions_per_second));
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+impl PolicyRule for PeerRateLimitRule {
+    fn check(&self, _transaction: &MutableTransaction, source: TransactionSource) -> Result<(), PolicyRejectReason> {
+        match source {
+            TransactionSource::Local => Ok(()),
+            TransactionSource::Peer(peer) => self.check_at(peer, Instant::now()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mempool::policy::MempoolPolicy;
+    use std::{net::Ipv4Addr, time::Duration};
+    use vecno_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, Transaction, TransactionOutput},
+    };
+    use vecno_txscript::{opcodes::codes::OpTrue, pay_to_script_hash_script};
+
+    fn transaction(outputs: Vec<TransactionOutput>, payload: Vec<u8>) -> MutableTransaction {
+        MutableTransaction::from_tx(Transaction::new(0, vec![], outputs, 0, SUBNETWORK_ID_NATIVE, 0, payload))
+    }
+
+    #[test]
+    fn test_policy_rules() {
+        let peer = TransactionSource::Peer(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let p2sh = pay_to_script_hash_script(&[OpTrue]);
+        let outputs = vec![TransactionOutput::new(1000, ScriptPublicKey::default()), TransactionOutput::new(500, p2sh)];
+
+        let rule = DustRule::new(600);
+        assert_eq!(rule.check(&transaction(outputs.clone(), vec![]), peer), Err(PolicyRejectReason::DustOutput(1, 500, 600)));
+        assert_eq!(DustRule::new(500).check(&transaction(outputs.clone(), vec![]), peer), Ok(()));
+
+        let rule = PayloadSizeRule::new(2);
+        assert_eq!(rule.check(&transaction(vec![], vec![0; 2]), peer), Ok(()));
+        assert_eq!(rule.check(&transaction(vec![], vec![0; 3]), peer), Err(PolicyRejectReason::PayloadTooLarge(3, 2)));
+
+        let rule = ScriptClassRule::new(vec![ScriptClass::ScriptHash]);
+        assert_eq!(
+            rule.check(&transaction(outputs.clone(), vec![]), peer),
+            Err(PolicyRejectReason::BlockedScriptClass(1, ScriptClass::ScriptHash.to_string()))
+        );
+        assert_eq!(rule.check(&transaction(outputs[..1].to_vec(), vec![]), peer), Ok(()));
+
+        // The chain reports the first broken rule
+        let policy = MempoolPolicy::new().with_rule(PayloadSizeRule::new(0)).with_rule(DustRule::new(600));
+        assert_eq!(policy.check(&transaction(outputs.clone(), vec![]), peer), Err(PolicyRejectReason::DustOutput(1, 500, 600)));
+        assert_eq!(policy.check(&transaction(outputs, vec![0]), peer), Err(PolicyRejectReason::PayloadTooLarge(1, 0)));
+    }
+
+    #[test]
+    fn test_peer_rate_limit_rule() {
+        let rule = PeerRateLimitRule::new(2, 4);
+        let (peer, other_peer) = (IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)), IpAddr::V4(Ipv4Addr::new(2, 2, 2, 2)));
+        let now = Instant::now();
+
+        // A burst is accepted, then the peer has to wait for its bucket to be refilled
+        (0..4).for_each(|_| assert_eq!(rule.check_at(peer, now), Ok(())));
+        assert_eq!(rule.check_at(peer, now), Err(PolicyRejectReason::PeerRateLimited(2)));
+        assert_eq!(rule.check_at(other_peer, now), Ok(()));
+        assert_eq!(rule.check_at(peer, now + Duration::from_millis(500)), Ok(()));
+        assert_eq!(rule.check_at(peer, now + Duration::from_millis(500)), Err(PolicyRejectReason::PeerRateLimited(2)));
+
+        // Local transactions are never limited
+        let tx = transaction(vec![], vec![]);
+        (0..10).for_each(|_| assert_eq!(rule.check(&tx, TransactionSource::Local), Ok(())));
+    }
+}
@@ -1,10 +1,13 @@
 use crate::mempool::{
     errors::{RuleError, RuleResult},
-    model::tx::{DoubleSpend, MempoolTransaction, TxRemovalReason},
+    model::{
+        pool::Pool,
+        tx::{DoubleSpend, MempoolTransaction, TxRemovalReason},
+    },
     tx::RbfPolicy,
     Mempool,
 };
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
 use vecno_consensus_core::tx::{MutableTransaction, Transaction};
 
 impl Mempool {
@@ -84,6 +87,7 @@ impl Mempool {
                             self.validate_double_spending_transaction(transaction, double_spend)?;
                         }
                         // We apply consequences such as removal only after we fully validate against all double spends
+                        let transaction_pool_len_before = self.transaction_pool.len();
                         for double_spend in double_spends {
                             self.remove_transaction(
                                 &double_spend.owner_id,
@@ -92,6 +96,7 @@ impl Mempool {
                                 format!("by {}", transaction.id()).as_str(),
                             )?;
                         }
+                        self.count_replaced_transactions(transaction_pool_len_before);
                         Ok(Some(removed))
                     }
                 }
@@ -103,12 +108,14 @@ impl Mempool {
                     0 => Err(RuleError::RejectRbfNoDoubleSpend),
                     1 => {
                         let removed = self.validate_double_spending_transaction(transaction, &double_spends[0])?.mtx.tx.clone();
+                        let transaction_pool_len_before = self.transaction_pool.len();
                         self.remove_transaction(
                             &double_spends[0].owner_id,
                             true,
                             TxRemovalReason::ReplacedByFee,
                             format!("by {}", transaction.id()).as_str(),
                         )?;
+                        self.count_replaced_transactions(transaction_pool_len_before);
                         Ok(Some(removed))
                     }
                     _ => Err(RuleError::RejectRbfTooManyDoubleSpendingTransactions),
@@ -117,6 +124,13 @@ impl Mempool {
         }
     }
 
+    /// Counts the transactions removed from the pool by a replacement, including the redeemers of the replaced transactions
+    fn count_replaced_transactions(&self, transaction_pool_len_before: usize) {
+        self.counters
+            .tx_replaced_counts
+            .fetch_add(transaction_pool_len_before.saturating_sub(self.transaction_pool.len()) as u64, Ordering::Relaxed);
+    }
+
     fn get_double_spend_feerate(&self, double_spend: &DoubleSpend) -> RuleResult<f64> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         match owner.mtx.calculated_feerate() {
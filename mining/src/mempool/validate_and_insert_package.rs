@@ -0,0 +1,151 @@
+use crate::mempool::{
+    errors::{RuleError, RuleResult},
+    model::{pool::Pool, tx::TransactionPreValidation},
+    tx::{Priority, RbfPolicy},
+    Mempool,
+};
+use std::{collections::HashSet, sync::Arc};
+use vecno_consensus_core::{
+    api::ConsensusApi,
+    constants::UNACCEPTED_DAA_SCORE,
+    tx::{MutableTransaction, Transaction, UtxoEntry},
+};
+use vecno_core::debug;
+
+impl Mempool {
+    /// Checks that a package holds a limited amount of distinct transactions, sorted topologically
+    /// and not double spending each other.
+    pub(crate) fn validate_package_structure(&self, transactions: &[MutableTransaction]) -> RuleResult<()> {
+        if transactions.is_empty() {
+            return Err(RuleError::RejectEmptyPackage);
+        }
+        if transactions.len() > self.config.maximum_package_transaction_count {
+            return Err(RuleError::RejectPackageTooLarge(transactions.len(), self.config.maximum_package_transaction_count));
+        }
+
+        let package_ids = transactions.iter().map(|tx| tx.id()).collect::<HashSet<_>>();
+        let mut visited_ids = HashSet::with_capacity(transactions.len());
+        let mut spent_outpoints = HashSet::new();
+        for transaction in transactions.iter() {
+            let transaction_id = transaction.id();
+            if !visited_ids.insert(transaction_id) {
+                return Err(RuleError::RejectPackageDuplicate(transaction_id));
+            }
+            for input in transaction.tx.inputs.iter() {
+                let parent_id = input.previous_outpoint.transaction_id;
+                if package_ids.contains(&parent_id) && !visited_ids.contains(&parent_id) {
+                    return Err(RuleError::RejectPackageNotSorted(transaction_id, parent_id));
+                }
+                if !spent_outpoints.insert(input.previous_outpoint) {
+                    return Err(RuleError::RejectPackageDoubleSpend(input.previous_outpoint, transaction_id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pre-validates every transaction of a package and populates the UTXO entries of the inputs spending
+    /// either a mempool transaction or a preceding transaction of the package.
+    ///
+    /// Double spends of mempool transactions are rejected since a package cannot replace transactions by fee.
+    pub(crate) fn pre_validate_and_populate_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+    ) -> RuleResult<Vec<MutableTransaction>> {
+        self.validate_package_structure(&transactions)?;
+
+        let mut populated_transactions: Vec<MutableTransaction> = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            let TransactionPreValidation { mut transaction, .. } = self
+                .pre_validate_and_populate_transaction(consensus, transaction, RbfPolicy::Forbidden)
+                .map_err(|err| RuleError::RejectPackageTransaction(transaction_id, Box::new(err)))?;
+            for (i, input) in transaction.tx.inputs.iter().enumerate() {
+                // Since the package is sorted, the parent of the input in the package was already populated
+                if let Some(parent) = populated_transactions.iter().find(|tx| tx.id() == input.previous_outpoint.transaction_id) {
+                    let output = parent.tx.outputs.get(input.previous_outpoint.index as usize).ok_or_else(|| {
+                        RuleError::RejectPackageTransaction(transaction_id, Box::new(RuleError::RejectMissingOutpoint))
+                    })?;
+                    transaction.entries[i] =
+                        Some(UtxoEntry::new(output.value, output.script_public_key.clone(), UNACCEPTED_DAA_SCORE, false));
+                }
+            }
+            populated_transactions.push(transaction);
+        }
+        Ok(populated_transactions)
+    }
+
+    /// Returns whether some validated transaction of the package pays less than the minimum relay fee on its own,
+    /// in which case peers can only accept it as part of the package.
+    pub(crate) fn requires_package_relay(&self, transactions: &[MutableTransaction]) -> bool {
+        transactions.iter().any(|transaction| self.check_transaction_relay_fee(transaction).is_err())
+    }
+
+    /// Inserts the validated transactions of a package into the mempool, all or none of them.
+    ///
+    /// The relay fee is required from the package as a whole and the package feerate is the one
+    /// competing for room in a full mempool, so a low fee transaction can be accepted thanks to its
+    /// descendants in the package.
+    ///
+    /// Returns the accepted transactions, in package order.
+    pub(crate) fn post_validate_and_insert_package(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<MutableTransaction>,
+        priority: Priority,
+    ) -> RuleResult<Vec<Arc<Transaction>>> {
+        // The mempool may have changed since the pre-validation, so the checks against its content are repeated
+        let virtual_daa_score = consensus.get_virtual_daa_score();
+        for transaction in transactions.iter() {
+            let transaction_id = transaction.id();
+            let wrap = |err: RuleError| RuleError::RejectPackageTransaction(transaction_id, Box::new(err));
+            if self.transaction_pool.has(&transaction_id) {
+                return Err(wrap(RuleError::RejectDuplicate(transaction_id)));
+            }
+            self.validate_transaction_unacceptance(transaction).map_err(wrap)?;
+            self.transaction_pool.check_double_spends(transaction).map_err(wrap)?;
+            self.validate_package_transaction_in_context(transaction, virtual_daa_score).map_err(wrap)?;
+        }
+
+        let package_fee: u64 = transactions.iter().map(|tx| tx.calculated_fee.unwrap()).sum();
+        if !self.config.accept_non_standard {
+            // Like for a single transaction, the fee is only required as a function of the compute mass
+            let package_compute_mass = transactions.iter().map(|tx| tx.calculated_compute_mass.unwrap()).sum();
+            let minimum_fee = self.minimum_required_transaction_relay_fee(package_compute_mass);
+            if package_fee < minimum_fee {
+                return Err(RuleError::RejectPackageInsufficientFee(package_fee, minimum_fee));
+            }
+        }
+        let package_mass: u64 = transactions.iter().map(|tx| tx.tx.mass()).sum();
+        let package_feerate = package_fee as f64 / package_mass as f64;
+
+        // Make room for the whole package at once, so that nothing is evicted if it does not fit
+        let package_size = transactions.iter().map(|tx| tx.mempool_estimated_bytes()).sum();
+        self.make_room_for_transactions(&transactions, package_size, package_feerate)?;
+
+        let mut accepted_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let transaction_size = transaction.mempool_estimated_bytes();
+            match self.transaction_pool.add_transaction(transaction, virtual_daa_score, priority, transaction_size) {
+                Ok(tx) => accepted_transactions.push(tx.mtx.tx.clone()),
+                Err(err) => {
+                    self.rollback_package_insertion(&accepted_transactions);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(accepted_transactions)
+    }
+
+    /// Removes the already inserted transactions of a rejected package, in reverse order so that each of them
+    /// is unchained when removed
+    fn rollback_package_insertion(&mut self, inserted_transactions: &[Arc<Transaction>]) {
+        for transaction in inserted_transactions.iter().rev() {
+            let transaction_id = transaction.id();
+            if let Err(err) = self.transaction_pool.remove_transaction(&transaction_id) {
+                debug!("Failed to roll back the insertion of package transaction {}: {}", transaction_id, err);
+            }
+        }
+    }
+}
@@ -70,7 +70,7 @@ impl Mempool {
         }
 
         // Perform mempool in-context validations prior to possible RBF replacements
-        self.validate_transaction_in_context(&transaction)?;
+        self.validate_transaction_in_context(&transaction, consensus.get_virtual_daa_score())?;
 
         // Check double spends and try to remove them if the RBF policy requires it
         let removed_transaction = self.execute_replace_by_fee(&transaction, rbf_policy)?;
@@ -85,21 +85,46 @@ impl Mempool {
 
         // Before adding the transaction, check if there is room in the pool
         let transaction_size = transaction.mempool_estimated_bytes();
-        let txs_to_remove = self.transaction_pool.limit_transaction_count(&transaction, transaction_size)?;
+        self.make_room_for_transactions(
+            std::slice::from_ref(&transaction),
+            transaction_size,
+            transaction.calculated_feerate().unwrap(),
+        )?;
+
+        // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
+        let accepted_transaction = self
+            .transaction_pool
+            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
+            .mtx
+            .tx
+            .clone();
+        Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
+    }
+
+    /// Evicts low-priority transactions having a feerate lower than `feerate_threshold` until the pool
+    /// has room for `transactions`, of overall size `transactions_size`. Nothing is evicted if there
+    /// cannot be room for all of them.
+    pub(crate) fn make_room_for_transactions(
+        &mut self,
+        transactions: &[MutableTransaction],
+        transactions_size: usize,
+        feerate_threshold: f64,
+    ) -> RuleResult<()> {
+        let txs_to_remove = self.transaction_pool.limit_transaction_count(transactions, transactions_size, feerate_threshold)?;
         if !txs_to_remove.is_empty() {
             let transaction_pool_len_before = self.transaction_pool.len();
             for x in txs_to_remove.iter() {
-                self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str())?;
-                // self.transaction_pool.limit_transaction_count(&transaction) returns the
+                self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transactions[0].id()).as_str())?;
+                // self.transaction_pool.limit_transaction_count(&transactions) returns the
                 // smallest prefix of `ready_transactions` (sorted by ascending fee-rate)
-                // that makes enough room for `transaction`, but since each call to `self.remove_transaction`
+                // that makes enough room for `transactions`, but since each call to `self.remove_transaction`
                 // also removes all transactions dependant on `x` we might already have sufficient space, so
                 // we constantly check the break condition.
                 //
-                // Note that self.transaction_pool.len() < self.config.maximum_transaction_count means we have
-                // at least one available slot in terms of the count limit
-                if self.transaction_pool.len() < self.config.maximum_transaction_count
-                    && self.transaction_pool.get_estimated_size() + transaction_size <= self.config.mempool_size_limit
+                // Note that self.transaction_pool.len() + transactions.len() <= self.config.maximum_transaction_count
+                // means we have enough available slots in terms of the count limit
+                if self.transaction_pool.len() + transactions.len() <= self.config.maximum_transaction_count
+                    && self.transaction_pool.get_estimated_size() + transactions_size <= self.config.mempool_size_limit
                 {
                     break;
                 }
@@ -110,27 +135,19 @@ impl Mempool {
         }
 
         assert!(
-            self.transaction_pool.len() < self.config.maximum_transaction_count
-                && self.transaction_pool.get_estimated_size() + transaction_size <= self.config.mempool_size_limit,
+            self.transaction_pool.len() + transactions.len() <= self.config.maximum_transaction_count
+                && self.transaction_pool.get_estimated_size() + transactions_size <= self.config.mempool_size_limit,
             "Transactions in mempool: {}, max: {}, mempool bytes size: {}, max: {}",
-            self.transaction_pool.len() + 1,
+            self.transaction_pool.len() + transactions.len(),
             self.config.maximum_transaction_count,
-            self.transaction_pool.get_estimated_size() + transaction_size,
+            self.transaction_pool.get_estimated_size() + transactions_size,
             self.config.mempool_size_limit,
         );
-
-        // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
-        let accepted_transaction = self
-            .transaction_pool
-            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
-            .mtx
-            .tx
-            .clone();
-        Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
+        Ok(())
     }
 
     /// Validates that the transaction wasn't already accepted into the DAG
-    fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(crate) fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // Reject if the transaction is registered as an accepted transaction
         let transaction_id = transaction.id();
         match self.accepted_transactions.has(&transaction_id) {
@@ -151,7 +168,21 @@ impl Mempool {
         Ok(())
     }
 
-    fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    fn validate_transaction_in_context(&self, transaction: &MutableTransaction, virtual_daa_score: u64) -> RuleResult<()> {
+        self.validate_package_transaction_in_context(transaction, virtual_daa_score)?;
+        if !self.config.accept_non_standard {
+            self.check_transaction_relay_fee(transaction)?;
+        }
+        Ok(())
+    }
+
+    /// Performs the in-context validations of a transaction, except for its relay fee which, for a transaction
+    /// being part of a package, is checked against the fee of the whole package
+    pub(crate) fn validate_package_transaction_in_context(
+        &self,
+        transaction: &MutableTransaction,
+        virtual_daa_score: u64,
+    ) -> RuleResult<()> {
         // TEMP: apply parts of go-vecnod mempool dust prevention patch
         let has_coinbase_input = transaction.entries.iter().any(|e| e.as_ref().unwrap().is_coinbase);
         let num_extra_outs = transaction.tx.outputs.len() as i64 - transaction.tx.inputs.len() as i64;
@@ -164,7 +195,7 @@ impl Mempool {
         }
 
         if !self.config.accept_non_standard {
-            self.check_transaction_standard_in_context(transaction)?;
+            self.check_transaction_standard_in_context(transaction, virtual_daa_score)?;
         }
         Ok(())
     }
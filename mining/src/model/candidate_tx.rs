@@ -1,4 +1,4 @@
-use crate::FeerateTransactionKey;
+use crate::{DescendantPackage, FeerateTransactionKey};
 use std::sync::Arc;
 use vecno_consensus_core::tx::Transaction;
 
@@ -12,10 +12,17 @@ pub struct CandidateTransaction {
     pub calculated_fee: u64,
     /// Populated mass
     pub calculated_mass: u64,
+    /// The package formed by the transaction and its mempool descendants
+    pub package: DescendantPackage,
 }
 
 impl CandidateTransaction {
     pub fn from_key(key: FeerateTransactionKey) -> Self {
-        Self { tx: key.tx, calculated_fee: key.fee, calculated_mass: key.mass }
+        Self { tx: key.tx, calculated_fee: key.fee, calculated_mass: key.mass, package: key.package }
+    }
+
+    /// The feerate by which the transaction is prioritized, see [`FeerateTransactionKey::effective_feerate`]
+    pub fn effective_feerate(&self) -> f64 {
+        (self.calculated_fee as f64 / self.calculated_mass as f64).max(self.package.feerate())
     }
 }
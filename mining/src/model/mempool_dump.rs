@@ -0,0 +1,170 @@
+use crate::mempool::{config::Config, tx::Priority};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+use thiserror::Error;
+use vecno_consensus_core::tx::{Transaction, TransactionId};
+
+/// The name of the mempool dump file, saved in the node data directory
+pub const MEMPOOL_DUMP_FILE_NAME: &str = "mempool.dat";
+
+/// The current version of the mempool dump file format
+pub const MEMPOOL_DUMP_VERSION: u16 = 1;
+
+/// The bytes starting a mempool dump file, followed by its version
+const MEMPOOL_DUMP_MAGIC: [u8; 4] = *b"VMPD";
+
+#[derive(Error, Debug)]
+pub enum MempoolDumpError {
+    #[error("mempool dump i/o error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("the file is not a mempool dump")]
+    InvalidMagic,
+
+    #[error("unsupported mempool dump version {0}, expected version {MEMPOOL_DUMP_VERSION}")]
+    UnsupportedVersion(u16),
+
+    #[error("corrupted mempool dump: {0}")]
+    Serialization(#[from] bincode::Error),
+}
+
+pub type MempoolDumpResult<T> = std::result::Result<T, MempoolDumpError>;
+
+/// A mempool transaction along with the state needed to restore it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DumpedTransaction {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    pub is_orphan: bool,
+    /// The virtual DAA score when the transaction entered the mempool, from which its expiration is measured
+    pub added_at_daa_score: u64,
+}
+
+impl AsRef<Transaction> for DumpedTransaction {
+    fn as_ref(&self) -> &Transaction {
+        &self.transaction
+    }
+}
+
+/// A snapshot of the mempool, saved when the node shuts down and restored on startup
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MempoolDump {
+    /// The transactions of both the transaction pool and the orphan pool
+    pub transactions: Vec<DumpedTransaction>,
+    /// The ids of recently accepted transactions along with the DAA score of their acceptance
+    pub accepted_transactions: Vec<(TransactionId, u64)>,
+}
+
+impl MempoolDump {
+    /// Drops the entries the mempool would have expired at `virtual_daa_score`, returning their count.
+    ///
+    /// Like in the mempool, high priority transactions never expire.
+    pub(crate) fn drop_expired(&mut self, config: &Config, virtual_daa_score: u64) -> usize {
+        let count = self.transactions.len() + self.accepted_transactions.len();
+        self.transactions.retain(|entry| {
+            let expire_interval =
+                if entry.is_orphan { config.orphan_expire_interval_daa_score } else { config.transaction_expire_interval_daa_score };
+            entry.priority == Priority::High || virtual_daa_score <= entry.added_at_daa_score + expire_interval
+        });
+        self.accepted_transactions
+            .retain(|(_, daa_score)| virtual_daa_score <= daa_score + config.accepted_transaction_expire_interval_daa_score);
+        count - self.transactions.len() - self.accepted_transactions.len()
+    }
+
+    /// Saves the dump to `path`, replacing any previous dump
+    pub fn save(&self, path: &Path) -> MempoolDumpResult<()> {
+        // Write a temporary file first so that an interrupted save never leaves a truncated dump behind
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, self.to_bytes()?)?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// Loads the dump saved at `path`, if any
+    pub fn load(path: &Path) -> MempoolDumpResult<Option<Self>> {
+        match fs::read(path) {
+            Ok(bytes) => Self::from_bytes(&bytes).map(Some),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn to_bytes(&self) -> MempoolDumpResult<Vec<u8>> {
+        let mut bytes = MEMPOOL_DUMP_MAGIC.to_vec();
+        bytes.extend(MEMPOOL_DUMP_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, self)?;
+        Ok(bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> MempoolDumpResult<Self> {
+        let payload = bytes.strip_prefix(MEMPOOL_DUMP_MAGIC.as_slice()).ok_or(MempoolDumpError::InvalidMagic)?;
+        let (version, payload) = payload.split_at_checked(size_of::<u16>()).ok_or(MempoolDumpError::InvalidMagic)?;
+        let version = u16::from_le_bytes(version.try_into().unwrap());
+        if version != MEMPOOL_DUMP_VERSION {
+            return Err(MempoolDumpError::UnsupportedVersion(version));
+        }
+        Ok(bincode::deserialize(payload)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vecno_consensus_core::{subnets::SUBNETWORK_ID_NATIVE, tx::TransactionOutput};
+    use vecno_hashes::Hash;
+
+    fn dumped_transaction(value: u64, priority: Priority, is_orphan: bool, added_at_daa_score: u64) -> DumpedTransaction {
+        let outputs = vec![TransactionOutput::new(value, Default::default())];
+        let transaction = Transaction::new(0, vec![], outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        DumpedTransaction { transaction, priority, is_orphan, added_at_daa_score }
+    }
+
+    #[test]
+    fn test_mempool_dump_expiration() {
+        let config = Config::build_default(1_000, false, 500_000);
+        let virtual_daa_score = 10 * config.transaction_expire_interval_daa_score;
+        let mut dump = MempoolDump {
+            transactions: vec![
+                dumped_transaction(1, Priority::High, false, 0),
+                dumped_transaction(2, Priority::Low, false, 0),
+                dumped_transaction(3, Priority::Low, false, virtual_daa_score - config.transaction_expire_interval_daa_score),
+                dumped_transaction(4, Priority::Low, true, virtual_daa_score - config.orphan_expire_interval_daa_score - 1),
+                dumped_transaction(5, Priority::Low, true, virtual_daa_score - config.orphan_expire_interval_daa_score),
+                dumped_transaction(6, Priority::High, true, 0),
+            ],
+            accepted_transactions: vec![
+                (Hash::from_u64_word(1), 0),
+                (Hash::from_u64_word(2), virtual_daa_score - config.accepted_transaction_expire_interval_daa_score),
+            ],
+        };
+
+        assert_eq!(dump.drop_expired(&config, virtual_daa_score), 3);
+        assert_eq!(dump.transactions.iter().map(|entry| entry.transaction.outputs[0].value).collect::<Vec<_>>(), vec![1, 3, 5, 6]);
+        assert_eq!(dump.accepted_transactions.len(), 1);
+        assert_eq!(dump.accepted_transactions[0].0, Hash::from_u64_word(2));
+    }
+
+    #[test]
+    fn test_mempool_dump_encoding() {
+        let dump = MempoolDump {
+            transactions: vec![dumped_transaction(1, Priority::High, false, 7), dumped_transaction(2, Priority::Low, true, 8)],
+            accepted_transactions: vec![(Hash::from_u64_word(3), 9)],
+        };
+        let entries = |dump: &MempoolDump| {
+            dump.transactions
+                .iter()
+                .map(|entry| (entry.transaction.id(), entry.priority, entry.is_orphan, entry.added_at_daa_score))
+                .collect::<Vec<_>>()
+        };
+        let bytes = dump.to_bytes().unwrap();
+        let decoded = MempoolDump::from_bytes(&bytes).unwrap();
+        assert_eq!(entries(&decoded), entries(&dump));
+        assert_eq!(decoded.accepted_transactions, dump.accepted_transactions);
+
+        let mut other_version = bytes.clone();
+        other_version[MEMPOOL_DUMP_MAGIC.len()] += 1;
+        assert!(matches!(MempoolDump::from_bytes(&other_version), Err(MempoolDumpError::UnsupportedVersion(2))));
+        assert!(matches!(MempoolDump::from_bytes(&bytes[1..]), Err(MempoolDumpError::InvalidMagic)));
+        assert!(matches!(MempoolDump::from_bytes(&bytes[..bytes.len() - 1]), Err(MempoolDumpError::Serialization(_))));
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 use vecno_consensus_core::tx::TransactionId;
 
 pub mod candidate_tx;
+pub mod mempool_dump;
 pub mod owner_txs;
 pub mod topological_index;
 pub mod topological_sort;
@@ -12,3 +12,20 @@ impl TransactionInsertion {
         Self { removed, accepted }
     }
 }
+
+/// The outcome of the insertion of a transaction package into the mempool
+#[derive(Debug)]
+pub struct TransactionPackageInsertion {
+    /// The accepted package transactions in package order, followed by the transactions they unorphaned
+    pub accepted: Vec<Arc<Transaction>>,
+
+    /// Whether some package transaction does not pay the minimum relay fee on its own, so that peers
+    /// can only accept it along with its package rather than through the regular transaction relay
+    pub requires_package_relay: bool,
+}
+
+impl TransactionPackageInsertion {
+    pub fn new(accepted: Vec<Arc<Transaction>>, requires_package_relay: bool) -> Self {
+        Self { accepted, requires_package_relay }
+    }
+}
@@ -1,5 +1,6 @@
 use super::MiningCounters;
-use crate::manager::MiningManagerProxy;
+use crate::{manager::MiningManagerProxy, mempool::errors::PolicyReasonCode};
+use itertools::Itertools;
 use std::{sync::Arc, time::Duration};
 use vecno_core::{
     debug, info,
@@ -75,6 +76,20 @@ impl MiningMonitor {
                     delta.tx_evicted_counts
                 );
             }
+            if delta.tx_replaced_counts > 0 {
+                info!("Mempool stats: {} transactions were replaced by fee", delta.tx_replaced_counts);
+            }
+            if delta.policy_rejected_total_count() > 0 {
+                info!(
+                    "Mempool policy stats: {} transactions were rejected ({})",
+                    delta.policy_rejected_total_count(),
+                    PolicyReasonCode::ALL
+                        .iter()
+                        .filter(|code| delta.policy_rejected_count(**code) > 0)
+                        .map(|code| format!("{}: {}", code, delta.policy_rejected_count(*code)))
+                        .join(", ")
+                );
+            }
             if tx_script_cache_snapshot != last_tx_script_cache_snapshot {
                 debug!(
                     "UTXO set stats: {} spent, {} created ({} signatures validated, {} cache hits, {:.2} hit ratio)",
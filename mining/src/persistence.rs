@@ -0,0 +1,73 @@
+use crate::{manager::MiningManagerProxy, model::mempool_dump::MempoolDump};
+use std::{path::PathBuf, sync::Arc};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+
+const PERSISTENCE: &str = "mempool-persistence";
+
+/// Restores the mempool from a dump file on startup and saves it back to the file on shutdown,
+/// so that pending transactions survive node restarts.
+pub struct MempoolPersistence {
+    mining_manager: MiningManagerProxy,
+    consensus_manager: Arc<ConsensusManager>,
+    path: PathBuf,
+}
+
+impl MempoolPersistence {
+    pub fn new(mining_manager: MiningManagerProxy, consensus_manager: Arc<ConsensusManager>, path: PathBuf) -> Self {
+        Self { mining_manager, consensus_manager, path }
+    }
+
+    async fn restore(&self) {
+        let dump = match MempoolDump::load(&self.path) {
+            Ok(Some(dump)) => dump,
+            Ok(None) => return,
+            Err(err) => {
+                warn!("Failed to load the mempool dump {}: {}", self.path.display(), err);
+                return;
+            }
+        };
+        let dumped_count = dump.transactions.len();
+        let session = self.consensus_manager.consensus().session().await;
+        let restored_count = self.mining_manager.clone().restore_mempool(&session, dump).await;
+        info!("Restored {} out of {} mempool transactions from {}", restored_count, dumped_count, self.path.display());
+    }
+
+    async fn save(&self) {
+        let dump = self.mining_manager.clone().dump_mempool().await;
+        match dump.save(&self.path) {
+            Ok(()) => info!("Saved {} mempool transactions to {}", dump.transactions.len(), self.path.display()),
+            Err(err) => warn!("Failed to save the mempool dump {}: {}", self.path.display(), err),
+        }
+    }
+}
+
+// service trait implementation for MempoolPersistence
+impl AsyncService for MempoolPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        PERSISTENCE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.restore().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", PERSISTENCE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.save().await;
+            trace!("{} stopped", PERSISTENCE);
+            Ok(())
+        })
+    }
+}
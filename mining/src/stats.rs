@@ -0,0 +1,204 @@
+//!
+//! Time-series statistics of the mempool.
+//!
+//! The [`MempoolStatsCollector`] service samples the state of the mempool at a fixed interval and
+//! records every sample in a bounded history, exposing how the mempool evolves over the last hour.
+//!
+
+use crate::manager::MiningManagerProxy;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use vecno_core::{
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace,
+};
+
+/// The interval between two mempool statistics samples
+pub const MEMPOOL_STATS_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The number of samples kept in the history, covering one hour
+pub const MEMPOOL_STATS_HISTORY_SIZE: usize = 360;
+
+/// The lower bounds, in sompi/gram, of the buckets of the feerate histogram.
+///
+/// Every bucket spans from its lower bound to the lower bound of the next bucket, the last one being unbounded.
+pub const MEMPOOL_STATS_FEERATE_BUCKETS: [f64; 12] = [0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 10000.0];
+
+/// Returns the index of the feerate histogram bucket containing `feerate`
+pub(crate) fn feerate_bucket_index(feerate: f64) -> usize {
+    MEMPOOL_STATS_FEERATE_BUCKETS.iter().rposition(|lower_bound| feerate >= *lower_bound).unwrap_or_default()
+}
+
+/// The state of the mempool at a given time
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MempoolStatsSample {
+    /// The time of the sample, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// The number of transactions in the transaction pool, orphans excluded
+    pub transaction_count: u64,
+    /// The total mass of the transactions in the transaction pool
+    pub total_mass: u64,
+    /// The number of orphan transactions
+    pub orphan_count: u64,
+    /// The number of transactions evicted to make room for higher feerate transactions since the previous sample
+    pub evicted_count: u64,
+    /// The number of transactions replaced by fee since the previous sample
+    pub replaced_count: u64,
+    /// The number of transactions rejected by the mempool policy since the previous sample
+    pub policy_rejected_count: u64,
+    /// The number of transactions of the transaction pool in every bucket of [`MEMPOOL_STATS_FEERATE_BUCKETS`]
+    pub feerate_histogram: Vec<u64>,
+}
+
+/// A bounded history of mempool statistics samples, the oldest samples being dropped first
+pub(crate) struct MempoolStatsHistory {
+    capacity: usize,
+    samples: VecDeque<MempoolStatsSample>,
+
+    // The cumulative counters at the time of the last sample
+    last_evicted_count: u64,
+    last_replaced_count: u64,
+    last_policy_rejected_count: u64,
+}
+
+impl MempoolStatsHistory {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            last_evicted_count: 0,
+            last_replaced_count: 0,
+            last_policy_rejected_count: 0,
+        }
+    }
+
+    /// Records a sample, turning the cumulative eviction, replacement and policy rejection counters into counts
+    /// since the previous sample
+    pub(crate) fn record(
+        &mut self,
+        mut sample: MempoolStatsSample,
+        evicted_count: u64,
+        replaced_count: u64,
+        policy_rejected_count: u64,
+    ) -> MempoolStatsSample {
+        sample.evicted_count = evicted_count.saturating_sub(self.last_evicted_count);
+        sample.replaced_count = replaced_count.saturating_sub(self.last_replaced_count);
+        sample.policy_rejected_count = policy_rejected_count.saturating_sub(self.last_policy_rejected_count);
+        self.last_evicted_count = evicted_count;
+        self.last_replaced_count = replaced_count;
+        self.last_policy_rejected_count = policy_rejected_count;
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample.clone());
+        sample
+    }
+
+    /// Returns the recorded samples, from the oldest to the most recent, keeping only the `limit` most recent ones if provided
+    pub(crate) fn samples(&self, limit: Option<usize>) -> Vec<MempoolStatsSample> {
+        let skip = limit.map_or(0, |limit| self.samples.len().saturating_sub(limit));
+        self.samples.iter().skip(skip).cloned().collect()
+    }
+}
+
+const COLLECTOR: &str = "mempool-stats-collector";
+
+/// Service sampling the mempool statistics at [`MEMPOOL_STATS_SAMPLE_INTERVAL`]
+pub struct MempoolStatsCollector {
+    mining_manager: MiningManagerProxy,
+
+    // Tick service
+    tick_service: Arc<TickService>,
+}
+
+impl MempoolStatsCollector {
+    pub fn new(mining_manager: MiningManagerProxy, tick_service: Arc<TickService>) -> MempoolStatsCollector {
+        MempoolStatsCollector { mining_manager, tick_service }
+    }
+
+    pub async fn worker(self: &Arc<MempoolStatsCollector>) {
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(MEMPOOL_STATS_SAMPLE_INTERVAL).await {
+                break;
+            }
+            let sample = self.mining_manager.clone().sample_mempool_stats().await;
+            trace!(
+                "Mempool stats sample: {} txs, {} mass, {} orphans, {} evicted, {} replaced, {} rejected by policy",
+                sample.transaction_count,
+                sample.total_mass,
+                sample.orphan_count,
+                sample.evicted_count,
+                sample.replaced_count,
+                sample.policy_rejected_count
+            );
+        }
+
+        trace!("{} thread exiting", COLLECTOR);
+    }
+}
+
+// service trait implementation for the collector
+impl AsyncService for MempoolStatsCollector {
+    fn ident(self: Arc<Self>) -> &'static str {
+        COLLECTOR
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", COLLECTOR);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", COLLECTOR);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feerate_bucket_index() {
+        assert_eq!(feerate_bucket_index(0.5), 0);
+        assert_eq!(feerate_bucket_index(1.0), 1);
+        assert_eq!(feerate_bucket_index(4.99), 2);
+        assert_eq!(feerate_bucket_index(1000.0), 10);
+        assert_eq!(feerate_bucket_index(1e9), MEMPOOL_STATS_FEERATE_BUCKETS.len() - 1);
+        assert_eq!(feerate_bucket_index(f64::NAN), 0);
+    }
+
+    #[test]
+    fn test_mempool_stats_history() {
+        let mut history = MempoolStatsHistory::new(3);
+        let sample = |timestamp| MempoolStatsSample { timestamp, ..Default::default() };
+
+        let recorded = history.record(sample(1), 4, 1, 2);
+        assert_eq!((recorded.evicted_count, recorded.replaced_count, recorded.policy_rejected_count), (4, 1, 2));
+        let recorded = history.record(sample(2), 6, 1, 5);
+        assert_eq!((recorded.evicted_count, recorded.replaced_count, recorded.policy_rejected_count), (2, 0, 3));
+        history.record(sample(3), 6, 3, 5);
+        assert_eq!(history.samples(None).iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // The oldest sample is dropped once the history is full
+        history.record(sample(4), 7, 3, 5);
+        let samples = history.samples(None);
+        assert_eq!(samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!((samples[2].evicted_count, samples[2].replaced_count, samples[2].policy_rejected_count), (1, 0, 0));
+
+        // A limit keeps the most recent samples only
+        assert_eq!(history.samples(Some(2)).iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(history.samples(Some(10)).len(), 3);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::errors::StratumResult;
+use async_trait::async_trait;
+use vecno_consensus_core::{
+    block::{Block, BlockTemplate},
+    coinbase::MinerData,
+};
+
+/// The node side of the stratum server: a source of block templates and a sink for mined blocks
+#[async_trait]
+pub trait StratumBackend: Send + Sync {
+    /// Builds a block template whose coinbase pays to the given miner
+    async fn get_block_template(&self, miner_data: MinerData) -> StratumResult<BlockTemplate>;
+
+    /// Validates, inserts and relays a block mined by a stratum client
+    async fn submit_block(&self, block: Block) -> StratumResult<()>;
+}
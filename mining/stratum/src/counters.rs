@@ -0,0 +1,189 @@
+use crate::difficulty::difficulty_to_hashes;
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+pub struct StratumCounters {
+    pub creation_time: Instant,
+
+    // Counters
+    pub connections_opened: AtomicU64,
+    pub connections_closed: AtomicU64,
+    pub shares_accepted: AtomicU64,
+    pub shares_rejected: AtomicU64,
+    pub shares_stale: AtomicU64,
+    pub blocks_found: AtomicU64,
+    pub blocks_rejected: AtomicU64,
+    pub jobs_issued: AtomicU64,
+
+    /// The sum of the difficulties of the accepted shares, in thousandths of share difficulty 1
+    pub accepted_work: AtomicU64,
+
+    /// Share accounting per worker, keyed by `address.worker` as authorized by the miner
+    workers: Mutex<HashMap<String, WorkerStats>>,
+}
+
+impl Default for StratumCounters {
+    fn default() -> Self {
+        Self {
+            creation_time: Instant::now(),
+            connections_opened: Default::default(),
+            connections_closed: Default::default(),
+            shares_accepted: Default::default(),
+            shares_rejected: Default::default(),
+            shares_stale: Default::default(),
+            blocks_found: Default::default(),
+            blocks_rejected: Default::default(),
+            jobs_issued: Default::default(),
+            accepted_work: Default::default(),
+            workers: Default::default(),
+        }
+    }
+}
+
+/// The kinds of events accounted per worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareEvent {
+    Accepted { difficulty_millis: u64 },
+    Rejected,
+    Stale,
+    BlockFound,
+    BlockRejected,
+}
+
+impl StratumCounters {
+    pub fn snapshot(&self) -> StratumCountersSnapshot {
+        StratumCountersSnapshot {
+            elapsed_time: (Instant::now() - self.creation_time),
+            connections_opened: self.connections_opened.load(Ordering::Relaxed),
+            connections_closed: self.connections_closed.load(Ordering::Relaxed),
+            shares_accepted: self.shares_accepted.load(Ordering::Relaxed),
+            shares_rejected: self.shares_rejected.load(Ordering::Relaxed),
+            shares_stale: self.shares_stale.load(Ordering::Relaxed),
+            blocks_found: self.blocks_found.load(Ordering::Relaxed),
+            blocks_rejected: self.blocks_rejected.load(Ordering::Relaxed),
+            jobs_issued: self.jobs_issued.load(Ordering::Relaxed),
+            accepted_work: self.accepted_work.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Accounts a share event both globally and for the given worker
+    pub fn record(&self, worker: &str, event: ShareEvent) {
+        match event {
+            ShareEvent::Accepted { difficulty_millis } => {
+                self.shares_accepted.fetch_add(1, Ordering::Relaxed);
+                self.accepted_work.fetch_add(difficulty_millis, Ordering::Relaxed);
+            }
+            ShareEvent::Rejected => {
+                self.shares_rejected.fetch_add(1, Ordering::Relaxed);
+            }
+            ShareEvent::Stale => {
+                self.shares_stale.fetch_add(1, Ordering::Relaxed);
+            }
+            ShareEvent::BlockFound => {
+                self.blocks_found.fetch_add(1, Ordering::Relaxed);
+            }
+            ShareEvent::BlockRejected => {
+                self.blocks_rejected.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.workers.lock().entry(worker.to_owned()).or_default().record(event);
+    }
+
+    /// Returns the share accounting of all workers seen since startup
+    pub fn worker_stats(&self) -> Vec<(String, WorkerStats)> {
+        let mut stats: Vec<_> = self.workers.lock().iter().map(|(worker, stats)| (worker.clone(), stats.clone())).collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    pub fn active_connections(&self) -> u64 {
+        self.connections_opened.load(Ordering::Relaxed).saturating_sub(self.connections_closed.load(Ordering::Relaxed))
+    }
+}
+
+/// Converts a share difficulty to the fixed point unit of the work counters
+pub fn difficulty_to_millis(difficulty: f64) -> u64 {
+    (difficulty * 1000.0).round() as u64
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkerStats {
+    pub shares_accepted: u64,
+    pub shares_rejected: u64,
+    pub shares_stale: u64,
+    pub blocks_found: u64,
+    pub blocks_rejected: u64,
+    /// The sum of the difficulties of the accepted shares
+    pub accepted_difficulty: f64,
+    pub last_share_time: Option<Instant>,
+}
+
+impl WorkerStats {
+    fn record(&mut self, event: ShareEvent) {
+        match event {
+            ShareEvent::Accepted { difficulty_millis } => {
+                self.shares_accepted += 1;
+                self.accepted_difficulty += difficulty_millis as f64 / 1000.0;
+                self.last_share_time = Some(Instant::now());
+            }
+            ShareEvent::Rejected => self.shares_rejected += 1,
+            ShareEvent::Stale => self.shares_stale += 1,
+            ShareEvent::BlockFound => self.blocks_found += 1,
+            ShareEvent::BlockRejected => self.blocks_rejected += 1,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StratumCountersSnapshot {
+    pub elapsed_time: Duration,
+    pub connections_opened: u64,
+    pub connections_closed: u64,
+    pub shares_accepted: u64,
+    pub shares_rejected: u64,
+    pub shares_stale: u64,
+    pub blocks_found: u64,
+    pub blocks_rejected: u64,
+    pub jobs_issued: u64,
+    pub accepted_work: u64,
+}
+
+impl StratumCountersSnapshot {
+    /// Indicates whether this snapshot has any share activity which is worth logging
+    pub fn has_share_activity(&self) -> bool {
+        self.shares_accepted > 0 || self.shares_rejected > 0 || self.shares_stale > 0 || self.blocks_found > 0
+    }
+
+    /// Returns an estimate of the hashrate of all connected miners in hashes per second, derived from the accepted shares
+    pub fn hashrate(&self) -> f64 {
+        let elapsed = self.elapsed_time.as_secs_f64();
+        if elapsed != 0f64 {
+            difficulty_to_hashes(self.accepted_work as f64 / 1000.0) / elapsed
+        } else {
+            0f64
+        }
+    }
+}
+
+impl core::ops::Sub for &StratumCountersSnapshot {
+    type Output = StratumCountersSnapshot;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            elapsed_time: self.elapsed_time.saturating_sub(rhs.elapsed_time),
+            connections_opened: self.connections_opened.saturating_sub(rhs.connections_opened),
+            connections_closed: self.connections_closed.saturating_sub(rhs.connections_closed),
+            shares_accepted: self.shares_accepted.saturating_sub(rhs.shares_accepted),
+            shares_rejected: self.shares_rejected.saturating_sub(rhs.shares_rejected),
+            shares_stale: self.shares_stale.saturating_sub(rhs.shares_stale),
+            blocks_found: self.blocks_found.saturating_sub(rhs.blocks_found),
+            blocks_rejected: self.blocks_rejected.saturating_sub(rhs.blocks_rejected),
+            jobs_issued: self.jobs_issued.saturating_sub(rhs.jobs_issued),
+            accepted_work: self.accepted_work.saturating_sub(rhs.accepted_work),
+        }
+    }
+}
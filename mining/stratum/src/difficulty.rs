@@ -0,0 +1,61 @@
+//! Conversions between stratum share difficulties and pow targets.
+//!
+//! A share difficulty of `1` corresponds to the target `2^224`, i.e., to an expected amount of `2^32` hashes per share.
+
+use vecno_math::Uint256;
+
+/// The log2 of the target of a share difficulty of `1`
+const DIFFICULTY_ONE_TARGET_BITS: u32 = 224;
+
+/// The fixed point precision used for converting fractional difficulties to targets
+const DIFFICULTY_PRECISION_BITS: u32 = 31;
+
+/// Returns the pow target matching the given share difficulty
+pub fn difficulty_to_target(difficulty: f64) -> Uint256 {
+    // Large difficulties need no fractional precision, and would overflow the fixed point representation
+    let precision_bits = if difficulty < (1u64 << 32) as f64 { DIFFICULTY_PRECISION_BITS } else { 0 };
+    let scaled_difficulty = (difficulty * (1u64 << precision_bits) as f64).round();
+    if scaled_difficulty.is_nan() || scaled_difficulty < 1.0 {
+        return Uint256::MAX;
+    }
+    let scaled_difficulty = if scaled_difficulty >= u64::MAX as f64 { u64::MAX } else { scaled_difficulty as u64 };
+    (Uint256::from_u64(1) << (DIFFICULTY_ONE_TARGET_BITS + precision_bits)) / scaled_difficulty
+}
+
+/// Returns the share difficulty matching the given pow target
+pub fn target_to_difficulty(target: Uint256) -> f64 {
+    if target.is_zero() {
+        return f64::INFINITY;
+    }
+    2f64.powi(DIFFICULTY_ONE_TARGET_BITS as i32) / target.as_f64()
+}
+
+/// Returns the expected amount of hashes required for finding a share of the given difficulty
+pub fn difficulty_to_hashes(difficulty: f64) -> f64 {
+    difficulty * 2f64.powi(32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_target_conversions() {
+        assert_eq!(difficulty_to_target(1.0), Uint256::from_u64(1) << 224);
+        assert_eq!(difficulty_to_target(4.0), Uint256::from_u64(1) << 222);
+        assert_eq!(difficulty_to_target(0.5), Uint256::from_u64(1) << 225);
+        assert_eq!(difficulty_to_target(0.0), Uint256::MAX);
+        assert_eq!(difficulty_to_target(-1.0), Uint256::MAX);
+        assert_eq!(difficulty_to_target(f64::NAN), Uint256::MAX);
+
+        for difficulty in [1e-6, 0.001, 0.25, 1.0, 3.5, 1024.0, 123456.75, 1e10, 1e15] {
+            let roundtrip = target_to_difficulty(difficulty_to_target(difficulty));
+            assert!((roundtrip - difficulty).abs() / difficulty < 1e-3, "{difficulty} vs. {roundtrip}");
+        }
+
+        // Higher difficulties must always map to lower targets
+        assert!(difficulty_to_target(2.0) < difficulty_to_target(1.0));
+        assert!(difficulty_to_target(1.0) < difficulty_to_target(0.01));
+        assert!(difficulty_to_target(1e12) < difficulty_to_target(1e9));
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum StratumError {
+    #[error("the node is not synced")]
+    NotSynced,
+
+    #[error("block template error: {0}")]
+    BlockTemplate(String),
+
+    #[error("block was rejected: {0}")]
+    BlockRejected(String),
+
+    #[error("failed to listen on {0}: {1}")]
+    Listen(String, String),
+
+    #[error("no extranonce is available for a new connection")]
+    ExtranonceExhausted,
+}
+
+pub type StratumResult<T> = std::result::Result<T, StratumError>;
@@ -0,0 +1,124 @@
+//! Extranonce allocation: every connection gets a distinct prefix of the nonce space, so that
+//! connections mining the same block template never search the same nonces.
+
+use std::collections::HashSet;
+
+/// The maximal extranonce size in bytes, leaving at least half of the nonce to the miner
+pub const MAX_EXTRANONCE_SIZE: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Extranonce {
+    value: u64,
+    size: usize,
+}
+
+impl Extranonce {
+    /// The extranonce size in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The hex representation sent to the miner, `2 * size` characters long
+    pub fn to_hex(&self) -> String {
+        if self.size == 0 {
+            return String::new();
+        }
+        format!("{:0width$x}", self.value, width = self.size * 2)
+    }
+
+    /// Builds the full nonce from the nonce part searched by the miner, with the extranonce occupying
+    /// the most significant bytes
+    pub fn apply(&self, miner_nonce: u64) -> u64 {
+        if self.size == 0 {
+            return miner_nonce;
+        }
+        let miner_bits = 64 - 8 * self.size as u32;
+        (self.value << miner_bits) | (miner_nonce & ((1u64 << miner_bits) - 1))
+    }
+
+    /// The number of hex characters left to the miner in a submitted nonce
+    pub fn miner_nonce_hex_len(&self) -> usize {
+        16 - 2 * self.size
+    }
+}
+
+#[derive(Debug)]
+pub struct ExtranonceAllocator {
+    size: usize,
+    next: u64,
+    in_use: HashSet<u64>,
+}
+
+impl ExtranonceAllocator {
+    pub fn new(size: usize) -> Self {
+        assert!(size <= MAX_EXTRANONCE_SIZE, "the extranonce size must not exceed {MAX_EXTRANONCE_SIZE} bytes");
+        Self { size, next: 0, in_use: HashSet::new() }
+    }
+
+    fn capacity(&self) -> u64 {
+        1u64 << (8 * self.size)
+    }
+
+    /// Allocates an unused extranonce, or returns `None` if all extranonces are in use.
+    /// A zero size allocator disables extranonces and always returns the empty extranonce.
+    pub fn allocate(&mut self) -> Option<Extranonce> {
+        if self.size == 0 {
+            return Some(Extranonce { value: 0, size: 0 });
+        }
+        if self.in_use.len() as u64 >= self.capacity() {
+            return None;
+        }
+        while self.in_use.contains(&self.next) {
+            self.next = (self.next + 1) % self.capacity();
+        }
+        let value = self.next;
+        self.in_use.insert(value);
+        self.next = (self.next + 1) % self.capacity();
+        Some(Extranonce { value, size: self.size })
+    }
+
+    pub fn release(&mut self, extranonce: Extranonce) {
+        self.in_use.remove(&extranonce.value);
+    }
+
+    pub fn in_use(&self) -> usize {
+        self.in_use.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extranonce_allocation() {
+        let mut allocator = ExtranonceAllocator::new(1);
+        let extranonces: Vec<_> = (0..256).map(|_| allocator.allocate().unwrap()).collect();
+        assert_eq!(extranonces.iter().collect::<HashSet<_>>().len(), 256);
+        assert!(allocator.allocate().is_none());
+
+        allocator.release(extranonces[17]);
+        assert_eq!(allocator.allocate(), Some(extranonces[17]));
+        assert!(allocator.allocate().is_none());
+        assert_eq!(allocator.in_use(), 256);
+
+        // Extranonces are disabled with a zero size
+        let mut allocator = ExtranonceAllocator::new(0);
+        let extranonce = allocator.allocate().unwrap();
+        assert_eq!(extranonce, allocator.allocate().unwrap());
+        assert_eq!(extranonce.to_hex(), "");
+        assert_eq!(extranonce.apply(0x1234), 0x1234);
+    }
+
+    #[test]
+    fn test_extranonce_nonce() {
+        let mut allocator = ExtranonceAllocator::new(2);
+        allocator.allocate().unwrap();
+        let extranonce = allocator.allocate().unwrap();
+        assert_eq!(extranonce.to_hex(), "0001");
+        assert_eq!(extranonce.miner_nonce_hex_len(), 12);
+        assert_eq!(extranonce.apply(0xabcdef), 0x0001_0000_00ab_cdef);
+        // Bits overlapping the extranonce are overridden
+        assert_eq!(extranonce.apply(u64::MAX), 0x0001_ffff_ffff_ffff);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{difficulty::difficulty_to_target, protocol::ErrorCode};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+use vecno_consensus_core::{
+    block::{Block, BlockTemplate},
+    hashing,
+    header::Header,
+    tx::Transaction,
+};
+use vecno_hashes::Hash;
+use vecno_math::Uint256;
+
+/// A block template handed to a single connection together with the share difficulty it is mined at
+pub struct Job {
+    id: u64,
+    header: Header,
+    transactions: Arc<Vec<Transaction>>,
+    pre_pow_hash: Hash,
+    pow_state: vecno_pow::State,
+    share_difficulty: f64,
+    share_target: Uint256,
+    submitted_nonces: HashSet<u64>,
+}
+
+/// A share which passed the share target, along with the block it forms if it also passed the network target
+pub struct ValidShare {
+    pub difficulty: f64,
+    pub block: Option<Block>,
+}
+
+impl Job {
+    pub fn new(id: u64, template: BlockTemplate, share_difficulty: f64) -> Self {
+        let header = template.block.header;
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&header, 0, 0);
+        let pow_state = vecno_pow::State::new(&header);
+        Self {
+            id,
+            header,
+            transactions: Arc::new(template.block.transactions),
+            pre_pow_hash,
+            pow_state,
+            share_difficulty,
+            share_target: difficulty_to_target(share_difficulty),
+            submitted_nonces: HashSet::new(),
+        }
+    }
+
+    /// Returns a new job mining the same block template at another share difficulty
+    pub fn with_share_difficulty(&self, id: u64, share_difficulty: f64) -> Self {
+        Self {
+            id,
+            header: self.header.clone(),
+            transactions: self.transactions.clone(),
+            pre_pow_hash: self.pre_pow_hash,
+            pow_state: vecno_pow::State::new(&self.header),
+            share_difficulty,
+            share_target: difficulty_to_target(share_difficulty),
+            submitted_nonces: HashSet::new(),
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn pre_pow_hash(&self) -> Hash {
+        self.pre_pow_hash
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.header.timestamp
+    }
+
+    pub fn share_difficulty(&self) -> f64 {
+        self.share_difficulty
+    }
+
+    /// Checks the pow of a submitted nonce against the share and network targets
+    pub fn submit(&mut self, nonce: u64) -> Result<ValidShare, ErrorCode> {
+        if !self.submitted_nonces.insert(nonce) {
+            return Err(ErrorCode::DuplicateShare);
+        }
+        let (is_block, pow) = self.pow_state.check_pow(nonce);
+        if is_block {
+            let mut header = self.header.clone();
+            header.nonce = nonce;
+            header.finalize();
+            let block = Block::from_arcs(Arc::new(header), self.transactions.clone());
+            return Ok(ValidShare { difficulty: self.share_difficulty, block: Some(block) });
+        }
+        if pow > self.share_target {
+            return Err(ErrorCode::LowDifficultyShare);
+        }
+        Ok(ValidShare { difficulty: self.share_difficulty, block: None })
+    }
+}
+
+/// The most recent jobs of a connection. Shares of jobs still held here are accepted even after newer jobs
+/// were issued, since in a DAG a block mined over a slightly outdated template is still a valid block.
+pub struct JobStore {
+    jobs: VecDeque<Job>,
+    capacity: usize,
+}
+
+impl JobStore {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self { jobs: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, job: Job) {
+        if self.jobs.len() == self.capacity {
+            self.jobs.pop_front();
+        }
+        self.jobs.push_back(job);
+    }
+
+    pub fn latest(&self) -> Option<&Job> {
+        self.jobs.back()
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+}
@@ -0,0 +1,23 @@
+//! A Stratum v1 mining server, serving block templates of the local node directly to miners.
+//!
+//! The server hands a job to every authorized miner whenever the node produces a new block template,
+//! adapts the share difficulty of each connection (vardiff), allocates a distinct extranonce per
+//! connection and keeps share accounting per worker.
+
+pub mod backend;
+pub mod counters;
+pub mod difficulty;
+pub mod errors;
+pub mod extranonce;
+pub mod job;
+pub mod monitor;
+pub mod protocol;
+pub mod server;
+pub mod service;
+pub mod vardiff;
+
+mod session;
+#[cfg(test)]
+mod testutils;
+
+pub const IDENT: &str = "stratum";
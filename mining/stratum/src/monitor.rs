@@ -0,0 +1,111 @@
+use crate::{counters::StratumCounters, server::StratumServer};
+use std::{sync::Arc, time::Duration};
+use vecno_core::{
+    debug, info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace,
+};
+
+const MONITOR: &str = "stratum-monitor";
+
+pub struct StratumMonitor {
+    server: Arc<StratumServer>,
+
+    // Counters
+    counters: Arc<StratumCounters>,
+
+    // Tick service
+    tick_service: Arc<TickService>,
+}
+
+impl StratumMonitor {
+    pub fn new(server: Arc<StratumServer>, tick_service: Arc<TickService>) -> StratumMonitor {
+        let counters = server.counters().clone();
+        StratumMonitor { server, counters, tick_service }
+    }
+
+    pub async fn worker(self: &Arc<StratumMonitor>) {
+        let mut last_snapshot = self.counters.snapshot();
+        let snapshot_interval = 10;
+        loop {
+            if let TickReason::Shutdown = self.tick_service.tick(Duration::from_secs(snapshot_interval)).await {
+                // Let the system print final logs before exiting
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                break;
+            }
+
+            let snapshot = self.counters.snapshot();
+            if snapshot == last_snapshot {
+                // No update, avoid printing useless info
+                continue;
+            }
+
+            // Subtract the snapshots
+            let delta = &snapshot - &last_snapshot;
+
+            if delta.has_share_activity() {
+                info!(
+                    "Stratum stats: {} miners, {:.2} MH/s, {} shares accepted, {} rejected, {} stale, {} blocks found",
+                    self.server.connection_count(),
+                    delta.hashrate() / 1e6,
+                    delta.shares_accepted,
+                    delta.shares_rejected,
+                    delta.shares_stale,
+                    delta.blocks_found,
+                );
+            }
+            if delta.blocks_rejected > 0 {
+                info!("Stratum stats: {} mined blocks were rejected by the node", delta.blocks_rejected);
+            }
+            if delta.connections_opened + delta.connections_closed > 0 {
+                debug!(
+                    "Stratum connections: {} opened, {} closed, {} jobs issued",
+                    delta.connections_opened, delta.connections_closed, delta.jobs_issued
+                );
+            }
+            for (worker, stats) in self.counters.worker_stats() {
+                trace!(
+                    "Stratum worker {}: {} shares accepted ({:.3} total difficulty), {} rejected, {} stale, {} blocks found",
+                    worker,
+                    stats.shares_accepted,
+                    stats.accepted_difficulty,
+                    stats.shares_rejected,
+                    stats.shares_stale,
+                    stats.blocks_found
+                );
+            }
+
+            last_snapshot = snapshot;
+        }
+
+        trace!("stratum monitor thread exiting");
+    }
+}
+
+// service trait implementation for Monitor
+impl AsyncService for StratumMonitor {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MONITOR
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MONITOR);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", MONITOR);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,152 @@
+//! Stratum v1 wire messages: newline delimited JSON-RPC requests, responses and notifications.
+//!
+//! Jobs are notified as `[job_id, [u64; 4], timestamp]` where the array holds the little endian words of the
+//! pre-pow hash, and nonces are submitted as big endian hex strings.
+
+use crate::job::Job;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const SUBSCRIBE: &str = "mining.subscribe";
+pub const EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const AUTHORIZE: &str = "mining.authorize";
+pub const SUBMIT: &str = "mining.submit";
+pub const SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const SET_EXTRANONCE: &str = "mining.set_extranonce";
+pub const NOTIFY: &str = "mining.notify";
+
+/// The protocol version string reported on subscription
+pub const PROTOCOL_VERSION: &str = "EthereumStratum/1.0.0";
+
+/// The stratum error codes, following the common pool conventions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Unknown = 20,
+    JobNotFound = 21,
+    DuplicateShare = 22,
+    LowDifficultyShare = 23,
+    UnauthorizedWorker = 24,
+    NotSubscribed = 25,
+}
+
+impl ErrorCode {
+    pub fn message(&self) -> &'static str {
+        match self {
+            ErrorCode::Unknown => "Other/Unknown",
+            ErrorCode::JobNotFound => "Job not found (=stale)",
+            ErrorCode::DuplicateShare => "Duplicate share",
+            ErrorCode::LowDifficultyShare => "Low difficulty share",
+            ErrorCode::UnauthorizedWorker => "Unauthorized worker",
+            ErrorCode::NotSubscribed => "Not subscribed",
+        }
+    }
+
+    fn to_value(self) -> Value {
+        json!([self as i32, self.message(), null])
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+impl Request {
+    pub fn str_param(&self, index: usize) -> Option<&str> {
+        self.params.get(index).and_then(|param| param.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub id: Value,
+    pub result: Value,
+    pub error: Value,
+}
+
+impl Response {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { id, result, error: Value::Null }
+    }
+
+    pub fn error(id: Value, code: ErrorCode) -> Self {
+        Self { id, result: Value::Null, error: code.to_value() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Value,
+    pub method: String,
+    pub params: Value,
+}
+
+impl Notification {
+    fn new(method: &str, params: Value) -> Self {
+        Self { id: Value::Null, method: method.to_owned(), params }
+    }
+
+    pub fn set_difficulty(difficulty: f64) -> Self {
+        Self::new(SET_DIFFICULTY, json!([difficulty]))
+    }
+
+    pub fn set_extranonce(extranonce: &str, miner_nonce_size: usize) -> Self {
+        Self::new(SET_EXTRANONCE, json!([extranonce, miner_nonce_size]))
+    }
+
+    pub fn notify(job: &Job) -> Self {
+        Self::new(NOTIFY, json!([job.id().to_string(), job.pre_pow_hash().to_le_u64(), job.timestamp()]))
+    }
+}
+
+/// Parses a submitted nonce, a big endian hex string with an optional `0x` prefix
+pub fn parse_nonce(nonce: &str) -> Option<(u64, usize)> {
+    let nonce = nonce.strip_prefix("0x").unwrap_or(nonce);
+    if nonce.is_empty() || nonce.len() > 16 {
+        return None;
+    }
+    u64::from_str_radix(nonce, 16).ok().map(|value| (value, nonce.len()))
+}
+
+/// Serializes an outgoing message as a single protocol line
+pub fn to_line<T: Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).expect("stratum messages are always serializable");
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        let request: Request =
+            serde_json::from_str(r#"{"id":4,"method":"mining.submit","params":["vecno:qq.worker","7","0x00000000deadbeef"]}"#)
+                .unwrap();
+        assert_eq!(request.method, SUBMIT);
+        assert_eq!(request.str_param(1), Some("7"));
+        assert_eq!(request.str_param(3), None);
+        assert_eq!(parse_nonce(request.str_param(2).unwrap()), Some((0xdeadbeef, 16)));
+
+        // Missing id and params are tolerated
+        let request: Request = serde_json::from_str(r#"{"method":"mining.subscribe"}"#).unwrap();
+        assert_eq!(request.id, Value::Null);
+        assert!(request.params.is_empty());
+
+        assert_eq!(parse_nonce("abc"), Some((0xabc, 3)));
+        assert_eq!(parse_nonce(""), None);
+        assert_eq!(parse_nonce("0x"), None);
+        assert_eq!(parse_nonce("00000000000000000"), None);
+        assert_eq!(parse_nonce("xyz"), None);
+
+        let line = to_line(&Response::error(json!(4), ErrorCode::LowDifficultyShare));
+        assert_eq!(line, "{\"id\":4,\"result\":null,\"error\":[23,\"Low difficulty share\",null]}\n");
+        let line = to_line(&Notification::set_difficulty(0.5));
+        assert_eq!(line, "{\"id\":null,\"method\":\"mining.set_difficulty\",\"params\":[0.5]}\n");
+    }
+}
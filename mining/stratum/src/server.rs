@@ -0,0 +1,294 @@
+use crate::{
+    backend::StratumBackend,
+    counters::StratumCounters,
+    extranonce::ExtranonceAllocator,
+    session::{Session, SessionCommand},
+    vardiff::VarDiffConfig,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+use vecno_addresses::Prefix;
+use vecno_core::{debug, warn};
+use vecno_utils::triggers::Listener;
+
+#[derive(Debug, Clone)]
+pub struct StratumOptions {
+    /// The address prefix of the network, miners authorizing with an address of another network are rejected
+    pub prefix: Prefix,
+    /// The size in bytes of the extranonce allocated to each connection, 0 disables extranonces
+    pub extranonce_size: usize,
+    pub vardiff: VarDiffConfig,
+    /// The number of recent jobs per connection whose shares are still accepted
+    pub job_history: usize,
+    pub max_connections: usize,
+}
+
+impl Default for StratumOptions {
+    fn default() -> Self {
+        Self { prefix: Prefix::Mainnet, extranonce_size: 2, vardiff: VarDiffConfig::default(), job_history: 16, max_connections: 1024 }
+    }
+}
+
+pub struct StratumServer {
+    options: StratumOptions,
+    backend: Arc<dyn StratumBackend>,
+    counters: Arc<StratumCounters>,
+    extranonces: Mutex<ExtranonceAllocator>,
+    sessions: Mutex<HashMap<u64, mpsc::Sender<SessionCommand>>>,
+    next_session_id: AtomicU64,
+    next_job_id: AtomicU64,
+}
+
+impl StratumServer {
+    pub fn new(options: StratumOptions, backend: Arc<dyn StratumBackend>, counters: Arc<StratumCounters>) -> Self {
+        let extranonces = Mutex::new(ExtranonceAllocator::new(options.extranonce_size));
+        Self {
+            options,
+            backend,
+            counters,
+            extranonces,
+            sessions: Default::default(),
+            next_session_id: AtomicU64::new(1),
+            next_job_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn options(&self) -> &StratumOptions {
+        &self.options
+    }
+
+    pub fn backend(&self) -> &Arc<dyn StratumBackend> {
+        &self.backend
+    }
+
+    pub fn counters(&self) -> &Arc<StratumCounters> {
+        &self.counters
+    }
+
+    /// Returns the number of currently connected miners
+    pub fn connection_count(&self) -> usize {
+        self.sessions.lock().len()
+    }
+
+    pub(crate) fn next_job_id(&self) -> u64 {
+        self.next_job_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Signals all connections that a new block template is available, so that each issues a fresh job.
+    /// Signals are coalesced for connections which did not yet handle the previous one.
+    pub fn notify_new_block_template(&self) {
+        for sender in self.sessions.lock().values() {
+            let _ = sender.try_send(SessionCommand::NewBlockTemplate);
+        }
+    }
+
+    /// Accepts miner connections on `listener` until `shutdown` is triggered
+    pub async fn serve(self: Arc<Self>, listener: TcpListener, shutdown: Listener) {
+        loop {
+            tokio::select! {
+                _ = shutdown.clone() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => self.clone().accept(stream, peer, shutdown.clone()),
+                    Err(err) => warn!("Stratum server failed to accept a connection: {}", err),
+                }
+            }
+        }
+    }
+
+    fn accept(self: Arc<Self>, stream: TcpStream, peer: SocketAddr, shutdown: Listener) {
+        if self.connection_count() >= self.options.max_connections {
+            debug!("Stratum connection from {} refused: max connections reached", peer);
+            return;
+        }
+        let Some(extranonce) = self.extranonces.lock().allocate() else {
+            warn!("Stratum connection from {} refused: no extranonce is available", peer);
+            return;
+        };
+
+        let id = self.next_session_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::channel(1);
+        self.sessions.lock().insert(id, sender);
+        self.counters.connections_opened.fetch_add(1, Ordering::Relaxed);
+        debug!("Stratum connection {} opened from {}", id, peer);
+
+        tokio::spawn(async move {
+            let session = Session::new(id, peer, self.clone(), extranonce);
+            session.run(stream, receiver, shutdown).await;
+
+            self.sessions.lock().remove(&id);
+            self.extranonces.lock().release(extranonce);
+            self.counters.connections_closed.fetch_add(1, Ordering::Relaxed);
+            debug!("Stratum connection {} from {} closed", id, peer);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{self, ErrorCode, Response},
+        testutils::{MockBackend, NotifiedJob, StratumTestClient},
+    };
+    use serde_json::json;
+    use std::time::Duration;
+    use vecno_addresses::{Address, Version};
+    use vecno_math::Uint256;
+    use vecno_utils::triggers::SingleTrigger;
+
+    const EASY_BITS: u32 = 0x207fffff;
+    const HARD_BITS: u32 = 0x1d00ffff;
+
+    async fn start_server(bits: u32, difficulty: f64) -> (Arc<StratumServer>, Arc<MockBackend>, SocketAddr, SingleTrigger) {
+        let backend = Arc::new(MockBackend::new(bits));
+        let vardiff = VarDiffConfig { enabled: false, initial_difficulty: difficulty, min_difficulty: 1e-12, ..Default::default() };
+        let options = StratumOptions { prefix: Prefix::Testnet, extranonce_size: 2, vardiff, ..Default::default() };
+        let server = Arc::new(StratumServer::new(options, backend.clone(), Default::default()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = SingleTrigger::default();
+        tokio::spawn(server.clone().serve(listener, shutdown.listener.clone()));
+        (server, backend, address, shutdown)
+    }
+
+    fn address(prefix: Prefix) -> Address {
+        Address::new(prefix, Version::PubKey, &[7u8; 32])
+    }
+
+    fn error_code(response: &Response) -> i64 {
+        response.error[0].as_i64().unwrap()
+    }
+
+    async fn subscribe(client: &mut StratumTestClient) -> String {
+        client.request(protocol::SUBSCRIBE, json!(["test-miner/1.0"])).await;
+        assert_eq!(client.response().await.result, json!([true, protocol::PROTOCOL_VERSION]));
+        let params = client.notification(protocol::SET_EXTRANONCE).await.params;
+        assert_eq!(params[1], json!(6));
+        params[0].as_str().unwrap().to_owned()
+    }
+
+    /// Searches a nonce in the miner part of the nonce space, returning it along with the full nonce
+    fn find_nonce(job: &NotifiedJob, extranonce: u64, start: u64, is_block: bool) -> (u64, u64) {
+        let network_target = Uint256::from_compact_target_bits(EASY_BITS);
+        (start..)
+            .map(|nonce| (nonce, (extranonce << 48) | nonce))
+            .find(|&(_, full_nonce)| (job.calculate_pow(full_nonce) <= network_target) == is_block)
+            .unwrap()
+    }
+
+    async fn submit(client: &mut StratumTestClient, login: &str, job_id: &str, nonce: &str) -> Response {
+        client.request(protocol::SUBMIT, json!([login, job_id, nonce])).await;
+        client.response().await
+    }
+
+    #[tokio::test]
+    async fn test_stratum_mining() {
+        let (server, backend, server_address, shutdown) = start_server(EASY_BITS, 1e-12).await;
+
+        // Every connection gets a distinct extranonce
+        let mut idle_client = StratumTestClient::connect(server_address).await;
+        assert_eq!(subscribe(&mut idle_client).await, "0000");
+        let mut client = StratumTestClient::connect(server_address).await;
+        assert_eq!(subscribe(&mut client).await, "0001");
+
+        // Submitting requires an authorized worker with an address of the server network
+        let login = format!("{}.rig1", address(Prefix::Testnet));
+        assert_eq!(error_code(&submit(&mut client, &login, "1", "00").await), ErrorCode::UnauthorizedWorker as i64);
+        for login in ["not-an-address.rig1".to_owned(), format!("{}.rig1", address(Prefix::Mainnet))] {
+            client.request(protocol::AUTHORIZE, json!([login, "x"])).await;
+            assert_eq!(error_code(&client.response().await), ErrorCode::UnauthorizedWorker as i64);
+        }
+
+        // No job is issued while the node is not synced, the next new template notification issues one
+        backend.set_synced(false);
+        client.request(protocol::AUTHORIZE, json!([login.strip_prefix("vecnotest:").unwrap(), "x"])).await;
+        assert_eq!(client.response().await.result, json!(true));
+        assert_eq!(client.notification(protocol::SET_DIFFICULTY).await.params, json!([1e-12]));
+        backend.set_synced(true);
+        server.notify_new_block_template();
+        let job = client.job().await;
+
+        // A share which is not a block
+        let (nonce, _) = find_nonce(&job, 1, 0, false);
+        assert_eq!(submit(&mut client, &login, &job.id, &format!("{nonce:012x}")).await.result, json!(true));
+        assert!(backend.submitted_blocks().is_empty());
+        assert_eq!(
+            error_code(&submit(&mut client, &login, &job.id, &format!("{nonce:012x}")).await),
+            ErrorCode::DuplicateShare as i64
+        );
+
+        // A share which is a block, its nonce is prefixed with the extranonce of the connection
+        let (nonce, full_nonce) = find_nonce(&job, 1, 0, true);
+        assert_eq!(submit(&mut client, &login, &job.id, &format!("0x{nonce:012x}")).await.result, json!(true));
+        let blocks = backend.submitted_blocks();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].header.nonce, full_nonce);
+        assert!(vecno_pow::State::new(&blocks[0].header).check_pow(full_nonce).0);
+        assert_eq!(blocks[0].header.hash, vecno_consensus_core::hashing::header::hash(&blocks[0].header));
+
+        // Unknown jobs and malformed nonces are rejected
+        assert_eq!(error_code(&submit(&mut client, &login, "999999", "00").await), ErrorCode::JobNotFound as i64);
+        assert_eq!(error_code(&submit(&mut client, &login, &job.id, "xyz").await), ErrorCode::Unknown as i64);
+
+        // A new template issues a new job, while shares of the previous job are still accepted
+        server.notify_new_block_template();
+        let new_job = client.job().await;
+        assert_ne!(new_job.id, job.id);
+        assert_ne!(new_job.pre_pow_hash, job.pre_pow_hash);
+        let (nonce, _) = find_nonce(&job, 1, nonce + 1, false);
+        assert_eq!(submit(&mut client, &login, &job.id, &format!("{nonce:012x}")).await.result, json!(true));
+
+        // Share accounting
+        let snapshot = server.counters().snapshot();
+        assert_eq!(
+            (snapshot.shares_accepted, snapshot.shares_rejected, snapshot.shares_stale, snapshot.blocks_found, snapshot.jobs_issued),
+            (3, 2, 1, 1, 2)
+        );
+        let worker_stats = server.counters().worker_stats();
+        assert_eq!(worker_stats.len(), 1);
+        assert_eq!(worker_stats[0].0, login);
+        let stats = &worker_stats[0].1;
+        assert_eq!((stats.shares_accepted, stats.shares_rejected, stats.shares_stale, stats.blocks_found), (3, 2, 1, 1));
+
+        // Oversized lines close the connection
+        idle_client.send_raw(&"a".repeat(5000)).await;
+        assert!(idle_client.is_closed().await);
+        while server.connection_count() > 1 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(server.counters().active_connections(), 1);
+
+        shutdown.trigger.trigger();
+        assert!(client.is_closed().await);
+    }
+
+    #[tokio::test]
+    async fn test_stratum_low_difficulty_share() {
+        let (server, backend, server_address, shutdown) = start_server(HARD_BITS, 1e6).await;
+        let mut client = StratumTestClient::connect(server_address).await;
+        subscribe(&mut client).await;
+
+        let login = format!("{}.rig1", address(Prefix::Testnet));
+        client.request(protocol::AUTHORIZE, json!([login, "x"])).await;
+        assert_eq!(client.response().await.result, json!(true));
+        assert_eq!(client.notification(protocol::SET_DIFFICULTY).await.params, json!([1e6]));
+        let job = client.job().await;
+
+        assert_eq!(error_code(&submit(&mut client, &login, &job.id, "00").await), ErrorCode::LowDifficultyShare as i64);
+        assert!(backend.submitted_blocks().is_empty());
+        assert_eq!(server.counters().snapshot().shares_rejected, 1);
+
+        shutdown.trigger.trigger();
+    }
+}
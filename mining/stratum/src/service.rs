@@ -0,0 +1,107 @@
+use crate::{errors::StratumError, server::StratumServer, IDENT};
+use std::{net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
+use vecno_consensus_notify::{
+    connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification, notifier::ConsensusNotifier,
+};
+use vecno_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use vecno_notify::{connection::ChannelType, listener::ListenerId, listener::ListenerLifespan, scope::NewBlockTemplateScope};
+use vecno_utils::{channel::Channel, triggers::SingleTrigger};
+
+const STRATUM_SERVICE: &str = IDENT;
+
+/// Runs the stratum server and feeds it with the new block template notifications of consensus
+pub struct StratumService {
+    server: Arc<StratumServer>,
+    listen_address: SocketAddr,
+    consensus_notifier: Arc<ConsensusNotifier>,
+    consensus_notify_listener_id: ListenerId,
+    consensus_notify_channel: Channel<ConsensusNotification>,
+    shutdown: SingleTrigger,
+}
+
+impl StratumService {
+    pub fn new(server: Arc<StratumServer>, listen_address: SocketAddr, consensus_notifier: &Arc<ConsensusNotifier>) -> Self {
+        let consensus_notify_channel = Channel::<ConsensusNotification>::default();
+        let consensus_notify_listener_id = consensus_notifier.register_new_listener(
+            ConsensusChannelConnection::new(STRATUM_SERVICE, consensus_notify_channel.sender(), ChannelType::Closable),
+            ListenerLifespan::Static(Default::default()),
+        );
+        consensus_notifier
+            .try_start_notify(consensus_notify_listener_id, NewBlockTemplateScope::default().into())
+            .expect("the subscription always succeeds");
+
+        Self {
+            server,
+            listen_address,
+            consensus_notifier: consensus_notifier.clone(),
+            consensus_notify_listener_id,
+            consensus_notify_channel,
+            shutdown: SingleTrigger::default(),
+        }
+    }
+
+    pub fn server(&self) -> Arc<StratumServer> {
+        self.server.clone()
+    }
+}
+
+impl AsyncService for StratumService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        STRATUM_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", STRATUM_SERVICE);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(self.listen_address).await.map_err(|err| {
+                AsyncServiceError::Service(StratumError::Listen(self.listen_address.to_string(), err.to_string()).to_string())
+            })?;
+            info!("Stratum server listening on {}", self.listen_address);
+            let server_task = tokio::spawn(self.server.clone().serve(listener, shutdown_signal.clone()));
+
+            // Forward new block templates to the miners until a service shutdown signal is received
+            let notifications = self.consensus_notify_channel.receiver();
+            loop {
+                tokio::select! {
+                    _ = shutdown_signal.clone() => break,
+                    notification = notifications.recv() => match notification {
+                        Ok(ConsensusNotification::NewBlockTemplate(_)) => self.server.notify_new_block_template(),
+                        Ok(_) => {}
+                        Err(_) => break,
+                    }
+                }
+            }
+
+            if let Err(err) = server_task.await {
+                warn!("Error while stopping {}: {}", STRATUM_SERVICE, err);
+                return Err(AsyncServiceError::Service(err.to_string()));
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", STRATUM_SERVICE);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            if let Err(err) = self.consensus_notifier.unregister_listener(self.consensus_notify_listener_id) {
+                warn!("Error while unregistering the {} notification listener: {}", STRATUM_SERVICE, err);
+            }
+            self.consensus_notify_channel.close();
+            trace!("{} stopped", STRATUM_SERVICE);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{
+    counters::{difficulty_to_millis, ShareEvent},
+    errors::StratumError,
+    extranonce::Extranonce,
+    job::{Job, JobStore},
+    protocol::{self, parse_nonce, to_line, ErrorCode, Notification, Request, Response},
+    server::StratumServer,
+    vardiff::VarDiff,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+    sync::mpsc,
+};
+use vecno_addresses::Address;
+use vecno_consensus_core::coinbase::MinerData;
+use vecno_core::{debug, info, trace, vecnod_env::version, warn};
+use vecno_txscript::pay_to_address_script;
+use vecno_utils::triggers::Listener;
+
+/// The maximal length of a single protocol line, longer lines close the connection
+const MAX_LINE_LENGTH: u64 = 4096;
+
+/// The interval at which idle connections get their difficulty retargeted and missing jobs are retried
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SessionCommand {
+    NewBlockTemplate,
+}
+
+struct Miner {
+    /// The name shares are accounted to, formatted as `address.worker`
+    worker: String,
+    miner_data: MinerData,
+}
+
+/// The state of a single miner connection
+pub(crate) struct Session {
+    id: u64,
+    peer: SocketAddr,
+    server: Arc<StratumServer>,
+    extranonce: Extranonce,
+    miner: Option<Miner>,
+    vardiff: VarDiff,
+    jobs: JobStore,
+    writer: Option<OwnedWriteHalf>,
+}
+
+impl Session {
+    pub(crate) fn new(id: u64, peer: SocketAddr, server: Arc<StratumServer>, extranonce: Extranonce) -> Self {
+        let vardiff = VarDiff::new(server.options().vardiff.clone(), Instant::now());
+        let jobs = JobStore::new(server.options().job_history);
+        Self { id, peer, server, extranonce, miner: None, vardiff, jobs, writer: None }
+    }
+
+    pub(crate) async fn run(mut self, stream: TcpStream, mut commands: mpsc::Receiver<SessionCommand>, shutdown: Listener) {
+        let (reader, writer) = stream.into_split();
+        self.writer = Some(writer);
+
+        // Lines are read by a dedicated task since reading a line is not cancellation safe
+        let (line_sender, mut lines) = mpsc::channel(16);
+        let reader_task = tokio::spawn(read_lines(reader, line_sender));
+
+        let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + TICK_INTERVAL, TICK_INTERVAL);
+        loop {
+            let result = tokio::select! {
+                _ = shutdown.clone() => break,
+                line = lines.recv() => match line {
+                    Some(line) => self.handle_line(&line).await,
+                    None => break,
+                },
+                command = commands.recv() => match command {
+                    Some(SessionCommand::NewBlockTemplate) => self.issue_job().await,
+                    None => break,
+                },
+                _ = ticker.tick() => self.handle_tick().await,
+            };
+            if let Err(err) = result {
+                debug!("Stratum connection {} from {} failed: {}", self.id, self.peer, err);
+                break;
+            }
+        }
+
+        reader_task.abort();
+    }
+
+    async fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        let line = to_line(message);
+        self.writer.as_mut().expect("the writer is set while running").write_all(line.as_bytes()).await
+    }
+
+    async fn handle_line(&mut self, line: &str) -> io::Result<()> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let request: Request = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                trace!("Stratum connection {} sent a malformed request: {}", self.id, err);
+                return self.send(&Response::error(Value::Null, ErrorCode::Unknown)).await;
+            }
+        };
+        match request.method.as_str() {
+            protocol::SUBSCRIBE => self.handle_subscribe(request).await,
+            protocol::EXTRANONCE_SUBSCRIBE => self.send(&Response::ok(request.id, json!(true))).await,
+            protocol::AUTHORIZE => self.handle_authorize(request).await,
+            protocol::SUBMIT => self.handle_submit(request).await,
+            method => {
+                trace!("Stratum connection {} called an unsupported method {}", self.id, method);
+                self.send(&Response::error(request.id, ErrorCode::Unknown)).await
+            }
+        }
+    }
+
+    async fn handle_subscribe(&mut self, request: Request) -> io::Result<()> {
+        self.send(&Response::ok(request.id, json!([true, protocol::PROTOCOL_VERSION]))).await?;
+        if self.extranonce.size() > 0 {
+            self.send(&Notification::set_extranonce(&self.extranonce.to_hex(), 8 - self.extranonce.size())).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_authorize(&mut self, request: Request) -> io::Result<()> {
+        let Some(login) = request.str_param(0) else {
+            return self.send(&Response::error(request.id, ErrorCode::UnauthorizedWorker)).await;
+        };
+        let Some(address) = self.parse_login_address(login) else {
+            debug!("Stratum connection {} tried to authorize with an invalid address: {}", self.id, login);
+            return self.send(&Response::error(request.id, ErrorCode::UnauthorizedWorker)).await;
+        };
+
+        // Workers are accounted by their full address, regardless of whether the miner omitted the prefix
+        let worker = match login.split_once('.') {
+            Some((_, name)) => format!("{address}.{name}"),
+            None => address.to_string(),
+        };
+        let extra_data = format!("{}/stratum", version()).into_bytes();
+        debug!("Stratum connection {} from {} authorized as {}", self.id, self.peer, worker);
+        self.miner = Some(Miner { worker, miner_data: MinerData::new(pay_to_address_script(&address), extra_data) });
+
+        self.send(&Response::ok(request.id, json!(true))).await?;
+        self.send(&Notification::set_difficulty(self.vardiff.difficulty())).await?;
+        self.issue_job().await
+    }
+
+    /// Parses the address part of an `address.worker` login, the address prefix may be omitted
+    fn parse_login_address(&self, login: &str) -> Option<Address> {
+        let address = login.split_once('.').map_or(login, |(address, _)| address);
+        let prefix = self.server.options().prefix;
+        let address =
+            if address.contains(':') { Address::try_from(address) } else { Address::try_from(format!("{prefix}:{address}")) };
+        address.ok().filter(|address| address.prefix == prefix)
+    }
+
+    async fn handle_submit(&mut self, request: Request) -> io::Result<()> {
+        let Some(worker) = self.miner.as_ref().map(|miner| miner.worker.clone()) else {
+            return self.send(&Response::error(request.id, ErrorCode::UnauthorizedWorker)).await;
+        };
+        let counters = self.server.counters().clone();
+
+        let Some(job) = request.str_param(1).and_then(|id| id.parse::<u64>().ok()).and_then(|id| self.jobs.get_mut(id)) else {
+            counters.record(&worker, ShareEvent::Stale);
+            return self.send(&Response::error(request.id, ErrorCode::JobNotFound)).await;
+        };
+        let Some((nonce, nonce_hex_len)) = request.str_param(2).and_then(parse_nonce) else {
+            counters.record(&worker, ShareEvent::Rejected);
+            return self.send(&Response::error(request.id, ErrorCode::Unknown)).await;
+        };
+        // Miners aware of the extranonce only submit their own part of the nonce
+        let nonce = if nonce_hex_len <= self.extranonce.miner_nonce_hex_len() { self.extranonce.apply(nonce) } else { nonce };
+
+        let share = match job.submit(nonce) {
+            Ok(share) => share,
+            Err(code) => {
+                counters.record(&worker, ShareEvent::Rejected);
+                return self.send(&Response::error(request.id, code)).await;
+            }
+        };
+
+        if let Some(block) = share.block {
+            let hash = block.hash();
+            match self.server.backend().submit_block(block).await {
+                Ok(()) => {
+                    info!("Stratum worker {} mined block {}", worker, hash);
+                    counters.record(&worker, ShareEvent::BlockFound);
+                }
+                Err(err) => {
+                    warn!("Block {} mined by stratum worker {} was rejected: {}", hash, worker, err);
+                    counters.record(&worker, ShareEvent::BlockRejected);
+                }
+            }
+        }
+        counters.record(&worker, ShareEvent::Accepted { difficulty_millis: difficulty_to_millis(share.difficulty) });
+        self.send(&Response::ok(request.id, json!(true))).await?;
+
+        match self.vardiff.on_share(Instant::now()) {
+            Some(difficulty) => self.retarget(difficulty).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn handle_tick(&mut self) -> io::Result<()> {
+        if self.miner.is_none() {
+            return Ok(());
+        }
+        if self.jobs.latest().is_none() {
+            // No template could be built so far, e.g. since the node is not synced
+            return self.issue_job().await;
+        }
+        match self.vardiff.on_tick(Instant::now()) {
+            Some(difficulty) => self.retarget(difficulty).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Applies a new share difficulty by reissuing the latest job at that difficulty
+    async fn retarget(&mut self, difficulty: f64) -> io::Result<()> {
+        trace!("Stratum connection {} retargeted to difficulty {}", self.id, difficulty);
+        self.send(&Notification::set_difficulty(difficulty)).await?;
+        let Some(job) = self.jobs.latest().map(|job| job.with_share_difficulty(self.server.next_job_id(), difficulty)) else {
+            return Ok(());
+        };
+        self.push_job(job).await
+    }
+
+    /// Builds a job over a fresh block template and notifies it to the miner
+    async fn issue_job(&mut self) -> io::Result<()> {
+        let Some(miner_data) = self.miner.as_ref().map(|miner| miner.miner_data.clone()) else {
+            return Ok(());
+        };
+        match self.server.backend().get_block_template(miner_data).await {
+            Ok(template) => {
+                let job = Job::new(self.server.next_job_id(), template, self.vardiff.difficulty());
+                self.push_job(job).await
+            }
+            Err(StratumError::NotSynced) => {
+                trace!("Stratum connection {} gets no job while the node is not synced", self.id);
+                Ok(())
+            }
+            Err(err) => {
+                debug!("Stratum connection {} failed to get a block template: {}", self.id, err);
+                Ok(())
+            }
+        }
+    }
+
+    async fn push_job(&mut self, job: Job) -> io::Result<()> {
+        let notification = Notification::notify(&job);
+        self.jobs.push(job);
+        self.server.counters().jobs_issued.fetch_add(1, Ordering::Relaxed);
+        self.send(&notification).await
+    }
+}
+
+async fn read_lines(reader: OwnedReadHalf, sender: mpsc::Sender<String>) {
+    let mut reader = BufReader::new(reader);
+    loop {
+        let mut line = String::new();
+        match (&mut reader).take(MAX_LINE_LENGTH).read_line(&mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                if read as u64 == MAX_LINE_LENGTH && !line.ends_with('\n') {
+                    break;
+                }
+                if sender.send(line).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
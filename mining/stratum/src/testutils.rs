@@ -0,0 +1,160 @@
+use crate::{
+    backend::StratumBackend,
+    errors::{StratumError, StratumResult},
+    protocol::{Notification, Response},
+};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
+use vecno_consensus_core::{
+    block::{Block, BlockTemplate, MutableBlock},
+    coinbase::MinerData,
+    header::Header,
+};
+use vecno_hashes::{Hash, PowHash};
+use vecno_math::Uint256;
+use vecno_pow::matrix::Matrix;
+
+/// A backend serving templates of a fixed difficulty and recording the submitted blocks
+pub(crate) struct MockBackend {
+    bits: u32,
+    synced: AtomicBool,
+    template_count: AtomicU64,
+    submitted_blocks: Mutex<Vec<Block>>,
+}
+
+impl MockBackend {
+    pub(crate) fn new(bits: u32) -> Self {
+        Self { bits, synced: AtomicBool::new(true), template_count: Default::default(), submitted_blocks: Default::default() }
+    }
+
+    pub(crate) fn set_synced(&self, synced: bool) {
+        self.synced.store(synced, Ordering::SeqCst);
+    }
+
+    pub(crate) fn submitted_blocks(&self) -> Vec<Block> {
+        self.submitted_blocks.lock().clone()
+    }
+}
+
+#[async_trait]
+impl StratumBackend for MockBackend {
+    async fn get_block_template(&self, miner_data: MinerData) -> StratumResult<BlockTemplate> {
+        if !self.synced.load(Ordering::SeqCst) {
+            return Err(StratumError::NotSynced);
+        }
+        // Every template is distinct so that notified jobs can be told apart
+        let daa_score = self.template_count.fetch_add(1, Ordering::SeqCst);
+        let header = Header::new_finalized(
+            1,
+            vec![vec![Hash::from_u64_word(daa_score)]],
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            1_700_000_000_000 + daa_score,
+            self.bits,
+            0,
+            daa_score,
+            Default::default(),
+            daa_score,
+            Default::default(),
+        );
+        Ok(BlockTemplate::new(MutableBlock::from_header(header), miner_data, false, 0, 0, Default::default(), vec![]))
+    }
+
+    async fn submit_block(&self, block: Block) -> StratumResult<()> {
+        self.submitted_blocks.lock().push(block);
+        Ok(())
+    }
+}
+
+/// A job as notified to a stratum client
+#[derive(Debug, Clone)]
+pub(crate) struct NotifiedJob {
+    pub(crate) id: String,
+    pub(crate) pre_pow_hash: Hash,
+    pub(crate) timestamp: u64,
+}
+
+impl NotifiedJob {
+    /// Computes the pow of a full nonce, as a miner would
+    pub(crate) fn calculate_pow(&self, nonce: u64) -> Uint256 {
+        let hash = PowHash::new(self.pre_pow_hash, self.timestamp).finalize_with_nonce(nonce);
+        let hash = Matrix::generate(self.pre_pow_hash).heavy_hash(hash);
+        Uint256::from_le_bytes(hash.as_bytes())
+    }
+}
+
+/// A minimal stand-in for a stratum mining client
+pub(crate) struct StratumTestClient {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+    next_id: u64,
+}
+
+impl StratumTestClient {
+    pub(crate) async fn connect(address: std::net::SocketAddr) -> Self {
+        let (reader, writer) = TcpStream::connect(address).await.unwrap().into_split();
+        Self { reader: BufReader::new(reader), writer, next_id: 1 }
+    }
+
+    /// Sends a request and returns its id
+    pub(crate) async fn request(&mut self, method: &str, params: Value) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let line = json!({ "id": id, "method": method, "params": params }).to_string() + "\n";
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+        id
+    }
+
+    pub(crate) async fn send_raw(&mut self, line: &str) {
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    async fn read_message(&mut self) -> Value {
+        let mut line = String::new();
+        let read = tokio::time::timeout(Duration::from_secs(10), self.reader.read_line(&mut line)).await.expect("read timed out");
+        assert!(read.unwrap() > 0, "the connection was closed");
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Returns whether the server closed the connection
+    pub(crate) async fn is_closed(&mut self) -> bool {
+        let mut line = String::new();
+        matches!(tokio::time::timeout(Duration::from_secs(10), self.reader.read_line(&mut line)).await, Ok(Ok(0)) | Ok(Err(_)))
+    }
+
+    pub(crate) async fn response(&mut self) -> Response {
+        let message = self.read_message().await;
+        assert!(message.get("method").is_none(), "expected a response, got {message}");
+        serde_json::from_value(message).unwrap()
+    }
+
+    pub(crate) async fn notification(&mut self, method: &str) -> Notification {
+        let message = self.read_message().await;
+        let notification: Notification = serde_json::from_value(message).unwrap();
+        assert_eq!(notification.method, method);
+        notification
+    }
+
+    pub(crate) async fn job(&mut self) -> NotifiedJob {
+        let params = self.notification(crate::protocol::NOTIFY).await.params;
+        let words: [u64; 4] = serde_json::from_value(params[1].clone()).unwrap();
+        NotifiedJob {
+            id: params[0].as_str().unwrap().to_owned(),
+            pre_pow_hash: Hash::from_le_u64(words),
+            timestamp: params[2].as_u64().unwrap(),
+        }
+    }
+}
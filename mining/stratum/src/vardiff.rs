@@ -0,0 +1,165 @@
+//! Variable share difficulty (vardiff) adjusting the share difficulty of a connection to its hashrate.
+
+use std::time::{Duration, Instant};
+
+/// The maximal factor by which a single retarget may raise or lower the difficulty
+const MAX_RETARGET_FACTOR: f64 = 4.0;
+
+/// Deviations of the observed share rate below this ratio do not trigger a retarget
+const RETARGET_TOLERANCE: f64 = 0.25;
+
+#[derive(Debug, Clone)]
+pub struct VarDiffConfig {
+    /// Whether the share difficulty adapts to the connection hashrate or stays at `initial_difficulty`
+    pub enabled: bool,
+    pub initial_difficulty: f64,
+    pub min_difficulty: f64,
+    pub max_difficulty: f64,
+    /// The desired average time between two shares of a connection
+    pub target_share_interval: Duration,
+    /// The time span over which the share rate is measured before retargeting
+    pub retarget_interval: Duration,
+}
+
+impl Default for VarDiffConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_difficulty: 1.0,
+            min_difficulty: 0.001,
+            max_difficulty: 1e12,
+            target_share_interval: Duration::from_secs(6),
+            retarget_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+impl VarDiffConfig {
+    pub fn with_shares_per_minute(mut self, shares_per_minute: f64) -> Self {
+        self.target_share_interval = Duration::from_secs_f64(60.0 / shares_per_minute);
+        self
+    }
+
+    fn clamp(&self, difficulty: f64) -> f64 {
+        difficulty.clamp(self.min_difficulty, self.max_difficulty)
+    }
+}
+
+#[derive(Debug)]
+pub struct VarDiff {
+    config: VarDiffConfig,
+    difficulty: f64,
+    window_start: Instant,
+    window_shares: u32,
+}
+
+impl VarDiff {
+    pub fn new(config: VarDiffConfig, now: Instant) -> Self {
+        let difficulty = config.clamp(config.initial_difficulty);
+        Self { config, difficulty, window_start: now, window_shares: 0 }
+    }
+
+    pub fn difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    /// Records a valid share. Returns the new difficulty if the share triggered a retarget.
+    pub fn on_share(&mut self, now: Instant) -> Option<f64> {
+        self.window_shares += 1;
+        self.retarget(now)
+    }
+
+    /// Gives a chance to lower the difficulty of a connection which did not find shares for a while.
+    /// Returns the new difficulty if a retarget occurred.
+    pub fn on_tick(&mut self, now: Instant) -> Option<f64> {
+        self.retarget(now)
+    }
+
+    fn retarget(&mut self, now: Instant) -> Option<f64> {
+        if !self.config.enabled {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < self.config.retarget_interval {
+            return None;
+        }
+
+        // With no share in the window, the elapsed time is a lower bound of the share interval
+        let observed_interval = elapsed.as_secs_f64() / self.window_shares.max(1) as f64;
+        let ratio = self.config.target_share_interval.as_secs_f64() / observed_interval;
+        self.window_start = now;
+        self.window_shares = 0;
+
+        if (ratio - 1.0).abs() < RETARGET_TOLERANCE {
+            return None;
+        }
+        let ratio = ratio.clamp(1.0 / MAX_RETARGET_FACTOR, MAX_RETARGET_FACTOR);
+        let difficulty = self.config.clamp(self.difficulty * ratio);
+        if difficulty == self.difficulty {
+            return None;
+        }
+        self.difficulty = difficulty;
+        Some(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> VarDiffConfig {
+        VarDiffConfig {
+            enabled: true,
+            initial_difficulty: 16.0,
+            min_difficulty: 1.0,
+            max_difficulty: 1024.0,
+            target_share_interval: Duration::from_secs(10),
+            retarget_interval: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_vardiff_retarget() {
+        let start = Instant::now();
+        let secs = |s: u64| start + Duration::from_secs(s);
+
+        // Shares at the target rate keep the difficulty
+        let mut vardiff = VarDiff::new(config(), start);
+        for i in 1..=6 {
+            assert_eq!(vardiff.on_share(secs(i * 10)), None);
+        }
+        assert_eq!(vardiff.difficulty(), 16.0);
+
+        // Twice the target rate doubles the difficulty once the retarget interval elapsed
+        let mut vardiff = VarDiff::new(config(), start);
+        for i in 1..12 {
+            assert_eq!(vardiff.on_share(secs(i * 5)), None);
+        }
+        assert_eq!(vardiff.on_share(secs(60)), Some(32.0));
+
+        // A much higher rate is limited by the max retarget factor, and further by the max difficulty
+        let mut vardiff = VarDiff::new(config(), start);
+        for i in 1..=1000 {
+            vardiff.on_share(start + Duration::from_millis(i * 60));
+        }
+        assert_eq!(vardiff.difficulty(), 64.0);
+        for i in 1..=10 {
+            for j in 0..1000 {
+                vardiff.on_share(secs(60 * i) + Duration::from_millis(j * 60));
+            }
+        }
+        assert_eq!(vardiff.difficulty(), 1024.0);
+
+        // A silent connection is lowered on ticks, down to the min difficulty
+        let mut vardiff = VarDiff::new(config(), start);
+        assert_eq!(vardiff.on_tick(secs(59)), None);
+        assert_eq!(vardiff.on_tick(secs(60)), Some(4.0));
+        assert_eq!(vardiff.on_tick(secs(120)), Some(1.0));
+        assert_eq!(vardiff.on_tick(secs(180)), None);
+
+        // A disabled vardiff keeps the initial difficulty
+        let mut vardiff = VarDiff::new(VarDiffConfig { enabled: false, ..config() }, start);
+        assert_eq!(vardiff.on_tick(secs(600)), None);
+        assert_eq!(vardiff.difficulty(), 16.0);
+    }
+}
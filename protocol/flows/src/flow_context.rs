@@ -44,8 +44,8 @@ use vecno_core::{
 };
 use vecno_core::{time::unix_now, warn};
 use vecno_hashes::Hash;
-use vecno_mining::mempool::tx::{Orphan, Priority};
-use vecno_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy};
+use vecno_mining::mempool::tx::{Orphan, Priority, TransactionSource};
+use vecno_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy, model::tx_insert::TransactionPackageInsertion};
 use vecno_notify::notifier::Notify;
 use vecno_p2p_lib::{
     common::ProtocolError,
@@ -58,7 +58,10 @@ use vecno_utils::iter::IterExtensions;
 use vecno_utils::networking::PeerId;
 
 /// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 7;
+const PROTOCOL_VERSION: u32 = 8;
+
+/// The first P2P protocol version supporting the relay of transaction packages
+pub(crate) const PACKAGE_RELAY_PROTOCOL_VERSION: u32 = 8;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -660,6 +663,54 @@ impl FlowContext {
         ))
     }
 
+    /// Adds the rpc-submitted package of dependent transactions to the mempool as a unit and propagates it to peers.
+    ///
+    /// The transactions are considered high priority, like any rpc-submitted transaction.
+    pub async fn submit_rpc_transaction_package(
+        &self,
+        consensus: &ConsensusProxy,
+        transactions: Vec<Transaction>,
+    ) -> Result<(), ProtocolError> {
+        let transaction_count = transactions.len();
+        let transaction_insertion = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_transaction_package(consensus, transactions, Priority::High, TransactionSource::Local)
+            .await?;
+        // The package transactions come first among the accepted ones, followed by the unorphaned transactions
+        self.relay_transaction_package(&transaction_insertion, transaction_count, None).await;
+        self.broadcast_transactions(
+            transaction_insertion.accepted.iter().map(|x| x.id()),
+            false, // RPC transactions are considered high priority, so we don't want to throttle them
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Sends the accepted package of `package_len` dependent transactions as a whole to the peers supporting package
+    /// relay, except for the peer it was received from, if any.
+    ///
+    /// The package is only sent if some of its transactions do not pay the relay fee on their own. Otherwise, the regular
+    /// transaction Inv messages suffice, as they do for the peers not supporting package relay.
+    pub async fn relay_transaction_package(
+        &self,
+        transaction_insertion: &TransactionPackageInsertion,
+        package_len: usize,
+        source_peer: Option<PeerKey>,
+    ) {
+        if !transaction_insertion.requires_package_relay {
+            return;
+        }
+        let transactions = &transaction_insertion.accepted[..package_len];
+        self.hub
+            .broadcast_to_protocol_version(
+                make_message!(Payload::TransactionPackage, transactions.into()),
+                PACKAGE_RELAY_PROTOCOL_VERSION,
+                source_peer,
+            )
+            .await
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread.write().await.should_run_mempool_scanning_task()
@@ -732,7 +783,9 @@ impl ConnectionInitializer for FlowContext {
 
         // Register all flows according to version
         let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone(), PROTOCOL_VERSION), PROTOCOL_VERSION),
+            // Version 7 peers share the v6 flows but do not support package relay
+            7 => (v6::register(self.clone(), router.clone(), 7), 7),
             5 => (v5::register(self.clone(), router.clone()), 5),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
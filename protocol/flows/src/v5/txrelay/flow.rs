@@ -12,7 +12,7 @@ use vecno_mining::{
     errors::MiningManagerError,
     mempool::{
         errors::RuleError,
-        tx::{Orphan, Priority, RbfPolicy},
+        tx::{Orphan, Priority, RbfPolicy, TransactionSource},
     },
     model::tx_query::TransactionQuery,
     P2pTxCountSample,
@@ -219,7 +219,14 @@ impl RelayTransactionsFlow {
             .ctx
             .mining_manager()
             .clone()
-            .validate_and_insert_transaction_batch(&consensus, transactions, Priority::Low, Orphan::Allowed, RbfPolicy::Allowed)
+            .validate_and_insert_transaction_batch(
+                &consensus,
+                transactions,
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Allowed,
+                TransactionSource::Peer(self.router.net_address().ip()),
+            )
             .await;
 
         for res in insert_results.iter() {
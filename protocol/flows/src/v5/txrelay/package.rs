@@ -0,0 +1,74 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use std::sync::Arc;
+use vecno_consensus_core::tx::Transaction;
+use vecno_core::debug;
+use vecno_mining::mempool::tx::{Priority, TransactionSource};
+use vecno_p2p_lib::{common::ProtocolError, dequeue, pb::vecnod_message::Payload, IncomingRoute, Router};
+
+/// Flow listening to TransactionPackage messages, adds the received packages to the mempool as a unit
+/// and propagates the accepted ones to the rest of the network.
+///
+/// Only registered for peers supporting package relay, see [`crate::flow_context::PACKAGE_RELAY_PROTOCOL_VERSION`].
+pub struct RelayTransactionPackagesFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for RelayTransactionPackagesFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl RelayTransactionPackagesFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transactions: Vec<Transaction> = dequeue!(self.incoming_route, Payload::TransactionPackage)?.try_into()?;
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync and thus not mining
+            if !session.async_is_nearly_synced().await {
+                continue;
+            }
+
+            let transaction_count = transactions.len();
+            let transaction_insertion = match self
+                .ctx
+                .mining_manager()
+                .clone()
+                .validate_and_insert_transaction_package(
+                    &session,
+                    transactions,
+                    Priority::Low,
+                    TransactionSource::Peer(self.router.net_address().ip()),
+                )
+                .await
+            {
+                Ok(transaction_insertion) => transaction_insertion,
+                Err(err) => {
+                    // A package already relayed by another peer is rejected as a duplicate, so rejections are expected
+                    debug!(
+                        "Transaction package of {} transactions from peer {} was rejected: {}",
+                        transaction_count, self.router, err
+                    );
+                    continue;
+                }
+            };
+
+            // The package transactions come first among the accepted ones, followed by the unorphaned transactions
+            self.ctx.relay_transaction_package(&transaction_insertion, transaction_count, Some(self.router.key())).await;
+            self.ctx.broadcast_transactions(transaction_insertion.accepted.iter().map(|x| x.id()), false).await;
+        }
+    }
+}
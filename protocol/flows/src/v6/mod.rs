@@ -10,9 +10,15 @@ use crate::v5::{
     request_ibd_chain_block_locator::RequestIbdChainBlockLocatorFlow,
     request_pp_proof::RequestPruningPointProofFlow,
     request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
-    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+    txrelay::{
+        flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+        package::RelayTransactionPackagesFlow,
+    },
+};
+use crate::{
+    flow_context::{FlowContext, PACKAGE_RELAY_PROTOCOL_VERSION},
+    flow_trait::Flow,
 };
-use crate::{flow_context::FlowContext, flow_trait::Flow};
 
 use std::sync::Arc;
 use vecno_p2p_lib::{Router, SharedIncomingRoute, VecnodMessagePayloadType};
@@ -22,7 +28,8 @@ use crate::v6::request_pruning_point_and_anticone::PruningPointAndItsAnticoneReq
 
 pub(crate) mod request_pruning_point_and_anticone;
 
-pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+/// Registers the flows of a peer which negotiated `protocol_version`, the v6 flows being shared by later versions
+pub fn register(ctx: FlowContext, router: Arc<Router>, protocol_version: u32) -> Vec<Box<dyn Flow>> {
     // IBD flow <-> invs flow communication uses a job channel in order to always
     // maintain at most a single pending job which can be updated
     let (ibd_sender, relay_receiver) = channel::job();
@@ -125,6 +132,14 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
         )),
     ];
 
+    if protocol_version >= PACKAGE_RELAY_PROTOCOL_VERSION {
+        flows.push(Box::new(RelayTransactionPackagesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![VecnodMessagePayloadType::TransactionPackage]),
+        )));
+    }
+
     let invs_route = router.subscribe_with_capacity(vec![VecnodMessagePayloadType::InvRelayBlock], ctx.block_invs_channel_size());
     let shared_invs_route = SharedIncomingRoute::new(invs_route);
 
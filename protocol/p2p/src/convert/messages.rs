@@ -10,7 +10,7 @@ use crate::pb as protowire;
 use vecno_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use vecno_hashes::Hash;
 use vecno_utils::networking::{IpAddress, PeerId};
@@ -37,6 +37,12 @@ impl From<Version> for protowire::VersionMessage {
     }
 }
 
+impl<T: AsRef<Transaction>> From<&[T]> for protowire::TransactionPackageMessage {
+    fn from(transactions: &[T]) -> Self {
+        Self { transactions: transactions.iter().map(|tx| tx.as_ref().into()).collect() }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -199,6 +205,14 @@ impl TryFrom<protowire::InvTransactionsMessage> for Vec<TransactionId> {
     }
 }
 
+impl TryFrom<protowire::TransactionPackageMessage> for Vec<Transaction> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::TransactionPackageMessage) -> Result<Self, Self::Error> {
+        msg.transactions.into_iter().map(|tx| tx.try_into()).collect()
+    }
+}
+
 impl TryFrom<protowire::TransactionNotFoundMessage> for TransactionId {
     type Error = ConversionError;
 
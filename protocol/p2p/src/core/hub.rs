@@ -142,6 +142,20 @@ impl Hub {
         }
     }
 
+    /// Broadcast a message to all peers having negotiated at least `protocol_version`, except for `excluded_peer` if provided
+    pub async fn broadcast_to_protocol_version(&self, msg: VecnodMessage, protocol_version: u32, excluded_peer: Option<PeerKey>) {
+        let peers = self
+            .peers
+            .read()
+            .values()
+            .filter(|router| router.properties().protocol_version >= protocol_version && Some(router.key()) != excluded_peer)
+            .cloned()
+            .collect::<Vec<_>>();
+        for router in peers {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+    }
+
     /// Broadcast a vector of messages to all peers
     pub async fn broadcast_many(&self, msgs: Vec<VecnodMessage>) {
         if msgs.is_empty() {
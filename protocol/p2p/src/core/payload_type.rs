@@ -46,6 +46,7 @@ pub enum VecnodMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    TransactionPackage,
 }
 
 impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
@@ -100,6 +101,7 @@ impl From<&VecnodMessagePayload> for VecnodMessagePayloadType {
             VecnodMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 VecnodMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            VecnodMessagePayload::TransactionPackage(_) => VecnodMessagePayloadType::TransactionPackage,
         }
     }
 }
@@ -64,6 +64,7 @@ impl EchoFlow {
             VecnodMessagePayloadType::IbdChainBlockLocator,
             VecnodMessagePayloadType::RequestAntipast,
             VecnodMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            VecnodMessagePayloadType::TransactionPackage,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
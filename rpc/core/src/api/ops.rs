@@ -136,6 +136,20 @@ pub enum RpcApiOps {
     GetFeeEstimateExperimental = 148,
     /// Block color determination by iterating DAG.
     GetCurrentBlockColor = 149,
+    /// Get an accepted transaction from the transaction index
+    GetTransaction = 150,
+    /// Get a list of accepted transactions from the transaction index
+    GetTransactionsByIds = 151,
+    /// Get a page of the accepted transactions of an address from the transaction index
+    GetAddressHistory = 152,
+    /// Rebuild the UTXO set as of a past selected chain block
+    GetUtxosAtChainBlock = 153,
+    /// Page through the historical selected chain of an archival node by score
+    GetArchivalChainBlocks = 154,
+    /// Extracts a package of dependent transactions out of the request message and attempts to add them to the mempool as a unit
+    SubmitTransactionPackage = 155,
+    /// Get the recent history of the mempool statistics
+    GetMempoolStats = 156,
 }
 
 impl RpcApiOps {
@@ -14,6 +14,8 @@ use std::sync::Arc;
 use vecno_notify::{listener::ListenerId, scope::Scope, subscription::Command};
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
+pub const MAX_ADDRESS_HISTORY_PAGE_SIZE: u32 = 1_000;
+pub const MAX_ARCHIVAL_CHAIN_PAGE_SIZE: u32 = 1_000;
 
 /// Client RPC Api
 ///
@@ -226,6 +228,19 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: SubmitTransactionReplacementRequest,
     ) -> RpcResult<SubmitTransactionReplacementResponse>;
 
+    /// Submits a package of dependent transactions, sorted topologically, to the mempool. The package is accepted
+    /// or rejected as a whole, with its fee considered as a whole.
+    ///
+    /// Returns the IDs of the accepted transactions, in package order.
+    async fn submit_transaction_package(&self, transactions: Vec<RpcTransaction>) -> RpcResult<Vec<RpcTransactionId>> {
+        Ok(self.submit_transaction_package_call(None, SubmitTransactionPackageRequest { transactions }).await?.transaction_ids)
+    }
+    async fn submit_transaction_package_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(None, GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -459,6 +474,17 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse>;
 
+    /// Requests the recent history of the mempool statistics, sampled by the node at a fixed interval.
+    /// Only the `limit` most recent samples are returned, unless `limit` is 0.
+    async fn get_mempool_stats(&self, limit: u32) -> RpcResult<GetMempoolStatsResponse> {
+        self.get_mempool_stats_call(None, GetMempoolStatsRequest { limit }).await
+    }
+    async fn get_mempool_stats_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMempoolStatsRequest,
+    ) -> RpcResult<GetMempoolStatsResponse>;
+
     ///
     async fn get_current_block_color(&self, hash: RpcHash) -> RpcResult<GetCurrentBlockColorResponse> {
         Ok(self.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?)
@@ -469,6 +495,94 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Transaction index API
+
+    /// Requests an accepted transaction from the transaction index.
+    async fn get_transaction(&self, transaction_id: RpcTransactionId, include_transaction: bool) -> RpcResult<RpcIndexedTransaction> {
+        Ok(self.get_transaction_call(None, GetTransactionRequest::new(transaction_id, include_transaction)).await?.transaction)
+    }
+    async fn get_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse>;
+
+    /// Requests a list of accepted transactions from the transaction index.
+    /// Transactions which are not indexed are omitted from the result.
+    async fn get_transactions_by_ids(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+        include_transactions: bool,
+    ) -> RpcResult<Vec<RpcIndexedTransaction>> {
+        Ok(self
+            .get_transactions_by_ids_call(None, GetTransactionsByIdsRequest::new(transaction_ids, include_transactions))
+            .await?
+            .transactions)
+    }
+    async fn get_transactions_by_ids_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse>;
+
+    /// Requests a page of the accepted transactions which spent from or paid to an address.
+    /// See [`GetAddressHistoryRequest`] for the paging semantics.
+    async fn get_address_history(
+        &self,
+        address: RpcAddress,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<RpcAddressHistoryCursor>,
+        limit: u32,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        self.get_address_history_call(None, GetAddressHistoryRequest::new(address, start_daa_score, end_daa_score, cursor, limit))
+            .await
+    }
+    async fn get_address_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse>;
+
+    /// Requests the UTXOs paying to a list of addresses as of a past selected chain block above the pruning point.
+    /// The UTXO set is rebuilt by reverse-applying the UTXO diffs of the chain and is verified against the UTXO
+    /// commitment of the block. An empty address list requests the full UTXO set. Requires an unsafe RPC node.
+    async fn get_utxos_at_chain_block(
+        &self,
+        chain_block_hash: RpcHash,
+        addresses: Vec<RpcAddress>,
+    ) -> RpcResult<GetUtxosAtChainBlockResponse> {
+        self.get_utxos_at_chain_block_call(None, GetUtxosAtChainBlockRequest::new(chain_block_hash, addresses)).await
+    }
+    async fn get_utxos_at_chain_block_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetUtxosAtChainBlockRequest,
+    ) -> RpcResult<GetUtxosAtChainBlockResponse>;
+
+    /// Requests a page of the historical selected chain of an archival node, with the merge set and optionally the
+    /// acceptance data of every chain block. Chain blocks are located by DAA score or blue score.
+    async fn get_archival_chain_blocks(
+        &self,
+        score_kind: RpcChainScoreKind,
+        start_score: u64,
+        end_score: Option<u64>,
+        limit: u32,
+        include_acceptance_data: bool,
+    ) -> RpcResult<GetArchivalChainBlocksResponse> {
+        self.get_archival_chain_blocks_call(
+            None,
+            GetArchivalChainBlocksRequest::new(score_kind, start_score, end_score, limit, include_acceptance_data),
+        )
+        .await
+    }
+    async fn get_archival_chain_blocks_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetArchivalChainBlocksRequest,
+    ) -> RpcResult<GetArchivalChainBlocksResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -2,8 +2,11 @@
 
 use std::sync::Arc;
 
-use crate::{RpcBlock, RpcError, RpcRawBlock, RpcResult, RpcTransaction};
-use vecno_consensus_core::block::{Block, MutableBlock};
+use crate::{RpcBlock, RpcChainScoreKind, RpcError, RpcRawBlock, RpcResult, RpcTransaction};
+use vecno_consensus_core::{
+    block::{Block, MutableBlock},
+    ChainScoreKind,
+};
 
 // ----------------------------------------------------------------------------
 // consensus_core to rpc_core
@@ -52,6 +55,15 @@ impl From<MutableBlock> for RpcRawBlock {
 // rpc_core to consensus_core
 // ----------------------------------------------------------------------------
 
+impl From<RpcChainScoreKind> for ChainScoreKind {
+    fn from(item: RpcChainScoreKind) -> Self {
+        match item {
+            RpcChainScoreKind::DaaScore => ChainScoreKind::DaaScore,
+            RpcChainScoreKind::BlueScore => ChainScoreKind::BlueScore,
+        }
+    }
+}
+
 impl TryFrom<RpcBlock> for Block {
     type Error = RpcError;
     fn try_from(item: RpcBlock) -> RpcResult<Self> {
@@ -3,6 +3,7 @@
 use crate::RpcUtxoEntry;
 use crate::RpcUtxosByAddressesEntry;
 use vecno_addresses::Prefix;
+use vecno_consensus_core::tx::{TransactionOutpoint, UtxoEntry};
 use vecno_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
 use vecno_txscript::extract_script_pub_key_address;
 
@@ -25,3 +26,18 @@ pub fn utxo_set_into_rpc(item: &UtxoSetByScriptPublicKey, prefix: Option<Prefix>
         })
         .collect::<Vec<_>>()
 }
+
+// ----------------------------------------------------------------------------
+// consensus_core to rpc_core
+// ----------------------------------------------------------------------------
+
+pub fn utxo_entries_into_rpc(entries: Vec<(TransactionOutpoint, UtxoEntry)>, prefix: Option<Prefix>) -> Vec<RpcUtxosByAddressesEntry> {
+    entries
+        .into_iter()
+        .map(|(outpoint, entry)| RpcUtxosByAddressesEntry {
+            address: prefix.and_then(|x| extract_script_pub_key_address(&entry.script_public_key, x).ok()),
+            outpoint: outpoint.into(),
+            utxo_entry: entry.into(),
+        })
+        .collect()
+}
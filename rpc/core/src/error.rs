@@ -51,6 +51,9 @@ pub enum RpcError {
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
+    #[error("Rejected transaction package: {0}")]
+    RejectedTransactionPackage(String),
+
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
@@ -63,6 +66,12 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --archival argument.")]
+    NoArchival,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -0,0 +1,84 @@
+use super::{RpcHash, RpcTransactionId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The score by which the selected chain of an archival node is paged through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcChainScoreKind {
+    DaaScore = 0,
+    BlueScore = 1,
+}
+
+/// The transactions of a merged block which were accepted by a chain block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMergedBlockAcceptance {
+    pub merged_block_hash: RpcHash,
+    pub accepted_transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl RpcMergedBlockAcceptance {
+    pub fn new(merged_block_hash: RpcHash, accepted_transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { merged_block_hash, accepted_transaction_ids }
+    }
+}
+
+/// A block of the historical selected chain together with its merge set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcArchivalChainBlock {
+    pub hash: RpcHash,
+    pub daa_score: u64,
+    pub blue_score: u64,
+    pub timestamp: u64,
+    pub mergeset_blues_hashes: Vec<RpcHash>,
+    pub mergeset_reds_hashes: Vec<RpcHash>,
+    /// The transactions accepted by the block, per merged block. Empty unless requested.
+    pub acceptance_data: Vec<RpcMergedBlockAcceptance>,
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_ARCHIVAL_CHAIN_BLOCK: &'static str = r#"
+            /**
+             * Score by which the selected chain of an archival node is paged through.
+             * 
+             * @category Node RPC
+             */
+            export enum ChainScoreKind {
+                DaaScore = "daaScore",
+                BlueScore = "blueScore",
+            }
+
+            /**
+             * Transactions of a merged block which were accepted by a chain block.
+             * 
+             * @category Node RPC
+             */
+            export interface IMergedBlockAcceptance {
+                mergedBlockHash : HexString;
+                acceptedTransactionIds : HexString[];
+            }
+
+            /**
+             * Block of the historical selected chain together with its merge set.
+             * 
+             * @category Node RPC
+             */
+            export interface IArchivalChainBlock {
+                hash : HexString;
+                daaScore : bigint;
+                blueScore : bigint;
+                timestamp : bigint;
+                mergesetBluesHashes : HexString[];
+                mergesetRedsHashes : HexString[];
+                acceptanceData : IMergedBlockAcceptance[];
+            }
+        "#;
+    }
+}
@@ -63,6 +63,67 @@ impl Deserializer for RpcMempoolEntryByAddress {
     }
 }
 
+/// The state of the mempool at a given time, as sampled by the node
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolStatsSample {
+    /// The time of the sample, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// The number of transactions in the transaction pool, orphans excluded
+    pub transaction_count: u64,
+    /// The total mass of the transactions in the transaction pool
+    pub total_mass: u64,
+    /// The number of orphan transactions
+    pub orphan_count: u64,
+    /// The number of transactions evicted to make room for higher feerate transactions since the previous sample
+    pub evicted_count: u64,
+    /// The number of transactions replaced by fee since the previous sample
+    pub replaced_count: u64,
+    /// The number of transactions rejected by the mempool policy since the previous sample
+    pub policy_rejected_count: u64,
+    /// The number of transactions of the transaction pool in every feerate bucket
+    pub feerate_histogram: Vec<u64>,
+}
+
+impl Serializer for RpcMempoolStatsSample {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        store!(u64, &self.transaction_count, writer)?;
+        store!(u64, &self.total_mass, writer)?;
+        store!(u64, &self.orphan_count, writer)?;
+        store!(u64, &self.evicted_count, writer)?;
+        store!(u64, &self.replaced_count, writer)?;
+        store!(u64, &self.policy_rejected_count, writer)?;
+        store!(Vec<u64>, &self.feerate_histogram, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMempoolStatsSample {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let timestamp = load!(u64, reader)?;
+        let transaction_count = load!(u64, reader)?;
+        let total_mass = load!(u64, reader)?;
+        let orphan_count = load!(u64, reader)?;
+        let evicted_count = load!(u64, reader)?;
+        let replaced_count = load!(u64, reader)?;
+        let policy_rejected_count = load!(u64, reader)?;
+        let feerate_histogram = load!(Vec<u64>, reader)?;
+        Ok(Self {
+            timestamp,
+            transaction_count,
+            total_mass,
+            orphan_count,
+            evicted_count,
+            replaced_count,
+            policy_rejected_count,
+            feerate_histogram,
+        })
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -80,5 +141,24 @@ cfg_if::cfg_if! {
                 isOrphan : boolean;
             }
         "#;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_MEMPOOL_STATS_SAMPLE: &'static str = r#"
+            /**
+             * Mempool statistics sample.
+             * 
+             * @category Node RPC
+             */
+            export interface IMempoolStatsSample {
+                timestamp : bigint;
+                transactionCount : bigint;
+                totalMass : bigint;
+                orphanCount : bigint;
+                evictedCount : bigint;
+                replacedCount : bigint;
+                policyRejectedCount : bigint;
+                feerateHistogram : bigint[];
+            }
+        "#;
     }
 }
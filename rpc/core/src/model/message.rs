@@ -731,6 +731,69 @@ impl Deserializer for SubmitTransactionResponse {
     }
 }
 
+/// Submits a set of dependent transactions, sorted topologically, to be accepted into the mempool as a unit.
+/// The fee of the package is considered as a whole, so a transaction can be paid for by its descendants.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageRequest {
+    pub transactions: Vec<RpcTransaction>,
+}
+
+impl SubmitTransactionPackageRequest {
+    pub fn new(transactions: Vec<RpcTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for SubmitTransactionPackageRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionPackageRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageResponse {
+    /// The ids of the accepted transactions, in package order
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl SubmitTransactionPackageResponse {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+impl Serializer for SubmitTransactionPackageResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SubmitTransactionPackageResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+
+        Ok(Self { transaction_ids })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionReplacementRequest {
@@ -2568,6 +2631,68 @@ impl Deserializer for GetFeeEstimateResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolStatsRequest {
+    /// The maximal number of most recent samples to return, 0 requesting the whole history
+    #[serde(default)]
+    pub limit: u32,
+}
+
+impl GetMempoolStatsRequest {
+    pub fn new(limit: u32) -> Self {
+        Self { limit }
+    }
+}
+
+impl Serializer for GetMempoolStatsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u32, &self.limit, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolStatsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let limit = load!(u32, reader)?;
+        Ok(Self { limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolStatsResponse {
+    /// The interval between two samples, in seconds
+    pub sample_interval: u64,
+    /// The lower bounds, in sompi/gram, of the buckets of the feerate histograms.
+    /// Every bucket spans up to the lower bound of the next one, the last bucket being unbounded.
+    pub feerate_buckets: Vec<f64>,
+    /// The samples, from the oldest to the most recent
+    pub samples: Vec<RpcMempoolStatsSample>,
+}
+
+impl Serializer for GetMempoolStatsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.sample_interval, writer)?;
+        store!(Vec<f64>, &self.feerate_buckets, writer)?;
+        serialize!(Vec<RpcMempoolStatsSample>, &self.samples, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolStatsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let sample_interval = load!(u64, reader)?;
+        let feerate_buckets = load!(Vec<f64>, reader)?;
+        let samples = deserialize!(Vec<RpcMempoolStatsSample>, reader)?;
+        Ok(Self { sample_interval, feerate_buckets, samples })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateExperimentalRequest {
@@ -2666,6 +2791,375 @@ impl Deserializer for GetCurrentBlockColorResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    pub include_transaction: bool,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId, include_transaction: bool) -> Self {
+        Self { transaction_id, include_transaction }
+    }
+}
+
+impl Serializer for GetTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(bool, &self.include_transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let include_transaction = load!(bool, reader)?;
+
+        Ok(Self { transaction_id, include_transaction })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    pub transaction: RpcIndexedTransaction,
+}
+
+impl GetTransactionResponse {
+    pub fn new(transaction: RpcIndexedTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+impl Serializer for GetTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcIndexedTransaction, &self.transaction, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcIndexedTransaction, reader)?;
+
+        Ok(Self { transaction })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub include_transactions: bool,
+}
+
+impl GetTransactionsByIdsRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, include_transactions: bool) -> Self {
+        Self { transaction_ids, include_transactions }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+        store!(bool, &self.include_transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let include_transactions = load!(bool, reader)?;
+
+        Ok(Self { transaction_ids, include_transactions })
+    }
+}
+
+/// Transactions which are not found in the transaction index are omitted from the response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsResponse {
+    pub transactions: Vec<RpcIndexedTransaction>,
+}
+
+impl GetTransactionsByIdsResponse {
+    pub fn new(transactions: Vec<RpcIndexedTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+impl Serializer for GetTransactionsByIdsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcIndexedTransaction>, &self.transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionsByIdsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcIndexedTransaction>, reader)?;
+
+        Ok(Self { transactions })
+    }
+}
+
+/// Requests a page of the history of an address, i.e., of the accepted transactions which spent from or paid to it.
+///
+/// Entries are ordered by accepting block DAA score and then by transaction id. A page starts at `cursor` if supplied
+/// (as returned in `next_cursor` by the previous page), else at `start_daa_score`, and only holds entries whose
+/// accepting block DAA score is lower than `end_daa_score`, if supplied.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryRequest {
+    pub address: RpcAddress,
+    pub start_daa_score: Option<u64>,
+    pub end_daa_score: Option<u64>,
+    pub cursor: Option<RpcAddressHistoryCursor>,
+    /// The maximum number of entries to return. Zero or values above the node limit are capped to the node limit.
+    pub limit: u32,
+}
+
+impl GetAddressHistoryRequest {
+    pub fn new(
+        address: RpcAddress,
+        start_daa_score: Option<u64>,
+        end_daa_score: Option<u64>,
+        cursor: Option<RpcAddressHistoryCursor>,
+        limit: u32,
+    ) -> Self {
+        Self { address, start_daa_score, end_daa_score, cursor, limit }
+    }
+}
+
+impl Serializer for GetAddressHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(Option<u64>, &self.start_daa_score, writer)?;
+        store!(Option<u64>, &self.end_daa_score, writer)?;
+        store!(Option<RpcAddressHistoryCursor>, &self.cursor, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let start_daa_score = load!(Option<u64>, reader)?;
+        let end_daa_score = load!(Option<u64>, reader)?;
+        let cursor = load!(Option<RpcAddressHistoryCursor>, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { address, start_daa_score, end_daa_score, cursor, limit })
+    }
+}
+
+/// `next_cursor` is set if more entries are available, and should be supplied in the request of the next page.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+    pub next_cursor: Option<RpcAddressHistoryCursor>,
+}
+
+impl GetAddressHistoryResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>, next_cursor: Option<RpcAddressHistoryCursor>) -> Self {
+        Self { entries, next_cursor }
+    }
+}
+
+impl Serializer for GetAddressHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcAddressHistoryEntry>, &self.entries, writer)?;
+        store!(Option<RpcAddressHistoryCursor>, &self.next_cursor, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = load!(Vec<RpcAddressHistoryEntry>, reader)?;
+        let next_cursor = load!(Option<RpcAddressHistoryCursor>, reader)?;
+
+        Ok(Self { entries, next_cursor })
+    }
+}
+
+/// Requests the UTXOs paying to `addresses` as of the selected chain block `chain_block_hash`,
+/// or the full UTXO set as of this block if `addresses` is empty.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUtxosAtChainBlockRequest {
+    pub chain_block_hash: RpcHash,
+    pub addresses: Vec<RpcAddress>,
+}
+
+impl GetUtxosAtChainBlockRequest {
+    pub fn new(chain_block_hash: RpcHash, addresses: Vec<RpcAddress>) -> Self {
+        Self { chain_block_hash, addresses }
+    }
+}
+
+impl Serializer for GetUtxosAtChainBlockRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.chain_block_hash, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetUtxosAtChainBlockRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let chain_block_hash = load!(RpcHash, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+
+        Ok(Self { chain_block_hash, addresses })
+    }
+}
+
+/// `utxo_commitment` is the UTXO commitment of the chain block, which the rebuilt UTXO set was verified against.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUtxosAtChainBlockResponse {
+    pub entries: Vec<RpcUtxosByAddressesEntry>,
+    pub utxo_commitment: RpcHash,
+}
+
+impl GetUtxosAtChainBlockResponse {
+    pub fn new(entries: Vec<RpcUtxosByAddressesEntry>, utxo_commitment: RpcHash) -> Self {
+        Self { entries, utxo_commitment }
+    }
+}
+
+impl Serializer for GetUtxosAtChainBlockResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcUtxosByAddressesEntry>, &self.entries, writer)?;
+        store!(RpcHash, &self.utxo_commitment, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetUtxosAtChainBlockResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcUtxosByAddressesEntry>, reader)?;
+        let utxo_commitment = load!(RpcHash, reader)?;
+
+        Ok(Self { entries, utxo_commitment })
+    }
+}
+
+/// Requests up to `limit` selected chain blocks of an archival node whose score of kind `score_kind` is at least
+/// `start_score` and lower than `end_score`, if supplied.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetArchivalChainBlocksRequest {
+    pub score_kind: RpcChainScoreKind,
+    pub start_score: u64,
+    pub end_score: Option<u64>,
+    /// The maximum number of chain blocks to return. Zero or values above the node limit are capped to the node limit.
+    pub limit: u32,
+    pub include_acceptance_data: bool,
+}
+
+impl GetArchivalChainBlocksRequest {
+    pub fn new(
+        score_kind: RpcChainScoreKind,
+        start_score: u64,
+        end_score: Option<u64>,
+        limit: u32,
+        include_acceptance_data: bool,
+    ) -> Self {
+        Self { score_kind, start_score, end_score, limit, include_acceptance_data }
+    }
+}
+
+impl Serializer for GetArchivalChainBlocksRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcChainScoreKind, &self.score_kind, writer)?;
+        store!(u64, &self.start_score, writer)?;
+        store!(Option<u64>, &self.end_score, writer)?;
+        store!(u32, &self.limit, writer)?;
+        store!(bool, &self.include_acceptance_data, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetArchivalChainBlocksRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let score_kind = load!(RpcChainScoreKind, reader)?;
+        let start_score = load!(u64, reader)?;
+        let end_score = load!(Option<u64>, reader)?;
+        let limit = load!(u32, reader)?;
+        let include_acceptance_data = load!(bool, reader)?;
+
+        Ok(Self { score_kind, start_score, end_score, limit, include_acceptance_data })
+    }
+}
+
+/// `next_start_score` is set if more chain blocks are available, and should be supplied as `start_score` in the request of the next page.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetArchivalChainBlocksResponse {
+    pub chain_blocks: Vec<RpcArchivalChainBlock>,
+    pub next_start_score: Option<u64>,
+}
+
+impl GetArchivalChainBlocksResponse {
+    pub fn new(chain_blocks: Vec<RpcArchivalChainBlock>, next_start_score: Option<u64>) -> Self {
+        Self { chain_blocks, next_start_score }
+    }
+}
+
+impl Serializer for GetArchivalChainBlocksResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcArchivalChainBlock>, &self.chain_blocks, writer)?;
+        store!(Option<u64>, &self.next_start_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetArchivalChainBlocksResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let chain_blocks = load!(Vec<RpcArchivalChainBlock>, reader)?;
+        let next_start_score = load!(Option<u64>, reader)?;
+
+        Ok(Self { chain_blocks, next_start_score })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
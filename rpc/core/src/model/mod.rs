@@ -2,6 +2,7 @@
 //! used in RPC methods.
 
 pub mod address;
+pub mod archival;
 pub mod block;
 pub mod blue_work;
 pub mod feerate_estimate;
@@ -16,8 +17,10 @@ pub mod script_class;
 pub mod subnets;
 mod tests;
 pub mod tx;
+pub mod txindex;
 
 pub use address::*;
+pub use archival::*;
 pub use block::*;
 pub use blue_work::*;
 pub use feerate_estimate::*;
@@ -30,3 +33,4 @@ pub use network::*;
 pub use peer::*;
 pub use subnets::*;
 pub use tx::*;
+pub use txindex::*;
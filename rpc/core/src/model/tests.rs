@@ -223,7 +223,11 @@ mod mockery {
 
     impl Mock for RpcTransactionOutputVerboseData {
         fn mock() -> Self {
-            RpcTransactionOutputVerboseData { script_public_key_type: RpcScriptClass::PubKey, script_public_key_address: mock() }
+            RpcTransactionOutputVerboseData {
+                script_public_key_type: RpcScriptClass::PubKey,
+                script_public_key_address: mock(),
+                script_public_key_asm: "OpData1 0x00 OpCheckSig".to_string(),
+            }
         }
     }
 
@@ -313,6 +317,71 @@ mod mockery {
         }
     }
 
+    impl Mock for RpcMempoolStatsSample {
+        fn mock() -> Self {
+            RpcMempoolStatsSample {
+                timestamp: mock(),
+                transaction_count: mock(),
+                total_mass: mock(),
+                orphan_count: mock(),
+                evicted_count: mock(),
+                replaced_count: mock(),
+                policy_rejected_count: mock(),
+                feerate_histogram: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcIndexedTransaction {
+        fn mock() -> Self {
+            RpcIndexedTransaction {
+                transaction_id: mock(),
+                including_block_hash: mock(),
+                index_within_block: mock(),
+                accepting_block_hash: mock(),
+                transaction: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcAddressHistoryEntry {
+        fn mock() -> Self {
+            RpcAddressHistoryEntry { transaction_id: mock(), accepting_block_hash: mock(), accepting_block_daa_score: mock() }
+        }
+    }
+
+    impl Mock for RpcAddressHistoryCursor {
+        fn mock() -> Self {
+            RpcAddressHistoryCursor { accepting_block_daa_score: mock(), transaction_id: mock() }
+        }
+    }
+
+    impl Mock for RpcChainScoreKind {
+        fn mock() -> Self {
+            RpcChainScoreKind::BlueScore
+        }
+    }
+
+    impl Mock for RpcMergedBlockAcceptance {
+        fn mock() -> Self {
+            RpcMergedBlockAcceptance { merged_block_hash: mock(), accepted_transaction_ids: mock() }
+        }
+    }
+
+    impl Mock for RpcArchivalChainBlock {
+        fn mock() -> Self {
+            RpcArchivalChainBlock {
+                hash: mock(),
+                daa_score: mock(),
+                blue_score: mock(),
+                timestamp: mock(),
+                mergeset_blues_hashes: mock(),
+                mergeset_reds_hashes: mock(),
+                acceptance_data: mock(),
+            }
+        }
+    }
+
     impl Mock for RpcMempoolEntryByAddress {
         fn mock() -> Self {
             RpcMempoolEntryByAddress { address: mock(), sending: mock(), receiving: mock() }
@@ -622,6 +691,22 @@ mod mockery {
 
     test!(SubmitTransactionResponse);
 
+    impl Mock for SubmitTransactionPackageRequest {
+        fn mock() -> Self {
+            SubmitTransactionPackageRequest { transactions: mock() }
+        }
+    }
+
+    test!(SubmitTransactionPackageRequest);
+
+    impl Mock for SubmitTransactionPackageResponse {
+        fn mock() -> Self {
+            SubmitTransactionPackageResponse { transaction_ids: mock() }
+        }
+    }
+
+    test!(SubmitTransactionPackageResponse);
+
     impl Mock for GetSubnetworkRequest {
         fn mock() -> Self {
             GetSubnetworkRequest { subnetwork_id: mock() }
@@ -905,6 +990,22 @@ mod mockery {
 
     test!(GetMempoolEntriesByAddressesResponse);
 
+    impl Mock for GetMempoolStatsRequest {
+        fn mock() -> Self {
+            GetMempoolStatsRequest { limit: mock() }
+        }
+    }
+
+    test!(GetMempoolStatsRequest);
+
+    impl Mock for GetMempoolStatsResponse {
+        fn mock() -> Self {
+            GetMempoolStatsResponse { sample_interval: mock(), feerate_buckets: mock(), samples: mock() }
+        }
+    }
+
+    test!(GetMempoolStatsResponse);
+
     impl Mock for GetCoinSupplyRequest {
         fn mock() -> Self {
             GetCoinSupplyRequest {}
@@ -1064,6 +1165,92 @@ mod mockery {
 
     test!(GetDaaScoreTimestampEstimateResponse);
 
+    impl Mock for GetTransactionRequest {
+        fn mock() -> Self {
+            GetTransactionRequest { transaction_id: mock(), include_transaction: true }
+        }
+    }
+
+    test!(GetTransactionRequest);
+
+    impl Mock for GetTransactionResponse {
+        fn mock() -> Self {
+            GetTransactionResponse { transaction: mock() }
+        }
+    }
+
+    test!(GetTransactionResponse);
+
+    impl Mock for GetTransactionsByIdsRequest {
+        fn mock() -> Self {
+            GetTransactionsByIdsRequest { transaction_ids: mock(), include_transactions: true }
+        }
+    }
+
+    test!(GetTransactionsByIdsRequest);
+
+    impl Mock for GetTransactionsByIdsResponse {
+        fn mock() -> Self {
+            GetTransactionsByIdsResponse { transactions: mock() }
+        }
+    }
+
+    test!(GetTransactionsByIdsResponse);
+
+    impl Mock for GetAddressHistoryRequest {
+        fn mock() -> Self {
+            GetAddressHistoryRequest { address: mock(), start_daa_score: mock(), end_daa_score: mock(), cursor: mock(), limit: mock() }
+        }
+    }
+
+    test!(GetAddressHistoryRequest);
+
+    impl Mock for GetAddressHistoryResponse {
+        fn mock() -> Self {
+            GetAddressHistoryResponse { entries: mock(), next_cursor: mock() }
+        }
+    }
+
+    test!(GetAddressHistoryResponse);
+
+    impl Mock for GetUtxosAtChainBlockRequest {
+        fn mock() -> Self {
+            GetUtxosAtChainBlockRequest { chain_block_hash: mock(), addresses: mock() }
+        }
+    }
+
+    test!(GetUtxosAtChainBlockRequest);
+
+    impl Mock for GetUtxosAtChainBlockResponse {
+        fn mock() -> Self {
+            GetUtxosAtChainBlockResponse { entries: mock(), utxo_commitment: mock() }
+        }
+    }
+
+    test!(GetUtxosAtChainBlockResponse);
+
+    impl Mock for GetArchivalChainBlocksRequest {
+        fn mock() -> Self {
+            GetArchivalChainBlocksRequest {
+                score_kind: mock(),
+                start_score: mock(),
+                end_score: mock(),
+                limit: mock(),
+                include_acceptance_data: mock(),
+            }
+        }
+    }
+
+    test!(GetArchivalChainBlocksRequest);
+
+    impl Mock for GetArchivalChainBlocksResponse {
+        fn mock() -> Self {
+            GetArchivalChainBlocksResponse { chain_blocks: mock(), next_start_score: mock() }
+        }
+    }
+
+    test!(GetArchivalChainBlocksResponse);
+
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
             NotifyBlockAddedRequest { command: Command::Start }
@@ -266,13 +266,17 @@ impl Deserializer for RpcTransactionOutput {
 pub struct RpcTransactionOutputVerboseData {
     pub script_public_key_type: RpcScriptClass,
     pub script_public_key_address: Address,
+    /// Disassembled script public key, see [`vecno_txscript::script_asm::disassemble`]
+    #[serde(default)]
+    pub script_public_key_asm: String,
 }
 
 impl Serializer for RpcTransactionOutputVerboseData {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u8, &1, writer)?;
+        store!(u8, &2, writer)?;
         store!(RpcScriptClass, &self.script_public_key_type, writer)?;
         store!(Address, &self.script_public_key_address, writer)?;
+        store!(String, &self.script_public_key_asm, writer)?;
 
         Ok(())
     }
@@ -280,11 +284,12 @@ impl Serializer for RpcTransactionOutputVerboseData {
 
 impl Deserializer for RpcTransactionOutputVerboseData {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u8, reader)?;
+        let version = load!(u8, reader)?;
         let script_public_key_type = load!(RpcScriptClass, reader)?;
         let script_public_key_address = load!(Address, reader)?;
+        let script_public_key_asm = if version > 1 { load!(String, reader)? } else { Default::default() };
 
-        Ok(Self { script_public_key_type, script_public_key_address })
+        Ok(Self { script_public_key_type, script_public_key_address, script_public_key_asm })
     }
 }
 
@@ -0,0 +1,131 @@
+use super::{RpcHash, RpcTransaction, RpcTransactionId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// An accepted transaction located via the transaction index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcIndexedTransaction {
+    pub transaction_id: RpcTransactionId,
+    /// The block containing the transaction
+    pub including_block_hash: RpcHash,
+    /// The position of the transaction within the including block
+    pub index_within_block: u32,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    /// The transaction itself, if requested and if the including block body is still available
+    pub transaction: Option<RpcTransaction>,
+}
+
+impl RpcIndexedTransaction {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        including_block_hash: RpcHash,
+        index_within_block: u32,
+        accepting_block_hash: RpcHash,
+        transaction: Option<RpcTransaction>,
+    ) -> Self {
+        Self { transaction_id, including_block_hash, index_within_block, accepting_block_hash, transaction }
+    }
+}
+
+impl Serializer for RpcIndexedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.including_block_hash, writer)?;
+        store!(u32, &self.index_within_block, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        serialize!(Option<RpcTransaction>, &self.transaction, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcIndexedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let including_block_hash = load!(RpcHash, reader)?;
+        let index_within_block = load!(u32, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let transaction = deserialize!(Option<RpcTransaction>, reader)?;
+        Ok(Self { transaction_id, including_block_hash, index_within_block, accepting_block_hash, transaction })
+    }
+}
+
+/// An accepted transaction which spent from or paid to an address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryEntry {
+    pub transaction_id: RpcTransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+    /// The DAA score of the accepting chain block
+    pub accepting_block_daa_score: u64,
+}
+
+impl RpcAddressHistoryEntry {
+    pub fn new(transaction_id: RpcTransactionId, accepting_block_hash: RpcHash, accepting_block_daa_score: u64) -> Self {
+        Self { transaction_id, accepting_block_hash, accepting_block_daa_score }
+    }
+}
+
+/// The position of an entry within the history of an address, used for paging through it.
+///
+/// The history of an address is ordered by the DAA score of the accepting block, and then by transaction id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryCursor {
+    pub accepting_block_daa_score: u64,
+    pub transaction_id: RpcTransactionId,
+}
+
+impl RpcAddressHistoryCursor {
+    pub fn new(accepting_block_daa_score: u64, transaction_id: RpcTransactionId) -> Self {
+        Self { accepting_block_daa_score, transaction_id }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_INDEXED_TRANSACTION: &'static str = r#"
+            /**
+             * Accepted transaction located via the transaction index.
+             * 
+             * @category Node RPC
+             */
+            export interface IIndexedTransaction {
+                transactionId : HexString;
+                includingBlockHash : HexString;
+                indexWithinBlock : number;
+                acceptingBlockHash : HexString;
+                transaction? : ITransaction;
+            }
+
+            /**
+             * Accepted transaction which spent from or paid to an address.
+             * 
+             * @category Node RPC
+             */
+            export interface IAddressHistoryEntry {
+                transactionId : HexString;
+                acceptingBlockHash : HexString;
+                acceptingBlockDaaScore : bigint;
+            }
+
+            /**
+             * Position of an entry within the history of an address.
+             * 
+             * @category Node RPC
+             */
+            export interface IAddressHistoryCursor {
+                acceptingBlockDaaScore : bigint;
+                transactionId : HexString;
+            }
+        "#;
+    }
+}
@@ -898,6 +898,219 @@ try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
 
 // ---
 
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--txindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId : HexString;
+        includeTransaction : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction : IIndexedTransaction;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionsByIdsRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--txindex` argument.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsRequest {
+        transactionIds : HexString[];
+        includeTransactions : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionsByIdsRequest, GetTransactionsByIdsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByIdsResponse,
+    r#"
+    /**
+     * Transactions which are not found in the transaction index are omitted.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsResponse {
+        transactions : IIndexedTransaction[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionsByIdsResponse, IGetTransactionsByIdsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressHistoryRequest,
+    r#"
+    /**
+     * Requires the node to run with the `--txindex` argument.
+     * Pass the `nextCursor` of a response as `cursor` to request the following page.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryRequest {
+        address : Address | string;
+        startDaaScore? : bigint;
+        endDaaScore? : bigint;
+        cursor? : IAddressHistoryCursor;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetAddressHistoryRequest, GetAddressHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressHistoryResponse,
+    r#"
+    /**
+     * `nextCursor` is set if more entries are available.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryResponse {
+        entries : IAddressHistoryEntry[];
+        nextCursor? : IAddressHistoryCursor;
+    }
+    "#,
+}
+
+try_from! ( args: GetAddressHistoryResponse, IGetAddressHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetUtxosAtChainBlockRequest,
+    r#"
+    /**
+     * Requests the UTXOs of the addresses as of a past selected chain block.
+     * An empty address list requests the full UTXO set as of the block.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetUtxosAtChainBlockRequest {
+        chainBlockHash : HexString;
+        addresses : Address[] | string[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetUtxosAtChainBlockRequest, GetUtxosAtChainBlockRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetUtxosAtChainBlockResponse,
+    r#"
+    /**
+     * `utxoCommitment` is the UTXO commitment of the chain block which the rebuilt UTXO set was verified against.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetUtxosAtChainBlockResponse {
+        entries : UtxoEntryReference[];
+        utxoCommitment : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetUtxosAtChainBlockResponse, IGetUtxosAtChainBlockResponse, {
+    let GetUtxosAtChainBlockResponse { entries, utxo_commitment } = args;
+    let entries = entries.into_iter().map(UtxoEntryReference::from).collect::<Vec<UtxoEntryReference>>();
+    let entries = js_sys::Array::from_iter(entries.into_iter().map(JsValue::from));
+    let response = IGetUtxosAtChainBlockResponse::default();
+    response.set("entries", entries.as_ref())?;
+    response.set("utxoCommitment", &utxo_commitment.into())?;
+    Ok(response)
+});
+
+// ---
+
+declare! {
+    IGetArchivalChainBlocksRequest,
+    r#"
+    /**
+     * Requests a page of the historical selected chain of an archival node,
+     * located by DAA score or blue score.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetArchivalChainBlocksRequest {
+        scoreKind : ChainScoreKind;
+        startScore : bigint;
+        endScore? : bigint;
+        limit : number;
+        includeAcceptanceData : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetArchivalChainBlocksRequest, GetArchivalChainBlocksRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetArchivalChainBlocksResponse,
+    r#"
+    /**
+     * `nextStartScore` is set if more chain blocks are available.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetArchivalChainBlocksResponse {
+        chainBlocks : IArchivalChainBlock[];
+        nextStartScore? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetArchivalChainBlocksResponse, IGetArchivalChainBlocksResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetDaaScoreTimestampEstimateRequest,
     r#"
@@ -1426,6 +1639,59 @@ try_from! ( args: SubmitTransactionReplacementResponse, ISubmitTransactionReplac
 
 // ---
 
+declare! {
+    ISubmitTransactionPackageRequest,
+    r#"
+    /**
+     * Submit a package of dependent transactions, sorted topologically, to the node.
+     * The package is accepted or rejected as a whole.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionPackageRequest {
+        transactions : Transaction[],
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionPackageRequest, SubmitTransactionPackageRequest, {
+    let transactions = args.try_get_value("transactions")?.ok_or_else(|| {
+        Error::MissingRpcFieldError("ISubmitTransactionPackageRequest".to_string(), "transactions".to_string())
+    })?;
+    let transactions = js_sys::Array::from(&transactions)
+        .iter()
+        .map(|transaction| -> Result<RpcTransaction> {
+            if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+                Ok(transaction.into())
+            } else {
+                let tx = Transaction::try_cast_from(&transaction)?;
+                Ok(tx.as_ref().into())
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SubmitTransactionPackageRequest { transactions })
+});
+
+declare! {
+    ISubmitTransactionPackageResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionPackageResponse {
+        transactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionPackageResponse, ISubmitTransactionPackageResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     ISubmitTransactionRequest,
     // "ISubmitTransactionRequest | Transaction",
@@ -1650,6 +1916,49 @@ try_from!( args: GetFeeEstimateResponse, IGetFeeEstimateResponse, {
 
 // ---
 
+declare! {
+    IGetMempoolStatsRequest,
+    r#"
+    /**
+     * Get the recent history of the mempool statistics from the node,
+     * limited to the `limit` most recent samples unless `limit` is omitted or 0.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetMempoolStatsRequest {
+        limit? : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetMempoolStatsRequest, GetMempoolStatsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMempoolStatsResponse,
+    r#"
+    /**
+     * Mempool statistics sampled every `sampleInterval` seconds, from the oldest
+     * to the most recent sample. The feerate histogram of every sample counts the
+     * transactions in each bucket of `feerateBuckets`, given by their lower bound in sompi/gram.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetMempoolStatsResponse {
+        sampleInterval : bigint;
+        feerateBuckets : number[];
+        samples : IMempoolStatsSample[];
+    }
+    "#,
+}
+
+try_from!( args: GetMempoolStatsResponse, IGetMempoolStatsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IFeeEstimateVerboseExperimentalData,
     r#"
@@ -255,6 +255,7 @@ impl RpcApi for GrpcClient {
     route!(add_peer_call, AddPeer);
     route!(submit_transaction_call, SubmitTransaction);
     route!(submit_transaction_replacement_call, SubmitTransactionReplacement);
+    route!(submit_transaction_package_call, SubmitTransactionPackage);
     route!(get_subnetwork_call, GetSubnetwork);
     route!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
     route!(get_blocks_call, GetBlocks);
@@ -275,7 +276,13 @@ impl RpcApi for GrpcClient {
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
     route!(get_fee_estimate_call, GetFeeEstimate);
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
+    route!(get_mempool_stats_call, GetMempoolStats);
     route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_transactions_by_ids_call, GetTransactionsByIds);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(get_utxos_at_chain_block_call, GetUtxosAtChainBlock);
+    route!(get_archival_chain_blocks_call, GetArchivalChainBlocks);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -38,6 +38,32 @@ from!(item: &vecno_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseData
     }
 });
 
+from!(item: &vecno_rpc_core::RpcChainScoreKind, protowire::RpcChainScoreKind, {
+    match item {
+        vecno_rpc_core::RpcChainScoreKind::DaaScore => protowire::RpcChainScoreKind::DaaScore,
+        vecno_rpc_core::RpcChainScoreKind::BlueScore => protowire::RpcChainScoreKind::BlueScore,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcMergedBlockAcceptance, protowire::RpcMergedBlockAcceptance, {
+    Self {
+        merged_block_hash: item.merged_block_hash.to_string(),
+        accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcArchivalChainBlock, protowire::RpcArchivalChainBlock, {
+    Self {
+        hash: item.hash.to_string(),
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        mergeset_blues_hashes: item.mergeset_blues_hashes.iter().map(|x| x.to_string()).collect(),
+        mergeset_reds_hashes: item.mergeset_reds_hashes.iter().map(|x| x.to_string()).collect(),
+        acceptance_data: item.acceptance_data.iter().map(|x| x.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -95,3 +121,41 @@ try_from!(item: &protowire::RpcBlockVerboseData, vecno_rpc_core::RpcBlockVerbose
         is_chain_block: item.is_chain_block,
     }
 });
+
+from!(item: protowire::RpcChainScoreKind, vecno_rpc_core::RpcChainScoreKind, {
+    match item {
+        protowire::RpcChainScoreKind::DaaScore => vecno_rpc_core::RpcChainScoreKind::DaaScore,
+        protowire::RpcChainScoreKind::BlueScore => vecno_rpc_core::RpcChainScoreKind::BlueScore,
+    }
+});
+
+try_from!(item: &protowire::RpcMergedBlockAcceptance, vecno_rpc_core::RpcMergedBlockAcceptance, {
+    Self {
+        merged_block_hash: RpcHash::from_str(&item.merged_block_hash)?,
+        accepted_transaction_ids: item
+            .accepted_transaction_ids
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<vecno_rpc_core::RpcHash>, faster_hex::Error>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcArchivalChainBlock, vecno_rpc_core::RpcArchivalChainBlock, {
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        timestamp: item.timestamp,
+        mergeset_blues_hashes: item
+            .mergeset_blues_hashes
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<vecno_rpc_core::RpcHash>, faster_hex::Error>>()?,
+        mergeset_reds_hashes: item
+            .mergeset_reds_hashes
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<vecno_rpc_core::RpcHash>, faster_hex::Error>>()?,
+        acceptance_data: item.acceptance_data.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
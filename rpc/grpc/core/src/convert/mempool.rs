@@ -18,6 +18,19 @@ from!(item: &vecno_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &vecno_rpc_core::RpcMempoolStatsSample, protowire::RpcMempoolStatsSample, {
+    Self {
+        timestamp: item.timestamp,
+        transaction_count: item.transaction_count,
+        total_mass: item.total_mass,
+        orphan_count: item.orphan_count,
+        evicted_count: item.evicted_count,
+        replaced_count: item.replaced_count,
+        policy_rejected_count: item.policy_rejected_count,
+        feerate_histogram: item.feerate_histogram.clone(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,3 +53,16 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, vecno_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+try_from!(item: &protowire::RpcMempoolStatsSample, vecno_rpc_core::RpcMempoolStatsSample, {
+    Self {
+        timestamp: item.timestamp,
+        transaction_count: item.transaction_count,
+        total_mass: item.total_mass,
+        orphan_count: item.orphan_count,
+        evicted_count: item.evicted_count,
+        replaced_count: item.replaced_count,
+        policy_rejected_count: item.policy_rejected_count,
+        feerate_histogram: item.feerate_histogram.clone(),
+    }
+});
@@ -256,6 +256,13 @@ from!(item: RpcResult<&vecno_rpc_core::SubmitTransactionReplacementResponse>, pr
     Self { transaction_id: item.transaction_id.to_string(), replaced_transaction: Some((&item.replaced_transaction).into()), error: None }
 });
 
+from!(item: &vecno_rpc_core::SubmitTransactionPackageRequest, protowire::SubmitTransactionPackageRequestMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&vecno_rpc_core::SubmitTransactionPackageResponse>, protowire::SubmitTransactionPackageResponseMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), error: None }
+});
+
 from!(item: &vecno_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
     Self { subnetwork_id: item.subnetwork_id.to_string() }
 });
@@ -421,6 +428,16 @@ from!(item: RpcResult<&vecno_rpc_core::GetFeeEstimateExperimentalResponse>, prot
     }
 });
 
+from!(item: &vecno_rpc_core::GetMempoolStatsRequest, protowire::GetMempoolStatsRequestMessage, { Self { limit: item.limit } });
+from!(item: RpcResult<&vecno_rpc_core::GetMempoolStatsResponse>, protowire::GetMempoolStatsResponseMessage, {
+    Self {
+        sample_interval: item.sample_interval,
+        feerate_buckets: item.feerate_buckets.clone(),
+        samples: item.samples.iter().map(|x| x.into()).collect(),
+        error: None
+    }
+});
+
 from!(item: &vecno_rpc_core::GetCurrentBlockColorRequest, protowire::GetCurrentBlockColorRequestMessage, {
     Self {
         hash: item.hash.to_string()
@@ -430,6 +447,65 @@ from!(item: RpcResult<&vecno_rpc_core::GetCurrentBlockColorResponse>, protowire:
     Self { blue: item.blue, error: None }
 });
 
+from!(item: &vecno_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), include_transaction: item.include_transaction }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self { transaction: Some((&item.transaction).into()), error: None }
+});
+
+from!(item: &vecno_rpc_core::GetTransactionsByIdsRequest, protowire::GetTransactionsByIdsRequestMessage, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        include_transactions: item.include_transactions,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetTransactionsByIdsResponse>, protowire::GetTransactionsByIdsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &vecno_rpc_core::GetAddressHistoryRequest, protowire::GetAddressHistoryRequestMessage, {
+    Self {
+        address: (&item.address).into(),
+        start_daa_score: item.start_daa_score.unwrap_or_default(),
+        end_daa_score: item.end_daa_score.unwrap_or_default(),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetAddressHistoryResponse>, protowire::GetAddressHistoryResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.as_ref().map(|x| x.into()),
+        error: None,
+    }
+});
+
+from!(item: &vecno_rpc_core::GetUtxosAtChainBlockRequest, protowire::GetUtxosAtChainBlockRequestMessage, {
+    Self { chain_block_hash: item.chain_block_hash.to_string(), addresses: item.addresses.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetUtxosAtChainBlockResponse>, protowire::GetUtxosAtChainBlockResponseMessage, {
+    debug!("GRPC, Creating GetUtxosAtChainBlock message with {} entries", item.entries.len());
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), utxo_commitment: item.utxo_commitment.to_string(), error: None }
+});
+
+from!(item: &vecno_rpc_core::GetArchivalChainBlocksRequest, protowire::GetArchivalChainBlocksRequestMessage, {
+    Self {
+        score_kind: protowire::RpcChainScoreKind::from(&item.score_kind) as i32,
+        start_score: item.start_score,
+        end_score: item.end_score.unwrap_or_default(),
+        limit: item.limit,
+        include_acceptance_data: item.include_acceptance_data,
+    }
+});
+from!(item: RpcResult<&vecno_rpc_core::GetArchivalChainBlocksResponse>, protowire::GetArchivalChainBlocksResponseMessage, {
+    Self {
+        chain_blocks: item.chain_blocks.iter().map(|x| x.into()).collect(),
+        next_start_score: item.next_start_score.unwrap_or_default(),
+        error: None,
+    }
+});
+
 from!(&vecno_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&vecno_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -738,6 +814,13 @@ try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResu
     }
 });
 
+try_from!(item: &protowire::SubmitTransactionPackageRequestMessage, vecno_rpc_core::SubmitTransactionPackageRequest, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::SubmitTransactionPackageResponseMessage, RpcResult<vecno_rpc_core::SubmitTransactionPackageResponse>, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, vecno_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: vecno_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -906,6 +989,15 @@ try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult
     }
 });
 
+try_from!(item: &protowire::GetMempoolStatsRequestMessage, vecno_rpc_core::GetMempoolStatsRequest, { Self { limit: item.limit } });
+try_from!(item: &protowire::GetMempoolStatsResponseMessage, RpcResult<vecno_rpc_core::GetMempoolStatsResponse>, {
+    Self {
+        sample_interval: item.sample_interval,
+        feerate_buckets: item.feerate_buckets.clone(),
+        samples: item.samples.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?
+    }
+});
+
 try_from!(item: &protowire::GetCurrentBlockColorRequestMessage, vecno_rpc_core::GetCurrentBlockColorRequest, {
     Self {
         hash: RpcHash::from_str(&item.hash)?
@@ -917,6 +1009,76 @@ try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<vecno
     }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, vecno_rpc_core::GetTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, include_transaction: item.include_transaction }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<vecno_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionsByIdsRequestMessage, vecno_rpc_core::GetTransactionsByIdsRequest, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        include_transactions: item.include_transactions,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByIdsResponseMessage, RpcResult<vecno_rpc_core::GetTransactionsByIdsResponse>, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetAddressHistoryRequestMessage, vecno_rpc_core::GetAddressHistoryRequest, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        start_daa_score: (item.start_daa_score > 0).then_some(item.start_daa_score),
+        end_daa_score: (item.end_daa_score > 0).then_some(item.end_daa_score),
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetAddressHistoryResponseMessage, RpcResult<vecno_rpc_core::GetAddressHistoryResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: item.next_cursor.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::GetUtxosAtChainBlockRequestMessage, vecno_rpc_core::GetUtxosAtChainBlockRequest, {
+    Self {
+        chain_block_hash: RpcHash::from_str(&item.chain_block_hash)?,
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+try_from!(item: &protowire::GetUtxosAtChainBlockResponseMessage, RpcResult<vecno_rpc_core::GetUtxosAtChainBlockResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        utxo_commitment: RpcHash::from_str(&item.utxo_commitment)?,
+    }
+});
+
+try_from!(item: &protowire::GetArchivalChainBlocksRequestMessage, vecno_rpc_core::GetArchivalChainBlocksRequest, {
+    Self {
+        score_kind: protowire::RpcChainScoreKind::try_from(item.score_kind)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+        start_score: item.start_score,
+        end_score: (item.end_score > 0).then_some(item.end_score),
+        limit: item.limit,
+        include_acceptance_data: item.include_acceptance_data,
+    }
+});
+try_from!(item: &protowire::GetArchivalChainBlocksResponseMessage, RpcResult<vecno_rpc_core::GetArchivalChainBlocksResponse>, {
+    Self {
+        chain_blocks: item.chain_blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_start_score: (item.next_start_score > 0).then_some(item.next_start_score),
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, vecno_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<vecno_rpc_core::PingResponse>);
 
@@ -72,6 +72,7 @@ from!(item: &vecno_rpc_core::RpcTransactionOutputVerboseData, protowire::RpcTran
     Self {
         script_public_key_type: item.script_public_key_type.to_string(),
         script_public_key_address: (&item.script_public_key_address).into(),
+        script_public_key_asm: item.script_public_key_asm.clone(),
     }
 });
 
@@ -90,6 +91,28 @@ from!(item: &vecno_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddr
     }
 });
 
+from!(item: &vecno_rpc_core::RpcIndexedTransaction, protowire::RpcIndexedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        including_block_hash: item.including_block_hash.to_string(),
+        index_within_block: item.index_within_block,
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        transaction: item.transaction.as_ref().map(|x| x.into()),
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
+from!(item: &vecno_rpc_core::RpcAddressHistoryCursor, protowire::RpcAddressHistoryCursor, {
+    Self { accepting_block_daa_score: item.accepting_block_daa_score, transaction_id: item.transaction_id.to_string() }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -179,6 +202,7 @@ try_from!(item: &protowire::RpcTransactionOutputVerboseData, vecno_rpc_core::Rpc
     Self {
         script_public_key_type: item.script_public_key_type.as_str().try_into()?,
         script_public_key_address: item.script_public_key_address.as_str().try_into()?,
+        script_public_key_asm: item.script_public_key_asm.clone(),
     }
 });
 
@@ -205,3 +229,25 @@ try_from!(item: &protowire::RpcUtxosByAddressesEntry, vecno_rpc_core::RpcUtxosBy
             .try_into()?,
     }
 });
+
+try_from!(item: &protowire::RpcIndexedTransaction, vecno_rpc_core::RpcIndexedTransaction, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        including_block_hash: RpcHash::from_str(&item.including_block_hash)?,
+        index_within_block: item.index_within_block,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        transaction: item.transaction.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, vecno_rpc_core::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_block_daa_score: item.accepting_block_daa_score,
+    }
+});
+
+try_from!(item: &protowire::RpcAddressHistoryCursor, vecno_rpc_core::RpcAddressHistoryCursor, {
+    Self { accepting_block_daa_score: item.accepting_block_daa_score, transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
@@ -37,6 +37,7 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(AddPeer);
     impl_into_vecnod_request!(SubmitTransaction);
     impl_into_vecnod_request!(SubmitTransactionReplacement);
+    impl_into_vecnod_request!(SubmitTransactionPackage);
     impl_into_vecnod_request!(GetSubnetwork);
     impl_into_vecnod_request!(GetVirtualChainFromBlock);
     impl_into_vecnod_request!(GetBlocks);
@@ -62,7 +63,13 @@ pub mod vecnod_request_convert {
     impl_into_vecnod_request!(GetDaaScoreTimestampEstimate);
     impl_into_vecnod_request!(GetFeeEstimate);
     impl_into_vecnod_request!(GetFeeEstimateExperimental);
+    impl_into_vecnod_request!(GetMempoolStats);
     impl_into_vecnod_request!(GetCurrentBlockColor);
+    impl_into_vecnod_request!(GetTransaction);
+    impl_into_vecnod_request!(GetTransactionsByIds);
+    impl_into_vecnod_request!(GetAddressHistory);
+    impl_into_vecnod_request!(GetUtxosAtChainBlock);
+    impl_into_vecnod_request!(GetArchivalChainBlocks);
 
     impl_into_vecnod_request!(NotifyBlockAdded);
     impl_into_vecnod_request!(NotifyNewBlockTemplate);
@@ -174,6 +181,7 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_response!(AddPeer);
     impl_into_vecnod_response!(SubmitTransaction);
     impl_into_vecnod_response!(SubmitTransactionReplacement);
+    impl_into_vecnod_response!(SubmitTransactionPackage);
     impl_into_vecnod_response!(GetSubnetwork);
     impl_into_vecnod_response!(GetVirtualChainFromBlock);
     impl_into_vecnod_response!(GetBlocks);
@@ -199,7 +207,13 @@ pub mod vecnod_response_convert {
     impl_into_vecnod_response!(GetDaaScoreTimestampEstimate);
     impl_into_vecnod_response!(GetFeeEstimate);
     impl_into_vecnod_response!(GetFeeEstimateExperimental);
+    impl_into_vecnod_response!(GetMempoolStats);
     impl_into_vecnod_response!(GetCurrentBlockColor);
+    impl_into_vecnod_response!(GetTransaction);
+    impl_into_vecnod_response!(GetTransactionsByIds);
+    impl_into_vecnod_response!(GetAddressHistory);
+    impl_into_vecnod_response!(GetUtxosAtChainBlock);
+    impl_into_vecnod_response!(GetArchivalChainBlocks);
 
     impl_into_vecnod_notify_response!(NotifyBlockAdded);
     impl_into_vecnod_notify_response!(NotifyNewBlockTemplate);
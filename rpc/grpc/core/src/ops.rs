@@ -62,6 +62,7 @@ pub enum VecnodPayloadOps {
     AddPeer,
     SubmitTransaction,
     SubmitTransactionReplacement,
+    SubmitTransactionPackage,
     GetSubnetwork,
     GetVirtualChainFromBlock,
     GetBlockCount,
@@ -86,7 +87,13 @@ pub enum VecnodPayloadOps {
     GetDaaScoreTimestampEstimate,
     GetFeeEstimate,
     GetFeeEstimateExperimental,
+    GetMempoolStats,
     GetCurrentBlockColor,
+    GetTransaction,
+    GetTransactionsByIds,
+    GetAddressHistory,
+    GetUtxosAtChainBlock,
+    GetArchivalChainBlocks,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -56,6 +56,7 @@ impl Factory {
                 AddPeer,
                 SubmitTransaction,
                 SubmitTransactionReplacement,
+                SubmitTransactionPackage,
                 GetSubnetwork,
                 GetVirtualChainFromBlock,
                 GetBlockCount,
@@ -80,7 +81,13 @@ impl Factory {
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
+                GetMempoolStats,
                 GetCurrentBlockColor,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetAddressHistory,
+                GetUtxosAtChainBlock,
+                GetArchivalChainBlocks,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
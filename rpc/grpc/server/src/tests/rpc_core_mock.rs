@@ -190,6 +190,14 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn submit_transaction_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn add_peer_call(&self, _connection: Option<&DynRpcConnection>, _request: AddPeerRequest) -> RpcResult<AddPeerResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -238,6 +246,46 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_ids_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_utxos_at_chain_block_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetUtxosAtChainBlockRequest,
+    ) -> RpcResult<GetUtxosAtChainBlockResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_archival_chain_blocks_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetArchivalChainBlocksRequest,
+    ) -> RpcResult<GetArchivalChainBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_block_count_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -362,6 +410,14 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_mempool_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolStatsRequest,
+    ) -> RpcResult<GetMempoolStatsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
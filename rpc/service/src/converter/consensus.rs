@@ -15,11 +15,12 @@ use vecno_math::Uint256;
 use vecno_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use vecno_notify::converter::Converter;
 use vecno_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcIndexedTransaction,
+    RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput,
+    RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
 };
-use vecno_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
+use vecno_txindex::model::TxIndexEntry;
+use vecno_txscript::{extract_script_pub_key_address, script_asm::disassemble, script_class::ScriptClass};
 
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
@@ -146,6 +147,35 @@ impl ConsensusConverter {
         }
     }
 
+    /// Converts a [`TxIndexEntry`] into an [`RpcIndexedTransaction`], optionally including the transaction with its verbose data.
+    ///
+    /// The transaction is omitted if the body of its including block is no longer available.
+    pub async fn get_indexed_transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction_id: TransactionId,
+        entry: TxIndexEntry,
+        include_transaction: bool,
+    ) -> RpcIndexedTransaction {
+        let transaction = if include_transaction {
+            consensus.async_get_block(entry.including_block_hash).await.ok().and_then(|block| {
+                block
+                    .transactions
+                    .get(entry.index_within_block as usize)
+                    .map(|transaction| self.get_transaction(consensus, transaction, Some(&block.header), true))
+            })
+        } else {
+            None
+        };
+        RpcIndexedTransaction::new(
+            transaction_id,
+            entry.including_block_hash,
+            entry.index_within_block,
+            entry.accepting_block_hash,
+            transaction,
+        )
+    }
+
     fn get_transaction_input(&self, input: &TransactionInput) -> RpcTransactionInput {
         input.into()
     }
@@ -153,8 +183,11 @@ impl ConsensusConverter {
     fn get_transaction_output(&self, output: &TransactionOutput) -> RpcTransactionOutput {
         let script_public_key_type = ScriptClass::from_script(&output.script_public_key);
         let address = extract_script_pub_key_address(&output.script_public_key, self.config.prefix()).ok();
-        let verbose_data =
-            address.map(|address| RpcTransactionOutputVerboseData { script_public_key_type, script_public_key_address: address });
+        let verbose_data = address.map(|address| RpcTransactionOutputVerboseData {
+            script_public_key_type,
+            script_public_key_address: address,
+            script_public_key_asm: disassemble(output.script_public_key.script()),
+        });
         RpcTransactionOutput { value: output.value, script_public_key: output.script_public_key.clone(), verbose_data }
     }
 
@@ -0,0 +1,35 @@
+use vecno_mining::stats::{MempoolStatsSample, MEMPOOL_STATS_FEERATE_BUCKETS, MEMPOOL_STATS_SAMPLE_INTERVAL};
+use vecno_rpc_core::{GetMempoolStatsResponse, RpcMempoolStatsSample};
+
+pub trait MempoolStatsSampleConverter {
+    fn into_rpc(self) -> RpcMempoolStatsSample;
+}
+
+impl MempoolStatsSampleConverter for MempoolStatsSample {
+    fn into_rpc(self) -> RpcMempoolStatsSample {
+        RpcMempoolStatsSample {
+            timestamp: self.timestamp,
+            transaction_count: self.transaction_count,
+            total_mass: self.total_mass,
+            orphan_count: self.orphan_count,
+            evicted_count: self.evicted_count,
+            replaced_count: self.replaced_count,
+            policy_rejected_count: self.policy_rejected_count,
+            feerate_histogram: self.feerate_histogram,
+        }
+    }
+}
+
+pub trait MempoolStatsConverter {
+    fn into_rpc(self) -> GetMempoolStatsResponse;
+}
+
+impl MempoolStatsConverter for Vec<MempoolStatsSample> {
+    fn into_rpc(self) -> GetMempoolStatsResponse {
+        GetMempoolStatsResponse {
+            sample_interval: MEMPOOL_STATS_SAMPLE_INTERVAL.as_secs(),
+            feerate_buckets: MEMPOOL_STATS_FEERATE_BUCKETS.to_vec(),
+            samples: self.into_iter().map(MempoolStatsSampleConverter::into_rpc).collect(),
+        }
+    }
+}
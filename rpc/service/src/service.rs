@@ -2,6 +2,7 @@
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::mempool_stats::MempoolStatsConverter;
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
@@ -66,12 +67,13 @@ use vecno_rpc_core::{
     api::{
         connection::DynRpcConnection,
         ops::{RPC_API_REVISION, RPC_API_VERSION},
-        rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
+        rpc::{RpcApi, MAX_ADDRESS_HISTORY_PAGE_SIZE, MAX_ARCHIVAL_CHAIN_PAGE_SIZE, MAX_SAFE_WINDOW_SIZE},
     },
     model::*,
     notify::connection::ChannelConnection,
-    Notification, RpcError, RpcResult,
+    utxo_entries_into_rpc, Notification, RpcError, RpcResult,
 };
+use vecno_txindex::{api::TxIndexProxy, model::AddressHistoryCursor};
 use vecno_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use vecno_utils::expiring_cache::ExpiringCache;
 use vecno_utils::sysinfo::SystemInfo;
@@ -103,6 +105,7 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -135,6 +138,7 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -206,6 +210,7 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
             config,
             consensus_converter,
             index_converter,
@@ -400,6 +405,179 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let Some(entry) = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?
+        else {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        };
+        let session = self.consensus_manager.consensus().session().await;
+        Ok(GetTransactionResponse::new(
+            self.consensus_converter
+                .get_indexed_transaction(&session, request.transaction_id, entry, request.include_transaction)
+                .await,
+        ))
+    }
+
+    async fn get_transactions_by_ids_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionsByIdsRequest,
+    ) -> RpcResult<GetTransactionsByIdsResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let entries = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entries(request.transaction_ids.clone())
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        let session = self.consensus_manager.consensus().session().await;
+        let mut transactions = Vec::with_capacity(entries.len());
+        for (transaction_id, entry) in request.transaction_ids.into_iter().zip(entries) {
+            if let Some(entry) = entry {
+                transactions.push(
+                    self.consensus_converter
+                        .get_indexed_transaction(&session, transaction_id, entry, request.include_transactions)
+                        .await,
+                );
+            }
+        }
+        Ok(GetTransactionsByIdsResponse::new(transactions))
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        // A cursor resumes a previous page, but never before the start of the requested range
+        let start = AddressHistoryCursor::new(request.start_daa_score.unwrap_or_default(), Default::default());
+        let from = match request.cursor {
+            Some(cursor) => AddressHistoryCursor::new(cursor.accepting_block_daa_score, cursor.transaction_id).max(start),
+            None => start,
+        };
+        let limit = match request.limit {
+            0 => MAX_ADDRESS_HISTORY_PAGE_SIZE,
+            limit => limit.min(MAX_ADDRESS_HISTORY_PAGE_SIZE),
+        };
+        let page = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_address_history(pay_to_address_script(&request.address), from, request.end_daa_score, limit as usize)
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        Ok(GetAddressHistoryResponse::new(
+            page.entries
+                .into_iter()
+                .map(|x| RpcAddressHistoryEntry::new(x.transaction_id, x.accepting_block_hash, x.accepting_block_daa_score))
+                .collect(),
+            page.next_cursor.map(|x| RpcAddressHistoryCursor::new(x.accepting_block_daa_score, x.transaction_id)),
+        ))
+    }
+
+    async fn get_utxos_at_chain_block_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetUtxosAtChainBlockRequest,
+    ) -> RpcResult<GetUtxosAtChainBlockResponse> {
+        // Any such call scans the whole UTXO set, which is too expensive to be served to untrusted clients
+        if !self.config.unsafe_rpc {
+            warn!("GetUtxosAtChainBlock RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let script_public_keys =
+            (!request.addresses.is_empty()).then(|| request.addresses.iter().map(pay_to_address_script).collect::<Vec<_>>());
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let entries = session.async_get_chain_block_utxos(request.chain_block_hash, script_public_keys).await?;
+        let utxo_commitment = session.async_get_header(request.chain_block_hash).await?.utxo_commitment;
+        Ok(GetUtxosAtChainBlockResponse::new(utxo_entries_into_rpc(entries, Some(self.config.prefix())), utxo_commitment))
+    }
+
+    async fn get_archival_chain_blocks_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetArchivalChainBlocksRequest,
+    ) -> RpcResult<GetArchivalChainBlocksResponse> {
+        // Non-archival nodes prune the chain history below the pruning point, so paging through it would silently return partial data
+        if !self.config.is_archival {
+            return Err(RpcError::NoArchival);
+        }
+        let limit = match request.limit {
+            0 => MAX_ARCHIVAL_CHAIN_PAGE_SIZE,
+            limit => limit.min(MAX_ARCHIVAL_CHAIN_PAGE_SIZE),
+        } as usize;
+        let session = self.consensus_manager.consensus().session().await;
+        // One extra chain block is requested for telling whether a next page exists
+        let mut hashes = session
+            .async_get_chain_blocks_by_score(request.score_kind.into(), request.start_score, request.end_score, limit + 1)
+            .await?;
+        let next = (hashes.len() > limit).then(|| hashes.pop().unwrap());
+
+        let acceptance_data = if request.include_acceptance_data {
+            session.async_get_blocks_acceptance_data(hashes.clone(), None).await?
+        } else {
+            vec![]
+        };
+        let mut chain_blocks = Vec::with_capacity(hashes.len());
+        for (i, hash) in hashes.into_iter().enumerate() {
+            let header = session.async_get_header(hash).await?;
+            let ghostdag_data = session.async_get_ghostdag_data(hash).await?;
+            chain_blocks.push(RpcArchivalChainBlock {
+                hash,
+                daa_score: header.daa_score,
+                blue_score: header.blue_score,
+                timestamp: header.timestamp,
+                mergeset_blues_hashes: ghostdag_data.mergeset_blues,
+                mergeset_reds_hashes: ghostdag_data.mergeset_reds,
+                acceptance_data: acceptance_data
+                    .get(i)
+                    .map(|block_acceptance| {
+                        block_acceptance
+                            .iter()
+                            .map(|merged| {
+                                RpcMergedBlockAcceptance::new(
+                                    merged.block_hash,
+                                    merged.accepted_transactions.iter().map(|tx| tx.transaction_id).collect(),
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+        }
+
+        let next_start_score = match next {
+            Some(hash) => {
+                let header = session.async_get_header(hash).await?;
+                Some(match request.score_kind {
+                    RpcChainScoreKind::DaaScore => header.daa_score,
+                    RpcChainScoreKind::BlueScore => header.blue_score,
+                })
+            }
+            None => None,
+        };
+        Ok(GetArchivalChainBlocksResponse::new(chain_blocks, next_start_score))
+    }
+
     async fn get_block_call(&self, _connection: Option<&DynRpcConnection>, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         // TODO: test
         let session = self.consensus_manager.consensus().session().await;
@@ -575,6 +753,23 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(SubmitTransactionReplacementResponse::new(transaction_id, (&*replaced_transaction).into()))
     }
 
+    async fn submit_transaction_package_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse> {
+        let transactions: Vec<Transaction> =
+            request.transactions.into_iter().map(|transaction| transaction.try_into()).collect::<Result<_, _>>()?;
+        let transaction_ids = transactions.iter().map(|transaction| transaction.id()).collect();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        self.flow_context.submit_rpc_transaction_package(&session, transactions).await.map_err(|err| {
+            let err = RpcError::RejectedTransactionPackage(err.to_string());
+            debug!("{err}");
+            err
+        })?;
+        Ok(SubmitTransactionPackageResponse::new(transaction_ids))
+    }
+
     async fn get_current_network_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -795,6 +990,15 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_mempool_stats_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetMempoolStatsRequest,
+    ) -> RpcResult<GetMempoolStatsResponse> {
+        let limit = (request.limit > 0).then_some(request.limit as usize);
+        Ok(self.mining_manager.get_mempool_stats(limit).into_rpc())
+    }
+
     async fn ping_call(&self, _connection: Option<&DynRpcConnection>, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
@@ -631,6 +631,7 @@ impl RpcApi for VecnoRpcClient {
             GetDaaScoreTimestampEstimate,
             GetFeeEstimate,
             GetFeeEstimateExperimental,
+            GetMempoolStats,
             GetHeaders,
             GetInfo,
             GetMempoolEntries,
@@ -644,6 +645,11 @@ impl RpcApi for VecnoRpcClient {
             GetSubnetwork,
             GetSyncStatus,
             GetSystemInfo,
+            GetTransaction,
+            GetTransactionsByIds,
+            GetAddressHistory,
+            GetUtxosAtChainBlock,
+            GetArchivalChainBlocks,
             GetUtxosByAddresses,
             GetVirtualChainFromBlock,
             ResolveFinalityConflict,
@@ -651,6 +657,7 @@ impl RpcApi for VecnoRpcClient {
             SubmitBlock,
             SubmitTransaction,
             SubmitTransactionReplacement,
+            SubmitTransactionPackage,
             Unban,
         ]
     );
@@ -51,6 +51,7 @@ impl Router {
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
+                GetMempoolStats,
                 GetHeaders,
                 GetInfo,
                 GetInfo,
@@ -66,6 +67,11 @@ impl Router {
                 GetSubnetwork,
                 GetSyncStatus,
                 GetSystemInfo,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetAddressHistory,
+                GetUtxosAtChainBlock,
+                GetArchivalChainBlocks,
                 GetUtxosByAddresses,
                 GetVirtualChainFromBlock,
                 ResolveFinalityConflict,
@@ -73,6 +79,7 @@ impl Router {
                 SubmitBlock,
                 SubmitTransaction,
                 SubmitTransactionReplacement,
+                SubmitTransactionPackage,
                 Unban,
             ]
         );
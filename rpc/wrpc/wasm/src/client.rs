@@ -1029,9 +1029,33 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves a specific mempool entry by transaction ID.
         /// Returned information: Mempool entry information.
         GetMempoolEntry,
+        /// Retrieves the recent history of the mempool statistics, sampled by the node
+        /// at a fixed interval.
+        /// Returned information: Mempool size, mass, orphan, eviction and replacement samples with feerate histograms.
+        GetMempoolStats,
         /// Retrieves information about a subnetwork in the Vecno BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves an accepted transaction from the transaction index.
+        /// Requires the node to run with `--txindex`.
+        /// Returned information: Indexed transaction information.
+        GetTransaction,
+        /// Retrieves multiple accepted transactions from the transaction index.
+        /// Requires the node to run with `--txindex`.
+        /// Returned information: List of indexed transactions.
+        GetTransactionsByIds,
+        /// Retrieves a page of the accepted transactions which spent from or paid to an address.
+        /// Requires the node to run with `--txindex`.
+        /// Returned information: History entries and the cursor of the next page, if any.
+        GetAddressHistory,
+        /// Retrieves the unspent transaction outputs (UTXOs) of specific addresses as of a past selected chain block.
+        /// Returned information: List of UTXOs and the verified UTXO commitment of the block.
+        GetUtxosAtChainBlock,
+        /// Retrieves a page of the historical selected chain, located by DAA score or blue score, with the merge set
+        /// and optionally the acceptance data of every chain block.
+        /// Requires the node to run with `--archival`.
+        /// Returned information: List of chain blocks and the start score of the next page, if any.
+        GetArchivalChainBlocks,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1051,6 +1075,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits an RBF transaction to the Vecno network.
         /// Returned information: Submitted Transaction Id, Transaction that was replaced.
         SubmitTransactionReplacement,
+        /// Submits a package of dependent transactions to the Vecno network,
+        /// accepting all or none of them.
+        /// Returned information: Submitted Transaction Ids.
+        SubmitTransactionPackage,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Vecno node again.
         /// Returned information: None.
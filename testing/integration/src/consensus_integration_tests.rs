@@ -838,6 +838,9 @@ impl VecnodGoParams {
             storage_mass_parameter: STORAGE_MASS_PARAMETER,
             storage_mass_activation: ForkActivation::never(),
             kip10_activation: ForkActivation::never(),
+            splice_and_arithmetic_activation: ForkActivation::never(),
+            full_introspection_activation: ForkActivation::never(),
+            mast_activation: ForkActivation::never(),
             premine_daa_score: self.DeflationaryPhaseDaaScore,
             premine_phase_base_subsidy: self.PreDeflationaryPhaseBaseSubsidy,
             coinbase_maturity: MAINNET_PARAMS.coinbase_maturity,
@@ -962,6 +965,7 @@ async fn json_test(file_path: &str, concurrency: bool) {
         &notify_service.notifier(),
         subscription_context.clone(),
         Some(UtxoIndexProxy::new(utxoindex.clone())),
+        None,
     ));
 
     let async_runtime = Arc::new(AsyncRuntime::new(2));
@@ -1764,6 +1768,81 @@ async fn staging_consensus_test() {
     core.join(joins);
 }
 
+/// Tests that a pruning point snapshot exported from a consensus imports into a fresh database with the same
+/// pruning point, past pruning points, headers selected tip and pruning point UTXO set
+#[tokio::test]
+async fn snapshot_export_import_test() {
+    use vecno_consensus::params::TESTNET_PARAMS;
+    use vecnod_lib::snapshot::{export::export_snapshot, import::import_snapshot};
+
+    init_allocator_with_default_settings();
+    let config = Arc::new(ConfigBuilder::new(TESTNET_PARAMS).skip_proof_of_work().build());
+
+    // Build a chain long enough for the pruning point to move past genesis
+    let tc = Arc::new(TestConsensus::new(&config));
+    let wait_handles = tc.init();
+    let mut tip = config.genesis.hash;
+    for i in 1..=config.pruning_depth + 2 * config.finality_depth {
+        let hash: Hash = i.into();
+        tc.add_utxo_valid_block_with_parents(hash, vec![tip], vec![]).await.unwrap();
+        tip = hash;
+    }
+    let pruning_point = tc.pruning_point();
+    assert_ne!(pruning_point, config.genesis.hash, "the pruning point is expected to move past genesis");
+
+    let snapshot_tempdir = get_vecno_tempdir();
+    let snapshot_path = snapshot_tempdir.path().join("snapshot.bin");
+    let source_manager = ConsensusManager::new(Arc::new(TestConsensusFactory::new(tc.clone())));
+    export_snapshot(&config, &source_manager, &snapshot_path).await.unwrap();
+
+    // Import the snapshot into a fresh database, which requires a factory supporting staging consensus
+    let db_tempdir = get_vecno_tempdir();
+    let meta_db = vecno_database::prelude::ConnBuilder::default()
+        .with_db_path(db_tempdir.path().join("meta"))
+        .with_files_limit(5)
+        .build()
+        .unwrap();
+    let (notification_send, _notification_recv) = unbounded();
+    let consensus_factory = Arc::new(ConsensusFactory::new(
+        meta_db,
+        &config,
+        db_tempdir.path().join("consensus"),
+        4,
+        Arc::new(ConsensusNotificationRoot::new(notification_send)),
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        200,
+    ));
+    let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+    let core = Arc::new(Core::new());
+    core.bind(consensus_manager.clone());
+    let joins = core.start();
+
+    import_snapshot(&config, &consensus_manager, &snapshot_path).await.unwrap();
+
+    let imported = consensus_manager.consensus().session().await;
+    assert_eq!(imported.async_pruning_point().await, pruning_point);
+    assert_eq!(imported.async_get_headers_selected_tip().await, tc.get_headers_selected_tip());
+    assert_eq!(
+        imported.async_pruning_point_headers().await.iter().map(|header| header.hash).collect_vec(),
+        tc.pruning_point_headers().iter().map(|header| header.hash).collect_vec()
+    );
+    let expected_utxos = tc.get_pruning_point_utxos(pruning_point, None, usize::MAX, false).unwrap();
+    assert!(!expected_utxos.is_empty());
+    assert_eq!(imported.async_get_pruning_point_utxos(pruning_point, None, usize::MAX, false).await.unwrap(), expected_utxos);
+    drop(imported);
+
+    // A snapshot is only imported into an empty database
+    assert!(matches!(
+        import_snapshot(&config, &consensus_manager, &snapshot_path).await,
+        Err(vecnod_lib::snapshot::SnapshotError::DatabaseNotEmpty)
+    ));
+
+    core.shutdown();
+    core.join(joins);
+    tc.shutdown(wait_handles);
+}
+
 /// Tests the KIP-10 transaction introspection opcode activation by verifying that:
 /// 1. Transactions using these opcodes are rejected before the activation DAA score
 /// 2. The same transactions are accepted at and after the activation score
@@ -1869,6 +1948,101 @@ async fn run_kip10_activation_test() {
     assert!(consensus.lkg_virtual_state.load().accepted_tx_ids.contains(&tx_id));
 }
 
+/// Tests the activation of the full transaction introspection opcodes by verifying that a transaction
+/// spending a script which introspects its own version is rejected before the activation DAA score
+/// and accepted at and after it
+#[tokio::test]
+async fn run_full_introspection_activation_test() {
+    use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use vecno_txscript::opcodes::codes::{Op0, OpEqual, OpTxVersion};
+    use vecno_txscript::pay_to_script_hash_script;
+    use vecno_txscript::script_builder::ScriptBuilder;
+
+    // Full introspection activates at DAA score 3 in this test
+    const FULL_INTROSPECTION_ACTIVATION_DAA_SCORE: u64 = 3;
+
+    init_allocator_with_default_settings();
+
+    // The redeem script checks that the spending transaction has version 0
+    let redeem_script = ScriptBuilder::new().add_op(OpTxVersion).unwrap().add_op(Op0).unwrap().add_op(OpEqual).unwrap().drain();
+    let spk = pay_to_script_hash_script(&redeem_script);
+
+    let initial_utxo_collection = [(
+        TransactionOutpoint::new(1.into(), 0),
+        UtxoEntry { amount: SOMPI_PER_VECNO, script_public_key: spk.clone(), block_daa_score: 0, is_coinbase: false },
+    )];
+
+    let config = ConfigBuilder::new(DEVNET_PARAMS)
+        .skip_proof_of_work()
+        .apply_args(|cfg| {
+            let mut genesis_multiset = MuHash::new();
+            initial_utxo_collection.iter().for_each(|(outpoint, utxo)| {
+                genesis_multiset.add_utxo(outpoint, utxo);
+            });
+            cfg.params.genesis.utxo_commitment = genesis_multiset.finalize();
+            let genesis_header: Header = (&cfg.params.genesis).into();
+            cfg.params.genesis.hash = genesis_header.hash;
+        })
+        .edit_consensus_params(|p| {
+            p.full_introspection_activation = ForkActivation::new(FULL_INTROSPECTION_ACTIVATION_DAA_SCORE);
+        })
+        .build();
+
+    let consensus = TestConsensus::new(&config);
+    let mut genesis_multiset = MuHash::new();
+    consensus.append_imported_pruning_point_utxos(&initial_utxo_collection, &mut genesis_multiset);
+    consensus.import_pruning_point_utxo_set(config.genesis.hash, genesis_multiset).unwrap();
+    consensus.init();
+
+    // Build blockchain up to one block before activation
+    let mut index = 0;
+    for _ in 0..FULL_INTROSPECTION_ACTIVATION_DAA_SCORE - 1 {
+        let parent = if index == 0 { config.genesis.hash } else { index.into() };
+        consensus.add_utxo_valid_block_with_parents((index + 1).into(), vec![parent], vec![]).await.unwrap();
+        index += 1;
+    }
+    assert_eq!(consensus.get_virtual_daa_score(), index);
+
+    let mut spending_tx = Transaction::new(
+        0,
+        vec![TransactionInput::new(
+            initial_utxo_collection[0].0,
+            ScriptBuilder::new().add_data(&redeem_script).unwrap().drain(),
+            0,
+            0,
+        )],
+        vec![TransactionOutput::new(initial_utxo_collection[0].1.amount - 5000, spk)],
+        0,
+        SUBNETWORK_ID_NATIVE,
+        0,
+        vec![],
+    );
+    spending_tx.finalize();
+    let tx_id = spending_tx.id();
+
+    // Before activation, a block including the transaction is disqualified from the chain
+    {
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        let mut block =
+            consensus.build_utxo_valid_block_with_parents((index + 1).into(), vec![index.into()], miner_data.clone(), vec![]);
+        block.transactions.push(spending_tx.clone());
+        block.header.hash_merkle_root = calc_hash_merkle_root(block.transactions.iter(), false);
+        let block_status = consensus.validate_and_insert_block(block.to_immutable()).virtual_state_task.await;
+        assert!(matches!(block_status, Ok(BlockStatus::StatusDisqualifiedFromChain)));
+        assert_eq!(consensus.lkg_virtual_state.load().daa_score, 2);
+        index += 1;
+    }
+
+    // Add one more block to reach the activation score
+    consensus.add_utxo_valid_block_with_parents((index + 1).into(), vec![(index - 1).into()], vec![]).await.unwrap();
+    index += 1;
+
+    // After activation, the same transaction is accepted
+    let status = consensus.add_utxo_valid_block_with_parents((index + 1).into(), vec![index.into()], vec![spending_tx.clone()]).await;
+    assert!(matches!(status, Ok(BlockStatus::StatusUTXOValid)));
+    assert!(consensus.lkg_virtual_state.load().accepted_tx_ids.contains(&tx_id));
+}
+
 #[tokio::test]
 async fn payload_test() {
     let config = ConfigBuilder::new(DEVNET_PARAMS)
@@ -2005,3 +2179,187 @@ async fn payload_activation_test() {
     assert!(matches!(status, Ok(BlockStatus::StatusUTXOValid)));
     assert!(consensus.lkg_virtual_state.load().accepted_tx_ids.contains(&tx_id));
 }
+
+/// Tests that the UTXO set rebuilt at an earlier selected chain block is unaffected by the spends mined above it
+#[tokio::test]
+async fn get_chain_block_utxos_test() {
+    use vecno_consensus_core::errors::consensus::ConsensusError;
+    use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use vecno_txscript::pay_to_script_hash_script;
+    use vecno_txscript::script_builder::ScriptBuilder;
+
+    init_allocator_with_default_settings();
+
+    let redeem_script = vec![OpTrue];
+    let spk = pay_to_script_hash_script(&redeem_script);
+    let initial_utxo_collection = (0..3u64)
+        .map(|i| {
+            (
+                TransactionOutpoint::new(1.into(), i as u32),
+                UtxoEntry {
+                    amount: (i + 1) * SOMPI_PER_VECNO,
+                    script_public_key: spk.clone(),
+                    block_daa_score: 0,
+                    is_coinbase: false,
+                },
+            )
+        })
+        .collect_vec();
+
+    let config = ConfigBuilder::new(DEVNET_PARAMS)
+        .skip_proof_of_work()
+        .apply_args(|cfg| {
+            let mut genesis_multiset = MuHash::new();
+            initial_utxo_collection.iter().for_each(|(outpoint, utxo)| {
+                genesis_multiset.add_utxo(outpoint, utxo);
+            });
+            cfg.params.genesis.utxo_commitment = genesis_multiset.finalize();
+            let genesis_header: Header = (&cfg.params.genesis).into();
+            cfg.params.genesis.hash = genesis_header.hash;
+        })
+        .build();
+
+    let consensus = TestConsensus::new(&config);
+    let mut genesis_multiset = MuHash::new();
+    consensus.append_imported_pruning_point_utxos(&initial_utxo_collection, &mut genesis_multiset);
+    consensus.import_pruning_point_utxo_set(config.genesis.hash, genesis_multiset).unwrap();
+    let wait_handles = consensus.init();
+
+    consensus.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(2.into(), vec![1.into()], vec![]).await.unwrap();
+    let utxos_at = |hash: u64, spks: Option<Vec<ScriptPublicKey>>| {
+        consensus.get_chain_block_utxos(hash.into(), spks).unwrap().into_iter().collect::<HashMap<_, _>>()
+    };
+    let utxos_at_block_2 = utxos_at(2, None);
+    assert!(initial_utxo_collection.iter().all(|(outpoint, entry)| utxos_at_block_2.get(outpoint) == Some(entry)));
+
+    // Spend the first two initial UTXOs in blocks 3 and 4, each spend being accepted by the next chain block
+    let spend = |index: usize| {
+        let (outpoint, entry) = &initial_utxo_collection[index];
+        let mut tx = Transaction::new(
+            0,
+            vec![TransactionInput::new(*outpoint, ScriptBuilder::new().add_data(&redeem_script).unwrap().drain(), 0, 0)],
+            vec![TransactionOutput::new(entry.amount - 5000, ScriptPublicKey::from_vec(0, vec![OpTrue]))],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        tx.finalize();
+        tx
+    };
+    let (spend_0, spend_1) = (spend(0), spend(1));
+    consensus.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![spend_0.clone()]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(4.into(), vec![3.into()], vec![spend_1.clone()]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(5.into(), vec![4.into()], vec![]).await.unwrap();
+    assert_eq!(consensus.get_sink(), 5.into());
+
+    // The UTXO set at block 2 is rebuilt as it was before the spends
+    assert_eq!(utxos_at(2, None), utxos_at_block_2);
+    assert_eq!(utxos_at(2, Some(vec![spk.clone()])).len(), 3);
+
+    // Block 4 accepts the spend of block 3 only, while block 5 accepts both spends
+    let utxos_at_block_4 = utxos_at(4, Some(vec![spk.clone()]));
+    assert_eq!(
+        utxos_at_block_4.keys().copied().collect::<HashSet<_>>(),
+        HashSet::from([initial_utxo_collection[1].0, initial_utxo_collection[2].0])
+    );
+    assert!(utxos_at(4, None).contains_key(&TransactionOutpoint::new(spend_0.id(), 0)));
+    assert!(!utxos_at(4, None).contains_key(&TransactionOutpoint::new(spend_1.id(), 0)));
+    let utxos_at_block_5 = utxos_at(5, Some(vec![spk.clone()]));
+    assert_eq!(utxos_at_block_5.keys().copied().collect_vec(), vec![initial_utxo_collection[2].0]);
+    assert!(utxos_at(5, None).contains_key(&TransactionOutpoint::new(spend_1.id(), 0)));
+
+    // Blocks which are not on the selected chain are rejected
+    let unknown = Hash::from_u64_word(1000);
+    assert!(matches!(consensus.get_chain_block_utxos(unknown, None), Err(ConsensusError::NotAChainBlock(hash)) if hash == unknown));
+
+    consensus.shutdown(wait_handles);
+}
+
+/// Tests locating selected chain blocks by DAA and blue score, including scores falling between chain blocks,
+/// scores out of the chain range and paging through the chain
+#[tokio::test]
+async fn get_chain_blocks_by_score_test() {
+    use vecno_consensus_core::ChainScoreKind;
+
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // A side branch merged by block 4 makes the scores of the selected chain skip values
+    consensus.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(2.into(), vec![1.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(10.into(), vec![1.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(11.into(), vec![10.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(4.into(), vec![3.into(), 11.into()], vec![]).await.unwrap();
+    for i in 5..=7u64 {
+        consensus.add_utxo_valid_block_with_parents(i.into(), vec![(i - 1).into()], vec![]).await.unwrap();
+    }
+
+    let chain = {
+        let selected_chain = consensus.selected_chain_store.read();
+        let (tip_index, _) = selected_chain.get_tip().unwrap();
+        (0..=tip_index).map(|index| selected_chain.get_by_index(index).unwrap()).collect_vec()
+    };
+    assert_eq!(chain.first(), Some(&config.genesis.hash));
+    assert_eq!(chain.last(), Some(&7.into()));
+    let score = |kind: ChainScoreKind, hash: Hash| {
+        let header = consensus.get_header(hash).unwrap();
+        match kind {
+            ChainScoreKind::DaaScore => header.daa_score,
+            ChainScoreKind::BlueScore => header.blue_score,
+        }
+    };
+    let get = |kind, start, end, limit| consensus.get_chain_blocks_by_score(kind, start, end, limit).unwrap();
+
+    for kind in [ChainScoreKind::DaaScore, ChainScoreKind::BlueScore] {
+        let scores = chain.iter().map(|hash| score(kind, *hash)).collect_vec();
+        assert!(scores.windows(2).all(|w| w[0] < w[1]), "scores are expected to strictly increase along the chain");
+        let gap_index = scores.windows(2).position(|w| w[1] - w[0] > 1).expect("merging the side branch creates a score gap") + 1;
+        let max_score = *scores.last().unwrap();
+
+        // Exact scores start at the chain block itself, while in-between scores start at the next chain block
+        assert_eq!(get(kind, scores[2], None, 1), vec![chain[2]]);
+        assert_eq!(get(kind, scores[gap_index] - 1, None, 1), vec![chain[gap_index]]);
+        // The end score is exclusive
+        assert_eq!(get(kind, scores[1], Some(scores[3]), usize::MAX), chain[1..3].to_vec());
+        assert_eq!(get(kind, scores[1], Some(scores[3] + 1), usize::MAX), chain[1..4].to_vec());
+
+        // Scores out of the chain range
+        assert_eq!(get(kind, 0, None, usize::MAX), chain);
+        assert!(get(kind, max_score + 1, None, usize::MAX).is_empty());
+        assert!(get(kind, scores[3], Some(scores[3]), usize::MAX).is_empty());
+        assert!(get(kind, scores[3], Some(scores[1]), usize::MAX).is_empty());
+
+        // Every range matches filtering the chain by score
+        for start in 0..=max_score + 1 {
+            for end in (start..=max_score + 2).map(Some).chain(std::iter::once(None)) {
+                let expected = chain
+                    .iter()
+                    .zip(scores.iter())
+                    .filter(|(_, &s)| s >= start && end.is_none_or(|end| s < end))
+                    .map(|(hash, _)| *hash)
+                    .collect_vec();
+                assert_eq!(get(kind, start, end, usize::MAX), expected, "start {start}, end {end:?}");
+            }
+        }
+
+        // Paging through the chain, each page requesting one extra block for locating the start of the next page
+        let limit = 2;
+        let mut pages = vec![];
+        let mut start = Some(0);
+        while let Some(page_start) = start {
+            let mut page = get(kind, page_start, None, limit + 1);
+            start = (page.len() > limit).then(|| score(kind, page.pop().unwrap()));
+            assert!(!page.is_empty() && page.len() <= limit);
+            pages.push(page);
+        }
+        assert_eq!(pages.len(), chain.len().div_ceil(limit));
+        assert_eq!(pages.concat(), chain);
+    }
+
+    consensus.shutdown(wait_handles);
+}
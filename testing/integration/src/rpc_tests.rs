@@ -49,6 +49,7 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -267,6 +268,103 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::GetTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are not indexed
+                    let result = rpc_client
+                        .get_transaction_call(None, GetTransactionRequest { transaction_id: 0.into(), include_transaction: true })
+                        .await;
+                    assert!(result.is_err());
+                })
+            }
+
+            VecnodPayloadOps::GetTransactionsByIds => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are omitted from the response
+                    let response = rpc_client
+                        .get_transactions_by_ids_call(
+                            None,
+                            GetTransactionsByIdsRequest { transaction_ids: vec![0.into()], include_transactions: true },
+                        )
+                        .await
+                        .unwrap();
+                    assert!(response.transactions.is_empty());
+                })
+            }
+
+            VecnodPayloadOps::GetAddressHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client
+                        .get_address_history_call(
+                            None,
+                            GetAddressHistoryRequest {
+                                address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
+                                start_daa_score: None,
+                                end_daa_score: None,
+                                cursor: None,
+                                limit: 0,
+                            },
+                        )
+                        .await
+                        .unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+                })
+            }
+
+            VecnodPayloadOps::GetUtxosAtChainBlock => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let sink = rpc_client.get_sink_call(None, GetSinkRequest {}).await.unwrap().sink;
+                    let response = rpc_client
+                        .get_utxos_at_chain_block_call(
+                            None,
+                            GetUtxosAtChainBlockRequest {
+                                chain_block_hash: sink,
+                                addresses: vec![Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32])],
+                            },
+                        )
+                        .await
+                        .unwrap();
+                    assert!(response.entries.is_empty());
+                    let header =
+                        rpc_client.get_block_call(None, GetBlockRequest { hash: sink, include_transactions: false }).await.unwrap();
+                    assert_eq!(response.utxo_commitment, header.block.header.utxo_commitment);
+
+                    // Blocks which are not on the selected chain are rejected
+                    let result = rpc_client
+                        .get_utxos_at_chain_block_call(
+                            None,
+                            GetUtxosAtChainBlockRequest { chain_block_hash: 0.into(), addresses: vec![] },
+                        )
+                        .await;
+                    assert!(result.is_err());
+                })
+            }
+
+            VecnodPayloadOps::GetArchivalChainBlocks => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The test node is not archival
+                    let result = rpc_client
+                        .get_archival_chain_blocks_call(
+                            None,
+                            GetArchivalChainBlocksRequest {
+                                score_kind: RpcChainScoreKind::DaaScore,
+                                start_score: 0,
+                                end_score: None,
+                                limit: 0,
+                                include_acceptance_data: true,
+                            },
+                        )
+                        .await;
+                    assert!(result.is_err());
+                })
+            }
+
             VecnodPayloadOps::GetMempoolEntries => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -347,6 +445,20 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::SubmitTransactionPackage => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An empty package...
+                    let result = rpc_client.submit_transaction_package(vec![]).await;
+                    assert!(result.is_err());
+
+                    // ...and a package of an erroneous transaction both get rejected
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let result = rpc_client.submit_transaction_package(vec![(&transaction).into()]).await;
+                    assert!(result.is_err());
+                })
+            }
+
             VecnodPayloadOps::GetSubnetwork => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -656,6 +768,20 @@ async fn sanity_test() {
                 })
             }
 
+            VecnodPayloadOps::GetMempoolStats => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mempool_stats(0).await.unwrap();
+                    assert!(response.sample_interval > 0);
+                    assert!(!response.feerate_buckets.is_empty());
+                    for sample in response.samples.iter() {
+                        assert_eq!(sample.feerate_histogram.len(), response.feerate_buckets.len());
+                    }
+                    let latest = rpc_client.get_mempool_stats(1).await.unwrap();
+                    assert!(latest.samples.len() <= 1);
+                })
+            }
+
             VecnodPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
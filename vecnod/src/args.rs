@@ -1,7 +1,7 @@
 use clap::{arg, Arg, ArgAction, Command};
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{ffi::OsString, fs, path::PathBuf};
 use toml::from_str;
 use vecno_consensus_core::{
     config::Config,
@@ -12,6 +12,8 @@ use vecno_notify::address::tracker::Tracker;
 use vecno_utils::networking::ContextualNetAddress;
 use vecno_wrpc_server::address::WrpcNetAddress;
 
+use crate::snapshot::SnapshotCommand;
+
 #[cfg(feature = "devnet-prealloc")]
 use std::sync::Arc;
 #[cfg(feature = "devnet-prealloc")]
@@ -53,6 +55,9 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub persist_mempool: bool,
+    pub mempool_policy: Option<String>,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -90,6 +95,21 @@ pub struct Args {
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratumlisten: Option<ContextualNetAddress>,
+    pub stratum_diff: f64,
+    pub stratum_shares_per_min: f64,
+    pub stratum_novardiff: bool,
+
+    /// A snapshot command to run instead of starting the node, only accepted from the command line
+    #[serde(skip)]
+    pub snapshot: Option<SnapshotCommand>,
+    /// Check the database integrity instead of starting the node, only accepted from the command line
+    #[serde(skip)]
+    pub check_db: bool,
+    /// Repair the inconsistencies found by the database check, only accepted from the command line
+    #[serde(skip)]
+    pub repair_db: bool,
 }
 
 impl Default for Args {
@@ -102,6 +122,9 @@ impl Default for Args {
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            persist_mempool: false,
+            mempool_policy: None,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -140,6 +163,13 @@ impl Default for Args {
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            stratumlisten: None,
+            stratum_diff: 1.0,
+            stratum_shares_per_min: 10.0,
+            stratum_novardiff: false,
+            snapshot: None,
+            check_db: false,
+            repair_db: false,
         }
     }
 }
@@ -147,6 +177,7 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
         config.disable_upnp = self.disable_upnp;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
@@ -311,6 +342,9 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction and address history index"))
+        .arg(arg!(--"persist-mempool" "Save the mempool on shutdown and restore it on startup, revalidating its transactions"))
+        .arg(arg!(--"mempool-policy" <POLICY_FILE> "Path of a TOML file defining additional mempool policy rules."))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -369,6 +403,48 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("stratumlisten")
+                .long("stratumlisten")
+                .value_name("IP[:PORT]")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("0.0.0.0")
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Enable the built-in stratum mining server on interface:port (default: all interfaces, port: 5110, testnet: 5210)."),
+        )
+        .arg(
+            Arg::new("stratum-diff")
+                .long("stratum-diff")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help(format!("Initial share difficulty of stratum connections, where difficulty 1 is 2^32 hashes per share (default: {}).", defaults.stratum_diff)),
+        )
+        .arg(
+            Arg::new("stratum-shares-per-min")
+                .long("stratum-shares-per-min")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help(format!("Number of shares per minute targeted by the variable difficulty of stratum connections (default: {}).", defaults.stratum_shares_per_min)),
+        )
+        .arg(arg!(--"stratum-novardiff" "Disable variable difficulty, keeping the share difficulty of stratum connections at --stratum-diff"))
+        .arg(arg!(--"check-db" "Check the integrity of the database and exit without starting the node"))
+        .arg(arg!(--"repair-db" "Check the integrity of the database, repair the inconsistencies found where possible and exit without starting the node"))
+        .subcommand(
+            Command::new("snapshot")
+                .about("Export or import a pruning point snapshot of the node database (the node must not be running)")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("export")
+                        .about("Export the pruning point, its proof, the headers above it and its UTXO set into a snapshot file")
+                        .arg(Arg::new("file").required(true).value_parser(clap::value_parser!(PathBuf)).help("Path of the snapshot file to write.")),
+                )
+                .subcommand(
+                    Command::new("import")
+                        .about("Validate a snapshot file and import it into a fresh node database")
+                        .arg(Arg::new("file").required(true).value_parser(clap::value_parser!(PathBuf)).help("Path of the snapshot file to read.")),
+                ),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -431,6 +507,9 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            persist_mempool: arg_match_unwrap_or::<bool>(&m, "persist-mempool", defaults.persist_mempool),
+            mempool_policy: m.get_one::<String>("mempool-policy").cloned().or(defaults.mempool_policy),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -448,6 +527,17 @@ impl Args {
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            stratumlisten: m.get_one::<ContextualNetAddress>("stratumlisten").cloned().or(defaults.stratumlisten),
+            stratum_diff: arg_match_unwrap_or::<f64>(&m, "stratum-diff", defaults.stratum_diff),
+            stratum_shares_per_min: arg_match_unwrap_or::<f64>(&m, "stratum-shares-per-min", defaults.stratum_shares_per_min),
+            stratum_novardiff: arg_match_unwrap_or::<bool>(&m, "stratum-novardiff", defaults.stratum_novardiff),
+            snapshot: m.subcommand_matches("snapshot").and_then(|m| match m.subcommand() {
+                Some(("export", m)) => m.get_one::<PathBuf>("file").cloned().map(SnapshotCommand::Export),
+                Some(("import", m)) => m.get_one::<PathBuf>("file").cloned().map(SnapshotCommand::Import),
+                _ => None,
+            }),
+            check_db: arg_match_unwrap_or::<bool>(&m, "check-db", defaults.check_db),
+            repair_db: arg_match_unwrap_or::<bool>(&m, "repair-db", defaults.repair_db),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -549,6 +639,7 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction and address history index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 7)
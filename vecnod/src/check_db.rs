@@ -0,0 +1,99 @@
+//!
+//! Offline consensus database integrity checks.
+//!
+//! The check walks the consensus stores and verifies that they are consistent with each other,
+//! e.g., that every block with a body has GHOSTDAG and reachability data, that reachability intervals
+//! are properly nested and that the UTXO sets hash to their commitments. Optionally, the inconsistencies
+//! which do not require a full resync are repaired in place.
+//!
+
+use crate::{
+    args::Args,
+    daemon::{get_app_dir_from_args, get_user_approval_or_exit, validate_args, DEFAULT_DATA_DIR, META_DB},
+    offline::OfflineConsensus,
+};
+use std::sync::Arc;
+use vecno_consensus_core::{
+    config::ConfigBuilder,
+    integrity::{IntegrityRepair, IntegrityReport},
+};
+use vecno_core::{error, info, warn};
+
+/// Checks the node database and optionally repairs it. Returns whether the database is consistent
+/// by the end of the run. The node itself must not be running.
+pub fn run_check_db(args: &Args, fd_total_budget: i32) -> bool {
+    if let Err(err) = validate_args(args) {
+        println!("{}", err);
+        std::process::exit(1);
+    }
+
+    let network = args.network();
+    let config = Arc::new(
+        ConfigBuilder::new(network.into())
+            .adjust_perf_params_to_consensus_params()
+            .apply_args(|config| args.apply_to_config(config))
+            .build(),
+    );
+
+    let db_dir = get_app_dir_from_args(args).join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    if !db_dir.join(META_DB).exists() {
+        error!("No database was found at {}", db_dir.display());
+        return false;
+    }
+    let Some(offline) = OfflineConsensus::open(args, &config, &db_dir, fd_total_budget) else {
+        error!("The database requires an upgrade, start the node once before checking it");
+        return false;
+    };
+
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    let consistent = runtime.block_on(async {
+        let session = offline.consensus_manager.consensus().session().await;
+        info!("Checking the integrity of the consensus database at {}", db_dir.display());
+        let report = session.clone().spawn_blocking(|c| c.check_integrity()).await;
+        log_report(&report);
+        if report.is_consistent() || !args.repair_db {
+            return report.is_consistent();
+        }
+
+        if report.required_repair() == Some(IntegrityRepair::Reset) {
+            error!("Some inconsistencies cannot be repaired in place, restart the node with --reset-db to resync");
+            return false;
+        }
+        let msg = "Repairing the database may remove store entries and roll the virtual state back to the pruning point, \
+do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm all interactive questions)";
+        get_user_approval_or_exit(msg, args.yes);
+        match session.spawn_blocking(|c| c.repair_integrity()).await {
+            Ok(report) => {
+                info!("Repair completed, checking the database again");
+                log_report(&report);
+                report.is_consistent()
+            }
+            Err(err) => {
+                error!("Repair failed: {}", err);
+                false
+            }
+        }
+    });
+
+    offline.shutdown();
+    consistent
+}
+
+fn log_report(report: &IntegrityReport) {
+    info!(
+        "Checked {} blocks and {} UTXO entries at pruning point {}",
+        report.blocks_checked, report.utxos_checked, report.pruning_point
+    );
+    for issue in report.issues.iter() {
+        warn!("Inconsistency: {} (repair: {:?})", issue, issue.repair());
+    }
+    if report.omitted_issues > 0 {
+        warn!("{} more inconsistencies were omitted", report.omitted_issues);
+    }
+    match report.required_repair() {
+        None => info!("The database is consistent"),
+        Some(repair) => {
+            warn!("Found {} inconsistencies, the required repair is {:?}", report.issues.len() as u64 + report.omitted_issues, repair)
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
 use vecno_consensus_core::{
@@ -15,6 +21,13 @@ use vecno_database::{
 use vecno_grpc_server::service::GrpcService;
 use vecno_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
 use vecno_rpc_service::service::RpcCoreService;
+use vecno_stratum::{
+    counters::StratumCounters,
+    monitor::StratumMonitor,
+    server::{StratumOptions, StratumServer},
+    service::StratumService,
+    vardiff::VarDiffConfig,
+};
 use vecno_txscript::caches::TxScriptCacheCounters;
 use vecno_utils::git;
 use vecno_utils::networking::ContextualNetAddress;
@@ -31,13 +44,18 @@ use vecno_core::task::runtime::AsyncRuntime;
 use vecno_index_processor::service::IndexService;
 use vecno_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::policy::{MempoolPolicy, PolicyConfig},
+    model::mempool_dump::MEMPOOL_DUMP_FILE_NAME,
     monitor::MiningMonitor,
+    persistence::MempoolPersistence,
+    stats::MempoolStatsCollector,
     MiningCounters,
 };
 use vecno_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
 use itertools::Itertools;
 use vecno_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use vecno_txindex::{api::TxIndexProxy, TxIndex};
 use vecno_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use vecno_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -51,12 +69,14 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
 use crate::args::Args;
+use crate::stratum::NodeStratumBackend;
 
-const DEFAULT_DATA_DIR: &str = "datadir";
-const CONSENSUS_DB: &str = "consensus";
+pub(crate) const DEFAULT_DATA_DIR: &str = "datadir";
+pub(crate) const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
-const META_DB: &str = "meta";
-const META_DB_FILE_LIMIT: i32 = 5;
+const TXINDEX_DB: &str = "txindex";
+pub(crate) const META_DB: &str = "meta";
+pub(crate) const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
 
 fn get_home_dir() -> PathBuf {
@@ -101,10 +121,16 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.stratum_diff.is_nan() || args.stratum_diff <= 0.0 {
+        return Err(ConfigError::StratumDifficultyNotPositive);
+    }
+    if args.stratum_shares_per_min.is_nan() || args.stratum_shares_per_min <= 0.0 {
+        return Err(ConfigError::StratumSharesPerMinNotPositive);
+    }
     Ok(())
 }
 
-fn get_user_approval_or_exit(message: &str, approve: bool) {
+pub(crate) fn get_user_approval_or_exit(message: &str, approve: bool) {
     if approve {
         return;
     }
@@ -220,6 +246,13 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -255,6 +288,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -274,6 +308,10 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = vecno_database::prelude::ConnBuilder::default()
@@ -438,6 +476,9 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = vecno_database::prelude::ConnBuilder::default()
@@ -510,15 +551,25 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = vecno_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex));
         Some(index_service)
     } else {
         None
@@ -526,16 +577,37 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
-    let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
-        config.target_time_per_block,
-        false,
-        config.max_block_mass,
-        config.ram_scale,
-        config.block_template_cache_lifetime,
-        mining_counters.clone(),
-    )));
+    let mempool_policy = match args.mempool_policy.as_ref() {
+        Some(path) => match PolicyConfig::load(Path::new(path)).and_then(|policy_config| MempoolPolicy::from_config(&policy_config)) {
+            Ok(policy) => {
+                info!("Loaded {} mempool policy rules from {}", policy.len(), path);
+                policy
+            }
+            Err(err) => {
+                println!("Failed loading the mempool policy from {}: {}", path, err);
+                exit(1);
+            }
+        },
+        None => MempoolPolicy::default(),
+    };
+    let mining_manager = MiningManagerProxy::new(Arc::new(
+        MiningManager::new_with_extended_config(
+            config.target_time_per_block,
+            false,
+            config.max_block_mass,
+            config.ram_scale,
+            config.mast_activation,
+            config.block_template_cache_lifetime,
+            mining_counters.clone(),
+        )
+        .with_policy(mempool_policy),
+    ));
     let mining_monitor =
         Arc::new(MiningMonitor::new(mining_manager.clone(), mining_counters, tx_script_cache_counters.clone(), tick_service.clone()));
+    let mempool_stats_collector = Arc::new(MempoolStatsCollector::new(mining_manager.clone(), tick_service.clone()));
+    let mempool_persistence = args.persist_mempool.then(|| {
+        Arc::new(MempoolPersistence::new(mining_manager.clone(), consensus_manager.clone(), db_dir.join(MEMPOOL_DUMP_FILE_NAME)))
+    });
 
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
@@ -557,14 +629,30 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         p2p_tower_counters.clone(),
     ));
 
+    let stratum_services = args.stratumlisten.map(|stratumlisten| {
+        let backend = NodeStratumBackend::new(consensus_manager.clone(), mining_manager.clone(), flow_context.clone(), config.clone());
+        let vardiff = VarDiffConfig {
+            enabled: !args.stratum_novardiff,
+            initial_difficulty: args.stratum_diff,
+            ..VarDiffConfig::default().with_shares_per_minute(args.stratum_shares_per_min)
+        };
+        let options = StratumOptions { prefix: config.prefix(), vardiff, ..Default::default() };
+        let stratum_server = Arc::new(StratumServer::new(options, Arc::new(backend), Arc::new(StratumCounters::default())));
+        let stratum_monitor = Arc::new(StratumMonitor::new(stratum_server.clone(), tick_service.clone()));
+        let stratum_server_addr = stratumlisten.normalize(config.default_stratum_port());
+        let stratum_service = Arc::new(StratumService::new(stratum_server, stratum_server_addr.into(), &notify_service.notifier()));
+        (stratum_service, stratum_monitor)
+    });
+
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
         config.clone(),
         core.clone(),
         processing_counters,
@@ -604,8 +692,16 @@ do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm
         async_runtime.register(grpc_service)
     }
     async_runtime.register(p2p_service);
+    if let Some((stratum_service, stratum_monitor)) = stratum_services {
+        async_runtime.register(stratum_service);
+        async_runtime.register(stratum_monitor);
+    }
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    async_runtime.register(mempool_stats_collector);
+    if let Some(mempool_persistence) = mempool_persistence {
+        async_runtime.register(mempool_persistence);
+    }
     async_runtime.register(perf_monitor);
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
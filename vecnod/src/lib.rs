@@ -1,2 +1,6 @@
 pub mod args;
+pub mod check_db;
 pub mod daemon;
+pub mod offline;
+pub mod snapshot;
+pub mod stratum;
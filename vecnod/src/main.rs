@@ -5,11 +5,13 @@ extern crate vecno_hashes;
 use std::sync::Arc;
 
 use vecno_alloc::init_allocator_with_default_settings;
-use vecno_core::{info, signals::Signals};
+use vecno_core::{error, info, signals::Signals};
 use vecno_utils::fd_budget;
 use vecnod_lib::{
     args::parse_args,
-    daemon::{create_core, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    check_db::run_check_db,
+    daemon::{create_core, Runtime, DESIRED_DAEMON_SOFT_FD_LIMIT, MINIMUM_DAEMON_SOFT_FD_LIMIT},
+    snapshot::run_snapshot_command,
 };
 
 #[cfg(feature = "heap")]
@@ -40,6 +42,24 @@ pub fn main() {
     }
 
     let fd_total_budget = fd_budget::limit() - args.rpc_max_clients as i32 - args.inbound_limit as i32 - args.outbound_target as i32;
+
+    if let Some(command) = args.snapshot.clone() {
+        let _runtime = Runtime::from_args(&args);
+        if let Err(err) = run_snapshot_command(&args, &command, fd_total_budget) {
+            error!("Snapshot command failed: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if args.check_db || args.repair_db {
+        let _runtime = Runtime::from_args(&args);
+        if !run_check_db(&args, fd_total_budget) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let (core, _) = create_core(args, fd_total_budget);
 
     // Bind the keyboard signal to the core
@@ -0,0 +1,69 @@
+//!
+//! Opening the consensus database without starting the node, for maintenance commands.
+//!
+
+use crate::{
+    args::Args,
+    daemon::{get_user_approval_or_exit, CONSENSUS_DB, META_DB, META_DB_FILE_LIMIT},
+};
+use async_channel::unbounded;
+use std::{path::Path, sync::Arc, thread::JoinHandle};
+use vecno_consensus::{
+    consensus::factory::{Factory as ConsensusFactory, MultiConsensusManagementStore},
+    pipeline::ProcessingCounters,
+};
+use vecno_consensus_core::config::Config;
+use vecno_consensus_notify::root::ConsensusNotificationRoot;
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{core::Core, service::Service};
+use vecno_txscript::caches::TxScriptCacheCounters;
+
+/// A consensus manager running over the node database with no P2P, RPC or index services attached
+pub struct OfflineConsensus {
+    pub consensus_manager: Arc<ConsensusManager>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl OfflineConsensus {
+    /// Opens the databases found in `db_dir`, creating them if missing. Returns `None` if the
+    /// databases were written by an older version and require an upgrade by the node itself.
+    pub fn open(args: &Args, config: &Arc<Config>, db_dir: &Path, fd_total_budget: i32) -> Option<Self> {
+        let meta_db = vecno_database::prelude::ConnBuilder::default()
+            .with_db_path(db_dir.join(META_DB))
+            .with_files_limit(META_DB_FILE_LIMIT)
+            .build()
+            .unwrap();
+        let management_store = MultiConsensusManagementStore::new(meta_db.clone());
+        if management_store.should_upgrade().unwrap() {
+            return None;
+        }
+        if !args.archival && management_store.is_archival_node().unwrap() {
+            get_user_approval_or_exit("--archival is set to false although the node was previously archival. Proceeding may delete archived data. Do you confirm? (y/n)", args.yes);
+        }
+        drop(management_store);
+
+        // Consensus notifications have no subscribers in this mode, so nothing is ever sent through this channel
+        let (notification_send, _notification_recv) = unbounded();
+        let consensus_factory = Arc::new(ConsensusFactory::new(
+            meta_db,
+            config,
+            db_dir.join(CONSENSUS_DB),
+            num_cpus::get(),
+            Arc::new(ConsensusNotificationRoot::new(notification_send)),
+            Arc::new(ProcessingCounters::default()),
+            Arc::new(TxScriptCacheCounters::default()),
+            fd_total_budget - META_DB_FILE_LIMIT,
+        ));
+        let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+        let handles = consensus_manager.clone().start(Arc::new(Core::new()));
+        Some(Self { consensus_manager, handles })
+    }
+
+    /// Stops the consensus processors and waits for them to exit
+    pub fn shutdown(self) {
+        self.consensus_manager.clone().stop();
+        for handle in self.handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use thiserror::Error;
+use vecno_consensus_core::{
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    network::NetworkId,
+};
+use vecno_p2p_lib::common::ProtocolError;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    #[error("the file is not a vecnod snapshot")]
+    InvalidMagic,
+
+    #[error("snapshot format version {0} is not supported (expected version {1})")]
+    UnsupportedVersion(u32, u32),
+
+    #[error("the snapshot file is truncated")]
+    Truncated,
+
+    #[error("the snapshot checksum does not match its content")]
+    ChecksumMismatch,
+
+    #[error("the snapshot contains unexpected trailing data")]
+    TrailingData,
+
+    #[error("the snapshot was made for network {0} while the node is configured for network {1}")]
+    NetworkMismatch(NetworkId, NetworkId),
+
+    #[error("no node database was found at {0}")]
+    MissingDatabase(String),
+
+    #[error("the node database is not empty, pass --reset-db to import the snapshot into a fresh database")]
+    DatabaseNotEmpty,
+
+    #[error("the node database requires an upgrade, start the node once before handling snapshots or pass --reset-db")]
+    DatabaseUpgradeRequired,
+
+    #[error("the pruning point of the node is still genesis, there is nothing to snapshot")]
+    GenesisPruningPoint,
+
+    #[error(transparent)]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error(transparent)]
+    RuleError(#[from] RuleError),
+
+    #[error(transparent)]
+    PruningImportError(#[from] PruningImportError),
+
+    #[error(transparent)]
+    ProtocolError(#[from] ProtocolError),
+
+    #[error("invalid snapshot: {0}")]
+    Invalid(&'static str),
+
+    #[error("invalid snapshot: {0}")]
+    InvalidOwned(String),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
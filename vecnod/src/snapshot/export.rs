@@ -0,0 +1,102 @@
+use super::{
+    error::{SnapshotError, SnapshotResult},
+    format::{SnapshotBlock, SnapshotHeadersChunk, SnapshotMetadata, SnapshotTrustedData, SnapshotUtxoChunk, SnapshotWriter},
+    HEADERS_CHUNK_SIZE, UTXO_CHUNK_SIZE,
+};
+use std::{cmp::max, fs, path::Path};
+use vecno_consensus_core::config::Config;
+use vecno_consensusmanager::ConsensusManager;
+use vecno_core::{info, time::unix_now};
+
+/// Writes the pruning point, its proof, the trusted data, the headers above the pruning point
+/// and the pruning point UTXO set of the active consensus into a snapshot file
+pub async fn export_snapshot(config: &Config, consensus_manager: &ConsensusManager, path: &Path) -> SnapshotResult<()> {
+    let session = consensus_manager.consensus().session().await;
+
+    let pruning_point = session.async_pruning_point().await;
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::GenesisPruningPoint);
+    }
+    let pruning_point_header = session.async_get_header(pruning_point).await?;
+    let hst = session.async_get_headers_selected_tip().await;
+    let hst_header = session.async_get_header(hst).await?;
+    info!(
+        "Exporting a snapshot of pruning point {} (DAA score {}) to {}",
+        pruning_point,
+        pruning_point_header.daa_score,
+        path.display()
+    );
+
+    // The snapshot is written aside and moved into place once complete, so that a partial export never looks valid
+    let tmp_path = path.with_extension("tmp");
+    let mut writer = SnapshotWriter::create(&tmp_path)?;
+    writer.write(&SnapshotMetadata {
+        network_id: config.net,
+        pruning_point,
+        pruning_point_daa_score: pruning_point_header.daa_score,
+        headers_selected_tip: hst,
+        headers_selected_tip_blue_work: hst_header.blue_work,
+        timestamp: unix_now(),
+    })?;
+
+    let proof = session.async_get_pruning_point_proof().await;
+    writer.write(&proof.iter().map(|level| level.iter().map(|header| (**header).clone()).collect::<Vec<_>>()).collect::<Vec<_>>())?;
+    info!("Exported the pruning proof with overall {} headers", proof.iter().map(|level| level.len()).sum::<usize>());
+
+    let pruning_points = session.async_pruning_point_headers().await;
+    writer.write(&pruning_points.iter().map(|header| (**header).clone()).collect::<Vec<_>>())?;
+
+    let trusted_data = session.async_get_pruning_point_anticone_and_trusted_data().await?;
+    writer.write(&SnapshotTrustedData::new(&trusted_data.daa_window_blocks, &trusted_data.ghostdag_blocks))?;
+    let mut anticone = Vec::with_capacity(trusted_data.anticone.len());
+    for hash in trusted_data.anticone.iter().copied() {
+        anticone.push(SnapshotBlock::from(&session.async_get_block(hash).await?));
+    }
+    writer.write(&anticone)?;
+    info!(
+        "Exported {} trusted blocks, {} DAA window entries and {} GHOSTDAG entries",
+        anticone.len(),
+        trusted_data.daa_window_blocks.len(),
+        trusted_data.ghostdag_blocks.len()
+    );
+
+    // Internal consensus logic requires that `max_blocks > mergeset_size_limit`
+    let max_blocks = max(HEADERS_CHUNK_SIZE, config.mergeset_size_limit as usize + 1);
+    let mut low = pruning_point;
+    let mut headers_count = 0;
+    while low != hst {
+        let hashes = session.async_get_hashes_between(low, hst, max_blocks).await?.0;
+        low = *hashes.last().expect("low and high are valid and different");
+        let mut chunk = SnapshotHeadersChunk::with_capacity(hashes.len());
+        for hash in hashes {
+            chunk.push((*session.async_get_header(hash).await?).clone());
+        }
+        headers_count += chunk.len();
+        writer.write(&chunk)?;
+    }
+    writer.write(&SnapshotHeadersChunk::new())?;
+    info!("Exported {} headers above the pruning point", headers_count);
+
+    let mut from_outpoint = None;
+    let mut utxos_count = 0;
+    loop {
+        let chunk: SnapshotUtxoChunk =
+            session.async_get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some()).await?;
+        utxos_count += chunk.len();
+        if chunk.is_empty() {
+            break;
+        }
+        writer.write(&chunk)?;
+        if chunk.len() < UTXO_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = Some(chunk.last().expect("not empty by prev condition").0);
+    }
+    writer.write(&SnapshotUtxoChunk::new())?;
+    info!("Exported {} UTXOs of the pruning point", utxos_count);
+
+    writer.finish()?;
+    fs::rename(&tmp_path, path)?;
+    info!("Snapshot of pruning point {} was written to {}", pruning_point, path.display());
+    Ok(())
+}
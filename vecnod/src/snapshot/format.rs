@@ -0,0 +1,276 @@
+//!
+//! The on-disk format of pruning point snapshots.
+//!
+//! A snapshot file is laid out as `magic | version | records | checksum`, where the records are
+//! bincode encoded and the trailing checksum is the SHA-256 digest of all the preceding bytes.
+//! The checksum is verified in full before any record is handed out to the reader.
+//!
+
+use super::error::{SnapshotError, SnapshotResult};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+};
+use vecno_consensus_core::{
+    block::Block,
+    header::Header,
+    network::NetworkId,
+    trusted::{ExternalGhostdagData, TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+    BlueWorkType,
+};
+use vecno_hashes::Hash;
+
+/// The magic bytes every snapshot file starts with
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"VECNOSNP";
+
+/// The current version of the snapshot format
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const CHECKSUM_SIZE: u64 = 32;
+
+/// General information about the snapshot, always the first record of the file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMetadata {
+    pub network_id: NetworkId,
+    pub pruning_point: Hash,
+    pub pruning_point_daa_score: u64,
+    pub headers_selected_tip: Hash,
+    pub headers_selected_tip_blue_work: BlueWorkType,
+    pub timestamp: u64,
+}
+
+/// A block of the pruning point anticone
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotBlock {
+    pub header: Header,
+    pub transactions: Vec<Transaction>,
+}
+
+impl From<&Block> for SnapshotBlock {
+    fn from(block: &Block) -> Self {
+        Self { header: (*block.header).clone(), transactions: block.transactions.to_vec() }
+    }
+}
+
+impl From<SnapshotBlock> for Block {
+    fn from(block: SnapshotBlock) -> Self {
+        let transactions = block.transactions.into_iter().map(finalized_transaction).collect();
+        Block::from_arcs(finalized_header(block.header), Arc::new(transactions))
+    }
+}
+
+/// The DAA window and GHOSTDAG data required to validate the blocks in the future of the pruning point
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotTrustedData {
+    pub daa_window: Vec<(Header, ExternalGhostdagData)>,
+    pub ghostdag_window: Vec<(Hash, ExternalGhostdagData)>,
+}
+
+impl SnapshotTrustedData {
+    pub fn new(daa_window: &[TrustedHeader], ghostdag_window: &[TrustedGhostdagData]) -> Self {
+        Self {
+            daa_window: daa_window.iter().map(|th| ((*th.header).clone(), th.ghostdag.clone())).collect(),
+            ghostdag_window: ghostdag_window.iter().map(|tg| (tg.hash, tg.ghostdag.clone())).collect(),
+        }
+    }
+
+    pub fn into_parts(self) -> (Vec<TrustedHeader>, Vec<TrustedGhostdagData>) {
+        (
+            self.daa_window.into_iter().map(|(header, ghostdag)| TrustedHeader::new(finalized_header(header), ghostdag)).collect(),
+            self.ghostdag_window.into_iter().map(|(hash, ghostdag)| TrustedGhostdagData::new(hash, ghostdag)).collect(),
+        )
+    }
+}
+
+pub type SnapshotHeadersChunk = Vec<Header>;
+
+pub type SnapshotUtxoChunk = Vec<(TransactionOutpoint, UtxoEntry)>;
+
+/// Recomputes the cached hash of a header read from a snapshot, so that it is never taken on trust
+pub fn finalized_header(mut header: Header) -> Arc<Header> {
+    header.finalize();
+    Arc::new(header)
+}
+
+/// Recomputes the cached id of a transaction read from a snapshot, so that it is never taken on trust
+fn finalized_transaction(mut tx: Transaction) -> Transaction {
+    tx.finalize();
+    tx
+}
+
+/// A writer wrapper digesting everything written through it
+struct DigestWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> Write for DigestWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub struct SnapshotWriter<W: Write> {
+    writer: DigestWriter<W>,
+}
+
+impl SnapshotWriter<BufWriter<File>> {
+    pub fn create(path: &Path) -> SnapshotResult<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    pub fn new(inner: W) -> SnapshotResult<Self> {
+        let mut writer = DigestWriter { inner, hasher: Sha256::new() };
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        Ok(Self { writer })
+    }
+
+    pub fn write<T: Serialize + ?Sized>(&mut self, record: &T) -> SnapshotResult<()> {
+        Ok(bincode::serialize_into(&mut self.writer, record)?)
+    }
+
+    /// Appends the checksum and flushes the underlying writer, returning it
+    pub fn finish(self) -> SnapshotResult<W> {
+        let DigestWriter { mut inner, hasher } = self.writer;
+        inner.write_all(&hasher.finalize())?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+pub struct SnapshotReader<R: Read> {
+    reader: io::Take<R>,
+}
+
+impl SnapshotReader<BufReader<File>> {
+    pub fn open(path: &Path) -> SnapshotResult<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> SnapshotReader<R> {
+    /// Verifies the header and the checksum of the snapshot and positions the reader on its first record
+    pub fn new(mut inner: R) -> SnapshotResult<Self> {
+        let len = inner.seek(SeekFrom::End(0))?;
+        let header_len = (SNAPSHOT_MAGIC.len() + std::mem::size_of::<u32>()) as u64;
+        if len < header_len + CHECKSUM_SIZE {
+            return Err(SnapshotError::Truncated);
+        }
+        let content_len = len - CHECKSUM_SIZE;
+
+        inner.seek(SeekFrom::Start(0))?;
+        let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+        inner.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidMagic);
+        }
+        let mut version = [0u8; std::mem::size_of::<u32>()];
+        inner.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version, SNAPSHOT_VERSION));
+        }
+
+        inner.seek(SeekFrom::Start(0))?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut (&mut inner).take(content_len), &mut hasher)?;
+        let mut checksum = [0u8; CHECKSUM_SIZE as usize];
+        inner.read_exact(&mut checksum)?;
+        if hasher.finalize().as_slice() != checksum {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+
+        inner.seek(SeekFrom::Start(header_len))?;
+        Ok(Self { reader: inner.take(content_len - header_len) })
+    }
+
+    pub fn read<T: DeserializeOwned>(&mut self) -> SnapshotResult<T> {
+        Ok(bincode::deserialize_from(&mut self.reader)?)
+    }
+
+    /// Ensures all the records of the snapshot were consumed
+    pub fn finish(&self) -> SnapshotResult<()> {
+        match self.reader.limit() {
+            0 => Ok(()),
+            _ => Err(SnapshotError::TrailingData),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use vecno_consensus_core::network::NetworkType;
+
+    fn metadata() -> SnapshotMetadata {
+        SnapshotMetadata {
+            network_id: NetworkId::with_suffix(NetworkType::Testnet, 10),
+            pruning_point: Hash::from_u64_word(1),
+            pruning_point_daa_score: 1000,
+            headers_selected_tip: Hash::from_u64_word(2),
+            headers_selected_tip_blue_work: 12345.into(),
+            timestamp: 1_700_000_000_000,
+        }
+    }
+
+    fn snapshot() -> Vec<u8> {
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        writer.write(&metadata()).unwrap();
+        writer.write(&vec![Hash::from_u64_word(3), Hash::from_u64_word(4)]).unwrap();
+        writer.write(&SnapshotHeadersChunk::new()).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut reader = SnapshotReader::new(Cursor::new(snapshot())).unwrap();
+        let read = reader.read::<SnapshotMetadata>().unwrap();
+        let expected = metadata();
+        assert_eq!(read.network_id, expected.network_id);
+        assert_eq!(read.pruning_point, expected.pruning_point);
+        assert_eq!(read.headers_selected_tip_blue_work, expected.headers_selected_tip_blue_work);
+        assert_eq!(reader.read::<Vec<Hash>>().unwrap(), vec![Hash::from_u64_word(3), Hash::from_u64_word(4)]);
+        assert!(reader.read::<SnapshotHeadersChunk>().unwrap().is_empty());
+        reader.finish().unwrap();
+
+        // Records left unread are reported
+        let mut reader = SnapshotReader::new(Cursor::new(snapshot())).unwrap();
+        reader.read::<SnapshotMetadata>().unwrap();
+        assert!(matches!(reader.finish(), Err(SnapshotError::TrailingData)));
+    }
+
+    #[test]
+    fn test_snapshot_corruption() {
+        let data = snapshot();
+
+        let mut tampered = data.clone();
+        tampered[20] ^= 1;
+        assert!(matches!(SnapshotReader::new(Cursor::new(tampered)), Err(SnapshotError::ChecksumMismatch)));
+
+        let mut tampered = data.clone();
+        tampered[0] = b'X';
+        assert!(matches!(SnapshotReader::new(Cursor::new(tampered)), Err(SnapshotError::InvalidMagic)));
+
+        let mut tampered = data.clone();
+        tampered[SNAPSHOT_MAGIC.len()] = 2;
+        assert!(matches!(SnapshotReader::new(Cursor::new(tampered)), Err(SnapshotError::UnsupportedVersion(2, SNAPSHOT_VERSION))));
+
+        assert!(matches!(SnapshotReader::new(Cursor::new(data[..data.len() - 1].to_vec())), Err(SnapshotError::ChecksumMismatch)));
+        assert!(matches!(SnapshotReader::new(Cursor::new(data[..16].to_vec())), Err(SnapshotError::Truncated)));
+    }
+}
@@ -0,0 +1,167 @@
+use super::{
+    error::{SnapshotError, SnapshotResult},
+    format::{
+        finalized_header, SnapshotBlock, SnapshotHeadersChunk, SnapshotMetadata, SnapshotReader, SnapshotTrustedData,
+        SnapshotUtxoChunk,
+    },
+};
+use futures_util::future::try_join_all;
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+use vecno_consensus_core::{
+    block::Block,
+    config::Config,
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+};
+use vecno_consensusmanager::{ConsensusManager, ConsensusProxy};
+use vecno_core::info;
+use vecno_muhash::MuHash;
+use vecno_p2p_lib::convert::model::trusted::{TrustedDataEntry, TrustedDataPackage};
+
+/// Imports a snapshot file into a staging consensus which replaces the active consensus once the
+/// snapshot is fully validated. The pruning proof goes through the same validation as during IBD
+/// and the imported UTXO set must match the UTXO commitment of the pruning point header.
+pub async fn import_snapshot(config: &Config, consensus_manager: &Arc<ConsensusManager>, path: &Path) -> SnapshotResult<()> {
+    let mut reader = SnapshotReader::open(path)?;
+    let metadata: SnapshotMetadata = reader.read()?;
+    if metadata.network_id != config.net {
+        return Err(SnapshotError::NetworkMismatch(metadata.network_id, config.net));
+    }
+    info!(
+        "Importing a snapshot of pruning point {} (DAA score {}) from {}",
+        metadata.pruning_point,
+        metadata.pruning_point_daa_score,
+        path.display()
+    );
+
+    {
+        let consensus = consensus_manager.consensus().session().await;
+        if consensus.async_get_headers_selected_tip().await != config.genesis.hash {
+            return Err(SnapshotError::DatabaseNotEmpty);
+        }
+    }
+
+    let staging = consensus_manager.new_staging_consensus();
+    let staging_session = staging.session().await;
+    match import_into_staging(config, consensus_manager, &staging_session, &metadata, &mut reader).await {
+        Ok(()) => {
+            drop(staging_session);
+            staging.commit();
+            info!("Snapshot of pruning point {} was imported successfully", metadata.pruning_point);
+            Ok(())
+        }
+        Err(err) => {
+            drop(staging_session);
+            staging.cancel();
+            Err(err)
+        }
+    }
+}
+
+async fn import_into_staging(
+    config: &Config,
+    consensus_manager: &ConsensusManager,
+    staging: &ConsensusProxy,
+    metadata: &SnapshotMetadata,
+    reader: &mut SnapshotReader<BufReader<File>>,
+) -> SnapshotResult<()> {
+    let proof: PruningPointProof =
+        reader.read::<Vec<Vec<Header>>>()?.into_iter().map(|level| level.into_iter().map(finalized_header).collect()).collect();
+    info!("Validating the pruning proof with overall {} headers", proof.iter().map(|level| level.len()).sum::<usize>());
+
+    // The proof is validated in the context of current consensus, exactly as a proof received during IBD
+    let proof_metadata = PruningProofMetadata::new(metadata.headers_selected_tip_blue_work);
+    let consensus = consensus_manager.consensus().session().await;
+    let proof = consensus.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?;
+
+    let pruning_point = proof[0].last().expect("was just ensured by validation").hash;
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::Invalid("the proof pruning point is the genesis block"));
+    }
+    if pruning_point != metadata.pruning_point {
+        return Err(SnapshotError::Invalid("the proof pruning point is not equal to the snapshot pruning point"));
+    }
+
+    let pruning_points: PruningPointsList = reader.read::<Vec<Header>>()?.into_iter().map(finalized_header).collect();
+    if pruning_points.last().map(|header| header.hash) != Some(pruning_point) {
+        return Err(SnapshotError::Invalid("the proof pruning point is not equal to the last pruning point in the list"));
+    }
+    if pruning_points.first().map(|header| header.hash) != Some(config.genesis.hash) {
+        return Err(SnapshotError::Invalid("the first pruning point in the list is expected to be genesis"));
+    }
+    if consensus.async_are_pruning_points_violating_finality(pruning_points.clone()).await {
+        return Err(SnapshotError::Invalid("pruning points are violating finality"));
+    }
+    drop(consensus);
+
+    let (daa_window, ghostdag_window) = reader.read::<SnapshotTrustedData>()?.into_parts();
+    let entries = reader
+        .read::<Vec<SnapshotBlock>>()?
+        .into_iter()
+        .map(|block| TrustedDataEntry::new(Block::from(block), vec![], vec![]))
+        .collect::<Vec<_>>();
+    if entries.first().map(|entry| entry.block.hash()) != Some(pruning_point) {
+        return Err(SnapshotError::Invalid("the proof pruning point is not equal to the first trusted block"));
+    }
+    let trusted_set = TrustedDataPackage::new(daa_window, ghostdag_window).build_trusted_subdag(entries)?;
+
+    let trusted_set = staging
+        .clone()
+        .spawn_blocking(move |c| {
+            c.apply_pruning_proof(proof, &trusted_set)?;
+            c.import_pruning_points(pruning_points);
+            SnapshotResult::Ok(trusted_set)
+        })
+        .await?;
+
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+    }
+
+    let mut headers_count = 0;
+    loop {
+        let chunk: SnapshotHeadersChunk = reader.read()?;
+        if chunk.is_empty() {
+            break;
+        }
+        headers_count += chunk.len();
+        let jobs = chunk
+            .into_iter()
+            .map(|header| staging.validate_and_insert_block(Block::from_header_arc(finalized_header(header))).virtual_state_task)
+            .collect::<Vec<_>>();
+        try_join_all(jobs).await?;
+        info!("Processed {} headers above the pruning point", headers_count);
+    }
+
+    let hst = staging.async_get_headers_selected_tip().await;
+    if hst != metadata.headers_selected_tip {
+        return Err(SnapshotError::InvalidOwned(format!(
+            "the headers selected tip {} does not match the snapshot headers selected tip {}",
+            hst, metadata.headers_selected_tip
+        )));
+    }
+    staging.async_validate_pruning_points().await?;
+
+    let mut multiset = MuHash::new();
+    let mut utxos_count = 0;
+    loop {
+        let chunk: SnapshotUtxoChunk = reader.read()?;
+        if chunk.is_empty() {
+            break;
+        }
+        utxos_count += chunk.len();
+        multiset = staging
+            .clone()
+            .spawn_blocking(move |c| {
+                c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                multiset
+            })
+            .await;
+    }
+    info!("Importing {} UTXOs of the pruning point", utxos_count);
+    // Fails if the imported set does not match the UTXO commitment of the pruning point header
+    staging.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset)).await?;
+
+    reader.finish()
+}
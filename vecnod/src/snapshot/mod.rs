@@ -0,0 +1,90 @@
+//!
+//! Pruning point snapshots.
+//!
+//! A snapshot holds the pruning point of a node together with its pruning proof, the trusted data
+//! required for validating its future, the headers above it and its UTXO set. A fresh node can
+//! import a snapshot offline and then sync the remaining block bodies from its peers, skipping the
+//! download of the pruning proof and of the UTXO set over P2P.
+//!
+
+use crate::{
+    args::Args,
+    daemon::{get_app_dir_from_args, get_user_approval_or_exit, validate_args, CONSENSUS_DB, DEFAULT_DATA_DIR, META_DB},
+    offline::OfflineConsensus,
+};
+use std::{fs, path::PathBuf, sync::Arc};
+use vecno_consensus_core::config::ConfigBuilder;
+use vecno_core::info;
+
+pub mod error;
+pub mod export;
+pub mod format;
+pub mod import;
+
+pub use error::{SnapshotError, SnapshotResult};
+
+/// The maximum amount of headers per snapshot record
+pub(crate) const HEADERS_CHUNK_SIZE: usize = 1 << 10;
+
+/// The maximum amount of UTXOs per snapshot record
+pub(crate) const UTXO_CHUNK_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotCommand {
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
+/// Runs a snapshot command against the node database. The node itself must not be running.
+pub fn run_snapshot_command(args: &Args, command: &SnapshotCommand, fd_total_budget: i32) -> SnapshotResult<()> {
+    if let Err(err) = validate_args(args) {
+        println!("{}", err);
+        std::process::exit(1);
+    }
+
+    let network = args.network();
+    let config = Arc::new(
+        ConfigBuilder::new(network.into())
+            .adjust_perf_params_to_consensus_params()
+            .apply_args(|config| args.apply_to_config(config))
+            .build(),
+    );
+
+    let db_dir = get_app_dir_from_args(args).join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    let consensus_db_dir = db_dir.join(CONSENSUS_DB);
+    let meta_db_dir = db_dir.join(META_DB);
+    match command {
+        SnapshotCommand::Export(_) => {
+            if !meta_db_dir.exists() {
+                return Err(SnapshotError::MissingDatabase(db_dir.display().to_string()));
+            }
+        }
+        SnapshotCommand::Import(_) => {
+            if args.reset_db && db_dir.exists() {
+                let msg = "Reset DB was requested -- this means the current databases will be fully deleted,
+do you confirm? (answer y/n or pass --yes to the Vecnod command line to confirm all interactive questions)";
+                get_user_approval_or_exit(msg, args.yes);
+                info!("Deleting databases");
+                fs::remove_dir_all(&db_dir)?;
+            }
+            fs::create_dir_all(&consensus_db_dir)?;
+            fs::create_dir_all(&meta_db_dir)?;
+        }
+    }
+
+    let Some(offline) = OfflineConsensus::open(args, &config, &db_dir, fd_total_budget) else {
+        return Err(SnapshotError::DatabaseUpgradeRequired);
+    };
+    let consensus_manager = offline.consensus_manager.clone();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+    let result = runtime.block_on(async {
+        match command {
+            SnapshotCommand::Export(path) => export::export_snapshot(&config, &consensus_manager, path).await,
+            SnapshotCommand::Import(path) => import::import_snapshot(&config, &consensus_manager, path).await,
+        }
+    });
+
+    offline.shutdown();
+    result
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use vecno_consensus_core::{
+    block::{Block, BlockTemplate},
+    coinbase::MinerData,
+    config::Config,
+    network::NetworkType::{Mainnet, Testnet},
+};
+use vecno_consensusmanager::ConsensusManager;
+use vecno_mining::manager::MiningManagerProxy;
+use vecno_p2p_flows::flow_context::FlowContext;
+use vecno_stratum::{
+    backend::StratumBackend,
+    errors::{StratumError, StratumResult},
+};
+
+/// Serves the stratum server with block templates of the local mining manager and
+/// submits the mined blocks the same way blocks submitted via RPC are
+pub struct NodeStratumBackend {
+    consensus_manager: Arc<ConsensusManager>,
+    mining_manager: MiningManagerProxy,
+    flow_context: Arc<FlowContext>,
+    config: Arc<Config>,
+}
+
+impl NodeStratumBackend {
+    pub fn new(
+        consensus_manager: Arc<ConsensusManager>,
+        mining_manager: MiningManagerProxy,
+        flow_context: Arc<FlowContext>,
+        config: Arc<Config>,
+    ) -> Self {
+        Self { consensus_manager, mining_manager, flow_context, config }
+    }
+
+    fn has_sufficient_peer_connectivity(&self) -> bool {
+        // Other network types can be used in an isolated environment without peers
+        !matches!(self.config.net.network_type, Mainnet | Testnet) || self.flow_context.hub().has_peers()
+    }
+}
+
+#[async_trait]
+impl StratumBackend for NodeStratumBackend {
+    async fn get_block_template(&self, miner_data: MinerData) -> StratumResult<BlockTemplate> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let block_template = self
+            .mining_manager
+            .clone()
+            .get_block_template(&session, miner_data)
+            .await
+            .map_err(|err| StratumError::BlockTemplate(err.to_string()))?;
+
+        let is_nearly_synced =
+            self.config.is_nearly_synced(block_template.selected_parent_timestamp, block_template.selected_parent_daa_score);
+        if !self.config.enable_unsynced_mining && !(is_nearly_synced && self.has_sufficient_peer_connectivity()) {
+            return Err(StratumError::NotSynced);
+        }
+        Ok(block_template)
+    }
+
+    async fn submit_block(&self, block: Block) -> StratumResult<()> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        self.flow_context.submit_rpc_block(&session, block).await.map_err(|err| StratumError::BlockRejected(err.to_string()))
+    }
+}
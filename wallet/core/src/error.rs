@@ -286,6 +286,9 @@ pub enum Error {
     #[error(transparent)]
     MultisigCreateError(#[from] vecno_txscript::MultisigCreateError),
 
+    #[error(transparent)]
+    TimelockScriptError(#[from] vecno_txscript::TimelockScriptError),
+
     #[error(transparent)]
     TxScriptError(#[from] vecno_txscript_errors::TxScriptError),
 
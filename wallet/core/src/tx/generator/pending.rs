@@ -6,10 +6,10 @@
 use crate::imports::*;
 use crate::result::Result;
 use crate::rpc::DynRpcApi;
-use crate::tx::{DataKind, Generator};
+use crate::tx::{sign_timelock_input, DataKind, Generator, TimelockSpend};
 use crate::utxo::{UtxoContext, UtxoEntryId, UtxoEntryReference};
 use vecno_consensus_core::hashing::sighash_type::SigHashType;
-use vecno_consensus_core::sign::{sign_input, sign_with_multiple_v2, Signed};
+use vecno_consensus_core::sign::{sign_input, sign_with_multiple_and_hash_types, sign_with_multiple_v2, Signed};
 use vecno_consensus_core::tx::{SignableTransaction, Transaction, TransactionId};
 use vecno_rpc_core::{RpcTransaction, RpcTransactionId};
 
@@ -277,10 +277,48 @@ impl PendingTransaction {
         Ok(())
     }
 
+    /// Signs an input locked by a timelocked script template (see [`TimelockSpend`]).
+    pub fn sign_timelock_input(
+        &self,
+        input_index: usize,
+        spend: &TimelockSpend,
+        private_key: &[u8; 32],
+        hash_type: SigHashType,
+    ) -> Result<()> {
+        let mut mutable_tx = self.inner.signable_tx.lock()?.clone();
+
+        let signature_script = {
+            let verifiable_tx = &mutable_tx.as_verifiable();
+            sign_timelock_input(verifiable_tx, input_index, spend, private_key, hash_type)?
+        };
+
+        mutable_tx.tx.inputs[input_index].signature_script = signature_script;
+        *self.inner.signable_tx.lock().unwrap() = mutable_tx;
+
+        Ok(())
+    }
+
     pub fn try_sign_with_keys(&self, privkeys: &[[u8; 32]], check_fully_signed: Option<bool>) -> Result<()> {
         let mutable_tx = self.inner.signable_tx.lock()?.clone();
         let signed = sign_with_multiple_v2(mutable_tx, privkeys);
+        self.apply_signed(signed, check_fully_signed)
+    }
+
+    /// Signs the inputs with the given keys like [`try_sign_with_keys`](Self::try_sign_with_keys), but with a sighash
+    /// type per input instead of `SIG_HASH_ALL`, such as `SIG_HASH_SINGLE | SIG_HASH_ANY_ONE_CAN_PAY` for an input
+    /// which only pays its matching output and may be combined with inputs of other parties.
+    pub fn try_sign_with_keys_and_hash_types(
+        &self,
+        privkeys: &[[u8; 32]],
+        hash_types: &[SigHashType],
+        check_fully_signed: Option<bool>,
+    ) -> Result<()> {
+        let mutable_tx = self.inner.signable_tx.lock()?.clone();
+        let signed = sign_with_multiple_and_hash_types(mutable_tx, privkeys, hash_types)?;
+        self.apply_signed(signed, check_fully_signed)
+    }
 
+    fn apply_signed(&self, signed: Signed, check_fully_signed: Option<bool>) -> Result<()> {
         let signed_tx = match signed {
             Signed::Fully(tx) => tx,
             Signed::Partially(_) => {
@@ -0,0 +1,104 @@
+//!
+//! Signing of inputs locked by the timelocked P2SH script templates
+//! ([`HtlcScript`], [`TimelockVaultScript`] and [`RecoveryScript`]).
+//!
+
+use crate::imports::*;
+use vecno_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync};
+use vecno_consensus_core::hashing::sighash_type::SigHashType;
+use vecno_consensus_core::tx::VerifiableTransaction;
+use vecno_txscript::{HtlcScript, RecoveryScript, TimelockVaultScript, HTLC_PREIMAGE_SIZE};
+
+/// The spending branch of a timelocked script template.
+///
+/// Branches guarded by a timelock require the spending transaction to satisfy it: the HTLC refund and the
+/// recovery key branches require the transaction lock time to be at least the script lock time or DAA score,
+/// with a non-final input sequence, and the vault owner branch requires the input sequence to be at least
+/// the vault delay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelockSpend {
+    /// HTLC claim by the receiver, revealing the payment preimage
+    HtlcClaim { script: HtlcScript, preimage: [u8; HTLC_PREIMAGE_SIZE] },
+    /// HTLC refund after the contract lock time
+    HtlcRefund(HtlcScript),
+    /// Vault spend by the owner after the relative timelock
+    VaultOwner(TimelockVaultScript),
+    /// Vault spend by the recovery key
+    VaultRecovery(TimelockVaultScript),
+    /// Spend by the primary key of a recovery script
+    RecoveryPrimary(RecoveryScript),
+    /// Spend by the recovery key of a recovery script after its DAA score
+    RecoveryBackup(RecoveryScript),
+}
+
+impl TimelockSpend {
+    /// Builds the input signature script from a Schnorr signature followed by the sighash type.
+    pub fn signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        let signature_script = match self {
+            TimelockSpend::HtlcClaim { script, preimage } => script.claim_signature_script(signature, preimage),
+            TimelockSpend::HtlcRefund(script) => script.refund_signature_script(signature),
+            TimelockSpend::VaultOwner(script) => script.owner_signature_script(signature),
+            TimelockSpend::VaultRecovery(script) => script.recovery_signature_script(signature),
+            TimelockSpend::RecoveryPrimary(script) => script.primary_signature_script(signature),
+            TimelockSpend::RecoveryBackup(script) => script.recovery_signature_script(signature),
+        }?;
+        Ok(signature_script)
+    }
+}
+
+/// Signs a transaction input locked by a timelocked script template and returns its signature script.
+pub fn sign_timelock_input(
+    tx: &impl VerifiableTransaction,
+    input_index: usize,
+    spend: &TimelockSpend,
+    private_key: &[u8; 32],
+    hash_type: SigHashType,
+) -> Result<Vec<u8>> {
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let hash = calc_schnorr_signature_hash(tx, input_index, hash_type, &reused_values);
+    let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice())?;
+    let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, private_key)?;
+    let signature: Vec<u8> = schnorr_key.sign_schnorr(msg).as_ref().iter().copied().chain([hash_type.to_u8()]).collect();
+    spend.signature_script(&signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::rand::thread_rng;
+    use vecno_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
+    use vecno_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use vecno_consensus_core::tx::{MutableTransaction, Transaction, TransactionInput, TransactionOutpoint, UtxoEntry};
+    use vecno_txscript::{caches::Cache, pay_to_script_hash_script, TxScriptEngine, TIMELOCK_TEMPLATE_SIG_OP_COUNT};
+
+    #[test]
+    fn test_sign_timelock_input() {
+        let primary = secp256k1::Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let recovery = secp256k1::Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let script = RecoveryScript {
+            primary: primary.x_only_public_key().0.serialize(),
+            recovery: recovery.x_only_public_key().0.serialize(),
+            daa_score: 1000,
+        };
+        let redeem_script = script.redeem_script().unwrap();
+
+        for (spend, private_key, lock_time, sequence) in [
+            (TimelockSpend::RecoveryPrimary(script), primary.secret_bytes(), 0, u64::MAX),
+            (TimelockSpend::RecoveryBackup(script), recovery.secret_bytes(), 1000, 0),
+        ] {
+            let input = TransactionInput::new(TransactionOutpoint::default(), vec![], sequence, TIMELOCK_TEMPLATE_SIG_OP_COUNT);
+            let tx = Transaction::new(0, vec![input], vec![], lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]);
+            let entry = UtxoEntry::new(1000, pay_to_script_hash_script(&redeem_script), 0, false);
+            let mut tx = MutableTransaction::with_entries(tx, vec![entry]);
+            let signature_script = sign_timelock_input(&tx.as_verifiable(), 0, &spend, &private_key, SIG_HASH_ALL).unwrap();
+            tx.tx.inputs[0].signature_script = signature_script;
+
+            let tx = tx.as_verifiable();
+            let (input, entry) = tx.populated_input(0);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let cache = Cache::new(10);
+            let mut engine = TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &cache, Default::default());
+            assert!(engine.execute().is_ok(), "{spend:?} failed");
+        }
+    }
+}
@@ -16,6 +16,16 @@ pub enum Error {
     MissingUtxoEntry,
     #[error("Missing redeem script")]
     MissingRedeemScript,
+    #[error("Redeem script is not a timelocked script template")]
+    UnsupportedRedeemScript,
+    #[error("Missing signature for the timelocked script template")]
+    MissingTimelockSignature,
+    #[error("Missing HTLC preimage")]
+    MissingPreimage,
+    #[error(transparent)]
+    TimelockScriptError(#[from] vecno_txscript::TimelockScriptError),
+    #[error(transparent)]
+    ScriptBuilderError(#[from] vecno_txscript::script_builder::ScriptBuilderError),
     #[error(transparent)]
     InputBuilder(#[from] crate::input::InputBuilderError),
     #[error(transparent)]
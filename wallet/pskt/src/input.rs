@@ -1,6 +1,7 @@
 //! PSKT input structure.
 
-use crate::pskt::{KeySource, PartialSigs};
+use crate::error::Error;
+use crate::pskt::{KeySource, PartialSigs, Signature};
 use crate::utils::{combine_if_no_conflicts, Error as CombineMapErr};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -9,6 +10,7 @@ use vecno_consensus_core::{
     hashing::sighash_type::{SigHashType, SIG_HASH_ALL},
     tx::{TransactionId, TransactionOutpoint, UtxoEntry},
 };
+use vecno_txscript::{script_builder::ScriptBuilder, HtlcScript, RecoveryScript, TimelockVaultScript};
 
 // todo add unknown field? combine them by deduplicating, if there are different values - return error?
 #[derive(Builder, Serialize, Deserialize, Debug, Clone)]
@@ -38,6 +40,11 @@ pub struct Input {
     #[builder(setter(strip_option))]
     /// The redeem script for this input.
     pub redeem_script: Option<Vec<u8>>,
+    #[serde(default, with = "vecno_utils::serde_bytes_optional")]
+    #[builder(setter(strip_option))]
+    /// The control block proving that the redeem script is a leaf of the MAST committed to by the spent output,
+    /// if the input spends a MAST output.
+    pub mast_control_block: Option<Vec<u8>>,
     #[builder(setter(strip_option))]
     pub sig_op_count: Option<u8>,
     /// A map from public keys needed to sign this input to their corresponding
@@ -68,6 +75,7 @@ impl Default for Input {
             partial_sigs: Default::default(),
             sighash_type: SIG_HASH_ALL,
             redeem_script: Default::default(),
+            mast_control_block: Default::default(),
             sig_op_count: Default::default(),
             bip32_derivations: Default::default(),
             final_script_sig: Default::default(),
@@ -78,6 +86,62 @@ impl Default for Input {
     }
 }
 
+impl Input {
+    /// Builds the final signature script of an input locked by a timelocked script template
+    /// ([`HtlcScript`], [`TimelockVaultScript`] or [`RecoveryScript`]) from its redeem script and partial signatures.
+    ///
+    /// The spending branch is selected by the key which signed the input. When both keys signed, the branch without
+    /// a timelock is preferred. An HTLC is claimed by the receiver only if the `preimage` is provided.
+    ///
+    /// If the input spends a MAST output, the redeem script is the revealed leaf script and the MAST control block
+    /// is pushed after it.
+    pub fn timelock_final_script_sig(&self, preimage: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        let signature_script = self.timelock_signature_script(preimage)?;
+        match &self.mast_control_block {
+            Some(control_block) => {
+                Ok(signature_script.into_iter().chain(ScriptBuilder::new().add_data(control_block)?.drain()).collect())
+            }
+            None => Ok(signature_script),
+        }
+    }
+
+    fn timelock_signature_script(&self, preimage: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        let redeem_script = self.redeem_script.as_ref().ok_or(Error::MissingRedeemScript)?;
+        if let Some(script) = HtlcScript::parse(redeem_script) {
+            match (self.schnorr_signature(&script.receiver), preimage, self.schnorr_signature(&script.refund)) {
+                (Some(signature), Some(preimage), _) => Ok(script.claim_signature_script(&signature, preimage)?),
+                (_, _, Some(signature)) => Ok(script.refund_signature_script(&signature)?),
+                (Some(_), None, None) => Err(Error::MissingPreimage),
+                (None, _, None) => Err(Error::MissingTimelockSignature),
+            }
+        } else if let Some(script) = TimelockVaultScript::parse(redeem_script) {
+            match (self.schnorr_signature(&script.recovery), self.schnorr_signature(&script.owner)) {
+                (Some(signature), _) => Ok(script.recovery_signature_script(&signature)?),
+                (None, Some(signature)) => Ok(script.owner_signature_script(&signature)?),
+                (None, None) => Err(Error::MissingTimelockSignature),
+            }
+        } else if let Some(script) = RecoveryScript::parse(redeem_script) {
+            match (self.schnorr_signature(&script.primary), self.schnorr_signature(&script.recovery)) {
+                (Some(signature), _) => Ok(script.primary_signature_script(&signature)?),
+                (None, Some(signature)) => Ok(script.recovery_signature_script(&signature)?),
+                (None, None) => Err(Error::MissingTimelockSignature),
+            }
+        } else {
+            Err(Error::UnsupportedRedeemScript)
+        }
+    }
+
+    /// Returns the Schnorr partial signature of the x-only public key, followed by the input sighash type.
+    fn schnorr_signature(&self, x_only_public_key: &[u8; 32]) -> Option<Vec<u8>> {
+        self.partial_sigs.iter().find_map(|(public_key, signature)| match signature {
+            Signature::Schnorr(signature) if public_key.x_only_public_key().0.serialize() == *x_only_public_key => {
+                Some(signature.as_ref().iter().copied().chain([self.sighash_type.to_u8()]).collect())
+            }
+            _ => None,
+        })
+    }
+}
+
 impl Add for Input {
     type Output = Result<Self, CombineError>;
 
@@ -105,9 +169,11 @@ impl Add for Input {
         // todo discuss merging. if sequence is equal - combine, otherwise use input which has bigger sequence number as is
         self.sequence = self.sequence.max(rhs.sequence);
         self.min_time = self.min_time.max(rhs.min_time);
+        // Signatures commit to the sighash type of the input, so every signer must have used the same one
+        if self.sighash_type.to_u8() != rhs.sighash_type.to_u8() {
+            return Err(CombineError::SighashTypeMismatch { this: self.sighash_type.to_u8(), that: rhs.sighash_type.to_u8() });
+        }
         self.partial_sigs.extend(rhs.partial_sigs);
-        // todo combine sighash? or always use sighash all since all signatures must be passed after completion of construction step
-        // self.sighash_type
 
         self.redeem_script = match (self.redeem_script.take(), rhs.redeem_script) {
             (None, None) => None,
@@ -118,6 +184,13 @@ impl Add for Input {
             }
         };
 
+        self.mast_control_block = match (self.mast_control_block.take(), rhs.mast_control_block) {
+            (None, None) => None,
+            (Some(control_block), None) | (None, Some(control_block)) => Some(control_block),
+            (Some(left), Some(right)) if left == right => Some(left),
+            (Some(left), Some(right)) => return Err(CombineError::NotCompatibleMastControlBlocks { this: left, that: right }),
+        };
+
         // todo Does Combiner allowed to change final script sig??
         self.final_script_sig = match (self.final_script_sig.take(), rhs.final_script_sig) {
             (None, None) => None,
@@ -154,8 +227,17 @@ pub enum CombineError {
         /// Into a PSKT with `that` spent output index.
         that: u32,
     },
+    #[error("The sighash types are not the same")]
+    SighashTypeMismatch {
+        /// Attempted to combine a PSKT with `this` sighash type.
+        this: u8,
+        /// Into a PSKT with `that` sighash type.
+        that: u8,
+    },
     #[error("Two different redeem scripts detected")]
     NotCompatibleRedeemScripts { this: Vec<u8>, that: Vec<u8> },
+    #[error("Two different MAST control blocks detected")]
+    NotCompatibleMastControlBlocks { this: Vec<u8>, that: Vec<u8> },
     #[error("Two different utxos detected")]
     NotCompatibleUtxos { this: UtxoEntry, that: UtxoEntry },
 
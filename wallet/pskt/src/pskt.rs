@@ -19,7 +19,7 @@ use vecno_consensus_core::{
     subnets::SUBNETWORK_ID_NATIVE,
     tx::{MutableTransaction, SignableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput},
 };
-use vecno_txscript::{caches::Cache, TxScriptEngine};
+use vecno_txscript::{caches::Cache, EngineFlags, TxScriptEngine};
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -257,6 +257,14 @@ impl PSKT<Updater> {
         Ok(self)
     }
 
+    /// Sets the sighash type the input must be signed with. For instance, `SIG_HASH_ALL | SIG_HASH_ANY_ONE_CAN_PAY`
+    /// lets other parties contribute inputs, as in crowd-funding, while `SIG_HASH_SINGLE | SIG_HASH_ANY_ONE_CAN_PAY`
+    /// only commits to the output with the index of the input, so the rest of the transaction can be completed by others.
+    pub fn set_sighash_type(mut self, sighash_type: SigHashType, input_index: usize) -> Result<Self, Error> {
+        self.inner_pskt.inputs.get_mut(input_index).ok_or(Error::OutOfBounds)?.sighash_type = sighash_type;
+        Ok(self)
+    }
+
     pub fn signer(self) -> PSKT<Signer> {
         PSKT { inner_pskt: self.inner_pskt, role: Default::default() }
     }
@@ -302,6 +310,22 @@ impl PSKT<Signer> {
         Ok(self)
     }
 
+    /// Signs the single input at `input_index` with its sighash type, leaving the other inputs to other signers.
+    /// The PSKTs of all the signers are then merged by a [`Combiner`].
+    pub fn pass_input_signature_sync<SignFn, E>(mut self, input_index: usize, sign_fn: SignFn) -> Result<Self, Error>
+    where
+        E: Display,
+        SignFn: FnOnce(SignableTransaction, SigHashType) -> Result<SignInputOk, E>,
+    {
+        let unsigned_tx = self.unsigned_tx();
+        let input = self.inner_pskt.inputs.get_mut(input_index).ok_or(Error::OutOfBounds)?;
+        let SignInputOk { signature, pub_key, key_source } =
+            sign_fn(unsigned_tx, input.sighash_type).map_err(|err| Error::Custom(err.to_string()))?;
+        input.bip32_derivations.insert(pub_key, key_source);
+        input.partial_sigs.insert(pub_key, signature);
+        Ok(self)
+    }
+
     pub fn calculate_id(&self) -> TransactionId {
         self.calculate_id_internal()
     }
@@ -380,6 +404,12 @@ impl PSKT<Finalizer> {
         self.finalize_internal(sigs)
     }
 
+    /// Finalizes a PSKT whose inputs are all locked by timelocked script templates, see [`Input::timelock_final_script_sig`].
+    /// The `preimage` is used to claim every HTLC input signed by its receiver.
+    pub fn finalize_timelock_sync(self, preimage: Option<&[u8]>) -> Result<Self, FinalizeError<Error>> {
+        self.finalize_sync(|inner: &Inner| inner.inputs.iter().map(|input| input.timelock_final_script_sig(preimage)).collect())
+    }
+
     pub fn id(&self) -> Option<TransactionId> {
         self.global.id
     }
@@ -426,6 +456,15 @@ impl PSKT<Extractor> {
     }
 
     pub fn extract_tx(self) -> Result<impl FnOnce(u64) -> (Transaction, Vec<Option<UtxoEntry>>), ExtractError> {
+        self.extract_tx_with_flags(Default::default())
+    }
+
+    /// Extracts the transaction after validating its scripts with the given script engine features, such as MAST
+    /// spends once they are activated.
+    pub fn extract_tx_with_flags(
+        self,
+        flags: EngineFlags,
+    ) -> Result<impl FnOnce(u64) -> (Transaction, Vec<Option<UtxoEntry>>), ExtractError> {
         let (tx, entries) = self.extract_tx_unchecked()?(0);
 
         let tx = MutableTransaction::with_entries(tx, entries.into_iter().flatten().collect());
@@ -436,7 +475,7 @@ impl PSKT<Extractor> {
             let reused_values = SigHashReusedValuesUnsync::new();
 
             tx.populated_inputs().enumerate().try_for_each(|(idx, (input, entry))| {
-                TxScriptEngine::from_transaction_input(&tx, input, idx, entry, &reused_values, &cache, false).execute()?;
+                TxScriptEngine::from_transaction_input(&tx, input, idx, entry, &reused_values, &cache, flags).execute()?;
                 <Result<(), ExtractError>>::Ok(())
             })?;
         }
@@ -485,6 +524,224 @@ pub struct TxNotFinalized {}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use secp256k1::{rand::thread_rng, Keypair};
+    use vecno_addresses::{Address, Prefix, Version as AddressVersion};
+    use vecno_consensus_core::hashing::sighash::calc_schnorr_signature_hash;
+    use vecno_consensus_core::hashing::sighash_type::{SIG_HASH_ALL, SIG_HASH_ANY_ONE_CAN_PAY, SIG_HASH_SINGLE};
+    use vecno_consensus_core::tx::{ScriptPublicKey, TransactionOutpoint};
+    use vecno_txscript::{
+        multisig_redeem_script, opcodes::codes::OpData65, pay_to_address_script, pay_to_script_hash_script, MastTree,
+        TimelockVaultScript, TIMELOCK_TEMPLATE_SIG_OP_COUNT,
+    };
+    use vecno_txscript_errors::TxScriptError;
+
+    fn signed_timelock_pskt(redeem_script: Vec<u8>, sequence: u64, signer: &Keypair) -> PSKT<Finalizer> {
+        let input = InputBuilder::default()
+            .utxo_entry(UtxoEntry::new(1000, pay_to_script_hash_script(&redeem_script), 0, false))
+            .previous_outpoint(Default::default())
+            .sig_op_count(TIMELOCK_TEMPLATE_SIG_OP_COUNT)
+            .redeem_script(redeem_script)
+            .build()
+            .unwrap();
+        signed_pskt(input, sequence, signer)
+    }
+
+    fn signed_pskt(input: Input, sequence: u64, signer: &Keypair) -> PSKT<Finalizer> {
+        let pskt = PSKT::<Creator>::default().constructor().input(input).updater().set_sequence(sequence, 0).unwrap().signer();
+        let reused_values = SigHashReusedValuesUnsync::new();
+        pskt.pass_signature_sync(|tx, sighash| -> Result<Vec<SignInputOk>, String> {
+            let hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, sighash[0], &reused_values);
+            let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
+            Ok(vec![SignInputOk {
+                signature: Signature::Schnorr(signer.sign_schnorr(msg)),
+                pub_key: signer.public_key(),
+                key_source: None,
+            }])
+        })
+        .unwrap()
+        .finalizer()
+    }
+
+    #[test]
+    fn test_finalize_timelock() {
+        let owner = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let recovery = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let vault = TimelockVaultScript {
+            owner: owner.x_only_public_key().0.serialize(),
+            recovery: recovery.x_only_public_key().0.serialize(),
+            delay: 100,
+        };
+        let redeem_script = vault.redeem_script().unwrap();
+
+        // Both branches are finalized according to the signer and pass script validation on extraction
+        for (signer, sequence) in [(&owner, 100), (&recovery, u64::MAX)] {
+            let pskt = signed_timelock_pskt(redeem_script.clone(), sequence, signer).finalize_timelock_sync(None).unwrap();
+            assert!(pskt.extractor().unwrap().extract_tx().is_ok());
+        }
+
+        // The owner branch is finalized but the relative timelock is not satisfied
+        let pskt = signed_timelock_pskt(redeem_script.clone(), 99, &owner).finalize_timelock_sync(None).unwrap();
+        assert!(matches!(pskt.extractor().unwrap().extract_tx(), Err(ExtractError::TxScriptError(_))));
+
+        let other = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let result = signed_timelock_pskt(redeem_script, 100, &other).finalize_timelock_sync(None);
+        assert!(matches!(result, Err(FinalizeError::FinalaziCb(Error::MissingTimelockSignature))));
+
+        let multisig = multisig_redeem_script([owner.x_only_public_key().0.serialize()].iter(), 1).unwrap();
+        let result = signed_timelock_pskt(multisig, 100, &owner).finalize_timelock_sync(None);
+        assert!(matches!(result, Err(FinalizeError::FinalaziCb(Error::UnsupportedRedeemScript))));
+    }
+
+    #[test]
+    fn test_finalize_timelock_mast_leaf() {
+        let owner = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let recovery = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+        let vault = |delay| TimelockVaultScript {
+            owner: owner.x_only_public_key().0.serialize(),
+            recovery: recovery.x_only_public_key().0.serialize(),
+            delay,
+        };
+        let tree = MastTree::new([10, 100, 1000].map(|delay| vault(delay).redeem_script().unwrap()).to_vec()).unwrap();
+        let input = InputBuilder::default()
+            .utxo_entry(UtxoEntry::new(1000, tree.script_public_key(), 0, false))
+            .previous_outpoint(Default::default())
+            .sig_op_count(TIMELOCK_TEMPLATE_SIG_OP_COUNT)
+            .redeem_script(tree.leaf_scripts()[1].clone())
+            .mast_control_block(tree.control_block(1).unwrap())
+            .build()
+            .unwrap();
+
+        // The leaf script is revealed along with its control block, and spends the output only once MAST is enabled
+        let pskt = signed_pskt(input, 100, &owner).finalize_timelock_sync(None).unwrap();
+        let flags = EngineFlags { mast_enabled: true, ..Default::default() };
+        assert!(pskt.clone().extractor().unwrap().extract_tx_with_flags(flags).is_ok());
+        assert!(matches!(pskt.extractor().unwrap().extract_tx(), Err(ExtractError::TxScriptError(TxScriptError::CleanStack(_)))));
+
+        // Combining inputs with different control blocks fails
+        let [input, other] =
+            [1, 0].map(|index| InputBuilder::default().mast_control_block(tree.control_block(index).unwrap()).build().unwrap());
+        assert!(matches!(input + other, Err(crate::input::CombineError::NotCompatibleMastControlBlocks { .. })));
+    }
+
+    fn p2pk_script(owner: &Keypair) -> ScriptPublicKey {
+        pay_to_address_script(&Address::new(Prefix::Testnet, AddressVersion::PubKey, &owner.x_only_public_key().0.serialize()))
+    }
+
+    fn p2pk_input(owner: &Keypair, amount: u64, index: u32) -> Input {
+        InputBuilder::default()
+            .utxo_entry(UtxoEntry::new(amount, p2pk_script(owner), 0, false))
+            .previous_outpoint(TransactionOutpoint::new(Default::default(), index))
+            .sig_op_count(1)
+            .build()
+            .unwrap()
+    }
+
+    fn p2pk_output(owner: &Keypair, amount: u64) -> Output {
+        OutputBuilder::default().amount(amount).script_public_key(p2pk_script(owner)).build().unwrap()
+    }
+
+    fn sign_input(pskt: PSKT<Signer>, input_index: usize, signer: &Keypair) -> PSKT<Signer> {
+        let reused_values = SigHashReusedValuesUnsync::new();
+        pskt.pass_input_signature_sync(input_index, |tx, sighash_type| -> Result<SignInputOk, String> {
+            let hash = calc_schnorr_signature_hash(&tx.as_verifiable(), input_index, sighash_type, &reused_values);
+            let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
+            Ok(SignInputOk { signature: Signature::Schnorr(signer.sign_schnorr(msg)), pub_key: signer.public_key(), key_source: None })
+        })
+        .unwrap()
+    }
+
+    fn finalize_p2pk(pskt: PSKT<Finalizer>) -> PSKT<Finalizer> {
+        pskt.finalize_sync(|inner: &Inner| -> Result<Vec<Vec<u8>>, String> {
+            Ok(inner
+                .inputs
+                .iter()
+                .map(|input| {
+                    let signature = input.partial_sigs.values().next().unwrap().into_bytes();
+                    [OpData65].into_iter().chain(signature).chain([input.sighash_type.to_u8()]).collect()
+                })
+                .collect())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mixed_sighash_types() {
+        let [alice, bob, carol, recipient] = [(); 4].map(|_| Keypair::new(secp256k1::SECP256K1, &mut thread_rng()));
+        let single_anyone_can_pay = SigHashType::from_u8(SIG_HASH_SINGLE.to_u8() | SIG_HASH_ANY_ONE_CAN_PAY.to_u8()).unwrap();
+        let pskt = PSKT::<Creator>::default()
+            .constructor()
+            .input(p2pk_input(&alice, 1000, 0))
+            .input(p2pk_input(&bob, 2000, 1))
+            .output(p2pk_output(&recipient, 900))
+            .output(p2pk_output(&bob, 1900))
+            .updater()
+            .set_sighash_type(single_anyone_can_pay, 0)
+            .unwrap()
+            .set_sighash_type(SIG_HASH_ALL, 1)
+            .unwrap()
+            .signer();
+
+        // Each party signs its own input with its own sighash type, and the combined PSKT is valid
+        let alice_pskt = sign_input(pskt.clone(), 0, &alice);
+        let bob_pskt = sign_input(pskt.clone(), 1, &bob);
+        let combined = (alice_pskt.clone().combiner() + bob_pskt.clone()).unwrap();
+        assert!(finalize_p2pk(combined.finalizer()).extractor().unwrap().extract_tx().is_ok());
+
+        // The SINGLE | ANYONECANPAY signature of Alice only commits to her input and the output at its index,
+        // so it remains valid along with other inputs and outputs
+        let pskt = PSKT::<Creator>::default()
+            .constructor()
+            .input(alice_pskt.inputs[0].clone())
+            .input(p2pk_input(&carol, 3000, 2))
+            .output(p2pk_output(&recipient, 900))
+            .output(p2pk_output(&carol, 2900))
+            .signer();
+        assert!(finalize_p2pk(sign_input(pskt, 1, &carol).finalizer()).extractor().unwrap().extract_tx().is_ok());
+
+        // The ALL signature of Bob is invalidated by changing any output
+        let pskt = PSKT::<Creator>::default()
+            .constructor()
+            .input(alice_pskt.inputs[0].clone())
+            .input(bob_pskt.inputs[1].clone())
+            .output(p2pk_output(&recipient, 900))
+            .output(p2pk_output(&carol, 1900))
+            .signer()
+            .finalizer();
+        assert!(matches!(finalize_p2pk(pskt).extractor().unwrap().extract_tx(), Err(ExtractError::TxScriptError(_))));
+
+        // Inputs signed with different sighash types cannot be combined
+        let other = sign_input(PSKT::<Creator>::default().constructor().input(p2pk_input(&alice, 1000, 0)).signer(), 0, &alice);
+        assert!(matches!(
+            other.combiner() + alice_pskt,
+            Err(CombineError::Inputs(crate::input::CombineError::SighashTypeMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_crowd_fund_any_one_can_pay() {
+        let [alice, bob, fundraiser] = [(); 3].map(|_| Keypair::new(secp256k1::SECP256K1, &mut thread_rng()));
+        let all_anyone_can_pay = SigHashType::from_u8(SIG_HASH_ALL.to_u8() | SIG_HASH_ANY_ONE_CAN_PAY.to_u8()).unwrap();
+        let goal = p2pk_output(&fundraiser, 3000);
+
+        // Every contributor signs a PSKT made of their own input and the goal output alone
+        let contributions = [(&alice, 1000, 0), (&bob, 2000, 1)].map(|(contributor, amount, index)| {
+            let pskt = PSKT::<Creator>::default()
+                .constructor()
+                .input(p2pk_input(contributor, amount, index))
+                .output(goal.clone())
+                .updater()
+                .set_sighash_type(all_anyone_can_pay, 0)
+                .unwrap()
+                .signer();
+            sign_input(pskt, 0, contributor).inputs[0].clone()
+        });
+
+        // The fundraiser gathers the signed inputs into a single transaction
+        let pskt = contributions.into_iter().fold(PSKT::<Creator>::default().constructor(), |pskt, input| pskt.input(input));
+        let pskt = finalize_p2pk(pskt.output(goal).signer().finalizer());
+        assert!(pskt.extractor().unwrap().extract_tx().is_ok());
+    }
 
     // #[test]
     // fn it_works() {